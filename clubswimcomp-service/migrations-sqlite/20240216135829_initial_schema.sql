@@ -0,0 +1,43 @@
+-- SQLite variant of the initial schema.
+--
+-- UUIDs are stored as 16 byte BLOBs and generated by the application, the
+-- gender and stroke enums are TEXT columns restricted by CHECK constraints.
+-- The participant short ids are generated by the application as SQLite has
+-- no stored functions.
+
+CREATE TABLE groups (
+	id			BLOB			PRIMARY KEY NOT NULL,
+	name		TEXT			NOT NULL
+);
+
+CREATE TABLE participants (
+	id 			BLOB			PRIMARY KEY NOT NULL,
+	short_id	INTEGER			NOT NULL					UNIQUE,
+	first_name	TEXT			NOT NULL,
+	last_name	TEXT			NOT NULL,
+	gender		TEXT			NOT NULL					CHECK (gender IN ('female', 'male')),
+	birthday	TEXT			NOT NULL,
+	group_id	BLOB			NOT NULL					REFERENCES groups(id)
+);
+
+CREATE TABLE competitions (
+	id			BLOB			PRIMARY KEY NOT NULL,
+	gender		TEXT			NOT NULL					CHECK (gender IN ('female', 'male')),
+	stroke		TEXT			NOT NULL					CHECK (stroke IN ('butterfly', 'back', 'breast', 'freestyle')),
+	distance	INTEGER			NOT NULL 					CHECK((distance % 25) = 0 AND distance > 0),
+	target_time INTEGER			NOT NULL					CHECK (target_time > 0),
+	CONSTRAINT no_same_competitions UNIQUE (gender, stroke, distance)
+);
+
+CREATE TABLE registrations (
+	id					BLOB			PRIMARY KEY NOT NULL,
+	participant_id		BLOB			NOT NULL					REFERENCES participants(id),
+	competition_id		BLOB			NOT NULL					REFERENCES competitions(id),
+	CONSTRAINT one_registration_per_participant UNIQUE (participant_id, competition_id)
+);
+
+CREATE TABLE registration_results (
+	registration_id		BLOB			PRIMARY KEY NOT NULL		REFERENCES registrations(id),
+	disqualified		INTEGER			NOT NULL,
+	time_millis			INTEGER			NOT NULL					CHECK (time_millis > 0)
+);
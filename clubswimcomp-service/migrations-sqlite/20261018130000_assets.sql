@@ -0,0 +1,5 @@
+CREATE TABLE assets (
+	name		TEXT			PRIMARY KEY NOT NULL,
+	content		BLOB			NOT NULL,
+	updated_at	TEXT			NOT NULL
+);
@@ -0,0 +1,8 @@
+-- The event itself, there is always at most one row
+CREATE TABLE event (
+	id			TEXT			PRIMARY KEY NOT NULL,
+	card_secret	BLOB			NOT NULL,
+	created_at	TEXT			NOT NULL
+);
+
+CREATE UNIQUE INDEX event_singleton ON event ((1));
@@ -0,0 +1,8 @@
+-- Number printed on the registration card, to enter results without the QR code
+ALTER TABLE registrations
+	ADD COLUMN card_number INT;
+
+UPDATE registrations
+	SET card_number = (SELECT COUNT(*) FROM registrations r WHERE r.id <= registrations.id);
+
+CREATE UNIQUE INDEX unique_card_number ON registrations (card_number);
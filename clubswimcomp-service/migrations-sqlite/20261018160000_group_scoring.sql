@@ -0,0 +1,7 @@
+-- How the points of the participants are added up for the group rankings
+ALTER TABLE event ADD COLUMN group_scoring TEXT NOT NULL DEFAULT 'sum'
+	CHECK (group_scoring IN ('sum', 'best_results', 'one_per_stroke', 'average'));
+-- Number of results counted with 'best_results'
+ALTER TABLE event ADD COLUMN scoring_result_count INTEGER NULL CHECK (scoring_result_count > 0);
+-- Participants with fewer starts are not ranked
+ALTER TABLE event ADD COLUMN min_starts INTEGER NOT NULL DEFAULT 0 CHECK (min_starts >= 0);
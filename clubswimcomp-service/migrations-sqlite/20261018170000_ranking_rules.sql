@@ -0,0 +1,9 @@
+-- Whether the ranks after a tie are skipped (1, 1, 3) or not (1, 1, 2)
+ALTER TABLE event ADD COLUMN shared_ranks TEXT NOT NULL DEFAULT 'skip'
+	CHECK (shared_ranks IN ('skip', 'dense'));
+-- Precision of the times compared in the competition rankings
+ALTER TABLE event ADD COLUMN time_precision TEXT NOT NULL DEFAULT 'hundredths'
+	CHECK (time_precision IN ('hundredths', 'thousandths'));
+-- How participants with the same points are ordered in the group rankings
+ALTER TABLE event ADD COLUMN group_tie_break TEXT NOT NULL DEFAULT 'none'
+	CHECK (group_tie_break IN ('none', 'best_single_result'));
@@ -0,0 +1,4 @@
+-- Points for the places in each competition, separated by commas
+ALTER TABLE event ADD COLUMN team_placing_points TEXT NOT NULL DEFAULT '9,7,6,5,4,3,2,1';
+-- Only the best swimmers of a team score in each competition
+ALTER TABLE event ADD COLUMN team_max_scorers INTEGER NULL CHECK (team_max_scorers > 0);
@@ -0,0 +1,19 @@
+-- Length of the pool, short course is 25 m and long course is 50 m
+ALTER TABLE event ADD COLUMN course TEXT NOT NULL DEFAULT 'short'
+	CHECK (course IN ('short', 'long'));
+
+-- Times of the swimmers from earlier events, the swimmers are identified by
+-- their names and birthday as the participants belong to a single event
+CREATE TABLE best_times (
+	id			BLOB			PRIMARY KEY NOT NULL,
+	first_name	TEXT			NOT NULL,
+	last_name	TEXT			NOT NULL,
+	birthday	TEXT			NOT NULL,
+	stroke		TEXT			NOT NULL					CHECK (stroke IN ('butterfly', 'back', 'breast', 'freestyle')),
+	distance	INTEGER			NOT NULL					CHECK (distance > 0),
+	course		TEXT			NOT NULL					CHECK (course IN ('short', 'long')),
+	time_millis	INTEGER			NOT NULL					CHECK (time_millis > 0),
+	swum_on		TEXT			NOT NULL,
+	meet		TEXT			NOT NULL,
+	CONSTRAINT one_time_per_swim UNIQUE (first_name, last_name, birthday, stroke, distance, course, swum_on)
+);
@@ -0,0 +1,29 @@
+-- Club records are the fastest times of the club, meet records the fastest
+-- times swum at this meet over the years. New records from results wait for
+-- an approval, approved records are superseded when a faster record is
+-- approved.
+CREATE TABLE records (
+	id				BLOB			PRIMARY KEY NOT NULL,
+	kind			TEXT			NOT NULL					CHECK (kind IN ('club', 'meet')),
+	gender			TEXT			NOT NULL					CHECK (gender IN ('female', 'male')),
+	stroke			TEXT			NOT NULL					CHECK (stroke IN ('butterfly', 'back', 'breast', 'freestyle')),
+	distance		INTEGER			NOT NULL					CHECK (distance > 0),
+	course			TEXT			NOT NULL					CHECK (course IN ('short', 'long')),
+	-- Age the swimmer turns in the year of the swim
+	age_class		INTEGER			NOT NULL					CHECK (age_class >= 0),
+	time_millis		INTEGER			NOT NULL					CHECK (time_millis > 0),
+	first_name		TEXT			NOT NULL,
+	last_name		TEXT			NOT NULL,
+	birthday		TEXT			NOT NULL,
+	swum_on			TEXT			NOT NULL,
+	-- Name of an earlier meet, missing for records swum at this event
+	meet			TEXT			NULL,
+	-- The result the record has been detected in, no reference as the result
+	-- may be removed after the record has been approved
+	registration_id	BLOB			NULL,
+	status			TEXT			NOT NULL					CHECK (status IN ('pending', 'approved', 'rejected', 'superseded')),
+	created_at		TEXT			NOT NULL,
+	decided_at		TEXT			NULL,
+	-- The approved record that replaced this one
+	superseded_by	BLOB			NULL
+);
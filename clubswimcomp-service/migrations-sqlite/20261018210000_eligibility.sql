@@ -0,0 +1,22 @@
+-- Individual starts per participant, unlimited if missing
+ALTER TABLE event ADD COLUMN max_individual_starts INTEGER NULL CHECK (max_individual_starts > 0);
+
+-- Who may register for a competition besides the gender of the competition,
+-- missing limits don't restrict the registrations
+CREATE TABLE competition_eligibility (
+	competition_id		BLOB		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	min_age				INTEGER		NULL						CHECK (min_age >= 0),
+	max_age				INTEGER		NULL						CHECK (max_age >= 0),
+	min_birth_year		INTEGER		NULL,
+	max_birth_year		INTEGER		NULL,
+	-- Participants need a best time in the course of the event that is at
+	-- least this fast
+	qualifying_time		INTEGER		NULL						CHECK (qualifying_time > 0)
+);
+
+-- Only participants of these groups may register, all if there are none
+CREATE TABLE competition_eligible_groups (
+	competition_id		BLOB		NOT NULL					REFERENCES competitions(id) ON DELETE CASCADE,
+	group_id			BLOB		NOT NULL					REFERENCES groups(id) ON DELETE CASCADE,
+	PRIMARY KEY (competition_id, group_id)
+);
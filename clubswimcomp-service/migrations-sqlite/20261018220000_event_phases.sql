@@ -0,0 +1,12 @@
+-- Where the event is in its course, the phase decides which changes are
+-- allowed
+ALTER TABLE event ADD COLUMN phase TEXT NOT NULL DEFAULT 'registration_open'
+	CHECK (phase IN ('registration_open', 'registration_closed', 'meet_in_progress', 'finalized'));
+
+-- Every change of the phase, re-opening a finalized event needs a reason
+CREATE TABLE event_phase_changes (
+	id				BLOB			PRIMARY KEY NOT NULL,
+	phase			TEXT			NOT NULL					CHECK (phase IN ('registration_open', 'registration_closed', 'meet_in_progress', 'finalized')),
+	changed_at		TEXT			NOT NULL,
+	reason			TEXT			NULL
+);
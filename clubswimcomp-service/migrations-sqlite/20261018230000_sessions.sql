@@ -0,0 +1,20 @@
+-- Parts of the event with their own warm-up, the start times of the heats
+-- are estimated from the start of the session
+CREATE TABLE sessions (
+	id					BLOB			PRIMARY KEY NOT NULL,
+	name				TEXT			NOT NULL,
+	-- Local time the first heat starts
+	starts_at			TEXT			NOT NULL,
+	warm_up_minutes		INTEGER			NOT NULL					CHECK (warm_up_minutes >= 0),
+	-- Time between the end of a heat and the start of the next one
+	turnaround_seconds	INTEGER			NOT NULL					CHECK (turnaround_seconds >= 0)
+);
+
+-- The competitions of a session in the order they are swum, a competition is
+-- swum in one session only
+CREATE TABLE session_competitions (
+	session_id			BLOB			NOT NULL					REFERENCES sessions(id) ON DELETE CASCADE,
+	competition_id		BLOB			NOT NULL UNIQUE				REFERENCES competitions(id) ON DELETE CASCADE,
+	position			INTEGER			NOT NULL,
+	PRIMARY KEY (session_id, position)
+);
@@ -0,0 +1,68 @@
+-- Competitions are swum in rounds, the finals and swim-offs are competitions
+-- of the same kind as their prelims.
+--
+-- SQLite can't drop the unique constraint of the competitions, so the table is
+-- rebuilt. The foreign keys are checked when the migration is committed, once
+-- the competitions are back. Dropping the table deletes the rows referencing
+-- it on cascade, they are saved beforehand and restored afterwards.
+PRAGMA defer_foreign_keys = ON;
+
+CREATE TEMPORARY TABLE saved_competitions AS SELECT * FROM competitions;
+CREATE TEMPORARY TABLE saved_competition_eligibility AS SELECT * FROM competition_eligibility;
+CREATE TEMPORARY TABLE saved_competition_eligible_groups AS SELECT * FROM competition_eligible_groups;
+CREATE TEMPORARY TABLE saved_session_competitions AS SELECT * FROM session_competitions;
+
+DROP TABLE competitions;
+
+CREATE TABLE competitions (
+	id			BLOB			PRIMARY KEY NOT NULL,
+	gender		TEXT			NOT NULL					CHECK (gender IN ('female', 'male')),
+	stroke		TEXT			NOT NULL					CHECK (stroke IN ('butterfly', 'back', 'breast', 'freestyle')),
+	distance	INTEGER			NOT NULL 					CHECK((distance % 25) = 0 AND distance > 0),
+	target_time INTEGER			NOT NULL					CHECK (target_time > 0),
+	round		TEXT			NOT NULL DEFAULT 'timed_final'	CHECK (round IN ('timed_final', 'prelims', 'finals', 'swim_off'))
+);
+
+-- A tie at the cutoff can need more than one swim-off
+CREATE UNIQUE INDEX no_same_competitions ON competitions (gender, stroke, distance, round)
+	WHERE round <> 'swim_off';
+
+INSERT INTO competitions (id, gender, stroke, distance, target_time)
+	SELECT id, gender, stroke, distance, target_time FROM saved_competitions;
+INSERT INTO competition_eligibility SELECT * FROM saved_competition_eligibility;
+INSERT INTO competition_eligible_groups SELECT * FROM saved_competition_eligible_groups;
+INSERT INTO session_competitions SELECT * FROM saved_session_competitions;
+
+DROP TABLE saved_competitions;
+DROP TABLE saved_competition_eligibility;
+DROP TABLE saved_competition_eligible_groups;
+DROP TABLE saved_session_competitions;
+
+-- How many swimmers advance from the prelims into their finals
+CREATE TABLE competition_finals (
+	competition_id			BLOB		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	finals_competition_id	BLOB		NOT NULL UNIQUE				REFERENCES competitions(id) ON DELETE CASCADE,
+	finalists				INTEGER		NOT NULL					CHECK (finalists > 0),
+	alternates				INTEGER		NOT NULL					CHECK (alternates >= 0)
+);
+
+-- The swimmers promoted from the prelims, ordered by their place
+CREATE TABLE finals_entries (
+	competition_id		BLOB		NOT NULL					REFERENCES competition_finals(competition_id) ON DELETE CASCADE,
+	participant_id		BLOB		NOT NULL					REFERENCES participants(id) ON DELETE CASCADE,
+	seed				INTEGER		NOT NULL					CHECK (seed > 0),
+	-- Time of the prelims, the finals are seeded with it
+	time_millis			INTEGER		NOT NULL					CHECK (time_millis > 0),
+	status				TEXT		NOT NULL					CHECK (status IN ('finalist', 'alternate', 'scratched')),
+	PRIMARY KEY (competition_id, participant_id),
+	CONSTRAINT one_entry_per_seed UNIQUE (competition_id, seed)
+);
+
+-- Races between the swimmers tied for the last place in the finals, in the
+-- order they are swum
+CREATE TABLE swim_offs (
+	competition_id		BLOB		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	prelims_id			BLOB		NOT NULL					REFERENCES competition_finals(competition_id) ON DELETE CASCADE,
+	number				INTEGER		NOT NULL					CHECK (number > 0),
+	CONSTRAINT one_swim_off_per_number UNIQUE (prelims_id, number)
+);
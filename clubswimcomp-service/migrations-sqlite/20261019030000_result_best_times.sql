@@ -0,0 +1,29 @@
+-- Best times recorded from the results of this event reference their result,
+-- they are removed with the result and are never taken for earlier times.
+-- A swimmer may swim the same race twice a day at this event, e.g. in the
+-- prelims and the finals, so only imported times are unique per day.
+CREATE TABLE best_times_new (
+	id				BLOB			PRIMARY KEY NOT NULL,
+	first_name		TEXT			NOT NULL,
+	last_name		TEXT			NOT NULL,
+	birthday		TEXT			NOT NULL,
+	stroke			TEXT			NOT NULL					CHECK (stroke IN ('butterfly', 'back', 'breast', 'freestyle')),
+	distance		INTEGER			NOT NULL					CHECK (distance > 0),
+	course			TEXT			NOT NULL					CHECK (course IN ('short', 'long')),
+	time_millis		INTEGER			NOT NULL					CHECK (time_millis > 0),
+	swum_on			TEXT			NOT NULL,
+	meet			TEXT			NOT NULL,
+	registration_id	BLOB			NULL		UNIQUE			REFERENCES registration_results(registration_id) ON DELETE CASCADE
+);
+
+INSERT INTO best_times_new (
+	id, first_name, last_name, birthday, stroke, distance, course, time_millis, swum_on, meet
+)
+SELECT id, first_name, last_name, birthday, stroke, distance, course, time_millis, swum_on, meet
+FROM best_times;
+
+DROP TABLE best_times;
+ALTER TABLE best_times_new RENAME TO best_times;
+
+CREATE UNIQUE INDEX one_time_per_swim ON best_times (first_name, last_name, birthday, stroke, distance, course, swum_on)
+	WHERE registration_id IS NULL;
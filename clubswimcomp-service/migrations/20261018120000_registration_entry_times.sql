@@ -0,0 +1,2 @@
+ALTER TABLE registrations
+	ADD COLUMN entry_time_millis INT CHECK (entry_time_millis > 0);
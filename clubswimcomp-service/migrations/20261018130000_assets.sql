@@ -0,0 +1,5 @@
+CREATE TABLE assets (
+	name		TEXT			PRIMARY KEY NOT NULL,
+	content		BYTEA			NOT NULL,
+	updated_at	TIMESTAMPTZ		NOT NULL
+);
@@ -0,0 +1,8 @@
+-- The event itself, there is always at most one row
+CREATE TABLE event (
+	id			UUID			PRIMARY KEY NOT NULL,
+	card_secret	BYTEA			NOT NULL,
+	created_at	TIMESTAMPTZ		NOT NULL
+);
+
+CREATE UNIQUE INDEX event_singleton ON event ((true));
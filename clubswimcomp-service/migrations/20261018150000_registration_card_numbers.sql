@@ -0,0 +1,12 @@
+-- Number printed on the registration card, to enter results without the QR code
+ALTER TABLE registrations
+	ADD COLUMN card_number INT;
+
+UPDATE registrations r
+	SET card_number = numbered.card_number
+	FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id) AS card_number FROM registrations) numbered
+	WHERE r.id = numbered.id;
+
+ALTER TABLE registrations
+	ALTER COLUMN card_number SET NOT NULL,
+	ADD CONSTRAINT unique_card_number UNIQUE (card_number);
@@ -0,0 +1,9 @@
+-- How the points of the participants are added up for the group rankings
+CREATE TYPE group_scoring AS ENUM ('sum', 'best_results', 'one_per_stroke', 'average');
+
+ALTER TABLE event
+	ADD COLUMN group_scoring			group_scoring	NOT NULL DEFAULT 'sum',
+	-- Number of results counted with 'best_results'
+	ADD COLUMN scoring_result_count	INTEGER			NULL CHECK (scoring_result_count > 0),
+	-- Participants with fewer starts are not ranked
+	ADD COLUMN min_starts			INTEGER			NOT NULL DEFAULT 0 CHECK (min_starts >= 0);
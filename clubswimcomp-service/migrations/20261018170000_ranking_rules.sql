@@ -0,0 +1,12 @@
+-- How tied participants are ranked
+CREATE TYPE shared_ranks AS ENUM ('skip', 'dense');
+CREATE TYPE time_precision AS ENUM ('hundredths', 'thousandths');
+CREATE TYPE group_tie_break AS ENUM ('none', 'best_single_result');
+
+ALTER TABLE event
+	-- Whether the ranks after a tie are skipped (1, 1, 3) or not (1, 1, 2)
+	ADD COLUMN shared_ranks		shared_ranks	NOT NULL DEFAULT 'skip',
+	-- Precision of the times compared in the competition rankings
+	ADD COLUMN time_precision	time_precision	NOT NULL DEFAULT 'hundredths',
+	-- How participants with the same points are ordered in the group rankings
+	ADD COLUMN group_tie_break	group_tie_break	NOT NULL DEFAULT 'none';
@@ -0,0 +1,5 @@
+ALTER TABLE event
+	-- Points for the places in each competition, separated by commas
+	ADD COLUMN team_placing_points	TEXT	NOT NULL DEFAULT '9,7,6,5,4,3,2,1',
+	-- Only the best swimmers of a team score in each competition
+	ADD COLUMN team_max_scorers		INTEGER	NULL CHECK (team_max_scorers > 0);
@@ -0,0 +1,21 @@
+-- Length of the pool, short course is 25 m and long course is 50 m
+CREATE TYPE course AS ENUM ('short', 'long');
+
+ALTER TABLE event
+	ADD COLUMN course			course			NOT NULL DEFAULT 'short';
+
+-- Times of the swimmers from earlier events, the swimmers are identified by
+-- their names and birthday as the participants belong to a single event
+CREATE TABLE best_times (
+	id			UUID			PRIMARY KEY NOT NULL		DEFAULT gen_random_uuid(),
+	first_name	TEXT			NOT NULL,
+	last_name	TEXT			NOT NULL,
+	birthday	DATE			NOT NULL,
+	stroke		stroke			NOT NULL,
+	distance	INT				NOT NULL					CHECK (distance > 0),
+	course		course			NOT NULL,
+	time_millis	INT				NOT NULL					CHECK (time_millis > 0),
+	swum_on		DATE			NOT NULL,
+	meet		TEXT			NOT NULL,
+	CONSTRAINT one_time_per_swim UNIQUE (first_name, last_name, birthday, stroke, distance, course, swum_on)
+);
@@ -0,0 +1,33 @@
+-- Club records are the fastest times of the club, meet records the fastest
+-- times swum at this meet over the years
+CREATE TYPE record_kind AS ENUM ('club', 'meet');
+
+-- New records from results wait for an approval, approved records are
+-- superseded when a faster record is approved
+CREATE TYPE record_status AS ENUM ('pending', 'approved', 'rejected', 'superseded');
+
+CREATE TABLE records (
+	id				UUID			PRIMARY KEY NOT NULL		DEFAULT gen_random_uuid(),
+	kind			record_kind		NOT NULL,
+	gender			gender			NOT NULL,
+	stroke			stroke			NOT NULL,
+	distance		INT				NOT NULL					CHECK (distance > 0),
+	course			course			NOT NULL,
+	-- Age the swimmer turns in the year of the swim
+	age_class		INT				NOT NULL					CHECK (age_class >= 0),
+	time_millis		INT				NOT NULL					CHECK (time_millis > 0),
+	first_name		TEXT			NOT NULL,
+	last_name		TEXT			NOT NULL,
+	birthday		DATE			NOT NULL,
+	swum_on			DATE			NOT NULL,
+	-- Name of an earlier meet, missing for records swum at this event
+	meet			TEXT			NULL,
+	-- The result the record has been detected in, no reference as the result
+	-- may be removed after the record has been approved
+	registration_id	UUID			NULL,
+	status			record_status	NOT NULL,
+	created_at		TIMESTAMPTZ		NOT NULL,
+	decided_at		TIMESTAMPTZ		NULL,
+	-- The approved record that replaced this one
+	superseded_by	UUID			NULL
+);
@@ -0,0 +1,23 @@
+-- Individual starts per participant, unlimited if missing
+ALTER TABLE event
+	ADD COLUMN max_individual_starts	INT		NULL CHECK (max_individual_starts > 0);
+
+-- Who may register for a competition besides the gender of the competition,
+-- missing limits don't restrict the registrations
+CREATE TABLE competition_eligibility (
+	competition_id		UUID		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	min_age				INT			NULL						CHECK (min_age >= 0),
+	max_age				INT			NULL						CHECK (max_age >= 0),
+	min_birth_year		INT			NULL,
+	max_birth_year		INT			NULL,
+	-- Participants need a best time in the course of the event that is at
+	-- least this fast
+	qualifying_time		INT			NULL						CHECK (qualifying_time > 0)
+);
+
+-- Only participants of these groups may register, all if there are none
+CREATE TABLE competition_eligible_groups (
+	competition_id		UUID		NOT NULL					REFERENCES competitions(id) ON DELETE CASCADE,
+	group_id			UUID		NOT NULL					REFERENCES groups(id) ON DELETE CASCADE,
+	PRIMARY KEY (competition_id, group_id)
+);
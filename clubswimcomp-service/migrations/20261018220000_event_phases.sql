@@ -0,0 +1,14 @@
+-- Where the event is in its course, the phase decides which changes are
+-- allowed
+CREATE TYPE event_phase AS ENUM ('registration_open', 'registration_closed', 'meet_in_progress', 'finalized');
+
+ALTER TABLE event
+	ADD COLUMN phase	event_phase		NOT NULL DEFAULT 'registration_open';
+
+-- Every change of the phase, re-opening a finalized event needs a reason
+CREATE TABLE event_phase_changes (
+	id				UUID			PRIMARY KEY NOT NULL		DEFAULT gen_random_uuid(),
+	phase			event_phase		NOT NULL,
+	changed_at		TIMESTAMPTZ		NOT NULL,
+	reason			TEXT			NULL
+);
@@ -0,0 +1,42 @@
+-- Competitions are swum in rounds, the finals and swim-offs are competitions
+-- of the same kind as their prelims
+CREATE TYPE competition_round AS ENUM ('timed_final', 'prelims', 'finals', 'swim_off');
+
+ALTER TABLE competitions
+	ADD COLUMN round	competition_round	NOT NULL DEFAULT 'timed_final',
+	DROP CONSTRAINT no_same_competitions;
+
+-- A tie at the cutoff can need more than one swim-off
+CREATE UNIQUE INDEX no_same_competitions ON competitions (gender, stroke, distance, round)
+	WHERE round <> 'swim_off';
+
+-- How many swimmers advance from the prelims into their finals
+CREATE TABLE competition_finals (
+	competition_id			UUID		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	finals_competition_id	UUID		NOT NULL UNIQUE				REFERENCES competitions(id) ON DELETE CASCADE,
+	finalists				INT			NOT NULL					CHECK (finalists > 0),
+	alternates				INT			NOT NULL					CHECK (alternates >= 0)
+);
+
+CREATE TYPE finals_entry_status AS ENUM ('finalist', 'alternate', 'scratched');
+
+-- The swimmers promoted from the prelims, ordered by their place
+CREATE TABLE finals_entries (
+	competition_id		UUID				NOT NULL				REFERENCES competition_finals(competition_id) ON DELETE CASCADE,
+	participant_id		UUID				NOT NULL				REFERENCES participants(id) ON DELETE CASCADE,
+	seed				INT					NOT NULL				CHECK (seed > 0),
+	-- Time of the prelims, the finals are seeded with it
+	time_millis			INT					NOT NULL				CHECK (time_millis > 0),
+	status				finals_entry_status	NOT NULL,
+	PRIMARY KEY (competition_id, participant_id),
+	CONSTRAINT one_entry_per_seed UNIQUE (competition_id, seed)
+);
+
+-- Races between the swimmers tied for the last place in the finals, in the
+-- order they are swum
+CREATE TABLE swim_offs (
+	competition_id		UUID		PRIMARY KEY NOT NULL		REFERENCES competitions(id) ON DELETE CASCADE,
+	prelims_id			UUID		NOT NULL					REFERENCES competition_finals(competition_id) ON DELETE CASCADE,
+	number				INT			NOT NULL					CHECK (number > 0),
+	CONSTRAINT one_swim_off_per_number UNIQUE (prelims_id, number)
+);
@@ -0,0 +1,21 @@
+-- Fees of the event in cents, the surcharge is added to every late entry
+ALTER TABLE event
+	ADD COLUMN participant_fee_cents		INT		NOT NULL DEFAULT 0	CHECK (participant_fee_cents >= 0),
+	ADD COLUMN start_fee_cents				INT		NOT NULL DEFAULT 0	CHECK (start_fee_cents >= 0),
+	ADD COLUMN late_entry_surcharge_cents	INT		NOT NULL DEFAULT 0	CHECK (late_entry_surcharge_cents >= 0),
+	ADD COLUMN currency						TEXT	NOT NULL DEFAULT 'EUR';
+
+-- Registrations accepted after the registration has been closed
+ALTER TABLE registrations
+	ADD COLUMN late_entry	BOOLEAN		NOT NULL DEFAULT false;
+
+-- Payments received from a participant or for a whole group
+CREATE TABLE payments (
+	id				UUID			PRIMARY KEY NOT NULL		DEFAULT gen_random_uuid(),
+	participant_id	UUID			NULL						REFERENCES participants(id) ON DELETE RESTRICT,
+	group_id		UUID			NULL						REFERENCES groups(id) ON DELETE RESTRICT,
+	amount_cents	INT				NOT NULL					CHECK (amount_cents > 0),
+	received_on		DATE			NOT NULL,
+	reference		TEXT			NOT NULL,
+	CHECK ((participant_id IS NULL) <> (group_id IS NULL))
+);
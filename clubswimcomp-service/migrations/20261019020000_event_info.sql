@@ -0,0 +1,4 @@
+-- Name of the event and the organizing club, printed on the documents
+ALTER TABLE event
+	ADD COLUMN name				TEXT	NOT NULL DEFAULT '',
+	ADD COLUMN organization		TEXT	NOT NULL DEFAULT '';
@@ -0,0 +1,10 @@
+-- Best times recorded from the results of this event reference their result,
+-- they are removed with the result and are never taken for earlier times
+ALTER TABLE best_times
+	ADD COLUMN registration_id	UUID	NULL	UNIQUE	REFERENCES registration_results(registration_id) ON DELETE CASCADE;
+
+-- A swimmer may swim the same race twice a day at this event, e.g. in the
+-- prelims and the finals, so only imported times are unique per day
+ALTER TABLE best_times DROP CONSTRAINT one_time_per_swim;
+CREATE UNIQUE INDEX one_time_per_swim ON best_times (first_name, last_name, birthday, stroke, distance, course, swum_on)
+	WHERE registration_id IS NULL;
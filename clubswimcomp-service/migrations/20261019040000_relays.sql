@@ -0,0 +1,12 @@
+-- Relays are swum by teams, one swimmer is registered for each team
+ALTER TABLE competitions
+	ADD COLUMN relay	BOOLEAN		NOT NULL DEFAULT FALSE;
+
+-- A relay can be swum over the same stroke and distance as a single race
+DROP INDEX no_same_competitions;
+CREATE UNIQUE INDEX no_same_competitions ON competitions (gender, stroke, distance, relay, round)
+	WHERE round <> 'swim_off';
+
+ALTER TABLE event
+	-- The places in relays score this many times the placing points
+	ADD COLUMN team_relay_weight	INTEGER	NOT NULL DEFAULT 2 CHECK (team_relay_weight > 0);
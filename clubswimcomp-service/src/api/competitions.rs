@@ -1,13 +1,17 @@
-use axum::{extract::*, http::StatusCode, routing::*};
+use axum::{extract::State, http::HeaderMap, routing::*};
 use clubswimcomp_types::{api, model};
 use tracing::instrument;
+use utoipa::OpenApi;
 use uuid::Uuid;
 
+use crate::infra::typst_compiler::TypstOutput;
 use crate::services::{
-    AddCompetitionError, CompetitionDetailsError, CompetitionScoreboardError,
-    DeleteCompetitionError,
+    AddCompetitionError, AddSwimOffError, CompetitionDetailsError, CompetitionScoreboardError,
+    CompetitionStartListError, DeleteCompetitionError, FinalsDetailsError, PromoteFinalistsError,
+    ScratchFinalistError, SetEligibilityError, SetFinalsError,
 };
 
+use super::extract::{Json, Path, Query};
 use super::{ApiError, AppState};
 
 pub fn router() -> axum::Router<super::AppState> {
@@ -17,46 +21,177 @@ pub fn router() -> axum::Router<super::AppState> {
         .route("/:competition_id", get(competition_details))
         .route("/:competition_id", delete(delete_competition))
         .route("/:competition_id/scoreboard", get(competition_scoreboard))
+        .route("/:competition_id/start-list", get(competition_start_list))
+        .route(
+            "/:competition_id/eligibility",
+            get(competition_eligibility).put(set_competition_eligibility),
+        )
+        .route(
+            "/:competition_id/finals",
+            get(competition_finals).put(set_competition_finals),
+        )
+        .route("/:competition_id/finals/promote", post(promote_finalists))
+        .route("/:competition_id/finals/swim-off", post(add_swim_off))
+        .route("/:competition_id/finals/scratch", post(scratch_finalist))
 }
 
-impl From<&AddCompetitionError> for StatusCode {
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_competitions,
+    add_competition,
+    competition_details,
+    delete_competition,
+    competition_scoreboard,
+    competition_start_list,
+    competition_eligibility,
+    set_competition_eligibility,
+    competition_finals,
+    set_competition_finals,
+    promote_finalists,
+    add_swim_off,
+    scratch_finalist,
+))]
+pub(super) struct ApiDoc;
+
+impl From<&AddCompetitionError> for api::ErrorCode {
     fn from(err: &AddCompetitionError) -> Self {
         match err {
-            AddCompetitionError::InvalidDistance => Self::BAD_REQUEST,
-            AddCompetitionError::SameCompetitionExists => Self::BAD_REQUEST,
-            AddCompetitionError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            AddCompetitionError::InvalidDistance => Self::InvalidDistance,
+            AddCompetitionError::SameCompetitionExists => Self::SameCompetitionExists,
+            AddCompetitionError::EventFinalized => Self::EventFinalized,
+            AddCompetitionError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&CompetitionDetailsError> for StatusCode {
+impl From<&CompetitionDetailsError> for api::ErrorCode {
     fn from(err: &CompetitionDetailsError) -> Self {
         match err {
-            CompetitionDetailsError::CompetitionDoesNotExist => Self::NOT_FOUND,
-            CompetitionDetailsError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            CompetitionDetailsError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            CompetitionDetailsError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&DeleteCompetitionError> for StatusCode {
+impl From<&DeleteCompetitionError> for api::ErrorCode {
     fn from(err: &DeleteCompetitionError) -> Self {
         match err {
-            DeleteCompetitionError::CompetitionDoesNotExist => Self::NOT_FOUND,
-            DeleteCompetitionError::CompetitionHasRegistrations => Self::BAD_REQUEST,
-            DeleteCompetitionError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            DeleteCompetitionError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            DeleteCompetitionError::CompetitionHasRegistrations => {
+                Self::CompetitionHasRegistrations
+            }
+            DeleteCompetitionError::EventFinalized => Self::EventFinalized,
+            DeleteCompetitionError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetEligibilityError> for api::ErrorCode {
+    fn from(err: &SetEligibilityError) -> Self {
+        match err {
+            SetEligibilityError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            SetEligibilityError::GroupDoesNotExist(_) => Self::GroupDoesNotExist,
+            SetEligibilityError::InvalidAgeRange => Self::InvalidRequest,
+            SetEligibilityError::InvalidBirthYearRange => Self::InvalidRequest,
+            SetEligibilityError::InvalidQualifyingTime => Self::InvalidRequest,
+            SetEligibilityError::EventFinalized => Self::EventFinalized,
+            SetEligibilityError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&CompetitionScoreboardError> for StatusCode {
+impl From<&CompetitionScoreboardError> for api::ErrorCode {
     fn from(err: &CompetitionScoreboardError) -> Self {
         match err {
-            CompetitionScoreboardError::CompetitionDoesNotExist => Self::NOT_FOUND,
-            CompetitionScoreboardError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            CompetitionScoreboardError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            CompetitionScoreboardError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&CompetitionStartListError> for api::ErrorCode {
+    fn from(err: &CompetitionStartListError) -> Self {
+        match err {
+            CompetitionStartListError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            CompetitionStartListError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            CompetitionStartListError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            CompetitionStartListError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&FinalsDetailsError> for api::ErrorCode {
+    fn from(err: &FinalsDetailsError) -> Self {
+        match err {
+            FinalsDetailsError::CompetitionHasNoFinals => Self::CompetitionHasNoFinals,
+            FinalsDetailsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetFinalsError> for api::ErrorCode {
+    fn from(err: &SetFinalsError) -> Self {
+        match err {
+            SetFinalsError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            SetFinalsError::InvalidFinalists => Self::InvalidRequest,
+            SetFinalsError::NotPrelims => Self::NotPrelims,
+            SetFinalsError::FinalistsAlreadyPromoted => Self::FinalistsAlreadyPromoted,
+            SetFinalsError::EventFinalized => Self::EventFinalized,
+            SetFinalsError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
+impl From<&PromoteFinalistsError> for api::ErrorCode {
+    fn from(err: &PromoteFinalistsError) -> Self {
+        match err {
+            PromoteFinalistsError::CompetitionHasNoFinals => Self::CompetitionHasNoFinals,
+            PromoteFinalistsError::PrelimsNotFinished => Self::PrelimsNotFinished,
+            PromoteFinalistsError::FinalistsAlreadyPromoted => Self::FinalistsAlreadyPromoted,
+            PromoteFinalistsError::SwimOffRequired => Self::SwimOffRequired,
+            PromoteFinalistsError::SwimOffNotFinished => Self::SwimOffNotFinished,
+            PromoteFinalistsError::EventFinalized => Self::EventFinalized,
+            PromoteFinalistsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&AddSwimOffError> for api::ErrorCode {
+    fn from(err: &AddSwimOffError) -> Self {
+        match err {
+            AddSwimOffError::CompetitionHasNoFinals => Self::CompetitionHasNoFinals,
+            AddSwimOffError::PrelimsNotFinished => Self::PrelimsNotFinished,
+            AddSwimOffError::FinalistsAlreadyPromoted => Self::FinalistsAlreadyPromoted,
+            AddSwimOffError::NoTieAtCutoff => Self::NoTieAtCutoff,
+            AddSwimOffError::SwimOffNotFinished => Self::SwimOffNotFinished,
+            AddSwimOffError::EventFinalized => Self::EventFinalized,
+            AddSwimOffError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ScratchFinalistError> for api::ErrorCode {
+    fn from(err: &ScratchFinalistError) -> Self {
+        match err {
+            ScratchFinalistError::CompetitionHasNoFinals => Self::CompetitionHasNoFinals,
+            ScratchFinalistError::FinalistsNotPromoted => Self::FinalistsNotPromoted,
+            ScratchFinalistError::NotAFinalist => Self::NotAFinalist,
+            ScratchFinalistError::ResultAlreadyExists => Self::ResultAlreadyExists,
+            ScratchFinalistError::EventFinalized => Self::EventFinalized,
+            ScratchFinalistError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/competitions",
+    tag = "competitions",
+    responses(
+        (status = 200, description = "All competitions", body = Vec<model::Competition>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn list_competitions(
     State(state): State<AppState>,
@@ -66,6 +201,17 @@ async fn list_competitions(
     Ok(Json(competitions))
 }
 
+#[utoipa::path(
+    post,
+    path = "/competitions",
+    tag = "competitions",
+    request_body = api::AddCompetitionRequest,
+    responses(
+        (status = 200, description = "The competition has been added", body = api::AddCompetitionResponse),
+        (status = 400, description = "The distance is invalid, the competition already exists or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn add_competition(
     State(state): State<AppState>,
@@ -73,12 +219,32 @@ async fn add_competition(
 ) -> Result<Json<api::AddCompetitionResponse>, ApiError> {
     let competition_service = state.competition_service();
     let competition_id = competition_service
-        .add_competition(b.distance, b.gender, b.stroke, b.target_time)
-        .await?;
+        .add_competition(b.distance, b.gender, b.stroke, b.target_time, b.relay)
+        .await
+        .map_err(|err| match err {
+            AddCompetitionError::InvalidDistance => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("distance", message)
+            }
+            err => ApiError::from(err),
+        })?;
 
     Ok(Json(api::AddCompetitionResponse { competition_id }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/competitions/{competition_id}",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+    ),
+    responses(
+        (status = 200, description = "The competition with all registrations", body = model::CompetitionDetails),
+        (status = 404, description = "The competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn competition_details(
     State(state): State<AppState>,
@@ -92,6 +258,21 @@ async fn competition_details(
     Ok(Json(competition_details))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/competitions/{competition_id}",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+        api::DeleteCompetitionParams,
+    ),
+    responses(
+        (status = 200, description = "The competition has been deleted"),
+        (status = 404, description = "The competition does not exist", body = api::ErrorResponse),
+        (status = 400, description = "There are still registrations for the competition or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn delete_competition(
     State(state): State<AppState>,
@@ -106,6 +287,19 @@ async fn delete_competition(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/competitions/{competition_id}/scoreboard",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+    ),
+    responses(
+        (status = 200, description = "Ranking of the competition", body = model::CompetitionScoreboard),
+        (status = 404, description = "The competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn competition_scoreboard(
     State(state): State<AppState>,
@@ -116,3 +310,241 @@ async fn competition_scoreboard(
 
     Ok(Json(scoreboard))
 }
+
+#[utoipa::path(
+    get,
+    path = "/competitions/{competition_id}/start-list",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+        api::DocumentParams,
+    ),
+    responses(
+        (status = 200, description = "Heat sheet of the competition", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The competition or the page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn competition_start_list(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let start_list_service = state.start_list_service();
+    let start_list = start_list_service
+        .competition_start_list(competition_id, TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, &format!("{competition_id}-start-list"));
+
+    Ok((headers, start_list))
+}
+
+#[utoipa::path(
+    get,
+    path = "/competitions/{competition_id}/eligibility",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+    ),
+    responses(
+        (status = 200, description = "Who may register for the competition", body = model::Eligibility),
+        (status = 404, description = "The competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn competition_eligibility(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+) -> Result<Json<model::Eligibility>, ApiError> {
+    let competition_service = state.competition_service();
+    let eligibility = competition_service
+        .competition_eligibility(competition_id)
+        .await?;
+
+    Ok(Json(eligibility))
+}
+
+#[utoipa::path(
+    put,
+    path = "/competitions/{competition_id}/eligibility",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+    ),
+    request_body = model::Eligibility,
+    responses(
+        (status = 200, description = "The following registrations are checked against the new rules"),
+        (status = 400, description = "The ranges or the qualifying time are invalid, or the event is finalized", body = api::ErrorResponse),
+        (status = 404, description = "The competition or one of the groups does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_competition_eligibility(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+    Json(eligibility): Json<model::Eligibility>,
+) -> Result<(), ApiError> {
+    let competition_service = state.competition_service();
+    competition_service
+        .set_competition_eligibility(competition_id, eligibility)
+        .await
+        .map_err(|err| {
+            let field = match err {
+                SetEligibilityError::GroupDoesNotExist(_) => "group_ids",
+                SetEligibilityError::InvalidAgeRange => "min_age",
+                SetEligibilityError::InvalidBirthYearRange => "min_birth_year",
+                SetEligibilityError::InvalidQualifyingTime => "qualifying_time",
+                SetEligibilityError::CompetitionDoesNotExist
+                | SetEligibilityError::EventFinalized
+                | SetEligibilityError::RepositoryError(_) => return ApiError::from(err),
+            };
+            let message = err.to_string();
+            ApiError::from(err).with_field_error(field, message)
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/competitions/{competition_id}/finals",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the prelims"),
+    ),
+    responses(
+        (status = 200, description = "The finals with the promoted swimmers", body = model::Finals),
+        (status = 404, description = "The competition has no finals", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn competition_finals(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+) -> Result<Json<model::Finals>, ApiError> {
+    let finals_service = state.finals_service();
+    let finals = finals_service.finals(competition_id).await?;
+    Ok(Json(finals))
+}
+
+#[utoipa::path(
+    put,
+    path = "/competitions/{competition_id}/finals",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the competition"),
+    ),
+    request_body = api::FinalsRequest,
+    responses(
+        (status = 200, description = "The competition is swum in prelims and finals", body = api::FinalsResponse),
+        (status = 400, description = "The number of finalists is invalid, the competition is no prelims or the finalists have been promoted", body = api::ErrorResponse),
+        (status = 404, description = "The competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_competition_finals(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+    Json(request): Json<api::FinalsRequest>,
+) -> Result<Json<api::FinalsResponse>, ApiError> {
+    let finals_service = state.finals_service();
+    let finals_competition_id = finals_service
+        .set_finals(competition_id, request)
+        .await
+        .map_err(|err| match err {
+            SetFinalsError::InvalidFinalists => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("finalists", message)
+            }
+            _ => ApiError::from(err),
+        })?;
+
+    Ok(Json(api::FinalsResponse {
+        finals_competition_id,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/competitions/{competition_id}/finals/promote",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the prelims"),
+    ),
+    responses(
+        (status = 200, description = "The finalists and alternates have been promoted"),
+        (status = 400, description = "The prelims are not finished, a swim-off is required or the finalists have been promoted", body = api::ErrorResponse),
+        (status = 404, description = "The competition has no finals", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn promote_finalists(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+) -> Result<(), ApiError> {
+    let finals_service = state.finals_service();
+    finals_service.promote_finalists(competition_id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/competitions/{competition_id}/finals/swim-off",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the prelims"),
+    ),
+    responses(
+        (status = 200, description = "The swim-off of the tie at the cutoff has been added", body = api::AddSwimOffResponse),
+        (status = 400, description = "There is no tie at the cutoff or its swim-off is not finished", body = api::ErrorResponse),
+        (status = 404, description = "The competition has no finals", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn add_swim_off(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+) -> Result<Json<api::AddSwimOffResponse>, ApiError> {
+    let finals_service = state.finals_service();
+    let competition_id = finals_service.add_swim_off(competition_id).await?;
+    Ok(Json(api::AddSwimOffResponse { competition_id }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/competitions/{competition_id}/finals/scratch",
+    tag = "competitions",
+    params(
+        ("competition_id" = Uuid, Path, description = "Id of the prelims"),
+    ),
+    request_body = api::ScratchFinalistRequest,
+    responses(
+        (status = 200, description = "The finalist has been withdrawn", body = api::ScratchFinalistResponse),
+        (status = 400, description = "The participant is not a finalist or already has a result", body = api::ErrorResponse),
+        (status = 404, description = "The competition has no finals", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn scratch_finalist(
+    State(state): State<AppState>,
+    Path(competition_id): Path<Uuid>,
+    Json(request): Json<api::ScratchFinalistRequest>,
+) -> Result<Json<api::ScratchFinalistResponse>, ApiError> {
+    let finals_service = state.finals_service();
+    let promoted_participant_id = finals_service
+        .scratch_finalist(competition_id, request.participant_id)
+        .await?;
+
+    Ok(Json(api::ScratchFinalistResponse {
+        promoted_participant_id,
+    }))
+}
@@ -0,0 +1,64 @@
+//! Correlation ids tie API responses to the tracing logs of a request.
+//!
+//! Every request gets a fresh id which is part of the request span, returned
+//! in the `x-correlation-id` header and included in error responses.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+use uuid::Uuid;
+
+static HEADER: HeaderName = HeaderName::from_static("x-correlation-id");
+
+tokio::task_local! {
+    static CORRELATION_ID: Uuid;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CorrelationId(Uuid);
+
+/// Middleware assigning a correlation id to each request.
+///
+/// Must wrap the [`tower_http::trace::TraceLayer`] so [`make_span`] can pick
+/// up the id.
+pub async fn assign(mut request: Request, next: Next) -> Response {
+    let correlation_id = Uuid::new_v4();
+    request
+        .extensions_mut()
+        .insert(CorrelationId(correlation_id));
+
+    let mut response = CORRELATION_ID
+        .scope(correlation_id, next.run(request))
+        .await;
+
+    let header_value = HeaderValue::from_str(&correlation_id.to_string())
+        .expect("UUIDs are always valid header values");
+    response.headers_mut().insert(HEADER.clone(), header_value);
+
+    response
+}
+
+/// Correlation id of the request currently being handled.
+pub fn current() -> Option<Uuid> {
+    CORRELATION_ID.try_with(|id| *id).ok()
+}
+
+/// Create the tracing span of a request including its correlation id.
+pub fn make_span<B>(request: &axum::http::Request<B>) -> Span {
+    let correlation_id = request
+        .extensions()
+        .get::<CorrelationId>()
+        .map(|id| id.0.to_string())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        correlation_id,
+    )
+}
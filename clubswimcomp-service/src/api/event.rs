@@ -1,40 +1,865 @@
 use axum::{
-    extract::State,
-    http::{header, HeaderMap, StatusCode},
+    extract::{DefaultBodyLimit, State},
+    http::{header, HeaderMap},
     routing::*,
 };
+use clubswimcomp_types::{api, backup, model};
 use tracing::instrument;
+use utoipa::OpenApi;
 
-use crate::services::EventRegistrationCardsError;
+use crate::infra::typst_compiler::TypstOutput;
+use crate::services::{
+    CardCalibrationError, ChangePhaseError, EventCertificatesError, EventRegistrationCardsError,
+    EventStartListError, ReopenEventError, RestoreBackupError, ResultsProtocolError,
+    ServiceRepositoryError, SetCourseError, SetEntryFeesError, SetEventInfoError,
+    SetGroupScoringError, SetRankingRulesError, SetRegistrationLimitsError, SetTeamScoringError,
+    TimetableError,
+};
 
+use super::extract::{Json, Query};
 use super::{ApiError, AppState};
 
+/// Backups contain the whole event and easily exceed the default body limit.
+const BACKUP_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
 pub fn router() -> axum::Router<super::AppState> {
-    Router::new().route("/cards", get(registration_cards))
+    Router::new()
+        .route("/cards", get(registration_cards))
+        .route("/cards/calibration", get(card_calibration))
+        .route("/start-list", get(start_list))
+        .route("/schedule", get(schedule))
+        .route("/timetable", get(timetable))
+        .route("/results.pdf", get(results_protocol))
+        .route("/certificates", get(certificates))
+        .route("/info", get(event_info).put(set_event_info))
+        .route("/group-scoring", get(group_scoring).put(set_group_scoring))
+        .route("/ranking-rules", get(ranking_rules).put(set_ranking_rules))
+        .route("/team-scoring", get(team_scoring).put(set_team_scoring))
+        .route("/course", get(course).put(set_course))
+        .route("/phase", get(event_phase).put(change_phase))
+        .route("/reopen", post(reopen_event))
+        .route(
+            "/registration-limits",
+            get(registration_limits).put(set_registration_limits),
+        )
+        .route("/entry-fees", get(entry_fees).put(set_entry_fees))
+        .route(
+            "/backup",
+            get(download_backup)
+                .post(restore_backup)
+                .layer(DefaultBodyLimit::max(BACKUP_BODY_LIMIT)),
+        )
 }
 
-impl From<&EventRegistrationCardsError> for StatusCode {
+#[derive(OpenApi)]
+#[openapi(paths(
+    registration_cards,
+    card_calibration,
+    start_list,
+    schedule,
+    timetable,
+    results_protocol,
+    certificates,
+    event_info,
+    set_event_info,
+    group_scoring,
+    set_group_scoring,
+    ranking_rules,
+    set_ranking_rules,
+    team_scoring,
+    set_team_scoring,
+    course,
+    set_course,
+    event_phase,
+    change_phase,
+    reopen_event,
+    registration_limits,
+    set_registration_limits,
+    entry_fees,
+    set_entry_fees,
+    download_backup,
+    restore_backup
+))]
+pub(super) struct ApiDoc;
+
+impl From<&EventRegistrationCardsError> for api::ErrorCode {
     fn from(err: &EventRegistrationCardsError) -> Self {
         match err {
-            EventRegistrationCardsError::PdfGenerationFailed(_) => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
-            EventRegistrationCardsError::RepositoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            EventRegistrationCardsError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            EventRegistrationCardsError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            EventRegistrationCardsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetGroupScoringError> for api::ErrorCode {
+    fn from(err: &SetGroupScoringError) -> Self {
+        match err {
+            SetGroupScoringError::InvalidResultCount => Self::InvalidRequest,
+            SetGroupScoringError::EventFinalized => Self::EventFinalized,
+            SetGroupScoringError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetTeamScoringError> for api::ErrorCode {
+    fn from(err: &SetTeamScoringError) -> Self {
+        match err {
+            SetTeamScoringError::NoPlacingPoints => Self::InvalidRequest,
+            SetTeamScoringError::PlacingPointsIncrease => Self::InvalidRequest,
+            SetTeamScoringError::InvalidMaxScorers => Self::InvalidRequest,
+            SetTeamScoringError::InvalidRelayWeight => Self::InvalidRequest,
+            SetTeamScoringError::EventFinalized => Self::EventFinalized,
+            SetTeamScoringError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetRankingRulesError> for api::ErrorCode {
+    fn from(err: &SetRankingRulesError) -> Self {
+        match err {
+            SetRankingRulesError::EventFinalized => Self::EventFinalized,
+            SetRankingRulesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetCourseError> for api::ErrorCode {
+    fn from(err: &SetCourseError) -> Self {
+        match err {
+            SetCourseError::EventFinalized => Self::EventFinalized,
+            SetCourseError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetRegistrationLimitsError> for api::ErrorCode {
+    fn from(err: &SetRegistrationLimitsError) -> Self {
+        match err {
+            SetRegistrationLimitsError::InvalidMaxIndividualStarts => Self::InvalidRequest,
+            SetRegistrationLimitsError::EventFinalized => Self::EventFinalized,
+            SetRegistrationLimitsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetEntryFeesError> for api::ErrorCode {
+    fn from(err: &SetEntryFeesError) -> Self {
+        match err {
+            SetEntryFeesError::InvalidFee => Self::InvalidRequest,
+            SetEntryFeesError::InvalidCurrency => Self::InvalidRequest,
+            SetEntryFeesError::EventFinalized => Self::EventFinalized,
+            SetEntryFeesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SetEventInfoError> for api::ErrorCode {
+    fn from(err: &SetEventInfoError) -> Self {
+        match err {
+            SetEventInfoError::MissingName => Self::InvalidRequest,
+            SetEventInfoError::EventFinalized => Self::EventFinalized,
+            SetEventInfoError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ChangePhaseError> for api::ErrorCode {
+    fn from(err: &ChangePhaseError) -> Self {
+        match err {
+            ChangePhaseError::EventFinalized => Self::EventFinalized,
+            ChangePhaseError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ReopenEventError> for api::ErrorCode {
+    fn from(err: &ReopenEventError) -> Self {
+        match err {
+            ReopenEventError::EventNotFinalized => Self::EventNotFinalized,
+            ReopenEventError::InvalidPhase => Self::InvalidRequest,
+            ReopenEventError::MissingReason => Self::InvalidRequest,
+            ReopenEventError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
+impl From<&CardCalibrationError> for api::ErrorCode {
+    fn from(err: &CardCalibrationError) -> Self {
+        match err {
+            CardCalibrationError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            CardCalibrationError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+        }
+    }
+}
+
+impl From<&EventStartListError> for api::ErrorCode {
+    fn from(err: &EventStartListError) -> Self {
+        match err {
+            EventStartListError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            EventStartListError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            EventStartListError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&TimetableError> for api::ErrorCode {
+    fn from(err: &TimetableError) -> Self {
+        match err {
+            TimetableError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            TimetableError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            TimetableError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&EventCertificatesError> for api::ErrorCode {
+    fn from(err: &EventCertificatesError) -> Self {
+        match err {
+            EventCertificatesError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            EventCertificatesError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            EventCertificatesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ResultsProtocolError> for api::ErrorCode {
+    fn from(err: &ResultsProtocolError) -> Self {
+        match err {
+            ResultsProtocolError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            ResultsProtocolError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            ResultsProtocolError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&RestoreBackupError> for api::ErrorCode {
+    fn from(err: &RestoreBackupError) -> Self {
+        match err {
+            RestoreBackupError::UnsupportedVersion(_) => Self::UnsupportedBackupVersion,
+            RestoreBackupError::DatabaseNotEmpty => Self::DatabaseNotEmpty,
+            RestoreBackupError::InvalidBackup(_) => Self::InvalidBackup,
+            RestoreBackupError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/cards",
+    operation_id = "event_registration_cards",
+    tag = "event",
+    params(api::CardLayoutParams, api::DocumentParams),
+    responses(
+        (status = 200, description = "Registration cards of all participants", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 400, description = "The label size is not supported", body = api::ErrorResponse),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn registration_cards(
     State(state): State<AppState>,
+    Query(layout): Query<api::CardLayoutParams>,
+    Query(document): Query<api::DocumentParams>,
 ) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let layout = super::card_layout(&layout)?;
+
     let registration_card_service = state.registration_card_service();
-    let cards = registration_card_service.all_registration_cards().await?;
+    let cards = registration_card_service
+        .all_registration_cards(layout, TypstOutput::from(&document))
+        .await?;
 
-    let file_name = format!("event-registration-cards.pdf");
+    let headers = super::document_headers(&document, "event-registration-cards");
+
+    Ok((headers, cards))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/cards/calibration",
+    operation_id = "event_card_calibration",
+    tag = "event",
+    params(api::CardLayoutParams, api::DocumentParams),
+    responses(
+        (status = 200, description = "Test grid with the outlines of the registration cards", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 400, description = "The label size is not supported", body = api::ErrorResponse),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn card_calibration(
+    State(state): State<AppState>,
+    Query(layout): Query<api::CardLayoutParams>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let layout = super::card_layout(&layout)?;
+
+    let registration_card_service = state.registration_card_service();
+    let calibration = registration_card_service
+        .calibration_sheet(layout, TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, "card-calibration");
+
+    Ok((headers, calibration))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/start-list",
+    operation_id = "event_start_list",
+    tag = "event",
+    params(api::DocumentParams),
+    responses(
+        (status = 200, description = "Heat sheets of all competitions", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn start_list(
+    State(state): State<AppState>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let start_list_service = state.start_list_service();
+    let start_list = start_list_service
+        .event_start_list(TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, "event-start-list");
+
+    Ok((headers, start_list))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/schedule",
+    tag = "event",
+    responses(
+        (status = 200, description = "Sessions with the estimated start of every heat", body = model::Schedule),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn schedule(State(state): State<AppState>) -> Result<Json<model::Schedule>, ApiError> {
+    let schedule_service = state.schedule_service();
+    let schedule = schedule_service.schedule().await?;
+    Ok(Json(schedule))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/timetable",
+    tag = "event",
+    params(api::DocumentParams),
+    responses(
+        (status = 200, description = "Printable timetable of all sessions", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn timetable(
+    State(state): State<AppState>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let schedule_service = state.schedule_service();
+    let timetable = schedule_service
+        .timetable(TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, "timetable");
+
+    Ok((headers, timetable))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/results.pdf",
+    tag = "event",
+    params(api::DocumentParams),
+    responses(
+        (status = 200, description = "Results protocol of the whole event", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn results_protocol(
+    State(state): State<AppState>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let score_service = state.score_service();
+    let protocol = score_service
+        .results_protocol(TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, "event-results");
+
+    Ok((headers, protocol))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/certificates",
+    operation_id = "event_certificates",
+    tag = "event",
+    params(api::CertificatesParams, api::DocumentParams),
+    responses(
+        (status = 200, description = "Certificates of all participants, sorted by group and name", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn certificates(
+    State(state): State<AppState>,
+    Query(params): Query<api::CertificatesParams>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let score_service = state.score_service();
+    let certificates = score_service
+        .event_certificates(
+            params.skip_missing_results.unwrap_or(false),
+            TypstOutput::from(&document),
+        )
+        .await?;
+
+    let headers = super::document_headers(&document, "event-certificates");
+
+    Ok((headers, certificates))
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/info",
+    tag = "event",
+    responses(
+        (status = 200, description = "The name and organizer of the event", body = model::EventInfo),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn event_info(State(state): State<AppState>) -> Result<Json<model::EventInfo>, ApiError> {
+    let event_service = state.event_service();
+    let info = event_service.event_info().await?;
+
+    Ok(Json(info))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/info",
+    tag = "event",
+    request_body = model::EventInfo,
+    responses(
+        (status = 200, description = "The documents are printed with the new name and organizer"),
+        (status = 400, description = "The name is missing or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_event_info(
+    State(state): State<AppState>,
+    Json(info): Json<model::EventInfo>,
+) -> Result<(), ApiError> {
+    let event_service = state.event_service();
+    event_service
+        .set_event_info(info)
+        .await
+        .map_err(|err| match err {
+            SetEventInfoError::MissingName => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("name", message)
+            }
+            err => ApiError::from(err),
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/group-scoring",
+    tag = "event",
+    responses(
+        (status = 200, description = "How the results are scored in the group rankings", body = model::GroupScoring),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn group_scoring(
+    State(state): State<AppState>,
+) -> Result<Json<model::GroupScoring>, ApiError> {
+    let score_service = state.score_service();
+    let scoring = score_service.group_scoring().await?;
+
+    Ok(Json(scoring))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/group-scoring",
+    tag = "event",
+    request_body = model::GroupScoring,
+    responses(
+        (status = 200, description = "The group rankings are scored with the new rules"),
+        (status = 400, description = "The rules do not count any result or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_group_scoring(
+    State(state): State<AppState>,
+    Json(scoring): Json<model::GroupScoring>,
+) -> Result<(), ApiError> {
+    let score_service = state.score_service();
+    score_service
+        .set_group_scoring(scoring)
+        .await
+        .map_err(|err| match err {
+            SetGroupScoringError::InvalidResultCount => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("rule.count", message)
+            }
+            err => ApiError::from(err),
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/ranking-rules",
+    tag = "event",
+    responses(
+        (status = 200, description = "How tied participants are ranked", body = model::RankingRules),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn ranking_rules(
+    State(state): State<AppState>,
+) -> Result<Json<model::RankingRules>, ApiError> {
+    let score_service = state.score_service();
+    let rules = score_service.ranking_rules().await?;
+
+    Ok(Json(rules))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/ranking-rules",
+    tag = "event",
+    request_body = model::RankingRules,
+    responses(
+        (status = 200, description = "The rankings use the new rules"),
+        (status = 400, description = "The event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_ranking_rules(
+    State(state): State<AppState>,
+    Json(rules): Json<model::RankingRules>,
+) -> Result<(), ApiError> {
+    let score_service = state.score_service();
+    score_service.set_ranking_rules(rules).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/team-scoring",
+    tag = "event",
+    responses(
+        (status = 200, description = "How the places score for the teams", body = model::TeamScoring),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn team_scoring(State(state): State<AppState>) -> Result<Json<model::TeamScoring>, ApiError> {
+    let team_service = state.team_service();
+    let scoring = team_service.team_scoring().await?;
+
+    Ok(Json(scoring))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/team-scoring",
+    tag = "event",
+    request_body = model::TeamScoring,
+    responses(
+        (status = 200, description = "The team standings use the new scoring"),
+        (status = 400, description = "The placing points, the maximum number of scorers or the relay weight are invalid, or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_team_scoring(
+    State(state): State<AppState>,
+    Json(scoring): Json<model::TeamScoring>,
+) -> Result<(), ApiError> {
+    let team_service = state.team_service();
+    team_service
+        .set_team_scoring(scoring)
+        .await
+        .map_err(|err| {
+            let field = match err {
+                SetTeamScoringError::NoPlacingPoints
+                | SetTeamScoringError::PlacingPointsIncrease => "placing_points",
+                SetTeamScoringError::InvalidMaxScorers => "max_scorers",
+                SetTeamScoringError::InvalidRelayWeight => "relay_weight",
+                SetTeamScoringError::EventFinalized | SetTeamScoringError::RepositoryError(_) => {
+                    return ApiError::from(err)
+                }
+            };
+            let message = err.to_string();
+            ApiError::from(err).with_field_error(field, message)
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/course",
+    tag = "event",
+    responses(
+        (status = 200, description = "The pool of the event, best times are compared in this course", body = model::Course),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn course(State(state): State<AppState>) -> Result<Json<model::Course>, ApiError> {
+    let best_time_service = state.best_time_service();
+    let course = best_time_service.course().await?;
+
+    Ok(Json(course))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/course",
+    tag = "event",
+    request_body = model::Course,
+    responses(
+        (status = 200, description = "The personal bests are compared in the new course"),
+        (status = 400, description = "The event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_course(
+    State(state): State<AppState>,
+    Json(course): Json<model::Course>,
+) -> Result<(), ApiError> {
+    let best_time_service = state.best_time_service();
+    best_time_service.set_course(course).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/phase",
+    tag = "event",
+    responses(
+        (status = 200, description = "The current phase of the event and all phase changes", body = model::EventPhaseDetails),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn event_phase(
+    State(state): State<AppState>,
+) -> Result<Json<model::EventPhaseDetails>, ApiError> {
+    let event_service = state.event_service();
+    let phase = event_service.event_phase().await?;
+
+    Ok(Json(phase))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/phase",
+    tag = "event",
+    request_body = api::ChangePhaseRequest,
+    responses(
+        (status = 200, description = "The event is in the new phase"),
+        (status = 400, description = "The event is finalized and has to be re-opened", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn change_phase(
+    State(state): State<AppState>,
+    Json(b): Json<api::ChangePhaseRequest>,
+) -> Result<(), ApiError> {
+    let event_service = state.event_service();
+    event_service.change_phase(b.phase).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/reopen",
+    tag = "event",
+    request_body = api::ReopenEventRequest,
+    responses(
+        (status = 200, description = "The event has been re-opened"),
+        (status = 400, description = "The event is not finalized or the request is invalid", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn reopen_event(
+    State(state): State<AppState>,
+    Json(b): Json<api::ReopenEventRequest>,
+) -> Result<(), ApiError> {
+    let event_service = state.event_service();
+    event_service
+        .reopen_event(b.phase, &b.reason)
+        .await
+        .map_err(|err| match err {
+            ReopenEventError::InvalidPhase => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("phase", message)
+            }
+            ReopenEventError::MissingReason => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("reason", message)
+            }
+            err => ApiError::from(err),
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/registration-limits",
+    tag = "event",
+    responses(
+        (status = 200, description = "The limits of the registrations of each participant", body = model::RegistrationLimits),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn registration_limits(
+    State(state): State<AppState>,
+) -> Result<Json<model::RegistrationLimits>, ApiError> {
+    let participant_service = state.participant_service();
+    let limits = participant_service.registration_limits().await?;
+
+    Ok(Json(limits))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/registration-limits",
+    tag = "event",
+    request_body = model::RegistrationLimits,
+    responses(
+        (status = 200, description = "The following registrations are checked against the new limits"),
+        (status = 400, description = "The maximum number of individual starts is invalid or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_registration_limits(
+    State(state): State<AppState>,
+    Json(limits): Json<model::RegistrationLimits>,
+) -> Result<(), ApiError> {
+    let participant_service = state.participant_service();
+    participant_service
+        .set_registration_limits(limits)
+        .await
+        .map_err(|err| match err {
+            SetRegistrationLimitsError::InvalidMaxIndividualStarts => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("max_individual_starts", message)
+            }
+            err => ApiError::from(err),
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/entry-fees",
+    tag = "event",
+    responses(
+        (status = 200, description = "The fees of the entries", body = model::EntryFees),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn entry_fees(State(state): State<AppState>) -> Result<Json<model::EntryFees>, ApiError> {
+    let fee_service = state.fee_service();
+    let fees = fee_service.entry_fees().await?;
+
+    Ok(Json(fees))
+}
+
+#[utoipa::path(
+    put,
+    path = "/event/entry-fees",
+    tag = "event",
+    request_body = model::EntryFees,
+    responses(
+        (status = 200, description = "The amounts owed are calculated with the new fees"),
+        (status = 400, description = "A fee or the currency is invalid, or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn set_entry_fees(
+    State(state): State<AppState>,
+    Json(fees): Json<model::EntryFees>,
+) -> Result<(), ApiError> {
+    let fee_service = state.fee_service();
+    fee_service
+        .set_entry_fees(fees)
+        .await
+        .map_err(|err| match err {
+            SetEntryFeesError::InvalidCurrency => {
+                let message = err.to_string();
+                ApiError::from(err).with_field_error("currency", message)
+            }
+            err => ApiError::from(err),
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/event/backup",
+    tag = "event",
+    responses(
+        (status = 200, description = "Backup of the whole event", body = backup::Backup),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn download_backup(
+    State(state): State<AppState>,
+) -> Result<(HeaderMap, Json<backup::Backup>), ApiError> {
+    let backup_service = state.backup_service();
+    let backup = backup_service.create_backup().await?;
+
+    let file_name = format!(
+        "clubswimcomp-backup-{}.json",
+        backup.created_at.format("%Y%m%d-%H%M%S")
+    );
 
     let mut headers = HeaderMap::new();
-    headers.append(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
     headers.append(
         header::CONTENT_DISPOSITION,
         format!("attachment; filename=\"{file_name}\"")
@@ -42,5 +867,34 @@ async fn registration_cards(
             .unwrap(),
     );
 
-    Ok((headers, cards))
+    Ok((headers, Json(backup)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/event/backup",
+    tag = "event",
+    request_body = backup::Backup,
+    responses(
+        (status = 200, description = "The backup has been restored"),
+        (status = 400, description = "The backup is invalid or the database is not empty", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state, backup))]
+async fn restore_backup(
+    State(state): State<AppState>,
+    Json(backup): Json<backup::Backup>,
+) -> Result<(), ApiError> {
+    let backup_service = state.backup_service();
+    backup_service.restore_backup(backup).await?;
+
+    // The restored assets have to be used for the following documents
+    let template_service = state.template_service();
+    template_service
+        .reload_assets()
+        .await
+        .map_err(ServiceRepositoryError::RepositoryError)?;
+
+    Ok(())
 }
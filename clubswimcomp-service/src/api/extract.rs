@@ -0,0 +1,119 @@
+//! Extractors rejecting invalid requests with an [`ApiError`].
+//!
+//! They wrap the axum extractors of the same name, so the handlers only have
+//! to import them instead of the ones from axum.
+
+use std::error::Error;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        FromRequest, FromRequestParts, Request,
+    },
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use clubswimcomp_types::api;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::ApiError;
+
+pub(super) struct Json<T>(pub T);
+
+pub(super) struct Path<T>(pub T);
+
+pub(super) struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Json::<T>::from_request(req, state).await {
+            Ok(axum::Json(value)) => Ok(Self(value)),
+            Err(rejection) => Err(json_rejection(rejection)),
+        }
+    }
+}
+
+impl<T> IntoResponse for Json<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Self(value)),
+            Err(rejection) => Err(path_rejection(rejection)),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Self(value)),
+            Err(rejection) => Err(query_rejection(rejection)),
+        }
+    }
+}
+
+fn json_rejection(rejection: JsonRejection) -> ApiError {
+    let error = ApiError::with_message(api::ErrorCode::InvalidRequest, rejection.body_text());
+
+    // The JSON deserializer records the path of the field that failed
+    let mut source = rejection.source();
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            return error.with_field_error(&err.path().to_string(), err.inner().to_string());
+        }
+        source = err.source();
+    }
+
+    error
+}
+
+fn path_rejection(rejection: PathRejection) -> ApiError {
+    let message = rejection.body_text();
+    match rejection {
+        PathRejection::FailedToDeserializePathParams(err) => {
+            let field = match err.kind() {
+                ErrorKind::ParseErrorAtKey { key, .. }
+                | ErrorKind::InvalidUtf8InPathParam { key } => key.clone(),
+                _ => "path".to_string(),
+            };
+            ApiError::with_message(api::ErrorCode::InvalidRequest, message)
+                .with_field_error(&field, err.body_text())
+        }
+        _ => ApiError::with_message(api::ErrorCode::InvalidRequest, message),
+    }
+}
+
+fn query_rejection(rejection: QueryRejection) -> ApiError {
+    ApiError::with_message(api::ErrorCode::InvalidRequest, rejection.body_text())
+}
@@ -1,31 +1,77 @@
-use axum::{extract::*, http::StatusCode, routing::*};
+use axum::{extract::State, http::HeaderMap, routing::*};
 use clubswimcomp_types::{
-    api::{AddGroupRequest, AddGroupResponse},
+    api::{self, AddGroupRequest, AddGroupResponse},
     model,
 };
 use tracing::instrument;
+use utoipa::OpenApi;
 use uuid::Uuid;
 
-use crate::services::GroupResultError;
+use crate::infra::typst_compiler::TypstOutput;
+use crate::services::{AddGroupError, GroupCertificatesError, GroupResultError};
 
+use super::extract::{Json, Path, Query};
 use super::{ApiError, AppState};
 
 pub fn router() -> axum::Router<super::AppState> {
     Router::new()
         .route("/", get(list_groups))
         .route("/", post(add_group))
+        .route("/team-standings", get(team_standings))
         .route("/:group_id", get(group_details))
+        .route("/:group_id/certificates", get(group_certificates))
+        .route("/:group_id/invoice", get(group_invoice))
 }
 
-impl From<&GroupResultError> for StatusCode {
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_groups,
+    add_group,
+    team_standings,
+    group_details,
+    group_certificates,
+    group_invoice
+))]
+pub(super) struct ApiDoc;
+
+impl From<&AddGroupError> for api::ErrorCode {
+    fn from(err: &AddGroupError) -> Self {
+        match err {
+            AddGroupError::EventFinalized => Self::EventFinalized,
+            AddGroupError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&GroupResultError> for api::ErrorCode {
     fn from(err: &GroupResultError) -> Self {
         match err {
-            GroupResultError::GroupDoesNotExist => StatusCode::NOT_FOUND,
-            GroupResultError::RepositoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            GroupResultError::GroupDoesNotExist => Self::GroupDoesNotExist,
+            GroupResultError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
+impl From<&GroupCertificatesError> for api::ErrorCode {
+    fn from(err: &GroupCertificatesError) -> Self {
+        match err {
+            GroupCertificatesError::GroupDoesNotExist => Self::GroupDoesNotExist,
+            GroupCertificatesError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            GroupCertificatesError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            GroupCertificatesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/groups",
+    tag = "groups",
+    responses(
+        (status = 200, description = "All groups", body = Vec<model::Group>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn list_groups(State(state): State<AppState>) -> Result<Json<Vec<model::Group>>, ApiError> {
     let group_service = state.group_service();
@@ -33,6 +79,35 @@ async fn list_groups(State(state): State<AppState>) -> Result<Json<Vec<model::Gr
     Ok(Json(groups))
 }
 
+#[utoipa::path(
+    get,
+    path = "/groups/team-standings",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Standings of the groups as teams, best first", body = Vec<model::TeamStanding>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn team_standings(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<model::TeamStanding>>, ApiError> {
+    let team_service = state.team_service();
+    let standings = team_service.team_standings().await?;
+    Ok(Json(standings))
+}
+
+#[utoipa::path(
+    get,
+    path = "/groups/{group_id}",
+    tag = "groups",
+    params(("group_id" = Uuid, Path, description = "Id of the group")),
+    responses(
+        (status = 200, description = "The group with the scores of its participants", body = model::GroupDetails),
+        (status = 404, description = "The group does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn group_details(
     State(state): State<AppState>,
@@ -43,6 +118,17 @@ async fn group_details(
     Ok(Json(group_details))
 }
 
+#[utoipa::path(
+    post,
+    path = "/groups",
+    tag = "groups",
+    request_body = AddGroupRequest,
+    responses(
+        (status = 200, description = "The group has been added", body = AddGroupResponse),
+        (status = 400, description = "The request is invalid or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn add_group(
     State(state): State<AppState>,
@@ -52,3 +138,69 @@ async fn add_group(
     let group_id = group_service.add_group(req.name).await?;
     Ok(Json(AddGroupResponse { group_id }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/groups/{group_id}/certificates",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "Id of the group"),
+        api::CertificatesParams,
+        api::DocumentParams,
+    ),
+    responses(
+        (status = 200, description = "Certificates of all participants in the group, sorted by name", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The group or the page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn group_certificates(
+    State(state): State<AppState>,
+    Path(group_id): Path<Uuid>,
+    Query(params): Query<api::CertificatesParams>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let score_service = state.score_service();
+    let certificates = score_service
+        .group_certificates(
+            group_id,
+            params.skip_missing_results.unwrap_or(false),
+            TypstOutput::from(&document),
+        )
+        .await?;
+
+    let headers = super::document_headers(&document, &format!("{group_id}-certificates"));
+
+    Ok((headers, certificates))
+}
+
+#[utoipa::path(
+    get,
+    path = "/groups/{group_id}/invoice",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "Id of the group"),
+        api::DocumentParams,
+    ),
+    responses(
+        (status = 200, description = "Invoice of the entry fees of all participants in the group", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The group or the page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn group_invoice(
+    State(state): State<AppState>,
+    Path(group_id): Path<Uuid>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let fee_service = state.fee_service();
+    let invoice = fee_service
+        .group_invoice(group_id, TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, &format!("{group_id}-invoice"));
+
+    Ok((headers, invoice))
+}
@@ -1,55 +1,132 @@
-use axum::http::StatusCode;
+use std::sync::Arc;
+
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Router;
 
-use crate::infra::typst_compiler::TypstCompiler;
+use clubswimcomp_types::api;
+
+use crate::infra::{registration_card::CardLayout, typst_compiler::TypstCompiler};
 use crate::services::{
-    CompetitionService, GroupService, ParticipantService, RegistrationCardService,
-    RegistrationService, ScoreService, ServiceRepositoryError,
+    BackupService, BestTimeService, CompetitionService, EventService, FeeService, FinalsService,
+    GroupService, ParticipantService, RecordService, RegistrationCardService, RegistrationService,
+    ScheduleService, ScoreService, ServiceRepositoryError, StartListService, TeamService,
+    TemplateService,
 };
 use crate::{db, infra, Config};
 
 mod competitions;
+pub mod correlation;
 mod event;
+mod extract;
 mod groups;
+mod openapi;
 mod participants;
+mod payments;
+mod records;
 mod registrations;
+mod sessions;
+mod templates;
 
 struct ApiError {
-    status_code: StatusCode,
+    code: api::ErrorCode,
     message: String,
+    details: Vec<api::FieldError>,
     internal_message: String,
 }
 
 impl ApiError {
-    pub fn with_message(status_code: StatusCode, message: String) -> Self {
+    pub fn with_message(code: api::ErrorCode, message: String) -> Self {
         Self {
-            status_code,
+            code,
             message,
+            details: Vec::new(),
             internal_message: String::new(),
         }
     }
 
-    pub fn with_internal_message(status_code: StatusCode, internal_message: String) -> Self {
+    pub fn with_internal_message(code: api::ErrorCode, internal_message: String) -> Self {
         Self {
-            status_code,
-            message: String::new(),
+            code,
+            message: "The service ran into an internal error".to_string(),
+            details: Vec::new(),
             internal_message,
         }
     }
+
+    /// Attach a validation error of a single request field.
+    pub fn with_field_error(mut self, field: &str, message: String) -> Self {
+        self.details.push(api::FieldError {
+            field: field.to_string(),
+            message,
+        });
+        self
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        use api::ErrorCode::*;
+
+        match self.code {
+            ParticipantDoesNotExist
+            | CompetitionDoesNotExist
+            | RegistrationDoesNotExist
+            | GroupDoesNotExist
+            | AssetDoesNotExist
+            | PageDoesNotExist
+            | RegistrationHasNoResult
+            | RecordDoesNotExist
+            | SessionDoesNotExist
+            | CompetitionHasNoFinals
+            | PaymentDoesNotExist
+            | RouteNotFound => StatusCode::NOT_FOUND,
+            ParticipantHasRegistrations
+            | ParticipantHasPayments
+            | CompetitionHasRegistrations
+            | AlreadyRegistered
+            | NotEligible
+            | InvalidDistance
+            | SameCompetitionExists
+            | ResultAlreadyExists
+            | UnsupportedBackupVersion
+            | DatabaseNotEmpty
+            | InvalidBackup
+            | InvalidAssetName
+            | InvalidTemplate
+            | InvalidCardCode
+            | CardOfOtherEvent
+            | InvalidCardSignature
+            | InvalidBestTimes
+            | RecordNotPending
+            | NotFasterThanRecord
+            | RegistrationClosed
+            | EventFinalized
+            | EventNotFinalized
+            | CompetitionAlreadyScheduled
+            | NotPrelims
+            | PrelimsNotFinished
+            | FinalistsAlreadyPromoted
+            | FinalistsNotPromoted
+            | SwimOffRequired
+            | SwimOffNotFinished
+            | NoTieAtCutoff
+            | NotAFinalist
+            | InvalidRequest => StatusCode::BAD_REQUEST,
+            PdfGenerationFailed | InternalError | Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
 }
 
 impl<T> From<T> for ApiError
 where
     T: std::error::Error,
-    for<'a> &'a T: Into<StatusCode>,
+    for<'a> &'a T: Into<api::ErrorCode>,
 {
     fn from(err: T) -> Self {
-        let status_code: StatusCode = (&err).into();
-        if status_code.is_server_error() {
-            ApiError::with_internal_message(status_code, err.to_string())
+        let api_error = ApiError::with_message((&err).into(), err.to_string());
+        if api_error.status_code().is_server_error() {
+            ApiError::with_internal_message(api_error.code, err.to_string())
         } else {
-            ApiError::with_message(status_code, err.to_string())
+            api_error
         }
     }
 }
@@ -59,39 +136,130 @@ impl IntoResponse for ApiError {
         if !self.internal_message.is_empty() {
             tracing::error!(self.internal_message, "API has internal error message");
         }
-        (self.status_code, self.message).into_response()
+
+        let status_code = self.status_code();
+        let body = api::ErrorResponse {
+            code: self.code,
+            message: self.message,
+            details: self.details,
+            correlation_id: correlation::current(),
+        };
+        (status_code, axum::Json(body)).into_response()
     }
 }
 
-impl From<&ServiceRepositoryError> for StatusCode {
+impl From<&ServiceRepositoryError> for api::ErrorCode {
     fn from(err: &ServiceRepositoryError) -> Self {
         match err {
-            ServiceRepositoryError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            ServiceRepositoryError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+/// Headers of a generated document, PDFs are downloaded while single pages
+/// are shown inline as previews.
+fn document_headers(document: &api::DocumentParams, file_name: &str) -> HeaderMap {
+    let (content_type, disposition, extension) = match document.format.unwrap_or_default() {
+        api::DocumentFormat::Pdf => ("application/pdf", "attachment", "pdf"),
+        api::DocumentFormat::Png => ("image/png", "inline", "png"),
+        api::DocumentFormat::Svg => ("image/svg+xml", "inline", "svg"),
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.append(header::CONTENT_TYPE, content_type.parse().unwrap());
+    headers.append(
+        header::CONTENT_DISPOSITION,
+        format!("{disposition}; filename=\"{file_name}.{extension}\"")
+            .parse()
+            .unwrap(),
+    );
+    headers
+}
+
+/// Label size used when the request does not specify one, fits the common
+/// 102 x 51 mm shipping labels.
+const DEFAULT_LABEL_SIZE_MM: (u32, u32) = (100, 50);
+/// Smallest and largest label edge the cards can be printed on.
+const LABEL_SIZE_RANGE_MM: std::ops::RangeInclusive<u32> = 20..=200;
+
+/// Layout of the registration cards, the label size must be in a printable range.
+fn card_layout(params: &api::CardLayoutParams) -> Result<CardLayout, ApiError> {
+    match params.layout.unwrap_or_default() {
+        api::CardLayout::A6 => Ok(CardLayout::A6),
+        api::CardLayout::A4Grid => Ok(CardLayout::A4Grid),
+        api::CardLayout::Label => {
+            let width_mm = params.label_width_mm.unwrap_or(DEFAULT_LABEL_SIZE_MM.0);
+            let height_mm = params.label_height_mm.unwrap_or(DEFAULT_LABEL_SIZE_MM.1);
+
+            let mut error = ApiError::with_message(
+                api::ErrorCode::InvalidRequest,
+                "The label size is not supported".to_string(),
+            );
+            for (field, value) in [("label_width_mm", width_mm), ("label_height_mm", height_mm)] {
+                if !LABEL_SIZE_RANGE_MM.contains(&value) {
+                    error = error.with_field_error(
+                        field,
+                        format!(
+                            "Must be between {} and {} mm",
+                            LABEL_SIZE_RANGE_MM.start(),
+                            LABEL_SIZE_RANGE_MM.end()
+                        ),
+                    );
+                }
+            }
+
+            match error.details.is_empty() {
+                true => Ok(CardLayout::Label {
+                    width_mm,
+                    height_mm,
+                }),
+                false => Err(error),
+            }
         }
     }
 }
 
 #[derive(Clone)]
 pub struct AppState {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
-    group_repo: db::groups::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    backup_repo: Arc<dyn db::backups::Repository>,
+    asset_repo: Arc<dyn db::assets::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    record_repo: Arc<dyn db::records::Repository>,
+    session_repo: Arc<dyn db::sessions::Repository>,
+    finals_repo: Arc<dyn db::finals::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
 
     typst_compiler: infra::typst_compiler::TypstCompiler,
+    pool_lanes: u32,
+    certificate_signatures: Vec<String>,
 }
 
 impl AppState {
-    pub fn new(config: Config, pool: db::DatabasePool) -> Self {
-        let typst_compiler = TypstCompiler::new(config.typst_bin, config.typst_assets);
+    pub fn new(config: Config, repositories: db::Repositories) -> Self {
+        let typst_compiler = TypstCompiler::new(config.typst_assets);
 
         Self {
-            participant_repo: db::participants::Repository::new(pool.clone()),
-            registration_repo: db::registrations::Repository::new(pool.clone()),
-            competition_repo: db::competitions::Repository::new(pool.clone()),
-            group_repo: db::groups::Repository::new(pool.clone()),
+            participant_repo: repositories.participants,
+            registration_repo: repositories.registrations,
+            competition_repo: repositories.competitions,
+            group_repo: repositories.groups,
+            backup_repo: repositories.backups,
+            asset_repo: repositories.assets,
+            event_repo: repositories.event,
+            best_time_repo: repositories.best_times,
+            record_repo: repositories.records,
+            session_repo: repositories.sessions,
+            finals_repo: repositories.finals,
+            payment_repo: repositories.payments,
 
             typst_compiler,
+            pool_lanes: config.pool_lanes,
+            certificate_signatures: config.certificate_signatures,
         }
     }
 
@@ -101,6 +269,9 @@ impl AppState {
             self.registration_repo.clone(),
             self.competition_repo.clone(),
             self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
         )
     }
 
@@ -109,6 +280,7 @@ impl AppState {
             self.participant_repo.clone(),
             self.registration_repo.clone(),
             self.competition_repo.clone(),
+            self.event_repo.clone(),
             self.typst_compiler.clone(),
         )
     }
@@ -118,6 +290,13 @@ impl AppState {
             self.participant_repo.clone(),
             self.registration_repo.clone(),
             self.competition_repo.clone(),
+            self.event_repo.clone(),
+            self.record_repo.clone(),
+            self.finals_repo.clone(),
+            self.group_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
         )
     }
 
@@ -126,6 +305,8 @@ impl AppState {
             self.participant_repo.clone(),
             self.registration_repo.clone(),
             self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
         )
     }
 
@@ -135,6 +316,9 @@ impl AppState {
             self.registration_repo.clone(),
             self.competition_repo.clone(),
             self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
             self.typst_compiler.clone(),
         )
     }
@@ -145,6 +329,101 @@ impl AppState {
             self.registration_repo.clone(),
             self.competition_repo.clone(),
             self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+        .with_certificate_signatures(self.certificate_signatures.clone())
+    }
+
+    pub fn team_service(&self) -> TeamService {
+        TeamService::new(
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    pub fn finals_service(&self) -> FinalsService {
+        FinalsService::new(
+            self.finals_repo.clone(),
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    pub fn fee_service(&self) -> FeeService {
+        FeeService::new(
+            self.payment_repo.clone(),
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    pub fn schedule_service(&self) -> ScheduleService {
+        ScheduleService::new(
+            self.session_repo.clone(),
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.event_repo.clone(),
+            self.typst_compiler.clone(),
+            self.pool_lanes,
+        )
+    }
+
+    pub fn start_list_service(&self) -> StartListService {
+        StartListService::new(
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.typst_compiler.clone(),
+            self.pool_lanes,
+        )
+    }
+
+    pub fn event_service(&self) -> EventService {
+        EventService::new(self.event_repo.clone())
+    }
+
+    pub fn best_time_service(&self) -> BestTimeService {
+        BestTimeService::new(
+            self.participant_repo.clone(),
+            self.best_time_repo.clone(),
+            self.event_repo.clone(),
+        )
+    }
+
+    pub fn record_service(&self) -> RecordService {
+        RecordService::new(self.record_repo.clone(), self.event_repo.clone())
+    }
+
+    pub fn backup_service(&self) -> BackupService {
+        BackupService::new(self.backup_repo.clone())
+    }
+
+    pub fn template_service(&self) -> TemplateService {
+        TemplateService::new(
+            self.asset_repo.clone(),
+            self.event_repo.clone(),
             self.typst_compiler.clone(),
         )
     }
@@ -155,6 +434,19 @@ pub fn routes() -> Router<AppState> {
         .nest("/participants", participants::router())
         .nest("/registrations", registrations::router())
         .nest("/competitions", competitions::router())
+        .nest("/records", records::router())
+        .nest("/sessions", sessions::router())
+        .nest("/payments", payments::router())
         .nest("/event", event::router())
         .nest("/groups", groups::router())
+        .nest("/templates", templates::router())
+        .merge(openapi::router())
+        .fallback(route_not_found)
+}
+
+async fn route_not_found() -> ApiError {
+    ApiError::with_message(
+        api::ErrorCode::RouteNotFound,
+        "The requested route does not exist".to_string(),
+    )
 }
@@ -0,0 +1,192 @@
+//! OpenAPI document of the API and the embedded docs viewer.
+//!
+//! Every API module documents its handlers in its own `ApiDoc`, which are
+//! merged here into one document.
+
+use axum::{response::Html, routing::*, Json, Router};
+use clubswimcomp_types::{api, model};
+use utoipa::OpenApi;
+use utoipa_rapidoc::RapiDoc;
+
+use super::{
+    competitions, event, groups, participants, payments, records, registrations, sessions,
+    templates, AppState,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "ClubSwimComp",
+        description = "API to manage the participants, competitions and results of a swimming event.",
+        license(name = "MIT OR Apache-2.0")
+    ),
+    tags(
+        (name = "participants", description = "Participants and their registrations"),
+        (name = "competitions", description = "Competitions and their rankings"),
+        (name = "registrations", description = "Registrations and their results"),
+        (name = "groups", description = "Groups and their rankings"),
+        (name = "records", description = "Club and meet records and their approval"),
+        (name = "sessions", description = "Sessions and the order of their competitions"),
+        (name = "payments", description = "Payments of the entry fees and the invoices"),
+        (name = "event", description = "Documents for the whole event"),
+        (name = "templates", description = "Templates and assets of the documents"),
+    ),
+    components(schemas(api::DocumentFormat, api::CardLayout, api::BestTimesFormat,
+        model::RecordKind,
+        model::RecordStatus
+    ))
+)]
+struct ApiDoc;
+
+/// The OpenAPI document of all routes.
+pub fn document() -> utoipa::openapi::OpenApi {
+    let mut document = ApiDoc::openapi();
+    document.merge(participants::ApiDoc::openapi());
+    document.merge(competitions::ApiDoc::openapi());
+    document.merge(registrations::ApiDoc::openapi());
+    document.merge(groups::ApiDoc::openapi());
+    document.merge(records::ApiDoc::openapi());
+    document.merge(sessions::ApiDoc::openapi());
+    document.merge(payments::ApiDoc::openapi());
+    document.merge(event::ApiDoc::openapi());
+    document.merge(templates::ApiDoc::openapi());
+    document
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+}
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(document())
+}
+
+async fn docs() -> Html<String> {
+    Html(RapiDoc::new("/openapi.json").to_html())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    use crate::{db, Config};
+
+    use super::*;
+
+    const DOCUMENT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    /// The checked in document must match the routes.
+    ///
+    /// Run with `UPDATE_OPENAPI=1` to update the checked in document.
+    #[test]
+    fn checked_in_document_is_up_to_date() {
+        let generated = document().to_pretty_json().unwrap() + "\n";
+
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(DOCUMENT_PATH, &generated).unwrap();
+        }
+
+        let checked_in = std::fs::read_to_string(DOCUMENT_PATH).unwrap_or_default();
+        assert!(
+            checked_in == generated,
+            "openapi.json is outdated, run the tests with UPDATE_OPENAPI=1 to update it"
+        );
+    }
+
+    /// Every referenced schema must be part of the document.
+    #[test]
+    fn schema_references_resolve() {
+        fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+            match value {
+                serde_json::Value::Object(object) => {
+                    if let Some(serde_json::Value::String(reference)) = object.get("$ref") {
+                        refs.push(reference.clone());
+                    }
+                    object.values().for_each(|v| collect_refs(v, refs));
+                }
+                serde_json::Value::Array(array) => array.iter().for_each(|v| collect_refs(v, refs)),
+                _ => {}
+            }
+        }
+
+        let document = serde_json::to_value(document()).unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+
+        for reference in refs {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            assert!(
+                document["components"]["schemas"].get(name).is_some(),
+                "{reference} is referenced but not part of the document"
+            );
+        }
+    }
+
+    /// Every documented operation must be handled by the router.
+    #[tokio::test]
+    async fn documented_operations_are_routed() {
+        let config = Config {
+            database_url: String::new(),
+            typst_assets: None,
+            pool_lanes: 6,
+            backup_dir: None,
+            backup_interval: std::time::Duration::from_secs(60),
+            backup_keep: std::num::NonZeroUsize::MIN,
+            certificate_signatures: Vec::new(),
+        };
+        let app =
+            super::super::routes().with_state(AppState::new(config, db::Repositories::in_memory()));
+
+        for (path, item) in document().paths.paths {
+            // Any id is fine, the request only has to reach the handler
+            let uri = path
+                .split('/')
+                .map(|segment| match segment.starts_with('{') {
+                    true => uuid::Uuid::nil().to_string(),
+                    false => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let operations = [
+                (Method::GET, item.get.is_some()),
+                (Method::POST, item.post.is_some()),
+                (Method::DELETE, item.delete.is_some()),
+                (Method::PUT, item.put.is_some()),
+                (Method::PATCH, item.patch.is_some()),
+            ];
+            let documented = operations.into_iter().filter(|(_, documented)| *documented);
+            for (method, _) in documented {
+                let request = Request::builder()
+                    .method(method.clone())
+                    .uri(&uri)
+                    .body(Body::empty())
+                    .unwrap();
+                let response = app.clone().oneshot(request).await.unwrap();
+
+                assert_ne!(
+                    response.status(),
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "{method} {path} is documented but not routed"
+                );
+                if response.status() == StatusCode::NOT_FOUND {
+                    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                        .await
+                        .unwrap();
+                    let error: api::ErrorResponse = serde_json::from_slice(&body).unwrap();
+                    assert_ne!(
+                        error.code,
+                        api::ErrorCode::RouteNotFound,
+                        "{method} {path} is documented but not routed"
+                    );
+                }
+            }
+        }
+    }
+}
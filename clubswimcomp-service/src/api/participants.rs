@@ -1,29 +1,41 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::{header, HeaderMap, StatusCode},
+    body::Bytes,
+    extract::{DefaultBodyLimit, State},
+    http::HeaderMap,
     routing::*,
-    Json,
 };
 use clubswimcomp_types::{api, model};
 use tracing::instrument;
+use utoipa::OpenApi;
 use uuid::Uuid;
 
+use crate::infra::typst_compiler::TypstOutput;
 use crate::services::{
-    AvailableCompetitionsForRegistrationError, ParticipantCertificateError,
-    ParticipantDetailsError, ParticipantRegistrationCardsError, ParticipantScoreboardError,
-    RegisterForCompetitionsError, RemoveParticipantError, UnregisterFromCompetitionError,
+    AddParticipantError, AvailableCompetitionsForRegistrationError, ImportBestTimesError,
+    ParticipantBestTimesError, ParticipantCertificateError, ParticipantDetailsError,
+    ParticipantRegistrationCardsError, ParticipantScoreboardError, RegisterForCompetitionsError,
+    RemoveParticipantError, UnregisterFromCompetitionError,
 };
 
+use super::extract::{Json, Path, Query};
 use super::{ApiError, AppState};
 
+/// Lenex files of large meets easily exceed the default body limit.
+const BEST_TIMES_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_participants))
         .route("/", post(add_participant))
+        .route(
+            "/best-times",
+            post(import_best_times).layer(DefaultBodyLimit::max(BEST_TIMES_BODY_LIMIT)),
+        )
         .route("/:participant_id", get(participant_details))
         .route("/:participant_id", delete(remove_participant))
         .route("/:participant_id/scoreboard", get(participant_scoreboard))
         .route("/:participant_id/certificate", get(participant_certificate))
+        .route("/:participant_id/best-times", get(participant_best_times))
         .route(
             "/:participant_id/registrations/available-competitions",
             get(available_competitions_for_registration),
@@ -42,88 +54,153 @@ pub fn router() -> Router<AppState> {
         )
 }
 
-impl From<&ParticipantDetailsError> for StatusCode {
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_participants,
+    add_participant,
+    import_best_times,
+    participant_details,
+    remove_participant,
+    participant_scoreboard,
+    participant_certificate,
+    participant_best_times,
+    available_competitions_for_registration,
+    register_for_competition,
+    unregister_from_competition,
+    registration_cards,
+))]
+pub(super) struct ApiDoc;
+
+impl From<&AddParticipantError> for api::ErrorCode {
+    fn from(err: &AddParticipantError) -> Self {
+        match err {
+            AddParticipantError::EventFinalized => Self::EventFinalized,
+            AddParticipantError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ParticipantDetailsError> for api::ErrorCode {
     fn from(err: &ParticipantDetailsError) -> Self {
         match err {
-            ParticipantDetailsError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            ParticipantDetailsError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            ParticipantDetailsError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            ParticipantDetailsError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&RemoveParticipantError> for StatusCode {
+impl From<&ImportBestTimesError> for api::ErrorCode {
+    fn from(err: &ImportBestTimesError) -> Self {
+        match err {
+            ImportBestTimesError::InvalidFile(_) => Self::InvalidBestTimes,
+            ImportBestTimesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ParticipantBestTimesError> for api::ErrorCode {
+    fn from(err: &ParticipantBestTimesError) -> Self {
+        match err {
+            ParticipantBestTimesError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            ParticipantBestTimesError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&RemoveParticipantError> for api::ErrorCode {
     fn from(err: &RemoveParticipantError) -> Self {
         match err {
-            RemoveParticipantError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            RemoveParticipantError::ParticipantHasRegistrations => Self::BAD_REQUEST,
-            RemoveParticipantError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            RemoveParticipantError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            RemoveParticipantError::ParticipantHasRegistrations => {
+                Self::ParticipantHasRegistrations
+            }
+            RemoveParticipantError::ParticipantHasPayments => Self::ParticipantHasPayments,
+            RemoveParticipantError::EventFinalized => Self::EventFinalized,
+            RemoveParticipantError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&AvailableCompetitionsForRegistrationError> for StatusCode {
+impl From<&AvailableCompetitionsForRegistrationError> for api::ErrorCode {
     fn from(err: &AvailableCompetitionsForRegistrationError) -> Self {
         match err {
-            AvailableCompetitionsForRegistrationError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            AvailableCompetitionsForRegistrationError::RepositoryError(_) => {
-                Self::INTERNAL_SERVER_ERROR
+            AvailableCompetitionsForRegistrationError::ParticipantDoesNotExist => {
+                Self::ParticipantDoesNotExist
             }
+            AvailableCompetitionsForRegistrationError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&RegisterForCompetitionsError> for StatusCode {
+impl From<&RegisterForCompetitionsError> for api::ErrorCode {
     fn from(err: &RegisterForCompetitionsError) -> Self {
         match err {
-            RegisterForCompetitionsError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            RegisterForCompetitionsError::CompetitionDoesNotExist => Self::NOT_FOUND,
-            RegisterForCompetitionsError::AlreadyRegistered => Self::BAD_REQUEST,
-            RegisterForCompetitionsError::NotEligible => Self::BAD_REQUEST,
-            RegisterForCompetitionsError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            RegisterForCompetitionsError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            RegisterForCompetitionsError::CompetitionDoesNotExist => Self::CompetitionDoesNotExist,
+            RegisterForCompetitionsError::AlreadyRegistered => Self::AlreadyRegistered,
+            RegisterForCompetitionsError::NotEligible(_) => Self::NotEligible,
+            RegisterForCompetitionsError::RegistrationClosed => Self::RegistrationClosed,
+            RegisterForCompetitionsError::EventFinalized => Self::EventFinalized,
+            RegisterForCompetitionsError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&UnregisterFromCompetitionError> for StatusCode {
+impl From<&UnregisterFromCompetitionError> for api::ErrorCode {
     fn from(err: &UnregisterFromCompetitionError) -> Self {
         match err {
-            UnregisterFromCompetitionError::RegistrationDoesNotExist => Self::NOT_FOUND,
-            UnregisterFromCompetitionError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            UnregisterFromCompetitionError::RegistrationDoesNotExist => {
+                Self::RegistrationDoesNotExist
+            }
+            UnregisterFromCompetitionError::RegistrationClosed => Self::RegistrationClosed,
+            UnregisterFromCompetitionError::EventFinalized => Self::EventFinalized,
+            UnregisterFromCompetitionError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&ParticipantRegistrationCardsError> for StatusCode {
+impl From<&ParticipantRegistrationCardsError> for api::ErrorCode {
     fn from(err: &ParticipantRegistrationCardsError) -> Self {
         match err {
-            ParticipantRegistrationCardsError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            ParticipantRegistrationCardsError::PdfGenerationFailed(_) => {
-                Self::INTERNAL_SERVER_ERROR
+            ParticipantRegistrationCardsError::ParticipantDoesNotExist => {
+                Self::ParticipantDoesNotExist
             }
-            ParticipantRegistrationCardsError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            ParticipantRegistrationCardsError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            ParticipantRegistrationCardsError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            ParticipantRegistrationCardsError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&ParticipantScoreboardError> for StatusCode {
+impl From<&ParticipantScoreboardError> for api::ErrorCode {
     fn from(err: &ParticipantScoreboardError) -> Self {
         match err {
-            ParticipantScoreboardError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            ParticipantScoreboardError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            ParticipantScoreboardError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            ParticipantScoreboardError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&ParticipantCertificateError> for StatusCode {
+impl From<&ParticipantCertificateError> for api::ErrorCode {
     fn from(err: &ParticipantCertificateError) -> Self {
         match err {
-            ParticipantCertificateError::ParticipantDoesNotExist => Self::NOT_FOUND,
-            ParticipantCertificateError::PdfGenerationFailed(_) => Self::INTERNAL_SERVER_ERROR,
-            ParticipantCertificateError::RepositoryError(_) => Self::INTERNAL_SERVER_ERROR,
+            ParticipantCertificateError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            ParticipantCertificateError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            ParticipantCertificateError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            ParticipantCertificateError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants",
+    tag = "participants",
+    responses(
+        (status = 200, description = "All participants", body = Vec<model::Participant>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn list_participants(
     State(state): State<AppState>,
@@ -133,6 +210,19 @@ async fn list_participants(
     Ok(Json(participants))
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+    ),
+    responses(
+        (status = 200, description = "The participant with all registrations", body = model::ParticipantDetails),
+        (status = 404, description = "The participant does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn participant_details(
     Path(participant_id): Path<Uuid>,
@@ -146,6 +236,17 @@ async fn participant_details(
     Ok(Json(participant_details))
 }
 
+#[utoipa::path(
+    post,
+    path = "/participants",
+    tag = "participants",
+    request_body = api::AddParticipantBody,
+    responses(
+        (status = 200, description = "The participant has been added", body = api::AddParticipantResponse),
+        (status = 400, description = "The request is invalid or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn add_participant(
     State(state): State<AppState>,
@@ -165,6 +266,21 @@ async fn add_participant(
     Ok(Json(api::AddParticipantResponse { participant_id }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/participants/{participant_id}",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+        api::RemoveParticipantParameters,
+    ),
+    responses(
+        (status = 200, description = "The participant has been removed"),
+        (status = 404, description = "The participant does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The participant is still registered for competitions, payments of the participant are recorded or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn remove_participant(
     Path(participant_id): Path<Uuid>,
@@ -178,6 +294,19 @@ async fn remove_participant(
         .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}/scoreboard",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+    ),
+    responses(
+        (status = 200, description = "Scores of the participant", body = model::ParticipantScoreboard),
+        (status = 404, description = "The participant does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn participant_scoreboard(
     Path(participant_id): Path<Uuid>,
@@ -189,30 +318,104 @@ async fn participant_scoreboard(
     Ok(Json(scoreboard))
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}/best-times",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+    ),
+    responses(
+        (status = 200, description = "Times the participant swam at earlier events and this one, oldest first", body = Vec<model::BestTime>),
+        (status = 404, description = "The participant does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn participant_best_times(
+    Path(participant_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<model::BestTime>>, ApiError> {
+    let best_time_service = state.best_time_service();
+    let best_times = best_time_service
+        .participant_best_times(participant_id)
+        .await?;
+
+    Ok(Json(best_times))
+}
+
+#[utoipa::path(
+    post,
+    path = "/participants/best-times",
+    tag = "participants",
+    params(api::ImportBestTimesParams),
+    request_body(content = [u8], description = "CSV file with the header `first_name,last_name,birthday,stroke,distance,course,time,date,meet` or a Lenex file, uncompressed (.lef) or zipped (.lxf)", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "The times have been imported", body = api::ImportBestTimesResponse),
+        (status = 400, description = "The file is invalid, nothing has been imported", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state, content))]
+async fn import_best_times(
+    State(state): State<AppState>,
+    Query(params): Query<api::ImportBestTimesParams>,
+    content: Bytes,
+) -> Result<Json<api::ImportBestTimesResponse>, ApiError> {
+    let best_time_service = state.best_time_service();
+    let import = best_time_service
+        .import_best_times(params.format.unwrap_or_default().into(), &content)
+        .await?;
+
+    Ok(Json(api::ImportBestTimesResponse {
+        imported: import.imported,
+        duplicates: import.duplicates,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}/certificate",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+        api::DocumentParams,
+    ),
+    responses(
+        (status = 200, description = "Certificate of the participant", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The participant or the page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn participant_certificate(
     Path(participant_id): Path<Uuid>,
+    Query(document): Query<api::DocumentParams>,
     State(state): State<AppState>,
 ) -> Result<(HeaderMap, Vec<u8>), ApiError> {
     let score_service = state.score_service();
     let certificate = score_service
-        .participant_certificate(participant_id)
+        .participant_certificate(participant_id, TypstOutput::from(&document))
         .await?;
 
-    let file_name = format!("{participant_id}-certificate.pdf");
-
-    let mut headers = HeaderMap::new();
-    headers.append(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
-    headers.append(
-        header::CONTENT_DISPOSITION,
-        format!("attachment; filename=\"{file_name}\"")
-            .parse()
-            .unwrap(),
-    );
+    let headers = super::document_headers(&document, &format!("{participant_id}-certificate"));
 
     Ok((headers, certificate))
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}/registrations/available-competitions",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+    ),
+    responses(
+        (status = 200, description = "Competitions the participant can register for", body = Vec<model::Competition>),
+        (status = 404, description = "The participant does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn available_competitions_for_registration(
     Path(participant_id): Path<Uuid>,
@@ -226,6 +429,21 @@ async fn available_competitions_for_registration(
     Ok(Json(competitions))
 }
 
+#[utoipa::path(
+    post,
+    path = "/participants/{participant_id}/registrations",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+    ),
+    request_body = api::RegisterForCompetitionBody,
+    responses(
+        (status = 200, description = "The participant has been registered", body = api::RegisterForCompetitionResponse),
+        (status = 404, description = "The participant or competition does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The participant is already registered, not eligible or the registration is closed", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn register_for_competition(
     Path(participant_id): Path<Uuid>,
@@ -234,7 +452,12 @@ async fn register_for_competition(
 ) -> Result<Json<api::RegisterForCompetitionResponse>, ApiError> {
     let participant_service = state.participant_service();
     let registration_id = participant_service
-        .register_for_competition(participant_id, b.competition_id)
+        .register_for_competition(
+            participant_id,
+            b.competition_id,
+            b.entry_time_millis,
+            b.late_entry,
+        )
         .await?;
 
     Ok(Json(api::RegisterForCompetitionResponse {
@@ -242,39 +465,68 @@ async fn register_for_competition(
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/participants/{participant_id}/registrations/{registration_id}",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+        ("registration_id" = Uuid, Path, description = "Id of the registration"),
+        api::UnregisterFromCompetitionParams,
+    ),
+    responses(
+        (status = 200, description = "The registration has been removed"),
+        (status = 404, description = "The registration does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The registration is closed or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn unregister_from_competition(
     Path((_participant_id, registration_id)): Path<(Uuid, Uuid)>,
+    Query(p): Query<api::UnregisterFromCompetitionParams>,
     State(state): State<AppState>,
 ) -> Result<(), ApiError> {
     let participant_service = state.participant_service();
     participant_service
-        .unregister_from_competition(registration_id)
+        .unregister_from_competition(registration_id, p.late_entry.unwrap_or_default())
         .await
         .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    get,
+    path = "/participants/{participant_id}/registrations/cards",
+    operation_id = "participant_registration_cards",
+    tag = "participants",
+    params(
+        ("participant_id" = Uuid, Path, description = "Id of the participant"),
+        api::CardLayoutParams,
+        api::DocumentParams,
+    ),
+    responses(
+        (status = 200, description = "Registration cards of the participant", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 400, description = "The label size is not supported", body = api::ErrorResponse),
+        (status = 404, description = "The participant or the page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn registration_cards(
     Path(participant_id): Path<Uuid>,
+    Query(layout): Query<api::CardLayoutParams>,
+    Query(document): Query<api::DocumentParams>,
     State(state): State<AppState>,
 ) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let layout = super::card_layout(&layout)?;
+
     let registration_card_service = state.registration_card_service();
     let registration_cards = registration_card_service
-        .participants_registration_cards(participant_id)
+        .participants_registration_cards(participant_id, layout, TypstOutput::from(&document))
         .await
         .map_err(ApiError::from)?;
 
-    let file_name = format!("{participant_id}-cards.pdf");
-
-    let mut headers = HeaderMap::new();
-    headers.append(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
-    headers.append(
-        header::CONTENT_DISPOSITION,
-        format!("attachment; filename=\"{file_name}\"")
-            .parse()
-            .unwrap(),
-    );
+    let headers = super::document_headers(&document, &format!("{participant_id}-cards"));
 
     Ok((headers, registration_cards))
 }
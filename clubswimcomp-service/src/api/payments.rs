@@ -0,0 +1,209 @@
+use axum::{extract::State, http::HeaderMap, routing::*};
+use clubswimcomp_types::{api, model};
+use tracing::instrument;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::infra::typst_compiler::TypstOutput;
+use crate::services::{AddPaymentError, BalanceReportError, InvoiceError, RemovePaymentError};
+
+use super::extract::{Json, Path, Query};
+use super::{ApiError, AppState};
+
+pub fn router() -> axum::Router<super::AppState> {
+    Router::new()
+        .route("/", get(list_payments).post(add_payment))
+        .route("/:payment_id", delete(remove_payment))
+        .route("/balances", get(balances))
+        .route("/invoices", get(invoices))
+        .route("/outstanding", get(balance_report))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_payments,
+    add_payment,
+    remove_payment,
+    balances,
+    invoices,
+    balance_report
+))]
+pub(super) struct ApiDoc;
+
+impl From<&AddPaymentError> for api::ErrorCode {
+    fn from(err: &AddPaymentError) -> Self {
+        match err {
+            AddPaymentError::InvalidPayer | AddPaymentError::InvalidAmount => Self::InvalidRequest,
+            AddPaymentError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            AddPaymentError::GroupDoesNotExist => Self::GroupDoesNotExist,
+            AddPaymentError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&RemovePaymentError> for api::ErrorCode {
+    fn from(err: &RemovePaymentError) -> Self {
+        match err {
+            RemovePaymentError::PaymentDoesNotExist => Self::PaymentDoesNotExist,
+            RemovePaymentError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&InvoiceError> for api::ErrorCode {
+    fn from(err: &InvoiceError) -> Self {
+        match err {
+            InvoiceError::GroupDoesNotExist => Self::GroupDoesNotExist,
+            InvoiceError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            InvoiceError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            InvoiceError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&BalanceReportError> for api::ErrorCode {
+    fn from(err: &BalanceReportError) -> Self {
+        match err {
+            BalanceReportError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            BalanceReportError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+            BalanceReportError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/payments",
+    tag = "payments",
+    responses(
+        (status = 200, description = "All payments ordered by the day they have been received", body = Vec<model::Payment>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn list_payments(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<model::Payment>>, ApiError> {
+    let fee_service = state.fee_service();
+    let payments = fee_service.list_payments().await?;
+    Ok(Json(payments))
+}
+
+#[utoipa::path(
+    post,
+    path = "/payments",
+    tag = "payments",
+    request_body = api::AddPaymentRequest,
+    responses(
+        (status = 200, description = "The payment has been recorded", body = api::AddPaymentResponse),
+        (status = 400, description = "The payer or the amount is invalid", body = api::ErrorResponse),
+        (status = 404, description = "The participant or group does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn add_payment(
+    State(state): State<AppState>,
+    Json(b): Json<api::AddPaymentRequest>,
+) -> Result<Json<api::AddPaymentResponse>, ApiError> {
+    let fee_service = state.fee_service();
+    let payment_id = fee_service.add_payment(b).await.map_err(|err| {
+        let field = match err {
+            AddPaymentError::InvalidPayer => "participant_id",
+            AddPaymentError::InvalidAmount => "amount",
+            err => return ApiError::from(err),
+        };
+        let message = err.to_string();
+        ApiError::from(err).with_field_error(field, message)
+    })?;
+    Ok(Json(api::AddPaymentResponse { payment_id }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/payments/{payment_id}",
+    tag = "payments",
+    params(
+        ("payment_id" = Uuid, Path, description = "Id of the payment"),
+    ),
+    responses(
+        (status = 200, description = "The payment has been removed"),
+        (status = 404, description = "The payment does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn remove_payment(
+    State(state): State<AppState>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<(), ApiError> {
+    let fee_service = state.fee_service();
+    fee_service.remove_payment(payment_id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/payments/balances",
+    tag = "payments",
+    responses(
+        (status = 200, description = "What every participant and group owes and has paid", body = model::Balances),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn balances(State(state): State<AppState>) -> Result<Json<model::Balances>, ApiError> {
+    let fee_service = state.fee_service();
+    let balances = fee_service.balances().await?;
+    Ok(Json(balances))
+}
+
+#[utoipa::path(
+    get,
+    path = "/payments/invoices",
+    tag = "payments",
+    params(api::DocumentParams),
+    responses(
+        (status = 200, description = "Invoices of all groups that owe or paid anything", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn invoices(
+    State(state): State<AppState>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let fee_service = state.fee_service();
+    let invoices = fee_service.invoices(TypstOutput::from(&document)).await?;
+
+    let headers = super::document_headers(&document, "invoices");
+
+    Ok((headers, invoices))
+}
+
+#[utoipa::path(
+    get,
+    path = "/payments/outstanding",
+    tag = "payments",
+    params(api::DocumentParams),
+    responses(
+        (status = 200, description = "Report of the groups with outstanding balances", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn balance_report(
+    State(state): State<AppState>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let fee_service = state.fee_service();
+    let report = fee_service
+        .balance_report(TypstOutput::from(&document))
+        .await?;
+
+    let headers = super::document_headers(&document, "outstanding-balances");
+
+    Ok((headers, report))
+}
@@ -0,0 +1,170 @@
+use axum::{extract::State, routing::*};
+use clubswimcomp_types::{api, model};
+use tracing::instrument;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::services::{AddRecordError, ApproveRecordError, NewRecord, RejectRecordError};
+
+use super::extract::{Json, Path, Query};
+use super::{ApiError, AppState};
+
+pub fn router() -> axum::Router<super::AppState> {
+    Router::new()
+        .route("/", get(list_records))
+        .route("/", post(add_record))
+        .route("/:record_id/approve", post(approve_record))
+        .route("/:record_id/reject", post(reject_record))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(list_records, add_record, approve_record, reject_record))]
+pub(super) struct ApiDoc;
+
+impl From<&AddRecordError> for api::ErrorCode {
+    fn from(err: &AddRecordError) -> Self {
+        match err {
+            AddRecordError::InvalidDistance | AddRecordError::InvalidTime => Self::InvalidRequest,
+            AddRecordError::NotFasterThanRecord => Self::NotFasterThanRecord,
+            AddRecordError::EventFinalized => Self::EventFinalized,
+            AddRecordError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ApproveRecordError> for api::ErrorCode {
+    fn from(err: &ApproveRecordError) -> Self {
+        match err {
+            ApproveRecordError::RecordDoesNotExist => Self::RecordDoesNotExist,
+            ApproveRecordError::RecordNotPending => Self::RecordNotPending,
+            ApproveRecordError::NotFasterThanRecord => Self::NotFasterThanRecord,
+            ApproveRecordError::EventFinalized => Self::EventFinalized,
+            ApproveRecordError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&RejectRecordError> for api::ErrorCode {
+    fn from(err: &RejectRecordError) -> Self {
+        match err {
+            RejectRecordError::RecordDoesNotExist => Self::RecordDoesNotExist,
+            RejectRecordError::RecordNotPending => Self::RecordNotPending,
+            RejectRecordError::EventFinalized => Self::EventFinalized,
+            RejectRecordError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/records",
+    tag = "records",
+    params(api::RecordsParams),
+    responses(
+        (status = 200, description = "The records including their history, oldest first", body = Vec<model::Record>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn list_records(
+    State(state): State<AppState>,
+    Query(params): Query<api::RecordsParams>,
+) -> Result<Json<Vec<model::Record>>, ApiError> {
+    let record_service = state.record_service();
+    let records = record_service.records(params.kind, params.status).await?;
+    Ok(Json(records))
+}
+
+#[utoipa::path(
+    post,
+    path = "/records",
+    tag = "records",
+    request_body = api::AddRecordRequest,
+    responses(
+        (status = 200, description = "The record has been added and replaces the former record", body = api::AddRecordResponse),
+        (status = 400, description = "The request is invalid, the time does not beat the current record or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn add_record(
+    State(state): State<AppState>,
+    Json(b): Json<api::AddRecordRequest>,
+) -> Result<Json<api::AddRecordResponse>, ApiError> {
+    let record_service = state.record_service();
+    let record_id = record_service
+        .add_record(NewRecord {
+            kind: b.kind,
+            gender: b.gender,
+            stroke: b.stroke,
+            distance: b.distance,
+            course: b.course,
+            age_class: b.age_class,
+            time_millis: b.time_millis,
+            first_name: b.first_name,
+            last_name: b.last_name,
+            birthday: b.birthday,
+            swum_on: b.swum_on,
+            meet: b.meet,
+        })
+        .await
+        .map_err(|err| {
+            let field = match err {
+                AddRecordError::InvalidDistance => "distance",
+                AddRecordError::InvalidTime => "time_millis",
+                _ => return ApiError::from(err),
+            };
+            let message = err.to_string();
+            ApiError::from(err).with_field_error(field, message)
+        })?;
+
+    Ok(Json(api::AddRecordResponse { record_id }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/records/{record_id}/approve",
+    tag = "records",
+    params(
+        ("record_id" = Uuid, Path, description = "Id of the record"),
+    ),
+    responses(
+        (status = 200, description = "The record has been approved, the former record is superseded"),
+        (status = 404, description = "The record does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The record is not pending or has been beaten in the meantime, or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn approve_record(
+    State(state): State<AppState>,
+    Path(record_id): Path<Uuid>,
+) -> Result<(), ApiError> {
+    let record_service = state.record_service();
+    record_service.approve_record(record_id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/records/{record_id}/reject",
+    tag = "records",
+    params(
+        ("record_id" = Uuid, Path, description = "Id of the record"),
+    ),
+    responses(
+        (status = 200, description = "The record has been rejected"),
+        (status = 404, description = "The record does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The record is not pending or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn reject_record(
+    State(state): State<AppState>,
+    Path(record_id): Path<Uuid>,
+) -> Result<(), ApiError> {
+    let record_service = state.record_service();
+    record_service.reject_record(record_id).await?;
+    Ok(())
+}
@@ -1,55 +1,200 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::*,
-    Json,
-};
+use axum::{extract::State, routing::*};
 use clubswimcomp_types::{api, model};
 use tracing::instrument;
+use utoipa::OpenApi;
 use uuid::Uuid;
 
 use crate::services::{
-    AddRegistrationResultError, RegistrationDetailsError, RemoveRegistrationResultError,
+    AddRegistrationResultError, FindRegistrationError, RegistrationDetailsError,
+    RegistrationLookup, RemoveRegistrationResultError, ScanCardError,
 };
 
+use super::extract::{Json, Path, Query};
 use super::{ApiError, AppState};
 
 pub fn router() -> axum::Router<super::AppState> {
     Router::new()
+        .route("/scan", post(scan_card))
+        .route("/lookup", get(lookup_registration))
         .route("/:registration_id", get(registration_details))
         .route("/:registration_id/result", post(add_registration_result))
         .route("/:registration_id/result", delete(delete_result))
 }
 
-impl From<&AddRegistrationResultError> for StatusCode {
+#[derive(OpenApi)]
+#[openapi(paths(
+    scan_card,
+    lookup_registration,
+    registration_details,
+    add_registration_result,
+    delete_result
+))]
+pub(super) struct ApiDoc;
+
+impl From<&AddRegistrationResultError> for api::ErrorCode {
     fn from(err: &AddRegistrationResultError) -> Self {
         match err {
-            AddRegistrationResultError::ResultAlreadyExists => StatusCode::BAD_REQUEST,
-            AddRegistrationResultError::RegistrationDoesNotExist => StatusCode::NOT_FOUND,
-            AddRegistrationResultError::RepositoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AddRegistrationResultError::ResultAlreadyExists => Self::ResultAlreadyExists,
+            AddRegistrationResultError::RegistrationDoesNotExist => Self::RegistrationDoesNotExist,
+            AddRegistrationResultError::FinalistsAlreadyPromoted => Self::FinalistsAlreadyPromoted,
+            AddRegistrationResultError::EventFinalized => Self::EventFinalized,
+            AddRegistrationResultError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&RemoveRegistrationResultError> for StatusCode {
+impl From<&RemoveRegistrationResultError> for api::ErrorCode {
     fn from(err: &RemoveRegistrationResultError) -> Self {
         match err {
-            RemoveRegistrationResultError::RegistrationDoesNotExist => StatusCode::NOT_FOUND,
-            RemoveRegistrationResultError::RegistrationHasNoResult => StatusCode::NOT_FOUND,
-            RemoveRegistrationResultError::RepositoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RemoveRegistrationResultError::RegistrationDoesNotExist => {
+                Self::RegistrationDoesNotExist
+            }
+            RemoveRegistrationResultError::RegistrationHasNoResult => Self::RegistrationHasNoResult,
+            RemoveRegistrationResultError::FinalistsAlreadyPromoted => {
+                Self::FinalistsAlreadyPromoted
+            }
+            RemoveRegistrationResultError::EventFinalized => Self::EventFinalized,
+            RemoveRegistrationResultError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
-impl From<&RegistrationDetailsError> for StatusCode {
+impl From<&RegistrationDetailsError> for api::ErrorCode {
     fn from(err: &RegistrationDetailsError) -> Self {
         match err {
-            RegistrationDetailsError::RegistrationDoesNotExist => StatusCode::NOT_FOUND,
-            RegistrationDetailsError::RepositoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RegistrationDetailsError::RegistrationDoesNotExist => Self::RegistrationDoesNotExist,
+            RegistrationDetailsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&ScanCardError> for api::ErrorCode {
+    fn from(err: &ScanCardError) -> Self {
+        match err {
+            ScanCardError::InvalidCode => Self::InvalidCardCode,
+            ScanCardError::CardOfOtherEvent => Self::CardOfOtherEvent,
+            ScanCardError::InvalidSignature => Self::InvalidCardSignature,
+            ScanCardError::RegistrationDoesNotExist => Self::RegistrationDoesNotExist,
+            ScanCardError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&FindRegistrationError> for api::ErrorCode {
+    fn from(err: &FindRegistrationError) -> Self {
+        match err {
+            FindRegistrationError::ParticipantDoesNotExist => Self::ParticipantDoesNotExist,
+            FindRegistrationError::RegistrationDoesNotExist => Self::RegistrationDoesNotExist,
+            FindRegistrationError::RepositoryError(_) => Self::InternalError,
         }
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/registrations/scan",
+    tag = "registrations",
+    request_body = api::ScanCardRequest,
+    responses(
+        (status = 200, description = "The registration of the card", body = api::ScanCardResponse),
+        (status = 400, description = "The code is no registration card of this event or has been forged", body = api::ErrorResponse),
+        (status = 404, description = "The registration does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn scan_card(
+    State(state): State<AppState>,
+    Json(b): Json<api::ScanCardRequest>,
+) -> Result<Json<api::ScanCardResponse>, ApiError> {
+    let registration_service = state.registration_service();
+    let scanned = registration_service.scan_card(&b.code).await?;
+
+    Ok(Json(api::ScanCardResponse {
+        registration_id: scanned.registration_id,
+        legacy: scanned.legacy,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/registrations/lookup",
+    tag = "registrations",
+    params(api::RegistrationLookupParams),
+    responses(
+        (status = 200, description = "The registration", body = model::RegistrationDetails),
+        (status = 400, description = "Neither the card number nor the short code and the competition are given", body = api::ErrorResponse),
+        (status = 404, description = "No participant has the short code or the registration does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn lookup_registration(
+    State(state): State<AppState>,
+    Query(params): Query<api::RegistrationLookupParams>,
+) -> Result<Json<model::RegistrationDetails>, ApiError> {
+    let lookup = match params {
+        api::RegistrationLookupParams {
+            card_number: Some(card_number),
+            short_code: None,
+            competition_id: None,
+        } => RegistrationLookup::CardNumber(card_number),
+        api::RegistrationLookupParams {
+            card_number: None,
+            short_code: Some(short_code),
+            competition_id: Some(competition_id),
+        } => RegistrationLookup::ShortCode {
+            short_code,
+            competition_id,
+        },
+        params => {
+            let mut error = ApiError::with_message(
+                api::ErrorCode::InvalidRequest,
+                "Either the card number or the short code and the competition are required"
+                    .to_string(),
+            );
+            let fields = [
+                ("short_code", params.short_code.is_some()),
+                ("competition_id", params.competition_id.is_some()),
+            ];
+            for (field, given) in fields {
+                match (params.card_number.is_some(), given) {
+                    (true, true) => {
+                        error = error.with_field_error(
+                            field,
+                            "Must not be combined with the card number".to_string(),
+                        )
+                    }
+                    (false, false) => {
+                        error = error
+                            .with_field_error(field, "Required without a card number".to_string())
+                    }
+                    _ => {}
+                }
+            }
+            return Err(error);
+        }
+    };
+
+    let registration_service = state.registration_service();
+    let registration = registration_service.find_registration(lookup).await?;
+
+    Ok(Json(registration))
+}
+
+#[utoipa::path(
+    get,
+    path = "/registrations/{registration_id}",
+    tag = "registrations",
+    params(
+        ("registration_id" = Uuid, Path, description = "Id of the registration"),
+    ),
+    responses(
+        (status = 200, description = "The registration", body = model::RegistrationDetails),
+        (status = 404, description = "The registration does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn registration_details(
     State(state): State<AppState>,
@@ -64,19 +209,49 @@ async fn registration_details(
     Ok(Json(registration_details))
 }
 
+#[utoipa::path(
+    post,
+    path = "/registrations/{registration_id}/result",
+    tag = "registrations",
+    params(
+        ("registration_id" = Uuid, Path, description = "Id of the registration"),
+    ),
+    request_body = api::EnterResultBody,
+    responses(
+        (status = 200, description = "The result has been added", body = api::EnterResultResponse),
+        (status = 404, description = "The registration does not exist", body = api::ErrorResponse),
+        (status = 400, description = "There is already a result for the registration or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn add_registration_result(
     State(state): State<AppState>,
     Path(registration_id): Path<Uuid>,
     Json(b): Json<api::EnterResultBody>,
-) -> Result<(), ApiError> {
+) -> Result<Json<api::EnterResultResponse>, ApiError> {
     let result_service = state.registration_service();
-    result_service
+    let new_records = result_service
         .add_result_for_registration(registration_id, b.disqualified, b.time_millis)
-        .await
-        .map_err(ApiError::from)
+        .await?;
+
+    Ok(Json(api::EnterResultResponse { new_records }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/registrations/{registration_id}/result",
+    tag = "registrations",
+    params(
+        ("registration_id" = Uuid, Path, description = "Id of the registration"),
+    ),
+    responses(
+        (status = 200, description = "The result has been removed"),
+        (status = 404, description = "The registration or its result does not exist", body = api::ErrorResponse),
+        (status = 400, description = "The event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
 #[instrument(skip(state))]
 async fn delete_result(
     State(state): State<AppState>,
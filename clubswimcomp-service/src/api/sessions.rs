@@ -0,0 +1,186 @@
+use axum::{extract::State, routing::*};
+use clubswimcomp_types::{api, model};
+use tracing::instrument;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::services::{RemoveSessionError, SaveSessionError, SessionDetailsError};
+
+use super::extract::{Json, Path};
+use super::{ApiError, AppState};
+
+pub fn router() -> axum::Router<super::AppState> {
+    Router::new()
+        .route("/", get(list_sessions).post(add_session))
+        .route(
+            "/:session_id",
+            get(session).put(update_session).delete(remove_session),
+        )
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(list_sessions, add_session, session, update_session, remove_session))]
+pub(super) struct ApiDoc;
+
+impl From<&SaveSessionError> for api::ErrorCode {
+    fn from(err: &SaveSessionError) -> Self {
+        match err {
+            SaveSessionError::SessionDoesNotExist => Self::SessionDoesNotExist,
+            SaveSessionError::InvalidName | SaveSessionError::InvalidDuration => {
+                Self::InvalidRequest
+            }
+            SaveSessionError::CompetitionDoesNotExist(_) => Self::CompetitionDoesNotExist,
+            SaveSessionError::CompetitionAlreadyScheduled(_) => Self::CompetitionAlreadyScheduled,
+            SaveSessionError::EventFinalized => Self::EventFinalized,
+            SaveSessionError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&SessionDetailsError> for api::ErrorCode {
+    fn from(err: &SessionDetailsError) -> Self {
+        match err {
+            SessionDetailsError::SessionDoesNotExist => Self::SessionDoesNotExist,
+            SessionDetailsError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&RemoveSessionError> for api::ErrorCode {
+    fn from(err: &RemoveSessionError) -> Self {
+        match err {
+            RemoveSessionError::SessionDoesNotExist => Self::SessionDoesNotExist,
+            RemoveSessionError::EventFinalized => Self::EventFinalized,
+            RemoveSessionError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+/// Attach the invalid field to validation errors of a session.
+fn save_session_error(err: SaveSessionError) -> ApiError {
+    let field = match err {
+        SaveSessionError::InvalidName => "name",
+        SaveSessionError::InvalidDuration => "turnaround_seconds",
+        _ => return ApiError::from(err),
+    };
+    let message = err.to_string();
+    ApiError::from(err).with_field_error(field, message)
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions",
+    tag = "sessions",
+    responses(
+        (status = 200, description = "All sessions ordered by their start", body = Vec<model::Session>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn list_sessions(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<model::Session>>, ApiError> {
+    let schedule_service = state.schedule_service();
+    let sessions = schedule_service.list_sessions().await?;
+    Ok(Json(sessions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/sessions",
+    tag = "sessions",
+    request_body = api::SessionBody,
+    responses(
+        (status = 200, description = "The session has been added", body = api::AddSessionResponse),
+        (status = 400, description = "The request is invalid, a competition is already swum in another session or the event is finalized", body = api::ErrorResponse),
+        (status = 404, description = "A competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn add_session(
+    State(state): State<AppState>,
+    Json(b): Json<api::SessionBody>,
+) -> Result<Json<api::AddSessionResponse>, ApiError> {
+    let schedule_service = state.schedule_service();
+    let session_id = schedule_service
+        .add_session(b)
+        .await
+        .map_err(save_session_error)?;
+    Ok(Json(api::AddSessionResponse { session_id }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}",
+    tag = "sessions",
+    params(
+        ("session_id" = Uuid, Path, description = "Id of the session"),
+    ),
+    responses(
+        (status = 200, description = "The session with its competitions in the order they are swum", body = model::Session),
+        (status = 404, description = "The session does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn session(
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<model::Session>, ApiError> {
+    let schedule_service = state.schedule_service();
+    let session = schedule_service.session(session_id).await?;
+    Ok(Json(session))
+}
+
+#[utoipa::path(
+    put,
+    path = "/sessions/{session_id}",
+    tag = "sessions",
+    params(
+        ("session_id" = Uuid, Path, description = "Id of the session"),
+    ),
+    request_body = api::SessionBody,
+    responses(
+        (status = 200, description = "The session and the order of its competitions have been replaced"),
+        (status = 400, description = "The request is invalid, a competition is already swum in another session or the event is finalized", body = api::ErrorResponse),
+        (status = 404, description = "The session or a competition does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn update_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+    Json(b): Json<api::SessionBody>,
+) -> Result<(), ApiError> {
+    let schedule_service = state.schedule_service();
+    schedule_service
+        .update_session(session_id, b)
+        .await
+        .map_err(save_session_error)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}",
+    tag = "sessions",
+    params(
+        ("session_id" = Uuid, Path, description = "Id of the session"),
+    ),
+    responses(
+        (status = 200, description = "The session has been removed, its competitions are no longer scheduled"),
+        (status = 400, description = "The event is finalized", body = api::ErrorResponse),
+        (status = 404, description = "The session does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn remove_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+) -> Result<(), ApiError> {
+    let schedule_service = state.schedule_service();
+    schedule_service.remove_session(session_id).await?;
+    Ok(())
+}
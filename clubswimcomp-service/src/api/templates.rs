@@ -0,0 +1,210 @@
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, State},
+    http::{header, HeaderMap},
+    routing::*,
+};
+use clubswimcomp_types::{api, model};
+use tracing::instrument;
+use utoipa::OpenApi;
+
+use crate::infra::{templates, typst_compiler::TypstOutput};
+use crate::services::{AssetError, DeleteAssetError, TemplatePreviewError, UploadAssetError};
+
+use super::extract::{Json, Path, Query};
+use super::{ApiError, AppState};
+
+/// Images and fonts easily exceed the default body limit.
+const ASSET_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+pub fn router() -> axum::Router<super::AppState> {
+    Router::new()
+        .route("/assets", get(list_assets))
+        .route(
+            "/assets/:name",
+            get(download_asset)
+                .put(upload_asset)
+                .delete(delete_asset)
+                .layer(DefaultBodyLimit::max(ASSET_BODY_LIMIT)),
+        )
+        .route("/:template/preview", get(preview))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_assets, download_asset, upload_asset, delete_asset, preview),
+    components(schemas(model::DocumentTemplate))
+)]
+pub(super) struct ApiDoc;
+
+impl From<&AssetError> for api::ErrorCode {
+    fn from(err: &AssetError) -> Self {
+        match err {
+            AssetError::AssetDoesNotExist => Self::AssetDoesNotExist,
+            AssetError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&UploadAssetError> for api::ErrorCode {
+    fn from(err: &UploadAssetError) -> Self {
+        match err {
+            UploadAssetError::InvalidAssetName => Self::InvalidAssetName,
+            UploadAssetError::InvalidTemplate(_) => Self::InvalidTemplate,
+            UploadAssetError::EventFinalized => Self::EventFinalized,
+            UploadAssetError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&DeleteAssetError> for api::ErrorCode {
+    fn from(err: &DeleteAssetError) -> Self {
+        match err {
+            DeleteAssetError::AssetDoesNotExist => Self::AssetDoesNotExist,
+            DeleteAssetError::InvalidTemplate(_) => Self::InvalidTemplate,
+            DeleteAssetError::EventFinalized => Self::EventFinalized,
+            DeleteAssetError::RepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<&TemplatePreviewError> for api::ErrorCode {
+    fn from(err: &TemplatePreviewError) -> Self {
+        match err {
+            TemplatePreviewError::PdfGenerationFailed(_) => Self::PdfGenerationFailed,
+            TemplatePreviewError::PageDoesNotExist(_) => Self::PageDoesNotExist,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/templates/assets",
+    tag = "templates",
+    responses(
+        (status = 200, description = "All bundled and uploaded assets", body = Vec<model::Asset>),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn list_assets(State(state): State<AppState>) -> Result<Json<Vec<model::Asset>>, ApiError> {
+    let template_service = state.template_service();
+    let assets = template_service.list_assets().await?;
+    Ok(Json(assets))
+}
+
+#[utoipa::path(
+    get,
+    path = "/templates/assets/{name}",
+    tag = "templates",
+    params(("name" = String, Path, description = "File name of the asset")),
+    responses(
+        (status = 200, description = "Content of the asset as it is used for the documents", body = [u8], content_type = "application/octet-stream"),
+        (status = 404, description = "The asset does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn download_asset(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let template_service = state.template_service();
+    let content = template_service.asset(&name).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.append(header::CONTENT_TYPE, content_type(&name).parse().unwrap());
+    headers.append(
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{name}\"").parse().unwrap(),
+    );
+
+    Ok((headers, content))
+}
+
+#[utoipa::path(
+    put,
+    path = "/templates/assets/{name}",
+    tag = "templates",
+    params(("name" = String, Path, description = "File name of the asset")),
+    request_body(content = [u8], content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "The asset has been uploaded"),
+        (status = 400, description = "The name is invalid, the documents can not be generated with the asset or the event is finalized", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state, content))]
+async fn upload_asset(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    content: Bytes,
+) -> Result<(), ApiError> {
+    let template_service = state.template_service();
+    template_service
+        .upload_asset(&name, content.to_vec())
+        .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/templates/assets/{name}",
+    tag = "templates",
+    params(("name" = String, Path, description = "File name of the asset")),
+    responses(
+        (status = 200, description = "The uploaded asset has been deleted, the bundled one is used again"),
+        (status = 400, description = "The documents can not be generated without the asset or the event is finalized", body = api::ErrorResponse),
+        (status = 404, description = "The asset has not been uploaded", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn delete_asset(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<(), ApiError> {
+    let template_service = state.template_service();
+    template_service.delete_asset(&name).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/templates/{template}/preview",
+    tag = "templates",
+    params(("template" = model::DocumentTemplate, Path, description = "The document to preview"), api::DocumentParams),
+    responses(
+        (status = 200, description = "The document generated with sample data", content(([u8] = "application/pdf"), ([u8] = "image/png"), ([u8] = "image/svg+xml"))),
+        (status = 404, description = "The page of the document does not exist", body = api::ErrorResponse),
+        (status = 500, description = "The service ran into an internal error", body = api::ErrorResponse),
+    )
+)]
+#[instrument(skip(state))]
+async fn preview(
+    State(state): State<AppState>,
+    Path(template): Path<model::DocumentTemplate>,
+    Query(document): Query<api::DocumentParams>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let template_service = state.template_service();
+    let preview = template_service
+        .preview(template, TypstOutput::from(&document))
+        .await?;
+
+    let file_name = templates::template_file(template).trim_end_matches(".typst");
+    let headers = super::document_headers(&document, &format!("{file_name}-preview"));
+
+    Ok((headers, preview))
+}
+
+fn content_type(name: &str) -> &'static str {
+    match name.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("typst") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
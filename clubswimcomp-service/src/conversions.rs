@@ -1,7 +1,12 @@
+use base64::prelude::*;
 use chrono::{NaiveDate, Utc};
 use clubswimcomp_types::model::*;
+use clubswimcomp_types::{api, backup};
+use uuid::Uuid;
 
 use crate::db;
+use crate::infra::best_times::ImportFormat;
+use crate::infra::typst_compiler::TypstOutput;
 
 impl From<db::Gender> for Gender {
     fn from(g: db::Gender) -> Self {
@@ -77,6 +82,30 @@ impl From<db::competitions::Competition> for Competition {
             distance: c.distance as _,
             stroke: c.stroke.into(),
             target_time: c.target_time as _,
+            relay: c.relay,
+            round: c.round.into(),
+        }
+    }
+}
+
+impl From<db::competitions::CompetitionRound> for CompetitionRound {
+    fn from(r: db::competitions::CompetitionRound) -> Self {
+        match r {
+            db::competitions::CompetitionRound::TimedFinal => Self::TimedFinal,
+            db::competitions::CompetitionRound::Prelims => Self::Prelims,
+            db::competitions::CompetitionRound::Finals => Self::Finals,
+            db::competitions::CompetitionRound::SwimOff => Self::SwimOff,
+        }
+    }
+}
+
+impl From<CompetitionRound> for db::competitions::CompetitionRound {
+    fn from(r: CompetitionRound) -> Self {
+        match r {
+            CompetitionRound::TimedFinal => Self::TimedFinal,
+            CompetitionRound::Prelims => Self::Prelims,
+            CompetitionRound::Finals => Self::Finals,
+            CompetitionRound::SwimOff => Self::SwimOff,
         }
     }
 }
@@ -99,3 +128,748 @@ impl From<db::groups::Group> for Group {
         }
     }
 }
+
+impl From<db::groups::Group> for backup::GroupRecord {
+    fn from(g: db::groups::Group) -> Self {
+        Self {
+            id: g.id,
+            name: g.name,
+        }
+    }
+}
+
+impl From<backup::GroupRecord> for db::groups::Group {
+    fn from(g: backup::GroupRecord) -> Self {
+        Self {
+            id: g.id,
+            name: g.name,
+        }
+    }
+}
+
+impl From<db::participants::Participant> for backup::ParticipantRecord {
+    fn from(p: db::participants::Participant) -> Self {
+        Self {
+            id: p.id,
+            short_id: p.short_id as _,
+            first_name: p.first_name,
+            last_name: p.last_name,
+            gender: p.gender.into(),
+            birthday: p.birthday,
+            group_id: p.group_id,
+        }
+    }
+}
+
+impl From<backup::ParticipantRecord> for db::participants::Participant {
+    fn from(p: backup::ParticipantRecord) -> Self {
+        Self {
+            id: p.id,
+            short_id: p.short_id as _,
+            first_name: p.first_name,
+            last_name: p.last_name,
+            gender: p.gender.into(),
+            birthday: p.birthday,
+            group_id: p.group_id,
+        }
+    }
+}
+
+impl From<db::competitions::Competition> for backup::CompetitionRecord {
+    fn from(c: db::competitions::Competition) -> Self {
+        Self {
+            id: c.id,
+            gender: c.gender.into(),
+            stroke: c.stroke.into(),
+            distance: c.distance as _,
+            target_time: c.target_time as _,
+            relay: c.relay,
+            round: c.round.into(),
+        }
+    }
+}
+
+impl From<backup::CompetitionRecord> for db::competitions::Competition {
+    fn from(c: backup::CompetitionRecord) -> Self {
+        Self {
+            id: c.id,
+            gender: c.gender.into(),
+            stroke: c.stroke.into(),
+            distance: c.distance as _,
+            target_time: c.target_time as _,
+            relay: c.relay,
+            round: c.round.into(),
+        }
+    }
+}
+
+impl From<db::registrations::Registration> for backup::RegistrationRecord {
+    fn from(r: db::registrations::Registration) -> Self {
+        Self {
+            id: r.id,
+            participant_id: r.participant_id,
+            competition_id: r.competition_id,
+            entry_time_millis: r.entry_time_millis.map(|t| t as _),
+            card_number: Some(r.card_number as _),
+            late_entry: r.late_entry,
+        }
+    }
+}
+
+/// Registrations without a card number must be numbered before, see
+/// [`crate::services::BackupService::restore_backup`].
+impl From<backup::RegistrationRecord> for db::registrations::Registration {
+    fn from(r: backup::RegistrationRecord) -> Self {
+        Self {
+            id: r.id,
+            participant_id: r.participant_id,
+            competition_id: r.competition_id,
+            entry_time_millis: r.entry_time_millis.map(|t| t as _),
+            card_number: r.card_number.unwrap_or_default() as _,
+            late_entry: r.late_entry,
+        }
+    }
+}
+
+impl From<db::backups::StoredResult> for backup::ResultRecord {
+    fn from(r: db::backups::StoredResult) -> Self {
+        Self {
+            registration_id: r.registration_id,
+            disqualified: r.disqualified,
+            time_millis: r.time_millis as _,
+        }
+    }
+}
+
+impl From<backup::ResultRecord> for db::backups::StoredResult {
+    fn from(r: backup::ResultRecord) -> Self {
+        Self {
+            registration_id: r.registration_id,
+            disqualified: r.disqualified,
+            time_millis: r.time_millis as _,
+        }
+    }
+}
+
+impl From<db::assets::Asset> for backup::AssetRecord {
+    fn from(a: db::assets::Asset) -> Self {
+        Self {
+            name: a.name,
+            content: BASE64_STANDARD.encode(a.content),
+            updated_at: a.updated_at,
+        }
+    }
+}
+
+impl TryFrom<backup::AssetRecord> for db::assets::Asset {
+    type Error = base64::DecodeError;
+
+    fn try_from(a: backup::AssetRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: a.name,
+            content: BASE64_STANDARD.decode(a.content)?,
+            updated_at: a.updated_at,
+        })
+    }
+}
+
+impl From<db::event::Event> for backup::EventRecord {
+    fn from(e: db::event::Event) -> Self {
+        Self {
+            id: e.id,
+            group_scoring: GroupScoring::from(&e),
+            ranking_rules: RankingRules::from(&e),
+            team_scoring: TeamScoring::from(&e),
+            registration_limits: RegistrationLimits::from(&e),
+            entry_fees: EntryFees::from(&e),
+            info: EventInfo::from(&e),
+            course: e.course.into(),
+            phase: e.phase.into(),
+            card_secret: BASE64_STANDARD.encode(e.card_secret),
+            created_at: e.created_at,
+        }
+    }
+}
+
+impl TryFrom<backup::EventRecord> for db::event::Event {
+    type Error = base64::DecodeError;
+
+    fn try_from(e: backup::EventRecord) -> Result<Self, Self::Error> {
+        let (group_scoring, scoring_result_count) = group_scoring_rule(e.group_scoring.rule);
+        Ok(Self {
+            id: e.id,
+            card_secret: BASE64_STANDARD.decode(e.card_secret)?,
+            created_at: e.created_at,
+            group_scoring,
+            scoring_result_count,
+            min_starts: e.group_scoring.min_starts as _,
+            shared_ranks: e.ranking_rules.shared_ranks.into(),
+            time_precision: e.ranking_rules.time_precision.into(),
+            group_tie_break: e.ranking_rules.group_tie_break.into(),
+            team_placing_points: team_placing_points(&e.team_scoring.placing_points),
+            team_max_scorers: e.team_scoring.max_scorers.map(|m| m as _),
+            team_relay_weight: e.team_scoring.relay_weight as _,
+            max_individual_starts: e.registration_limits.max_individual_starts.map(|m| m as _),
+            course: e.course.into(),
+            phase: e.phase.into(),
+            participant_fee_cents: e.entry_fees.participant_fee as _,
+            start_fee_cents: e.entry_fees.start_fee as _,
+            late_entry_surcharge_cents: e.entry_fees.late_entry_surcharge as _,
+            currency: e.entry_fees.currency,
+            name: e.info.name,
+            organization: e.info.organization,
+        })
+    }
+}
+
+impl From<&db::event::Event> for GroupScoring {
+    fn from(e: &db::event::Event) -> Self {
+        let rule = match e.group_scoring {
+            db::event::GroupScoring::Sum => GroupScoringRule::Sum,
+            db::event::GroupScoring::BestResults => GroupScoringRule::BestResults {
+                count: e.scoring_result_count.unwrap_or(1) as _,
+            },
+            db::event::GroupScoring::OnePerStroke => GroupScoringRule::OnePerStroke,
+            db::event::GroupScoring::Average => GroupScoringRule::Average,
+        };
+
+        Self {
+            rule,
+            min_starts: e.min_starts as _,
+        }
+    }
+}
+
+/// The scoring rule as it is stored with the event, the number of results
+/// is only stored for the best results.
+pub fn group_scoring_rule(rule: GroupScoringRule) -> (db::event::GroupScoring, Option<i32>) {
+    match rule {
+        GroupScoringRule::Sum => (db::event::GroupScoring::Sum, None),
+        GroupScoringRule::BestResults { count } => {
+            (db::event::GroupScoring::BestResults, Some(count as _))
+        }
+        GroupScoringRule::OnePerStroke => (db::event::GroupScoring::OnePerStroke, None),
+        GroupScoringRule::Average => (db::event::GroupScoring::Average, None),
+    }
+}
+
+impl From<&api::DocumentParams> for TypstOutput {
+    fn from(params: &api::DocumentParams) -> Self {
+        let page = params.page.unwrap_or(1) as usize;
+        match params.format.unwrap_or_default() {
+            api::DocumentFormat::Pdf => Self::Pdf,
+            api::DocumentFormat::Png => Self::Png { page },
+            api::DocumentFormat::Svg => Self::Svg { page },
+        }
+    }
+}
+
+impl From<&db::event::Event> for RankingRules {
+    fn from(e: &db::event::Event) -> Self {
+        Self {
+            shared_ranks: e.shared_ranks.into(),
+            time_precision: e.time_precision.into(),
+            group_tie_break: e.group_tie_break.into(),
+        }
+    }
+}
+
+impl From<db::event::SharedRanks> for SharedRanks {
+    fn from(s: db::event::SharedRanks) -> Self {
+        match s {
+            db::event::SharedRanks::Skip => Self::Skip,
+            db::event::SharedRanks::Dense => Self::Dense,
+        }
+    }
+}
+
+impl From<SharedRanks> for db::event::SharedRanks {
+    fn from(s: SharedRanks) -> Self {
+        match s {
+            SharedRanks::Skip => Self::Skip,
+            SharedRanks::Dense => Self::Dense,
+        }
+    }
+}
+
+impl From<db::event::TimePrecision> for TimePrecision {
+    fn from(p: db::event::TimePrecision) -> Self {
+        match p {
+            db::event::TimePrecision::Hundredths => Self::Hundredths,
+            db::event::TimePrecision::Thousandths => Self::Thousandths,
+        }
+    }
+}
+
+impl From<TimePrecision> for db::event::TimePrecision {
+    fn from(p: TimePrecision) -> Self {
+        match p {
+            TimePrecision::Hundredths => Self::Hundredths,
+            TimePrecision::Thousandths => Self::Thousandths,
+        }
+    }
+}
+
+impl From<db::event::GroupTieBreak> for GroupTieBreak {
+    fn from(t: db::event::GroupTieBreak) -> Self {
+        match t {
+            db::event::GroupTieBreak::None => Self::None,
+            db::event::GroupTieBreak::BestSingleResult => Self::BestSingleResult,
+        }
+    }
+}
+
+impl From<GroupTieBreak> for db::event::GroupTieBreak {
+    fn from(t: GroupTieBreak) -> Self {
+        match t {
+            GroupTieBreak::None => Self::None,
+            GroupTieBreak::BestSingleResult => Self::BestSingleResult,
+        }
+    }
+}
+
+impl From<&db::event::Event> for TeamScoring {
+    fn from(e: &db::event::Event) -> Self {
+        Self {
+            placing_points: e
+                .team_placing_points
+                .split(',')
+                .filter_map(|points| points.trim().parse().ok())
+                .collect(),
+            max_scorers: e.team_max_scorers.map(|m| m as _),
+            relay_weight: e.team_relay_weight as _,
+        }
+    }
+}
+
+/// The placing points as they are stored with the event.
+pub fn team_placing_points(placing_points: &[u32]) -> String {
+    placing_points
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl From<&db::event::Event> for RegistrationLimits {
+    fn from(e: &db::event::Event) -> Self {
+        Self {
+            max_individual_starts: e.max_individual_starts.map(|m| m as _),
+        }
+    }
+}
+
+impl From<&db::event::Event> for EntryFees {
+    fn from(e: &db::event::Event) -> Self {
+        Self {
+            participant_fee: e.participant_fee_cents as _,
+            start_fee: e.start_fee_cents as _,
+            late_entry_surcharge: e.late_entry_surcharge_cents as _,
+            currency: e.currency.clone(),
+        }
+    }
+}
+
+impl From<&db::event::Event> for EventInfo {
+    fn from(e: &db::event::Event) -> Self {
+        Self {
+            name: e.name.clone(),
+            organization: e.organization.clone(),
+        }
+    }
+}
+
+impl From<db::Course> for Course {
+    fn from(c: db::Course) -> Self {
+        match c {
+            db::Course::Short => Self::Short,
+            db::Course::Long => Self::Long,
+        }
+    }
+}
+
+impl From<Course> for db::Course {
+    fn from(c: Course) -> Self {
+        match c {
+            Course::Short => Self::Short,
+            Course::Long => Self::Long,
+        }
+    }
+}
+
+impl From<db::best_times::BestTime> for BestTime {
+    fn from(b: db::best_times::BestTime) -> Self {
+        Self {
+            stroke: b.stroke.into(),
+            distance: b.distance as _,
+            course: b.course.into(),
+            time: b.time_millis as _,
+            swum_on: b.swum_on,
+            meet: b.meet,
+        }
+    }
+}
+
+impl From<db::best_times::BestTime> for backup::BestTimeRecord {
+    fn from(b: db::best_times::BestTime) -> Self {
+        Self {
+            id: b.id,
+            first_name: b.first_name,
+            last_name: b.last_name,
+            birthday: b.birthday,
+            stroke: b.stroke.into(),
+            distance: b.distance as _,
+            course: b.course.into(),
+            time_millis: b.time_millis as _,
+            swum_on: b.swum_on,
+            meet: b.meet,
+            registration_id: b.registration_id,
+        }
+    }
+}
+
+impl From<backup::BestTimeRecord> for db::best_times::BestTime {
+    fn from(b: backup::BestTimeRecord) -> Self {
+        Self {
+            id: b.id,
+            first_name: b.first_name,
+            last_name: b.last_name,
+            birthday: b.birthday,
+            stroke: b.stroke.into(),
+            distance: b.distance as _,
+            course: b.course.into(),
+            time_millis: b.time_millis as _,
+            swum_on: b.swum_on,
+            meet: b.meet,
+            registration_id: b.registration_id,
+        }
+    }
+}
+
+impl From<db::records::RecordKind> for RecordKind {
+    fn from(k: db::records::RecordKind) -> Self {
+        match k {
+            db::records::RecordKind::Club => Self::Club,
+            db::records::RecordKind::Meet => Self::Meet,
+        }
+    }
+}
+
+impl From<RecordKind> for db::records::RecordKind {
+    fn from(k: RecordKind) -> Self {
+        match k {
+            RecordKind::Club => Self::Club,
+            RecordKind::Meet => Self::Meet,
+        }
+    }
+}
+
+impl From<db::records::RecordStatus> for RecordStatus {
+    fn from(s: db::records::RecordStatus) -> Self {
+        match s {
+            db::records::RecordStatus::Pending => Self::Pending,
+            db::records::RecordStatus::Approved => Self::Approved,
+            db::records::RecordStatus::Rejected => Self::Rejected,
+            db::records::RecordStatus::Superseded => Self::Superseded,
+        }
+    }
+}
+
+impl From<RecordStatus> for db::records::RecordStatus {
+    fn from(s: RecordStatus) -> Self {
+        match s {
+            RecordStatus::Pending => Self::Pending,
+            RecordStatus::Approved => Self::Approved,
+            RecordStatus::Rejected => Self::Rejected,
+            RecordStatus::Superseded => Self::Superseded,
+        }
+    }
+}
+
+impl From<db::records::Record> for Record {
+    fn from(r: db::records::Record) -> Self {
+        Self {
+            id: r.id,
+            kind: r.kind.into(),
+            gender: r.gender.into(),
+            stroke: r.stroke.into(),
+            distance: r.distance as _,
+            course: r.course.into(),
+            age_class: r.age_class as _,
+            time: r.time_millis as _,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            birthday: r.birthday,
+            swum_on: r.swum_on,
+            meet: r.meet,
+            registration_id: r.registration_id,
+            status: r.status.into(),
+            created_at: r.created_at,
+            decided_at: r.decided_at,
+            superseded_by: r.superseded_by,
+        }
+    }
+}
+
+impl From<db::event::EventPhase> for EventPhase {
+    fn from(p: db::event::EventPhase) -> Self {
+        match p {
+            db::event::EventPhase::RegistrationOpen => Self::RegistrationOpen,
+            db::event::EventPhase::RegistrationClosed => Self::RegistrationClosed,
+            db::event::EventPhase::MeetInProgress => Self::MeetInProgress,
+            db::event::EventPhase::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<EventPhase> for db::event::EventPhase {
+    fn from(p: EventPhase) -> Self {
+        match p {
+            EventPhase::RegistrationOpen => Self::RegistrationOpen,
+            EventPhase::RegistrationClosed => Self::RegistrationClosed,
+            EventPhase::MeetInProgress => Self::MeetInProgress,
+            EventPhase::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<db::event::PhaseChange> for PhaseChange {
+    fn from(c: db::event::PhaseChange) -> Self {
+        Self {
+            phase: c.phase.into(),
+            changed_at: c.changed_at,
+            reason: c.reason,
+        }
+    }
+}
+
+impl From<db::event::PhaseChange> for backup::PhaseChangeRecord {
+    fn from(c: db::event::PhaseChange) -> Self {
+        Self {
+            id: c.id,
+            phase: c.phase.into(),
+            changed_at: c.changed_at,
+            reason: c.reason,
+        }
+    }
+}
+
+impl From<backup::PhaseChangeRecord> for db::event::PhaseChange {
+    fn from(c: backup::PhaseChangeRecord) -> Self {
+        Self {
+            id: c.id,
+            phase: c.phase.into(),
+            changed_at: c.changed_at,
+            reason: c.reason,
+        }
+    }
+}
+
+impl From<db::records::Record> for backup::RecordRecord {
+    fn from(r: db::records::Record) -> Self {
+        Self {
+            id: r.id,
+            kind: r.kind.into(),
+            gender: r.gender.into(),
+            stroke: r.stroke.into(),
+            distance: r.distance as _,
+            course: r.course.into(),
+            age_class: r.age_class as _,
+            time_millis: r.time_millis as _,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            birthday: r.birthday,
+            swum_on: r.swum_on,
+            meet: r.meet,
+            registration_id: r.registration_id,
+            status: r.status.into(),
+            created_at: r.created_at,
+            decided_at: r.decided_at,
+            superseded_by: r.superseded_by,
+        }
+    }
+}
+
+impl From<backup::RecordRecord> for db::records::Record {
+    fn from(r: backup::RecordRecord) -> Self {
+        Self {
+            id: r.id,
+            kind: r.kind.into(),
+            gender: r.gender.into(),
+            stroke: r.stroke.into(),
+            distance: r.distance as _,
+            course: r.course.into(),
+            age_class: r.age_class as _,
+            time_millis: r.time_millis as _,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            birthday: r.birthday,
+            swum_on: r.swum_on,
+            meet: r.meet,
+            registration_id: r.registration_id,
+            status: r.status.into(),
+            created_at: r.created_at,
+            decided_at: r.decided_at,
+            superseded_by: r.superseded_by,
+        }
+    }
+}
+
+impl From<api::BestTimesFormat> for ImportFormat {
+    fn from(f: api::BestTimesFormat) -> Self {
+        match f {
+            api::BestTimesFormat::Csv => Self::Csv,
+            api::BestTimesFormat::Lenex => Self::Lenex,
+        }
+    }
+}
+
+impl From<db::competitions::Eligibility> for Eligibility {
+    fn from(e: db::competitions::Eligibility) -> Self {
+        Self {
+            min_age: e.min_age.map(|a| a as _),
+            max_age: e.max_age.map(|a| a as _),
+            min_birth_year: e.min_birth_year,
+            max_birth_year: e.max_birth_year,
+            group_ids: e.group_ids,
+            qualifying_time: e.qualifying_time.map(|t| t as _),
+        }
+    }
+}
+
+impl From<db::competitions::Eligibility> for backup::EligibilityRecord {
+    fn from(e: db::competitions::Eligibility) -> Self {
+        Self {
+            competition_id: e.competition_id,
+            eligibility: e.into(),
+        }
+    }
+}
+
+impl From<backup::EligibilityRecord> for db::competitions::Eligibility {
+    fn from(e: backup::EligibilityRecord) -> Self {
+        eligibility(e.competition_id, e.eligibility)
+    }
+}
+
+/// The eligibility of a competition as it is stored.
+pub fn eligibility(competition_id: Uuid, e: Eligibility) -> db::competitions::Eligibility {
+    db::competitions::Eligibility {
+        competition_id,
+        min_age: e.min_age.map(|a| a as _),
+        max_age: e.max_age.map(|a| a as _),
+        min_birth_year: e.min_birth_year,
+        max_birth_year: e.max_birth_year,
+        qualifying_time: e.qualifying_time.map(|t| t as _),
+        group_ids: e.group_ids,
+    }
+}
+
+impl From<db::sessions::Session> for Session {
+    fn from(s: db::sessions::Session) -> Self {
+        Self {
+            id: s.id,
+            name: s.name,
+            starts_at: s.starts_at,
+            warm_up_minutes: s.warm_up_minutes as u32,
+            turnaround_seconds: s.turnaround_seconds as u32,
+            competition_ids: s.competition_ids,
+        }
+    }
+}
+
+impl From<db::sessions::Session> for backup::SessionRecord {
+    fn from(s: db::sessions::Session) -> Self {
+        Self {
+            id: s.id,
+            name: s.name,
+            starts_at: s.starts_at,
+            warm_up_minutes: s.warm_up_minutes as u32,
+            turnaround_seconds: s.turnaround_seconds as u32,
+            competition_ids: s.competition_ids,
+        }
+    }
+}
+
+impl From<backup::SessionRecord> for db::sessions::Session {
+    fn from(s: backup::SessionRecord) -> Self {
+        Self {
+            id: s.id,
+            name: s.name,
+            starts_at: s.starts_at,
+            warm_up_minutes: s.warm_up_minutes as i32,
+            turnaround_seconds: s.turnaround_seconds as i32,
+            competition_ids: s.competition_ids,
+        }
+    }
+}
+
+impl From<db::finals::FinalsEntryStatus> for FinalsEntryStatus {
+    fn from(s: db::finals::FinalsEntryStatus) -> Self {
+        match s {
+            db::finals::FinalsEntryStatus::Finalist => Self::Finalist,
+            db::finals::FinalsEntryStatus::Alternate => Self::Alternate,
+            db::finals::FinalsEntryStatus::Scratched => Self::Scratched,
+        }
+    }
+}
+
+impl From<FinalsEntryStatus> for db::finals::FinalsEntryStatus {
+    fn from(s: FinalsEntryStatus) -> Self {
+        match s {
+            FinalsEntryStatus::Finalist => Self::Finalist,
+            FinalsEntryStatus::Alternate => Self::Alternate,
+            FinalsEntryStatus::Scratched => Self::Scratched,
+        }
+    }
+}
+
+impl From<db::finals::FinalsEntry> for backup::FinalsEntryRecord {
+    fn from(e: db::finals::FinalsEntry) -> Self {
+        Self {
+            participant_id: e.participant_id,
+            seed: e.seed as _,
+            time_millis: e.time_millis as _,
+            status: e.status.into(),
+        }
+    }
+}
+
+impl From<db::payments::Payment> for Payment {
+    fn from(p: db::payments::Payment) -> Self {
+        Self {
+            id: p.id,
+            participant_id: p.participant_id,
+            group_id: p.group_id,
+            amount: p.amount_cents as _,
+            received_on: p.received_on,
+            reference: p.reference,
+        }
+    }
+}
+
+impl From<db::payments::Payment> for backup::PaymentRecord {
+    fn from(p: db::payments::Payment) -> Self {
+        Self {
+            id: p.id,
+            participant_id: p.participant_id,
+            group_id: p.group_id,
+            amount: p.amount_cents as _,
+            received_on: p.received_on,
+            reference: p.reference,
+        }
+    }
+}
+
+impl From<backup::PaymentRecord> for db::payments::Payment {
+    fn from(p: backup::PaymentRecord) -> Self {
+        Self {
+            id: p.id,
+            participant_id: p.participant_id,
+            group_id: p.group_id,
+            amount_cents: p.amount as _,
+            received_on: p.received_on,
+            reference: p.reference,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// A template, image or font uploaded for the event.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Asset {
+    pub name: String,
+    pub content: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Store the asset, replacing the content of an asset with the same name.
+    async fn upsert_asset(&self, name: &str, content: &[u8]) -> Result<()>;
+
+    async fn all_assets(&self) -> Result<Vec<Asset>>;
+
+    async fn delete_asset(&self, name: &str) -> Result<Option<()>>;
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::assets::Asset;
+use super::best_times::BestTime;
+use super::competitions::{Competition, Eligibility};
+use super::event::{Event, PhaseChange};
+use super::finals::{Finals, FinalsEntry};
+use super::groups::Group;
+use super::participants::Participant;
+use super::payments::Payment;
+use super::records::Record;
+use super::registrations::Registration;
+use super::sessions::Session;
+
+/// Result of a registration as it is stored, without derived values.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct StoredResult {
+    pub registration_id: Uuid,
+    pub disqualified: bool,
+    pub time_millis: i64,
+}
+
+/// The complete dataset of the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub groups: Vec<Group>,
+    pub participants: Vec<Participant>,
+    pub competitions: Vec<Competition>,
+    pub registrations: Vec<Registration>,
+    pub results: Vec<StoredResult>,
+    pub assets: Vec<Asset>,
+    pub best_times: Vec<BestTime>,
+    pub records: Vec<Record>,
+    pub eligibilities: Vec<Eligibility>,
+    pub phase_changes: Vec<PhaseChange>,
+    pub sessions: Vec<Session>,
+    pub finals: Vec<Finals>,
+    pub finals_entries: Vec<FinalsEntry>,
+    pub payments: Vec<Payment>,
+    pub event: Option<Event>,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Read the complete dataset as one consistent snapshot.
+    ///
+    /// All entities are ordered by their id.
+    async fn export_snapshot(&self) -> Result<Snapshot>;
+
+    /// Check if the database does not contain any data.
+    ///
+    /// The event is created on demand and does not count as data.
+    async fn is_empty(&self) -> Result<bool>;
+
+    /// Insert all entities of the snapshot, keeping their ids.
+    ///
+    /// The event of the snapshot replaces the existing one.
+    ///
+    /// Either all entities are inserted or none of them.
+    async fn import_snapshot(&self, snapshot: &Snapshot) -> Result<()>;
+}
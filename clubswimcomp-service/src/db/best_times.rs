@@ -0,0 +1,50 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::{Course, Stroke};
+
+/// Time of a swimmer from an earlier event.
+///
+/// The swimmer is identified by the name and birthday, as the participants
+/// only belong to this event.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct BestTime {
+    pub id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub stroke: Stroke,
+    pub distance: i32,
+    pub course: Course,
+    pub time_millis: i32,
+    pub swum_on: NaiveDate,
+    /// Name of the event the time has been swum at.
+    pub meet: String,
+    /// The result of this event the time has been recorded from, `None` for
+    /// the imported times of earlier events.
+    pub registration_id: Option<Uuid>,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Store the times, skipping the ones of swims that are already stored.
+    ///
+    /// Returns the number of stored times.
+    async fn insert_best_times(&self, best_times: &[BestTime]) -> Result<u64>;
+
+    /// Store the time of a result of this event, replacing the time stored
+    /// for the result before.
+    ///
+    /// The time is removed with the result.
+    async fn record_best_time(&self, best_time: &BestTime) -> Result<()>;
+
+    /// All times of a swimmer, ordered by the day they have been swum.
+    async fn best_times_of_swimmer(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        birthday: NaiveDate,
+    ) -> Result<Vec<BestTime>>;
+}
@@ -1,124 +1,99 @@
-use anyhow::{Context as _, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use uuid::Uuid;
 
 use super::{Gender, Stroke};
 
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct Competition {
     pub id: Uuid,
     pub gender: super::Gender,
     pub stroke: super::Stroke,
     pub distance: i32,
     pub target_time: i64,
+    /// Swum by teams, each team is registered with one of its swimmers.
+    pub relay: bool,
+    pub round: CompetitionRound,
 }
 
-#[derive(Clone)]
-pub struct Repository {
-    pool: super::DatabasePool,
+/// The round a competition is swum in, finals and swim-offs are created with
+/// the finals of their prelims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "competition_round")]
+#[sqlx(rename_all = "snake_case")]
+pub enum CompetitionRound {
+    TimedFinal,
+    Prelims,
+    Finals,
+    SwimOff,
 }
 
-impl Repository {
-    pub fn new(pool: super::DatabasePool) -> Self {
-        Self { pool }
+impl CompetitionRound {
+    /// See [`clubswimcomp_types::model::CompetitionRound::is_individual_start`].
+    pub fn is_individual_start(self) -> bool {
+        matches!(self, Self::TimedFinal | Self::Prelims)
     }
+}
+
+/// Who may register for a competition besides the gender of the competition.
+///
+/// Limits that are `None` don't restrict the registrations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct Eligibility {
+    pub competition_id: Uuid,
+    pub min_age: Option<i32>,
+    pub max_age: Option<i32>,
+    pub min_birth_year: Option<i32>,
+    pub max_birth_year: Option<i32>,
+    /// Participants need a best time in the course of the event that is at
+    /// least this fast.
+    pub qualifying_time: Option<i32>,
+    /// Only participants of these groups may register, all if empty.
+    #[sqlx(skip)]
+    pub group_ids: Vec<Uuid>,
+}
 
-    pub async fn create_competition(
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Create a competition swum as timed final.
+    async fn create_competition(
         &self,
         gender: Gender,
         stroke: Stroke,
         distance: i32,
         target_time: i64,
-    ) -> Result<Uuid> {
-        sqlx::query_scalar!(
-            r#"
-                INSERT INTO competitions (
-                    gender, stroke, distance, target_time
-                ) VALUES (
-                    $1, $2, $3, $4
-                ) RETURNING id;
-            "#,
-            gender as Gender,
-            stroke as Stroke,
-            distance,
-            target_time as _,
-        )
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to execute INSERT INTO query")
-    }
+        relay: bool,
+    ) -> Result<Uuid>;
 
-    pub async fn search_competition(
+    /// Search for competitions matching all of the given filters.
+    ///
+    /// Filters that are `None` match every competition.
+    async fn search_competition(
         &self,
         gender: Option<Gender>,
         stroke: Option<Stroke>,
         distance: Option<i32>,
-    ) -> Result<Vec<Competition>> {
-        sqlx::query_as!(
-            Competition,
-            r#"
-                SELECT
-                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time
-                FROM competitions
-                WHERE
-                    (gender = $1 OR $1 IS NULL) AND
-                    (stroke = $2 OR $2 IS NULL) AND
-                    (distance = $3 OR $3 IS NULL);
-            "#,
-            gender as Option<Gender>,
-            stroke as Option<Stroke>,
-            distance
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch search results of competitions from database")
-    }
+    ) -> Result<Vec<Competition>>;
 
-    pub async fn all_competitions(&self) -> Result<Vec<Competition>> {
-        sqlx::query_as!(
-            Competition,
-            r#"
-                SELECT
-                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time
-                FROM competitions;
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of all competitions from database")
-    }
+    async fn all_competitions(&self) -> Result<Vec<Competition>>;
 
-    pub async fn competition_by_id(&self, competition_id: Uuid) -> Result<Option<Competition>> {
-        sqlx::query_as!(
-            Competition,
-            r#"
-                SELECT
-                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time
-                FROM competitions
-                WHERE id = $1;
-            "#,
-            competition_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to fetch competition by id from database")
-    }
+    async fn competition_by_id(&self, competition_id: Uuid) -> Result<Option<Competition>>;
 
-    pub async fn delete_competition(&self, competition_id: Uuid) -> Result<Option<()>> {
-        let rows = sqlx::query!(
-            r#"
-                DELETE FROM competitions
-                WHERE id = $1
-            "#,
-            competition_id
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to delete competition in database")?
-        .rows_affected();
+    async fn delete_competition(&self, competition_id: Uuid) -> Result<Option<()>>;
 
-        if rows > 0 {
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
-    }
+    /// The eligibility of the competition, `None` if only the gender is
+    /// checked.
+    async fn eligibility_of_competition(&self, competition_id: Uuid)
+        -> Result<Option<Eligibility>>;
+
+    /// The eligibilities of all competitions, ordered by the competition id.
+    ///
+    /// The groups of each eligibility are ordered by their id.
+    async fn all_eligibilities(&self) -> Result<Vec<Eligibility>>;
+
+    /// Store the eligibility of a competition, replacing the existing one.
+    async fn set_eligibility(&self, eligibility: &Eligibility) -> Result<()>;
+
+    /// Remove the eligibility of a competition, `None` if there is none.
+    async fn delete_eligibility(&self, competition_id: Uuid) -> Result<Option<()>>;
 }
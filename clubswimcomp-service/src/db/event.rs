@@ -0,0 +1,167 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::Course;
+
+/// The event the database belongs to, there is at most one.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Event {
+    pub id: Uuid,
+    /// Key of the signatures in the QR codes of the registration cards.
+    pub card_secret: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub group_scoring: GroupScoring,
+    /// Number of results counted with [`GroupScoring::BestResults`].
+    pub scoring_result_count: Option<i32>,
+    /// Participants with fewer starts are not ranked in their group.
+    pub min_starts: i32,
+    pub shared_ranks: SharedRanks,
+    pub time_precision: TimePrecision,
+    pub group_tie_break: GroupTieBreak,
+    /// Points for the places in each competition, separated by commas.
+    pub team_placing_points: String,
+    /// Only the best swimmers of a team score in each competition.
+    pub team_max_scorers: Option<i32>,
+    /// The places in relays score this many times the placing points.
+    pub team_relay_weight: i32,
+    /// Individual starts per participant, unlimited if `None`.
+    pub max_individual_starts: Option<i32>,
+    /// The pool of the event, times are only compared within a course.
+    pub course: Course,
+    pub phase: EventPhase,
+    /// Fee of every participant with at least one start, in cents.
+    pub participant_fee_cents: i32,
+    /// Fee of every start, in cents.
+    pub start_fee_cents: i32,
+    /// Added to the fee of every late entry, in cents.
+    pub late_entry_surcharge_cents: i32,
+    pub currency: String,
+    /// Printed in the header of the documents.
+    pub name: String,
+    /// The club organizing the event, printed next to the name.
+    pub organization: String,
+}
+
+/// Where the event is in its course, the phase decides which changes are
+/// allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "event_phase")]
+#[sqlx(rename_all = "snake_case")]
+pub enum EventPhase {
+    RegistrationOpen,
+    RegistrationClosed,
+    MeetInProgress,
+    Finalized,
+}
+
+/// A change of the phase of the event, kept to audit re-opened events.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct PhaseChange {
+    pub id: Uuid,
+    pub phase: EventPhase,
+    pub changed_at: DateTime<Utc>,
+    /// Why a finalized event has been re-opened.
+    pub reason: Option<String>,
+}
+
+/// How the points of the participants are added up for the group rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "group_scoring")]
+#[sqlx(rename_all = "snake_case")]
+pub enum GroupScoring {
+    Sum,
+    BestResults,
+    OnePerStroke,
+    Average,
+}
+
+/// Whether the ranks after a tie are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "shared_ranks")]
+#[sqlx(rename_all = "snake_case")]
+pub enum SharedRanks {
+    Skip,
+    Dense,
+}
+
+/// Precision of the times compared in the competition rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "time_precision")]
+#[sqlx(rename_all = "snake_case")]
+pub enum TimePrecision {
+    Hundredths,
+    Thousandths,
+}
+
+/// How participants with the same points are ordered in the group rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "group_tie_break")]
+#[sqlx(rename_all = "snake_case")]
+pub enum GroupTieBreak {
+    None,
+    BestSingleResult,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn event(&self) -> Result<Option<Event>>;
+
+    /// Store the event unless there already is one, returns the stored event.
+    async fn init_event(&self, event: &Event) -> Result<Event>;
+
+    /// Change the group scoring of the event, `None` if there is no event.
+    async fn update_group_scoring(
+        &self,
+        group_scoring: GroupScoring,
+        scoring_result_count: Option<i32>,
+        min_starts: i32,
+    ) -> Result<Option<()>>;
+
+    /// Change the ranking rules of the event, `None` if there is no event.
+    async fn update_ranking_rules(
+        &self,
+        shared_ranks: SharedRanks,
+        time_precision: TimePrecision,
+        group_tie_break: GroupTieBreak,
+    ) -> Result<Option<()>>;
+
+    /// Change the team scoring of the event, `None` if there is no event.
+    async fn update_team_scoring(
+        &self,
+        team_placing_points: &str,
+        team_max_scorers: Option<i32>,
+        team_relay_weight: i32,
+    ) -> Result<Option<()>>;
+
+    /// Change the registration limits of the event, `None` if there is no
+    /// event.
+    async fn update_registration_limits(
+        &self,
+        max_individual_starts: Option<i32>,
+    ) -> Result<Option<()>>;
+
+    /// Change the entry fees of the event, `None` if there is no event.
+    async fn update_entry_fees(
+        &self,
+        participant_fee_cents: i32,
+        start_fee_cents: i32,
+        late_entry_surcharge_cents: i32,
+        currency: &str,
+    ) -> Result<Option<()>>;
+
+    /// Change the name and the organizer of the event, `None` if there is no
+    /// event.
+    async fn update_info(&self, name: &str, organization: &str) -> Result<Option<()>>;
+
+    /// Change the course of the event, `None` if there is no event.
+    async fn update_course(&self, course: Course) -> Result<Option<()>>;
+
+    /// Change the phase of the event and add the change to the history,
+    /// `None` if there is no event.
+    async fn update_phase(&self, change: &PhaseChange) -> Result<Option<()>>;
+
+    /// All changes of the phase, ordered by the time they have been made.
+    async fn phase_changes(&self) -> Result<Vec<PhaseChange>>;
+}
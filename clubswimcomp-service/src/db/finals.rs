@@ -0,0 +1,80 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::competitions::Competition;
+
+/// The finals of a competition swum in prelims.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Finals {
+    /// The prelims the finalists advance from.
+    pub competition_id: Uuid,
+    pub finals_competition_id: Uuid,
+    pub finalists: i32,
+    pub alternates: i32,
+    /// Swim-offs of ties at the cutoff, in the order they are swum.
+    #[sqlx(skip)]
+    pub swim_off_ids: Vec<Uuid>,
+}
+
+/// A swimmer promoted from the prelims into the finals.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct FinalsEntry {
+    /// The prelims of the finals.
+    pub competition_id: Uuid,
+    pub participant_id: Uuid,
+    pub seed: i32,
+    pub time_millis: i32,
+    pub status: FinalsEntryStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "finals_entry_status")]
+#[sqlx(rename_all = "snake_case")]
+pub enum FinalsEntryStatus {
+    Finalist,
+    Alternate,
+    Scratched,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Create the finals of a competition.
+    ///
+    /// The competition is turned into prelims and the finals competition is
+    /// created along with the finals settings.
+    async fn create_finals(&self, finals: &Finals, finals_competition: &Competition) -> Result<()>;
+
+    async fn finals_of_competition(&self, competition_id: Uuid) -> Result<Option<Finals>>;
+
+    /// Change the number of finalists and alternates, `None` if the
+    /// competition has no finals.
+    async fn update_finals(&self, finals: &Finals) -> Result<Option<()>>;
+
+    /// The entries of the finals of the competition, ordered by their seed.
+    async fn finals_entries(&self, competition_id: Uuid) -> Result<Vec<FinalsEntry>>;
+
+    /// Insert the promoted swimmers and register the finalists for the finals
+    /// competition with their time of the prelims, either all of it or
+    /// nothing.
+    async fn promote_finalists(
+        &self,
+        finals_competition_id: Uuid,
+        entries: &[FinalsEntry],
+    ) -> Result<()>;
+
+    /// # Returns:
+    /// - `Ok(Some(()))` - if the status has been updated
+    /// - `Ok(None)` - if the swimmer has no entry in the finals
+    /// - `Err(e)` - in case of a database error
+    async fn update_finals_entry_status(
+        &self,
+        competition_id: Uuid,
+        participant_id: Uuid,
+        status: FinalsEntryStatus,
+    ) -> Result<Option<()>>;
+
+    /// Create the swim-off competition after the existing swim-offs of the
+    /// prelims.
+    async fn create_swim_off(&self, competition_id: Uuid, swim_off: &Competition) -> Result<()>;
+}
@@ -1,84 +1,20 @@
-use anyhow::{Context as _, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct Group {
     pub id: Uuid,
     pub name: String,
 }
 
-#[derive(Clone)]
-pub struct Repository {
-    pool: super::DatabasePool,
-}
-
-impl Repository {
-    pub fn new(pool: super::DatabasePool) -> Self {
-        Self { pool }
-    }
-
-    pub async fn create_group(&self, name: String) -> Result<Uuid> {
-        sqlx::query_scalar!(
-            r#"
-                INSERT INTO groups (
-                    name
-                ) VALUES (
-                    $1
-                ) RETURNING id;
-            "#,
-            name,
-        )
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to execute INSERT INTO query")
-    }
-
-    pub async fn all_groups(&self) -> Result<Vec<Group>> {
-        sqlx::query_as!(
-            Group,
-            r#"
-                SELECT
-                    id, name
-                FROM groups;
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of all groups from database")
-    }
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn create_group(&self, name: String) -> Result<Uuid>;
 
-    pub async fn group_by_id(&self, group_id: Uuid) -> Result<Option<Group>> {
-        sqlx::query_as!(
-            Group,
-            r#"
-                SELECT
-                    id, name
-                FROM groups
-                WHERE id = $1;
-            "#,
-            group_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to fetch group by id from database")
-    }
+    async fn all_groups(&self) -> Result<Vec<Group>>;
 
-    pub async fn delete_group(&self, group_id: Uuid) -> Result<Option<()>> {
-        let rows = sqlx::query!(
-            r#"
-                DELETE FROM groups
-                WHERE id = $1
-            "#,
-            group_id
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to delete group in database")?
-        .rows_affected();
+    async fn group_by_id(&self, group_id: Uuid) -> Result<Option<Group>>;
 
-        if rows > 0 {
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
-    }
+    async fn delete_group(&self, group_id: Uuid) -> Result<Option<()>>;
 }
@@ -0,0 +1,1407 @@
+//! In-memory repositories for tests.
+//!
+//! All repositories share one [`Store`] and enforce the same uniqueness and
+//! foreign key constraints as the database schemas, so services behave the
+//! same as with a real database.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{ensure, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::assets::{self, Asset};
+use super::backups::{self, Snapshot, StoredResult as SnapshotResult};
+use super::best_times::{self, BestTime};
+use super::competitions::{self, Competition, CompetitionRound, Eligibility};
+use super::event::{
+    self, Event, GroupScoring, GroupTieBreak, PhaseChange, SharedRanks, TimePrecision,
+};
+use super::finals::{self, Finals, FinalsEntry, FinalsEntryStatus};
+use super::groups::{self, Group};
+use super::participants::{self, Participant};
+use super::payments::{self, Payment};
+use super::records::{self, Record, RecordStatus};
+use super::registrations::{self, Registration, RegistrationResult};
+use super::sessions::{self, Session};
+use super::{Course, Gender, Stroke};
+
+#[derive(Clone, Default)]
+struct Store {
+    groups: Vec<Group>,
+    participants: Vec<Participant>,
+    competitions: Vec<Competition>,
+    registrations: Vec<Registration>,
+    results: Vec<StoredResult>,
+    assets: Vec<Asset>,
+    best_times: Vec<BestTime>,
+    records: Vec<Record>,
+    eligibilities: Vec<Eligibility>,
+    phase_changes: Vec<PhaseChange>,
+    sessions: Vec<Session>,
+    finals: Vec<Finals>,
+    finals_entries: Vec<FinalsEntry>,
+    payments: Vec<Payment>,
+    event: Option<Event>,
+    next_short_id: i32,
+}
+
+#[derive(Clone)]
+struct StoredResult {
+    registration_id: Uuid,
+    disqualified: bool,
+    time_millis: i64,
+}
+
+/// Handle to a shared in-memory store implementing all repositories.
+#[derive(Clone, Default)]
+pub struct Repository {
+    store: Arc<Mutex<Store>>,
+}
+
+impl Repository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'_, Store> {
+        self.store
+            .lock()
+            .expect("In-memory store has been poisoned")
+    }
+}
+
+/// FINA points as calculated by the database backends.
+fn fina_points(target_time: i64, time_millis: i64) -> i64 {
+    let ratio = target_time as f64 / time_millis as f64;
+    (1000.0 * ratio.powi(3)).floor() as i64
+}
+
+#[async_trait]
+impl participants::Repository for Repository {
+    async fn list_participants(&self) -> Result<Vec<Participant>> {
+        Ok(self.store().participants.clone())
+    }
+
+    async fn participant_by_id(&self, participant_id: Uuid) -> Result<Option<Participant>> {
+        let store = self.store();
+        Ok(store
+            .participants
+            .iter()
+            .find(|p| p.id == participant_id)
+            .cloned())
+    }
+
+    async fn participant_by_short_code(&self, short_id: i32) -> Result<Option<Participant>> {
+        let store = self.store();
+        Ok(store
+            .participants
+            .iter()
+            .find(|p| p.short_id == short_id)
+            .cloned())
+    }
+
+    async fn create_participant(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        gender: Gender,
+        birthday: NaiveDate,
+        group_id: Uuid,
+    ) -> Result<Uuid> {
+        let mut store = self.store();
+        ensure!(
+            store.groups.iter().any(|g| g.id == group_id),
+            "Foreign key violation: group does not exist"
+        );
+
+        // Short ids are handed out sequentially to keep tests deterministic
+        let short_id = 1000 + store.next_short_id;
+        store.next_short_id += 1;
+
+        let participant = Participant {
+            id: Uuid::new_v4(),
+            short_id,
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            gender,
+            birthday,
+            group_id,
+        };
+        let participant_id = participant.id;
+        store.participants.push(participant);
+
+        Ok(participant_id)
+    }
+
+    async fn delete_participant(&self, participant_id: Uuid) -> Result<bool> {
+        let mut store = self.store();
+        ensure!(
+            !store
+                .registrations
+                .iter()
+                .any(|r| r.participant_id == participant_id),
+            "Foreign key violation: participant has registrations"
+        );
+        ensure!(
+            !store
+                .payments
+                .iter()
+                .any(|p| p.participant_id == Some(participant_id)),
+            "Foreign key violation: participant has payments"
+        );
+
+        let count = store.participants.len();
+        store.participants.retain(|p| p.id != participant_id);
+        store
+            .finals_entries
+            .retain(|e| e.participant_id != participant_id);
+        Ok(store.participants.len() < count)
+    }
+
+    async fn list_participants_in_group(&self, group_id: Uuid) -> Result<Vec<Participant>> {
+        let store = self.store();
+        Ok(store
+            .participants
+            .iter()
+            .filter(|p| p.group_id == group_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl competitions::Repository for Repository {
+    async fn create_competition(
+        &self,
+        gender: Gender,
+        stroke: Stroke,
+        distance: i32,
+        target_time: i64,
+        relay: bool,
+    ) -> Result<Uuid> {
+        let mut store = self.store();
+        ensure!(
+            distance > 0 && distance % 25 == 0,
+            "Check violation: invalid distance"
+        );
+        ensure!(target_time > 0, "Check violation: invalid target time");
+
+        let competition = Competition {
+            id: Uuid::new_v4(),
+            gender,
+            stroke,
+            distance,
+            target_time,
+            relay,
+            round: CompetitionRound::TimedFinal,
+        };
+        ensure_unique_competition(&store, &competition)?;
+        let competition_id = competition.id;
+        store.competitions.push(competition);
+
+        Ok(competition_id)
+    }
+
+    async fn search_competition(
+        &self,
+        gender: Option<Gender>,
+        stroke: Option<Stroke>,
+        distance: Option<i32>,
+    ) -> Result<Vec<Competition>> {
+        let store = self.store();
+        Ok(store
+            .competitions
+            .iter()
+            .filter(|c| gender.is_none_or(|g| c.gender == g))
+            .filter(|c| stroke.is_none_or(|s| c.stroke == s))
+            .filter(|c| distance.is_none_or(|d| c.distance == d))
+            .cloned()
+            .collect())
+    }
+
+    async fn all_competitions(&self) -> Result<Vec<Competition>> {
+        Ok(self.store().competitions.clone())
+    }
+
+    async fn competition_by_id(&self, competition_id: Uuid) -> Result<Option<Competition>> {
+        let store = self.store();
+        Ok(store
+            .competitions
+            .iter()
+            .find(|c| c.id == competition_id)
+            .cloned())
+    }
+
+    async fn delete_competition(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        ensure!(
+            !store
+                .registrations
+                .iter()
+                .any(|r| r.competition_id == competition_id),
+            "Foreign key violation: competition has registrations"
+        );
+
+        let count = store.competitions.len();
+        store.competitions.retain(|c| c.id != competition_id);
+        store
+            .eligibilities
+            .retain(|e| e.competition_id != competition_id);
+        for session in store.sessions.iter_mut() {
+            session.competition_ids.retain(|c| *c != competition_id);
+        }
+        let removed_finals: Vec<Uuid> = store
+            .finals
+            .iter()
+            .filter(|f| {
+                f.competition_id == competition_id || f.finals_competition_id == competition_id
+            })
+            .map(|f| f.competition_id)
+            .collect();
+        store
+            .finals
+            .retain(|f| !removed_finals.contains(&f.competition_id));
+        store
+            .finals_entries
+            .retain(|e| !removed_finals.contains(&e.competition_id));
+        for finals in store.finals.iter_mut() {
+            finals.swim_off_ids.retain(|s| *s != competition_id);
+        }
+        Ok((store.competitions.len() < count).then_some(()))
+    }
+
+    async fn eligibility_of_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Option<Eligibility>> {
+        Ok(self
+            .store()
+            .eligibilities
+            .iter()
+            .find(|e| e.competition_id == competition_id)
+            .cloned())
+    }
+
+    async fn all_eligibilities(&self) -> Result<Vec<Eligibility>> {
+        let mut eligibilities = self.store().eligibilities.clone();
+        eligibilities.sort_by_key(|e| e.competition_id);
+        Ok(eligibilities)
+    }
+
+    async fn set_eligibility(&self, eligibility: &Eligibility) -> Result<()> {
+        let mut store = self.store();
+        ensure_eligibility_references(&store, eligibility)?;
+
+        let mut eligibility = eligibility.clone();
+        eligibility.group_ids.sort();
+        ensure!(
+            eligibility.group_ids.windows(2).all(|w| w[0] != w[1]),
+            "Unique violation: group is eligible more than once"
+        );
+        store
+            .eligibilities
+            .retain(|e| e.competition_id != eligibility.competition_id);
+        store.eligibilities.push(eligibility);
+        Ok(())
+    }
+
+    async fn delete_eligibility(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        let count = store.eligibilities.len();
+        store
+            .eligibilities
+            .retain(|e| e.competition_id != competition_id);
+        Ok((store.eligibilities.len() < count).then_some(()))
+    }
+}
+
+#[async_trait]
+impl registrations::Repository for Repository {
+    async fn registrations_of_participant(
+        &self,
+        participant_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        let store = self.store();
+        Ok(store
+            .registrations
+            .iter()
+            .filter(|r| r.participant_id == participant_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn result_for_registration(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<RegistrationResult>> {
+        let store = self.store();
+        let Some(result) = store
+            .results
+            .iter()
+            .find(|r| r.registration_id == registration_id)
+        else {
+            return Ok(None);
+        };
+
+        let target_time = store
+            .registrations
+            .iter()
+            .find(|r| r.id == registration_id)
+            .and_then(|r| store.competitions.iter().find(|c| c.id == r.competition_id))
+            .map(|c| c.target_time)
+            .context("Result is stored without registration or competition")?;
+
+        Ok(Some(RegistrationResult {
+            registration_id,
+            disqualified: result.disqualified,
+            time_millis: result.time_millis,
+            fina_points: fina_points(target_time, result.time_millis),
+        }))
+    }
+
+    async fn delete_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        ensure!(
+            !store
+                .results
+                .iter()
+                .any(|r| r.registration_id == registration_id),
+            "Foreign key violation: registration has a result"
+        );
+
+        let count = store.registrations.len();
+        store.registrations.retain(|r| r.id != registration_id);
+        Ok((store.registrations.len() < count).then_some(()))
+    }
+
+    async fn delete_result_for_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        let count = store.results.len();
+        store
+            .results
+            .retain(|r| r.registration_id != registration_id);
+        store
+            .best_times
+            .retain(|b| b.registration_id != Some(registration_id));
+        Ok((store.results.len() < count).then_some(()))
+    }
+
+    async fn create_registration(
+        &self,
+        participant_id: Uuid,
+        competition_id: Uuid,
+        entry_time_millis: Option<i32>,
+        late_entry: bool,
+    ) -> Result<Uuid> {
+        let mut store = self.store();
+        if let Some(existing) = store
+            .registrations
+            .iter()
+            .find(|r| r.participant_id == participant_id && r.competition_id == competition_id)
+        {
+            return Ok(existing.id);
+        }
+
+        ensure!(
+            store.participants.iter().any(|p| p.id == participant_id),
+            "Foreign key violation: participant does not exist"
+        );
+        ensure!(
+            store.competitions.iter().any(|c| c.id == competition_id),
+            "Foreign key violation: competition does not exist"
+        );
+
+        let card_number = store
+            .registrations
+            .iter()
+            .map(|r| r.card_number)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let registration = Registration {
+            id: Uuid::new_v4(),
+            participant_id,
+            competition_id,
+            entry_time_millis,
+            card_number,
+            late_entry,
+        };
+        let registration_id = registration.id;
+        store.registrations.push(registration);
+
+        Ok(registration_id)
+    }
+
+    async fn registration_by_id(&self, registration_id: Uuid) -> Result<Option<Registration>> {
+        let store = self.store();
+        Ok(store
+            .registrations
+            .iter()
+            .find(|r| r.id == registration_id)
+            .cloned())
+    }
+
+    async fn registration_by_card_number(&self, card_number: i32) -> Result<Option<Registration>> {
+        let store = self.store();
+        Ok(store
+            .registrations
+            .iter()
+            .find(|r| r.card_number == card_number)
+            .cloned())
+    }
+
+    async fn create_registration_result(
+        &self,
+        registration_id: Uuid,
+        time_millis: i32,
+        disqualified: bool,
+    ) -> Result<()> {
+        let mut store = self.store();
+        ensure!(time_millis > 0, "Check violation: invalid time");
+        ensure!(
+            store.registrations.iter().any(|r| r.id == registration_id),
+            "Foreign key violation: registration does not exist"
+        );
+        ensure!(
+            !store
+                .results
+                .iter()
+                .any(|r| r.registration_id == registration_id),
+            "Unique violation: registration already has a result"
+        );
+
+        store.results.push(StoredResult {
+            registration_id,
+            disqualified,
+            time_millis: time_millis as _,
+        });
+
+        Ok(())
+    }
+
+    async fn registrations_for_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        let store = self.store();
+        Ok(store
+            .registrations
+            .iter()
+            .filter(|r| r.competition_id == competition_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl groups::Repository for Repository {
+    async fn create_group(&self, name: String) -> Result<Uuid> {
+        let group = Group {
+            id: Uuid::new_v4(),
+            name,
+        };
+        let group_id = group.id;
+        self.store().groups.push(group);
+
+        Ok(group_id)
+    }
+
+    async fn all_groups(&self) -> Result<Vec<Group>> {
+        Ok(self.store().groups.clone())
+    }
+
+    async fn group_by_id(&self, group_id: Uuid) -> Result<Option<Group>> {
+        let store = self.store();
+        Ok(store.groups.iter().find(|g| g.id == group_id).cloned())
+    }
+
+    async fn delete_group(&self, group_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        ensure!(
+            !store.participants.iter().any(|p| p.group_id == group_id),
+            "Foreign key violation: group has participants"
+        );
+        ensure!(
+            !store.payments.iter().any(|p| p.group_id == Some(group_id)),
+            "Foreign key violation: group has payments"
+        );
+
+        let count = store.groups.len();
+        store.groups.retain(|g| g.id != group_id);
+        for eligibility in store.eligibilities.iter_mut() {
+            eligibility.group_ids.retain(|g| *g != group_id);
+        }
+        Ok((store.groups.len() < count).then_some(()))
+    }
+}
+
+#[async_trait]
+impl backups::Repository for Repository {
+    async fn export_snapshot(&self) -> Result<Snapshot> {
+        let store = self.store();
+        let mut snapshot = Snapshot {
+            groups: store.groups.clone(),
+            participants: store.participants.clone(),
+            competitions: store.competitions.clone(),
+            registrations: store.registrations.clone(),
+            results: store
+                .results
+                .iter()
+                .map(|r| SnapshotResult {
+                    registration_id: r.registration_id,
+                    disqualified: r.disqualified,
+                    time_millis: r.time_millis,
+                })
+                .collect(),
+            assets: store.assets.clone(),
+            best_times: store.best_times.clone(),
+            records: store.records.clone(),
+            eligibilities: store.eligibilities.clone(),
+            phase_changes: store.phase_changes.clone(),
+            sessions: store.sessions.clone(),
+            finals: store.finals.clone(),
+            finals_entries: store.finals_entries.clone(),
+            payments: store.payments.clone(),
+            event: store.event.clone(),
+        };
+
+        snapshot.groups.sort_by_key(|g| g.id);
+        snapshot.participants.sort_by_key(|p| p.id);
+        snapshot.competitions.sort_by_key(|c| c.id);
+        snapshot.registrations.sort_by_key(|r| r.id);
+        snapshot.results.sort_by_key(|r| r.registration_id);
+        snapshot.assets.sort_by(|a, b| a.name.cmp(&b.name));
+        snapshot.best_times.sort_by_key(|b| b.id);
+        snapshot.records.sort_by_key(|r| r.id);
+        snapshot.eligibilities.sort_by_key(|e| e.competition_id);
+        snapshot.phase_changes.sort_by_key(|c| c.id);
+        snapshot.sessions.sort_by_key(|s| s.id);
+        snapshot.finals.sort_by_key(|f| f.competition_id);
+        snapshot
+            .finals_entries
+            .sort_by_key(|e| (e.competition_id, e.seed));
+        snapshot.payments.sort_by_key(|p| p.id);
+
+        Ok(snapshot)
+    }
+
+    async fn is_empty(&self) -> Result<bool> {
+        let store = self.store();
+        Ok(store.groups.is_empty()
+            && store.participants.is_empty()
+            && store.competitions.is_empty()
+            && store.registrations.is_empty()
+            && store.results.is_empty()
+            && store.assets.is_empty()
+            && store.best_times.is_empty()
+            && store.records.is_empty())
+    }
+
+    async fn import_snapshot(&self, snapshot: &Snapshot) -> Result<()> {
+        let mut store = self.store();
+
+        // Changes are applied to a copy, so nothing is stored on errors
+        let mut new_store = store.clone();
+        for group in snapshot.groups.iter() {
+            ensure!(
+                !new_store.groups.iter().any(|g| g.id == group.id),
+                "Unique violation: group id already exists"
+            );
+            new_store.groups.push(group.clone());
+        }
+        for participant in snapshot.participants.iter() {
+            ensure!(
+                !new_store
+                    .participants
+                    .iter()
+                    .any(|p| p.id == participant.id || p.short_id == participant.short_id),
+                "Unique violation: participant id or short id already exists"
+            );
+            ensure!(
+                new_store
+                    .groups
+                    .iter()
+                    .any(|g| g.id == participant.group_id),
+                "Foreign key violation: group does not exist"
+            );
+            new_store.participants.push(participant.clone());
+        }
+        for competition in snapshot.competitions.iter() {
+            ensure!(
+                !new_store
+                    .competitions
+                    .iter()
+                    .any(|c| c.id == competition.id),
+                "Unique violation: competition id already exists"
+            );
+            new_store.competitions.push(competition.clone());
+        }
+        for registration in snapshot.registrations.iter() {
+            ensure!(
+                !new_store
+                    .registrations
+                    .iter()
+                    .any(|r| r.id == registration.id || r.card_number == registration.card_number),
+                "Unique violation: registration id or card number already exists"
+            );
+            ensure!(
+                new_store
+                    .participants
+                    .iter()
+                    .any(|p| p.id == registration.participant_id),
+                "Foreign key violation: participant does not exist"
+            );
+            ensure!(
+                new_store
+                    .competitions
+                    .iter()
+                    .any(|c| c.id == registration.competition_id),
+                "Foreign key violation: competition does not exist"
+            );
+            new_store.registrations.push(registration.clone());
+        }
+        for result in snapshot.results.iter() {
+            ensure!(
+                !new_store
+                    .results
+                    .iter()
+                    .any(|r| r.registration_id == result.registration_id),
+                "Unique violation: registration already has a result"
+            );
+            ensure!(
+                new_store
+                    .registrations
+                    .iter()
+                    .any(|r| r.id == result.registration_id),
+                "Foreign key violation: registration does not exist"
+            );
+            new_store.results.push(StoredResult {
+                registration_id: result.registration_id,
+                disqualified: result.disqualified,
+                time_millis: result.time_millis,
+            });
+        }
+
+        for asset in snapshot.assets.iter() {
+            ensure!(
+                !new_store.assets.iter().any(|a| a.name == asset.name),
+                "Unique violation: asset name already exists"
+            );
+            new_store.assets.push(asset.clone());
+        }
+
+        for best_time in snapshot.best_times.iter() {
+            ensure!(
+                !new_store
+                    .best_times
+                    .iter()
+                    .any(|b| b.id == best_time.id || same_swim(b, best_time)),
+                "Unique violation: best time id or swim already exists"
+            );
+            if let Some(registration_id) = best_time.registration_id {
+                ensure!(
+                    new_store
+                        .results
+                        .iter()
+                        .any(|r| r.registration_id == registration_id),
+                    "Foreign key violation: result does not exist"
+                );
+            }
+            new_store.best_times.push(best_time.clone());
+        }
+
+        for record in snapshot.records.iter() {
+            ensure!(
+                !new_store.records.iter().any(|r| r.id == record.id),
+                "Unique violation: record id already exists"
+            );
+            new_store.records.push(record.clone());
+        }
+
+        for eligibility in snapshot.eligibilities.iter() {
+            ensure_eligibility_references(&new_store, eligibility)?;
+            ensure!(
+                !new_store
+                    .eligibilities
+                    .iter()
+                    .any(|e| e.competition_id == eligibility.competition_id),
+                "Unique violation: competition already has an eligibility"
+            );
+            new_store.eligibilities.push(eligibility.clone());
+        }
+
+        for change in snapshot.phase_changes.iter() {
+            ensure!(
+                !new_store.phase_changes.iter().any(|c| c.id == change.id),
+                "Unique violation: phase change id already exists"
+            );
+            new_store.phase_changes.push(change.clone());
+        }
+
+        for session in snapshot.sessions.iter() {
+            ensure!(
+                !new_store.sessions.iter().any(|s| s.id == session.id),
+                "Unique violation: session id already exists"
+            );
+            ensure_session_references(&new_store, session)?;
+            new_store.sessions.push(session.clone());
+        }
+
+        for finals in snapshot.finals.iter() {
+            ensure_finals_references(&new_store, finals)?;
+            new_store.finals.push(finals.clone());
+        }
+
+        for entry in snapshot.finals_entries.iter() {
+            ensure_finals_entry_references(&new_store, entry)?;
+            new_store.finals_entries.push(entry.clone());
+        }
+
+        for payment in snapshot.payments.iter() {
+            ensure!(
+                !new_store.payments.iter().any(|p| p.id == payment.id),
+                "Unique violation: payment id already exists"
+            );
+            ensure_payment_references(&new_store, payment)?;
+            new_store.payments.push(payment.clone());
+        }
+
+        if let Some(event) = snapshot.event.as_ref() {
+            new_store.event = Some(event.clone());
+        }
+
+        *store = new_store;
+        Ok(())
+    }
+}
+
+/// Both times have been recorded from the same result of this event, or both
+/// have been imported for the same race of a swimmer on the same day.
+fn same_swim(a: &BestTime, b: &BestTime) -> bool {
+    match (a.registration_id, b.registration_id) {
+        (Some(a_id), Some(b_id)) => a_id == b_id,
+        (None, None) => {
+            (
+                &a.first_name,
+                &a.last_name,
+                a.birthday,
+                a.stroke,
+                a.distance,
+                a.course,
+                a.swum_on,
+            ) == (
+                &b.first_name,
+                &b.last_name,
+                b.birthday,
+                b.stroke,
+                b.distance,
+                b.course,
+                b.swum_on,
+            )
+        }
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl best_times::Repository for Repository {
+    async fn insert_best_times(&self, best_times: &[BestTime]) -> Result<u64> {
+        let mut store = self.store();
+        let mut inserted = 0;
+        for best_time in best_times {
+            if !store.best_times.iter().any(|b| same_swim(b, best_time)) {
+                store.best_times.push(best_time.clone());
+                inserted += 1;
+            }
+        }
+        Ok(inserted)
+    }
+
+    async fn record_best_time(&self, best_time: &BestTime) -> Result<()> {
+        let mut store = self.store();
+        ensure!(
+            store
+                .results
+                .iter()
+                .any(|r| Some(r.registration_id) == best_time.registration_id),
+            "Foreign key violation: result does not exist"
+        );
+        match store
+            .best_times
+            .iter_mut()
+            .find(|b| same_swim(b, best_time))
+        {
+            Some(stored) => {
+                stored.time_millis = best_time.time_millis;
+                stored.swum_on = best_time.swum_on;
+                stored.meet = best_time.meet.clone();
+            }
+            None => store.best_times.push(best_time.clone()),
+        }
+        Ok(())
+    }
+
+    async fn best_times_of_swimmer(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        birthday: NaiveDate,
+    ) -> Result<Vec<BestTime>> {
+        let mut best_times = self
+            .store()
+            .best_times
+            .iter()
+            .filter(|b| {
+                b.first_name == first_name && b.last_name == last_name && b.birthday == birthday
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        best_times.sort_by_key(|b| (b.swum_on, b.id));
+        Ok(best_times)
+    }
+}
+
+#[async_trait]
+impl records::Repository for Repository {
+    async fn create_record(&self, record: &Record) -> Result<()> {
+        let mut store = self.store();
+        ensure!(
+            !store.records.iter().any(|r| r.id == record.id),
+            "Unique violation: record id already exists"
+        );
+        store.records.push(record.clone());
+        Ok(())
+    }
+
+    async fn list_records(&self) -> Result<Vec<Record>> {
+        let mut records = self.store().records.clone();
+        records.sort_by_key(|r| (r.created_at, r.id));
+        Ok(records)
+    }
+
+    async fn record_by_id(&self, record_id: Uuid) -> Result<Option<Record>> {
+        Ok(self
+            .store()
+            .records
+            .iter()
+            .find(|r| r.id == record_id)
+            .cloned())
+    }
+
+    async fn update_record_status(
+        &self,
+        record_id: Uuid,
+        status: RecordStatus,
+        decided_at: DateTime<Utc>,
+        superseded_by: Option<Uuid>,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store
+            .records
+            .iter_mut()
+            .find(|r| r.id == record_id)
+            .map(|record| {
+                record.status = status;
+                record.decided_at = Some(decided_at);
+                record.superseded_by = superseded_by;
+            }))
+    }
+}
+
+/// Ensure the competition and groups of the eligibility exist.
+fn ensure_eligibility_references(store: &Store, eligibility: &Eligibility) -> Result<()> {
+    ensure!(
+        store
+            .competitions
+            .iter()
+            .any(|c| c.id == eligibility.competition_id),
+        "Foreign key violation: competition does not exist"
+    );
+    ensure!(
+        eligibility
+            .group_ids
+            .iter()
+            .all(|group_id| store.groups.iter().any(|g| g.id == *group_id)),
+        "Foreign key violation: group does not exist"
+    );
+    Ok(())
+}
+
+/// Competitions must exist and may only be swum in one session.
+fn ensure_session_references(store: &Store, session: &Session) -> Result<()> {
+    for (index, competition_id) in session.competition_ids.iter().enumerate() {
+        ensure!(
+            store.competitions.iter().any(|c| c.id == *competition_id),
+            "Foreign key violation: competition does not exist"
+        );
+        ensure!(
+            !session.competition_ids[..index].contains(competition_id)
+                && !store
+                    .sessions
+                    .iter()
+                    .filter(|s| s.id != session.id)
+                    .any(|s| s.competition_ids.contains(competition_id)),
+            "Unique violation: competition is already in a session"
+        );
+    }
+    Ok(())
+}
+
+/// A payment is received either from a participant or for a group.
+fn ensure_payment_references(store: &Store, payment: &Payment) -> Result<()> {
+    match (payment.participant_id, payment.group_id) {
+        (Some(participant_id), None) => ensure!(
+            store.participants.iter().any(|p| p.id == participant_id),
+            "Foreign key violation: participant does not exist"
+        ),
+        (None, Some(group_id)) => ensure!(
+            store.groups.iter().any(|g| g.id == group_id),
+            "Foreign key violation: group does not exist"
+        ),
+        _ => anyhow::bail!("Check violation: payment needs either a participant or a group"),
+    }
+    ensure!(
+        payment.amount_cents > 0,
+        "Check violation: amount must be positive"
+    );
+    Ok(())
+}
+
+/// Competitions of a kind are unique per round, except for the swim-offs.
+/// Relays are another kind than the single races.
+fn ensure_unique_competition(store: &Store, competition: &Competition) -> Result<()> {
+    ensure!(
+        !store.competitions.iter().any(|c| c.id == competition.id),
+        "Unique violation: competition id already exists"
+    );
+    ensure!(
+        competition.round == CompetitionRound::SwimOff
+            || !store.competitions.iter().any(|c| {
+                c.gender == competition.gender
+                    && c.stroke == competition.stroke
+                    && c.distance == competition.distance
+                    && c.relay == competition.relay
+                    && c.round == competition.round
+            }),
+        "Unique violation: same competition already exists"
+    );
+    Ok(())
+}
+
+/// Both competitions of the finals must exist, each competition may only be
+/// part of one finals.
+fn ensure_finals_references(store: &Store, finals: &Finals) -> Result<()> {
+    ensure!(
+        finals.finalists > 0 && finals.alternates >= 0,
+        "Check violation: invalid number of finalists or alternates"
+    );
+    for competition_id in [finals.competition_id, finals.finals_competition_id]
+        .iter()
+        .chain(finals.swim_off_ids.iter())
+    {
+        ensure!(
+            store.competitions.iter().any(|c| c.id == *competition_id),
+            "Foreign key violation: competition does not exist"
+        );
+    }
+    ensure!(
+        !store.finals.iter().any(|f| {
+            f.competition_id == finals.competition_id
+                || f.finals_competition_id == finals.finals_competition_id
+        }),
+        "Unique violation: competition already has finals"
+    );
+    Ok(())
+}
+
+/// The finals and the participant of the entry must exist, seeds are unique
+/// per finals.
+fn ensure_finals_entry_references(store: &Store, entry: &FinalsEntry) -> Result<()> {
+    ensure!(
+        entry.seed > 0 && entry.time_millis > 0,
+        "Check violation: invalid seed or time"
+    );
+    ensure!(
+        store
+            .finals
+            .iter()
+            .any(|f| f.competition_id == entry.competition_id),
+        "Foreign key violation: finals do not exist"
+    );
+    ensure!(
+        store
+            .participants
+            .iter()
+            .any(|p| p.id == entry.participant_id),
+        "Foreign key violation: participant does not exist"
+    );
+    ensure!(
+        !store.finals_entries.iter().any(|e| {
+            e.competition_id == entry.competition_id
+                && (e.participant_id == entry.participant_id || e.seed == entry.seed)
+        }),
+        "Unique violation: participant or seed already has an entry"
+    );
+    Ok(())
+}
+
+#[async_trait]
+impl finals::Repository for Repository {
+    async fn create_finals(&self, finals: &Finals, finals_competition: &Competition) -> Result<()> {
+        let mut store = self.store();
+
+        // Changes are applied to a copy, so nothing is stored on errors
+        let mut new_store = store.clone();
+        let prelims = new_store
+            .competitions
+            .iter_mut()
+            .find(|c| c.id == finals.competition_id)
+            .context("Foreign key violation: competition does not exist")?;
+        prelims.round = CompetitionRound::Prelims;
+        let prelims = prelims.clone();
+        new_store.competitions.retain(|c| c.id != prelims.id);
+        ensure_unique_competition(&new_store, &prelims)?;
+        new_store.competitions.push(prelims);
+
+        ensure_unique_competition(&new_store, finals_competition)?;
+        new_store.competitions.push(finals_competition.clone());
+
+        ensure_finals_references(&new_store, finals)?;
+        new_store.finals.push(Finals {
+            swim_off_ids: Vec::new(),
+            ..finals.clone()
+        });
+
+        *store = new_store;
+        Ok(())
+    }
+
+    async fn finals_of_competition(&self, competition_id: Uuid) -> Result<Option<Finals>> {
+        let store = self.store();
+        Ok(store
+            .finals
+            .iter()
+            .find(|f| f.competition_id == competition_id)
+            .cloned())
+    }
+
+    async fn update_finals(&self, finals: &Finals) -> Result<Option<()>> {
+        let mut store = self.store();
+        ensure!(
+            finals.finalists > 0 && finals.alternates >= 0,
+            "Check violation: invalid number of finalists or alternates"
+        );
+        let Some(stored) = store
+            .finals
+            .iter_mut()
+            .find(|f| f.competition_id == finals.competition_id)
+        else {
+            return Ok(None);
+        };
+        stored.finalists = finals.finalists;
+        stored.alternates = finals.alternates;
+        Ok(Some(()))
+    }
+
+    async fn finals_entries(&self, competition_id: Uuid) -> Result<Vec<FinalsEntry>> {
+        let store = self.store();
+        let mut entries: Vec<FinalsEntry> = store
+            .finals_entries
+            .iter()
+            .filter(|e| e.competition_id == competition_id)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.seed);
+        Ok(entries)
+    }
+
+    async fn promote_finalists(
+        &self,
+        finals_competition_id: Uuid,
+        entries: &[FinalsEntry],
+    ) -> Result<()> {
+        let mut store = self.store();
+
+        // Changes are applied to a copy, so nothing is stored on errors
+        let mut new_store = store.clone();
+        for entry in entries.iter() {
+            ensure_finals_entry_references(&new_store, entry)?;
+            new_store.finals_entries.push(entry.clone());
+
+            if entry.status != FinalsEntryStatus::Finalist {
+                continue;
+            }
+            ensure!(
+                new_store
+                    .competitions
+                    .iter()
+                    .any(|c| c.id == finals_competition_id),
+                "Foreign key violation: competition does not exist"
+            );
+            ensure!(
+                !new_store.registrations.iter().any(|r| {
+                    r.participant_id == entry.participant_id
+                        && r.competition_id == finals_competition_id
+                }),
+                "Unique violation: finalist is already registered for the finals"
+            );
+            let card_number = new_store
+                .registrations
+                .iter()
+                .map(|r| r.card_number)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            new_store.registrations.push(Registration {
+                id: Uuid::new_v4(),
+                participant_id: entry.participant_id,
+                competition_id: finals_competition_id,
+                entry_time_millis: Some(entry.time_millis),
+                card_number,
+                late_entry: false,
+            });
+        }
+
+        *store = new_store;
+        Ok(())
+    }
+
+    async fn update_finals_entry_status(
+        &self,
+        competition_id: Uuid,
+        participant_id: Uuid,
+        status: FinalsEntryStatus,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        let Some(entry) = store
+            .finals_entries
+            .iter_mut()
+            .find(|e| e.competition_id == competition_id && e.participant_id == participant_id)
+        else {
+            return Ok(None);
+        };
+        entry.status = status;
+        Ok(Some(()))
+    }
+
+    async fn create_swim_off(&self, competition_id: Uuid, swim_off: &Competition) -> Result<()> {
+        let mut store = self.store();
+        ensure_unique_competition(&store, swim_off)?;
+        let finals = store
+            .finals
+            .iter_mut()
+            .find(|f| f.competition_id == competition_id)
+            .context("Foreign key violation: finals do not exist")?;
+        finals.swim_off_ids.push(swim_off.id);
+        store.competitions.push(swim_off.clone());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl sessions::Repository for Repository {
+    async fn create_session(&self, session: &Session) -> Result<()> {
+        let mut store = self.store();
+        ensure!(
+            !store.sessions.iter().any(|s| s.id == session.id),
+            "Unique violation: session id already exists"
+        );
+        ensure_session_references(&store, session)?;
+        store.sessions.push(session.clone());
+        Ok(())
+    }
+
+    async fn all_sessions(&self) -> Result<Vec<Session>> {
+        let mut sessions = self.store().sessions.clone();
+        sessions.sort_by_key(|s| (s.starts_at, s.id));
+        Ok(sessions)
+    }
+
+    async fn session_by_id(&self, session_id: Uuid) -> Result<Option<Session>> {
+        let store = self.store();
+        Ok(store.sessions.iter().find(|s| s.id == session_id).cloned())
+    }
+
+    async fn update_session(&self, session: &Session) -> Result<Option<()>> {
+        let mut store = self.store();
+        ensure_session_references(&store, session)?;
+        let Some(stored) = store.sessions.iter_mut().find(|s| s.id == session.id) else {
+            return Ok(None);
+        };
+        *stored = session.clone();
+        Ok(Some(()))
+    }
+
+    async fn delete_session(&self, session_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        let count = store.sessions.len();
+        store.sessions.retain(|s| s.id != session_id);
+        Ok((store.sessions.len() < count).then_some(()))
+    }
+}
+
+#[async_trait]
+impl payments::Repository for Repository {
+    async fn create_payment(&self, payment: &Payment) -> Result<()> {
+        let mut store = self.store();
+        ensure!(
+            !store.payments.iter().any(|p| p.id == payment.id),
+            "Unique violation: payment id already exists"
+        );
+        ensure_payment_references(&store, payment)?;
+        store.payments.push(payment.clone());
+        Ok(())
+    }
+
+    async fn all_payments(&self) -> Result<Vec<Payment>> {
+        let mut payments = self.store().payments.clone();
+        payments.sort_by_key(|p| (p.received_on, p.id));
+        Ok(payments)
+    }
+
+    async fn delete_payment(&self, payment_id: Uuid) -> Result<Option<()>> {
+        let mut store = self.store();
+        let count = store.payments.len();
+        store.payments.retain(|p| p.id != payment_id);
+        Ok((store.payments.len() < count).then_some(()))
+    }
+}
+
+#[async_trait]
+impl assets::Repository for Repository {
+    async fn upsert_asset(&self, name: &str, content: &[u8]) -> Result<()> {
+        let mut store = self.store();
+        store.assets.retain(|a| a.name != name);
+        store.assets.push(Asset {
+            name: name.to_string(),
+            content: content.to_vec(),
+            updated_at: chrono::Utc::now(),
+        });
+        Ok(())
+    }
+
+    async fn all_assets(&self) -> Result<Vec<Asset>> {
+        let mut assets = self.store().assets.clone();
+        assets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(assets)
+    }
+
+    async fn delete_asset(&self, name: &str) -> Result<Option<()>> {
+        let mut store = self.store();
+        let count = store.assets.len();
+        store.assets.retain(|a| a.name != name);
+        Ok((store.assets.len() < count).then_some(()))
+    }
+}
+
+#[async_trait]
+impl event::Repository for Repository {
+    async fn event(&self) -> Result<Option<Event>> {
+        Ok(self.store().event.clone())
+    }
+
+    async fn init_event(&self, event: &Event) -> Result<Event> {
+        let mut store = self.store();
+        Ok(store.event.get_or_insert_with(|| event.clone()).clone())
+    }
+
+    async fn update_group_scoring(
+        &self,
+        group_scoring: GroupScoring,
+        scoring_result_count: Option<i32>,
+        min_starts: i32,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| {
+            event.group_scoring = group_scoring;
+            event.scoring_result_count = scoring_result_count;
+            event.min_starts = min_starts;
+        }))
+    }
+
+    async fn update_ranking_rules(
+        &self,
+        shared_ranks: SharedRanks,
+        time_precision: TimePrecision,
+        group_tie_break: GroupTieBreak,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| {
+            event.shared_ranks = shared_ranks;
+            event.time_precision = time_precision;
+            event.group_tie_break = group_tie_break;
+        }))
+    }
+
+    async fn update_team_scoring(
+        &self,
+        team_placing_points: &str,
+        team_max_scorers: Option<i32>,
+        team_relay_weight: i32,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| {
+            event.team_placing_points = team_placing_points.to_string();
+            event.team_max_scorers = team_max_scorers;
+            event.team_relay_weight = team_relay_weight;
+        }))
+    }
+
+    async fn update_registration_limits(
+        &self,
+        max_individual_starts: Option<i32>,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store
+            .event
+            .as_mut()
+            .map(|event| event.max_individual_starts = max_individual_starts))
+    }
+
+    async fn update_entry_fees(
+        &self,
+        participant_fee_cents: i32,
+        start_fee_cents: i32,
+        late_entry_surcharge_cents: i32,
+        currency: &str,
+    ) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| {
+            event.participant_fee_cents = participant_fee_cents;
+            event.start_fee_cents = start_fee_cents;
+            event.late_entry_surcharge_cents = late_entry_surcharge_cents;
+            event.currency = currency.to_string();
+        }))
+    }
+
+    async fn update_info(&self, name: &str, organization: &str) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| {
+            event.name = name.to_string();
+            event.organization = organization.to_string();
+        }))
+    }
+
+    async fn update_course(&self, course: Course) -> Result<Option<()>> {
+        let mut store = self.store();
+        Ok(store.event.as_mut().map(|event| event.course = course))
+    }
+
+    async fn update_phase(&self, change: &PhaseChange) -> Result<Option<()>> {
+        let mut store = self.store();
+        let Some(event) = store.event.as_mut() else {
+            return Ok(None);
+        };
+        event.phase = change.phase;
+        store.phase_changes.push(change.clone());
+        Ok(Some(()))
+    }
+
+    async fn phase_changes(&self) -> Result<Vec<PhaseChange>> {
+        let mut changes = self.store().phase_changes.clone();
+        changes.sort_by_key(|c| (c.changed_at, c.id));
+        Ok(changes)
+    }
+}
@@ -1,10 +1,26 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+pub mod assets;
+pub mod backups;
+pub mod best_times;
 pub mod competitions;
+pub mod event;
+pub mod finals;
+pub mod groups;
+#[cfg(test)]
+pub mod memory;
 pub mod participants;
+pub mod payments;
+pub mod postgres;
+pub mod records;
 pub mod registrations;
-pub mod groups;
+pub mod sessions;
+pub mod sqlite;
 
-pub type Database = sqlx::Postgres;
-pub type DatabasePool = sqlx::Pool<Database>;
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "gender")]
@@ -23,3 +39,101 @@ pub enum Stroke {
     Breast,
     Freestyle,
 }
+
+/// Length of the pool, short course is 25 m and long course is 50 m.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "course")]
+#[sqlx(rename_all = "lowercase")]
+pub enum Course {
+    Short,
+    Long,
+}
+
+/// The repositories of all entities, backed by the same database.
+#[derive(Clone)]
+pub struct Repositories {
+    pub participants: Arc<dyn participants::Repository>,
+    pub registrations: Arc<dyn registrations::Repository>,
+    pub competitions: Arc<dyn competitions::Repository>,
+    pub groups: Arc<dyn groups::Repository>,
+    pub backups: Arc<dyn backups::Repository>,
+    pub assets: Arc<dyn assets::Repository>,
+    pub best_times: Arc<dyn best_times::Repository>,
+    pub records: Arc<dyn records::Repository>,
+    pub sessions: Arc<dyn sessions::Repository>,
+    pub finals: Arc<dyn finals::Repository>,
+    pub payments: Arc<dyn payments::Repository>,
+    pub event: Arc<dyn event::Repository>,
+}
+
+impl Repositories {
+    /// Connect to the database behind the given URL.
+    ///
+    /// The backend is selected by the scheme of the URL: `postgres://` for a
+    /// PostgreSQL server and `sqlite://` for a local SQLite database file.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        if database_url.starts_with("postgres:") || database_url.starts_with("postgresql:") {
+            let pool = postgres::connect(database_url).await?;
+            Ok(Self::postgres(pool))
+        } else if database_url.starts_with("sqlite:") {
+            let pool = sqlite::connect(database_url).await?;
+            Ok(Self::sqlite(pool))
+        } else {
+            anyhow::bail!("Unsupported database URL, expected 'postgres://' or 'sqlite://'")
+        }
+    }
+
+    pub fn postgres(pool: postgres::DatabasePool) -> Self {
+        Self {
+            participants: Arc::new(postgres::participants::Repository::new(pool.clone())),
+            registrations: Arc::new(postgres::registrations::Repository::new(pool.clone())),
+            competitions: Arc::new(postgres::competitions::Repository::new(pool.clone())),
+            groups: Arc::new(postgres::groups::Repository::new(pool.clone())),
+            backups: Arc::new(postgres::backups::Repository::new(pool.clone())),
+            assets: Arc::new(postgres::assets::Repository::new(pool.clone())),
+            best_times: Arc::new(postgres::best_times::Repository::new(pool.clone())),
+            records: Arc::new(postgres::records::Repository::new(pool.clone())),
+            sessions: Arc::new(postgres::sessions::Repository::new(pool.clone())),
+            finals: Arc::new(postgres::finals::Repository::new(pool.clone())),
+            payments: Arc::new(postgres::payments::Repository::new(pool.clone())),
+            event: Arc::new(postgres::event::Repository::new(pool)),
+        }
+    }
+
+    pub fn sqlite(pool: sqlite::DatabasePool) -> Self {
+        Self {
+            participants: Arc::new(sqlite::participants::Repository::new(pool.clone())),
+            registrations: Arc::new(sqlite::registrations::Repository::new(pool.clone())),
+            competitions: Arc::new(sqlite::competitions::Repository::new(pool.clone())),
+            groups: Arc::new(sqlite::groups::Repository::new(pool.clone())),
+            backups: Arc::new(sqlite::backups::Repository::new(pool.clone())),
+            assets: Arc::new(sqlite::assets::Repository::new(pool.clone())),
+            best_times: Arc::new(sqlite::best_times::Repository::new(pool.clone())),
+            records: Arc::new(sqlite::records::Repository::new(pool.clone())),
+            sessions: Arc::new(sqlite::sessions::Repository::new(pool.clone())),
+            finals: Arc::new(sqlite::finals::Repository::new(pool.clone())),
+            payments: Arc::new(sqlite::payments::Repository::new(pool.clone())),
+            event: Arc::new(sqlite::event::Repository::new(pool)),
+        }
+    }
+
+    /// Repositories backed by a fresh in-memory store.
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let repository = memory::Repository::new();
+        Self {
+            participants: Arc::new(repository.clone()),
+            registrations: Arc::new(repository.clone()),
+            competitions: Arc::new(repository.clone()),
+            groups: Arc::new(repository.clone()),
+            backups: Arc::new(repository.clone()),
+            assets: Arc::new(repository.clone()),
+            best_times: Arc::new(repository.clone()),
+            records: Arc::new(repository.clone()),
+            sessions: Arc::new(repository.clone()),
+            finals: Arc::new(repository.clone()),
+            payments: Arc::new(repository.clone()),
+            event: Arc::new(repository),
+        }
+    }
+}
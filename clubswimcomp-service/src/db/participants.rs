@@ -1,10 +1,11 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use chrono::NaiveDate;
 use uuid::Uuid;
 
 use super::Gender;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct Participant {
     pub id: Uuid,
     pub short_id: i32,
@@ -15,31 +16,10 @@ pub struct Participant {
     pub group_id: Uuid,
 }
 
-#[derive(Clone)]
-pub struct Repository {
-    pool: super::DatabasePool,
-}
-
-impl Repository {
-    pub fn new(pool: super::DatabasePool) -> Self {
-        Self { pool }
-    }
-
+#[async_trait]
+pub trait Repository: Send + Sync {
     /// List all participants in the database.
-    pub async fn list_participants(&self) -> Result<Vec<Participant>> {
-        sqlx::query_as!(
-            Participant,
-            r#"
-                SELECT
-                    id, short_id, first_name, last_name, gender AS "gender: _",
-                    birthday, group_id
-                FROM participants;
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of all participants from database")
-    }
+    async fn list_participants(&self) -> Result<Vec<Participant>>;
 
     /// Get a participant by its id.
     ///
@@ -47,50 +27,28 @@ impl Repository {
     /// - `Ok(Some(...))` - if the participant has been found
     /// - `Ok(None)` - if no participant with the given `participant_id` exists
     /// - `Error(...)` - in case of an database error
-    pub async fn participant_by_id(&self, participant_id: Uuid) -> Result<Option<Participant>> {
-        sqlx::query_as!(
-            Participant,
-            r#"
-                SELECT
-                    id, short_id, first_name, last_name, gender AS "gender: _",
-                    birthday, group_id
-                FROM participants
-                WHERE id = $1;
-            "#,
-            participant_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to fetch list of all participants from database")
-    }
+    async fn participant_by_id(&self, participant_id: Uuid) -> Result<Option<Participant>>;
+
+    /// Get a participant by the short code typed in at the result desk, which
+    /// is the short id printed on the registration cards.
+    ///
+    /// # Returns
+    /// - `Ok(Some(...))` - if the participant has been found
+    /// - `Ok(None)` - if no participant with the given `short_id` exists
+    /// - `Error(...)` - in case of an database error
+    async fn participant_by_short_code(&self, short_id: i32) -> Result<Option<Participant>>;
 
     /// Create a new participant in the database.
-    pub async fn create_participant(
+    ///
+    /// The participant is assigned a random and unique four digit short id.
+    async fn create_participant(
         &self,
         first_name: &str,
         last_name: &str,
         gender: Gender,
         birthday: NaiveDate,
         group_id: Uuid,
-    ) -> Result<Uuid> {
-        sqlx::query_scalar!(
-            r#"
-                INSERT INTO participants (
-                    first_name, last_name, gender, birthday, group_id
-                ) VALUES (
-                    $1, $2, $3, $4, $5
-                ) RETURNING id;
-            "#,
-            first_name,
-            last_name,
-            gender as Gender,
-            birthday,
-            group_id,
-        )
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to create participant in database")
-    }
+    ) -> Result<Uuid>;
 
     /// Delete a participant from the database.
     ///
@@ -98,37 +56,8 @@ impl Repository {
     /// - `Ok(true)` - if the participant has been deleted
     /// - `Ok(false)` - if the participant did not exist
     /// - `Err(e)` - in case of an database error
-    pub async fn delete_participant(&self, participant_id: Uuid) -> Result<bool> {
-        let rows = sqlx::query!(
-            r#"
-                DELETE FROM participants
-                WHERE id = $1
-            "#,
-            participant_id
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to delete participant from database")?
-        .rows_affected();
-
-        Ok(rows > 0)
-    }
+    async fn delete_participant(&self, participant_id: Uuid) -> Result<bool>;
 
     /// List participants in the group in the database.
-    pub async fn list_participants_in_group(&self, group_id: Uuid) -> Result<Vec<Participant>> {
-        sqlx::query_as!(
-            Participant,
-            r#"
-                SELECT
-                    id, short_id, first_name, last_name, gender AS "gender: _",
-                    birthday, group_id
-                FROM participants
-                WHERE group_id = $1;
-            "#,
-            group_id
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of participants in group from database")
-    }
+    async fn list_participants_in_group(&self, group_id: Uuid) -> Result<Vec<Participant>>;
 }
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// Money received for the entry fees, either from a participant or for a
+/// whole group.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Payment {
+    pub id: Uuid,
+    pub participant_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub amount_cents: i32,
+    pub received_on: NaiveDate,
+    /// Reference of the transfer or receipt, used to reconcile the payments.
+    pub reference: String,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Store a payment, the participant or the group must exist.
+    async fn create_payment(&self, payment: &Payment) -> Result<()>;
+
+    /// All payments, ordered by the day they have been received.
+    async fn all_payments(&self) -> Result<Vec<Payment>>;
+
+    /// # Returns:
+    /// - `Ok(Some(()))` - if the payment has been deleted
+    /// - `Ok(None)` - if the payment does not exist
+    /// - `Err(e)` - in case of a database error
+    async fn delete_payment(&self, payment_id: Uuid) -> Result<Option<()>>;
+}
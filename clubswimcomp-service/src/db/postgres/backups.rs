@@ -0,0 +1,765 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+
+use crate::db::assets::Asset;
+use crate::db::backups::{self, Snapshot, StoredResult};
+use crate::db::best_times::BestTime;
+use crate::db::competitions::{Competition, Eligibility};
+use crate::db::event::{Event, PhaseChange};
+use crate::db::finals::{Finals, FinalsEntry};
+use crate::db::groups::Group;
+use crate::db::participants::Participant;
+use crate::db::payments::Payment;
+use crate::db::records::Record;
+use crate::db::registrations::Registration;
+use crate::db::sessions::Session;
+use crate::db::{Course, Gender, Stroke};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl backups::Repository for Repository {
+    async fn export_snapshot(&self) -> Result<Snapshot> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // All following queries have to see the same state of the database
+        sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY;")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to set transaction isolation level")?;
+
+        let groups = sqlx::query_as!(
+            Group,
+            r#"
+                SELECT id, name FROM groups ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch groups from database")?;
+
+        let participants = sqlx::query_as!(
+            Participant,
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender AS "gender: _",
+                    birthday, group_id
+                FROM participants
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch participants from database")?;
+
+        let competitions = sqlx::query_as!(
+            Competition,
+            r#"
+                SELECT
+                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time,
+                    relay, round AS "round: _"
+                FROM competitions
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions from database")?;
+
+        let registrations = sqlx::query_as!(
+            Registration,
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch registrations from database")?;
+
+        let results = sqlx::query_as!(
+            StoredResult,
+            r#"
+                SELECT
+                    registration_id, disqualified, time_millis
+                FROM registration_results
+                ORDER BY registration_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch registration results from database")?;
+
+        let assets = sqlx::query_as!(
+            Asset,
+            r#"
+                SELECT
+                    name, content, updated_at
+                FROM assets
+                ORDER BY name;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch assets from database")?;
+
+        let best_times = sqlx::query_as!(
+            BestTime,
+            r#"
+                SELECT
+                    id, first_name, last_name, birthday, stroke AS "stroke: _", distance,
+                    course AS "course: _", time_millis, swum_on, meet, registration_id
+                FROM best_times
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch best times from database")?;
+
+        let records = sqlx::query_as!(
+            Record,
+            r#"
+                SELECT
+                    id, kind AS "kind: _", gender AS "gender: _", stroke AS "stroke: _",
+                    distance, course AS "course: _", age_class, time_millis, first_name,
+                    last_name, birthday, swum_on, meet, registration_id,
+                    status AS "status: _", created_at, decided_at, superseded_by
+                FROM records
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch records from database")?;
+
+        let eligible_groups = sqlx::query!(
+            r#"
+                SELECT competition_id, group_id
+                FROM competition_eligible_groups
+                ORDER BY competition_id, group_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligible groups from database")?;
+
+        let eligibilities = sqlx::query!(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                ORDER BY competition_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligibilities from database")?
+        .into_iter()
+        .map(|e| Eligibility {
+            competition_id: e.competition_id,
+            min_age: e.min_age,
+            max_age: e.max_age,
+            min_birth_year: e.min_birth_year,
+            max_birth_year: e.max_birth_year,
+            qualifying_time: e.qualifying_time,
+            group_ids: eligible_groups
+                .iter()
+                .filter(|g| g.competition_id == e.competition_id)
+                .map(|g| g.group_id)
+                .collect(),
+        })
+        .collect();
+
+        let phase_changes = sqlx::query_as!(
+            PhaseChange,
+            r#"
+                SELECT id, phase AS "phase: _", changed_at, reason
+                FROM event_phase_changes
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch phase changes from database")?;
+
+        let session_competitions = sqlx::query!(
+            r#"
+                SELECT session_id, competition_id
+                FROM session_competitions
+                ORDER BY session_id, position;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions of sessions from database")?;
+
+        let sessions = sqlx::query!(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch sessions from database")?
+        .into_iter()
+        .map(|s| Session {
+            id: s.id,
+            name: s.name,
+            starts_at: s.starts_at,
+            warm_up_minutes: s.warm_up_minutes,
+            turnaround_seconds: s.turnaround_seconds,
+            competition_ids: session_competitions
+                .iter()
+                .filter(|c| c.session_id == s.id)
+                .map(|c| c.competition_id)
+                .collect(),
+        })
+        .collect();
+
+        let swim_offs = sqlx::query!(
+            r#"
+                SELECT competition_id, prelims_id
+                FROM swim_offs
+                ORDER BY prelims_id, number;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch swim-offs from database")?;
+
+        let finals = sqlx::query!(
+            r#"
+                SELECT competition_id, finals_competition_id, finalists, alternates
+                FROM competition_finals
+                ORDER BY competition_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch finals from database")?
+        .into_iter()
+        .map(|f| Finals {
+            competition_id: f.competition_id,
+            finals_competition_id: f.finals_competition_id,
+            finalists: f.finalists,
+            alternates: f.alternates,
+            swim_off_ids: swim_offs
+                .iter()
+                .filter(|s| s.prelims_id == f.competition_id)
+                .map(|s| s.competition_id)
+                .collect(),
+        })
+        .collect();
+
+        let finals_entries = sqlx::query_as!(
+            FinalsEntry,
+            r#"
+                SELECT
+                    competition_id, participant_id, seed, time_millis,
+                    status AS "status: _"
+                FROM finals_entries
+                ORDER BY competition_id, seed;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch finals entries from database")?;
+
+        let payments = sqlx::query_as!(
+            Payment,
+            r#"
+                SELECT id, participant_id, group_id, amount_cents, received_on, reference
+                FROM payments
+                ORDER BY id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch payments from database")?;
+
+        let event = sqlx::query_as!(
+            Event,
+            r#"
+                SELECT
+                    id, card_secret, created_at, group_scoring AS "group_scoring: _",
+                    scoring_result_count, min_starts, shared_ranks AS "shared_ranks: _",
+                    time_precision AS "time_precision: _",
+                    group_tie_break AS "group_tie_break: _", team_placing_points,
+                    team_max_scorers, max_individual_starts, course AS "course: _",
+                    phase AS "phase: _", participant_fee_cents, start_fee_cents,
+                    late_entry_surcharge_cents, currency, name, organization,
+                    team_relay_weight
+                FROM event;
+            "#
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch event from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(Snapshot {
+            groups,
+            participants,
+            competitions,
+            registrations,
+            results,
+            assets,
+            best_times,
+            records,
+            eligibilities,
+            phase_changes,
+            sessions,
+            finals,
+            finals_entries,
+            payments,
+            event,
+        })
+    }
+
+    async fn is_empty(&self) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+                SELECT
+                    NOT EXISTS (SELECT * FROM groups) AND
+                    NOT EXISTS (SELECT * FROM participants) AND
+                    NOT EXISTS (SELECT * FROM competitions) AND
+                    NOT EXISTS (SELECT * FROM registrations) AND
+                    NOT EXISTS (SELECT * FROM registration_results) AND
+                    NOT EXISTS (SELECT * FROM assets) AND
+                    NOT EXISTS (SELECT * FROM best_times) AND
+                    NOT EXISTS (SELECT * FROM records) AS "is_empty!";
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check if database is empty")?;
+
+        Ok(row.is_empty)
+    }
+
+    async fn import_snapshot(&self, snapshot: &Snapshot) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for group in snapshot.groups.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO groups (id, name) VALUES ($1, $2);
+                "#,
+                group.id,
+                group.name,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert group into database")?;
+        }
+
+        for participant in snapshot.participants.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO participants (
+                        id, short_id, first_name, last_name, gender, birthday, group_id
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7
+                    );
+                "#,
+                participant.id,
+                participant.short_id,
+                participant.first_name,
+                participant.last_name,
+                participant.gender as Gender,
+                participant.birthday,
+                participant.group_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert participant into database")?;
+        }
+
+        for competition in snapshot.competitions.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO competitions (
+                        id, gender, stroke, distance, target_time, relay, round
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7
+                    );
+                "#,
+                competition.id,
+                competition.gender as Gender,
+                competition.stroke as Stroke,
+                competition.distance,
+                competition.target_time as _,
+                competition.relay,
+                competition.round as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert competition into database")?;
+        }
+
+        for registration in snapshot.registrations.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO registrations (
+                        id, participant_id, competition_id, entry_time_millis, card_number,
+                        late_entry
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6
+                    );
+                "#,
+                registration.id,
+                registration.participant_id,
+                registration.competition_id,
+                registration.entry_time_millis,
+                registration.card_number,
+                registration.late_entry,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration into database")?;
+        }
+
+        for result in snapshot.results.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO registration_results (
+                        registration_id, disqualified, time_millis
+                    ) VALUES (
+                        $1, $2, $3
+                    );
+                "#,
+                result.registration_id,
+                result.disqualified,
+                result.time_millis as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration result into database")?;
+        }
+
+        for asset in snapshot.assets.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO assets (
+                        name, content, updated_at
+                    ) VALUES (
+                        $1, $2, $3
+                    );
+                "#,
+                asset.name,
+                asset.content,
+                asset.updated_at,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert asset into database")?;
+        }
+
+        for best_time in snapshot.best_times.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO best_times (
+                        id, first_name, last_name, birthday, stroke, distance, course,
+                        time_millis, swum_on, meet, registration_id
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+                    );
+                "#,
+                best_time.id,
+                best_time.first_name,
+                best_time.last_name,
+                best_time.birthday,
+                best_time.stroke as Stroke,
+                best_time.distance,
+                best_time.course as Course,
+                best_time.time_millis,
+                best_time.swum_on,
+                best_time.meet,
+                best_time.registration_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert best time into database")?;
+        }
+
+        for record in snapshot.records.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO records (
+                        id, kind, gender, stroke, distance, course, age_class, time_millis,
+                        first_name, last_name, birthday, swum_on, meet, registration_id,
+                        status, created_at, decided_at, superseded_by
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                        $16, $17, $18
+                    );
+                "#,
+                record.id,
+                record.kind as _,
+                record.gender as _,
+                record.stroke as _,
+                record.distance,
+                record.course as _,
+                record.age_class,
+                record.time_millis,
+                record.first_name,
+                record.last_name,
+                record.birthday,
+                record.swum_on,
+                record.meet,
+                record.registration_id,
+                record.status as _,
+                record.created_at,
+                record.decided_at,
+                record.superseded_by,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert record into database")?;
+        }
+
+        for eligibility in snapshot.eligibilities.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO competition_eligibility (
+                        competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                        qualifying_time
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6
+                    );
+                "#,
+                eligibility.competition_id,
+                eligibility.min_age,
+                eligibility.max_age,
+                eligibility.min_birth_year,
+                eligibility.max_birth_year,
+                eligibility.qualifying_time,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert eligibility into database")?;
+
+            for group_id in eligibility.group_ids.iter() {
+                sqlx::query!(
+                    r#"
+                        INSERT INTO competition_eligible_groups (
+                            competition_id, group_id
+                        ) VALUES (
+                            $1, $2
+                        );
+                    "#,
+                    eligibility.competition_id,
+                    group_id,
+                )
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert eligible group into database")?;
+            }
+        }
+
+        for change in snapshot.phase_changes.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO event_phase_changes (id, phase, changed_at, reason)
+                    VALUES ($1, $2, $3, $4);
+                "#,
+                change.id,
+                change.phase as _,
+                change.changed_at,
+                change.reason,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert phase change into database")?;
+        }
+
+        for session in snapshot.sessions.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO sessions (
+                        id, name, starts_at, warm_up_minutes, turnaround_seconds
+                    ) VALUES (
+                        $1, $2, $3, $4, $5
+                    );
+                "#,
+                session.id,
+                session.name,
+                session.starts_at,
+                session.warm_up_minutes,
+                session.turnaround_seconds,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert session into database")?;
+
+            for (position, competition_id) in session.competition_ids.iter().enumerate() {
+                sqlx::query!(
+                    r#"
+                        INSERT INTO session_competitions (
+                            session_id, competition_id, position
+                        ) VALUES (
+                            $1, $2, $3
+                        );
+                    "#,
+                    session.id,
+                    competition_id,
+                    position as i32,
+                )
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert competition of session into database")?;
+            }
+        }
+
+        for finals in snapshot.finals.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO competition_finals (
+                        competition_id, finals_competition_id, finalists, alternates
+                    ) VALUES (
+                        $1, $2, $3, $4
+                    );
+                "#,
+                finals.competition_id,
+                finals.finals_competition_id,
+                finals.finalists,
+                finals.alternates,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals into database")?;
+
+            for (index, swim_off_id) in finals.swim_off_ids.iter().enumerate() {
+                sqlx::query!(
+                    r#"
+                        INSERT INTO swim_offs (
+                            competition_id, prelims_id, number
+                        ) VALUES (
+                            $1, $2, $3
+                        );
+                    "#,
+                    swim_off_id,
+                    finals.competition_id,
+                    index as i32 + 1,
+                )
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert swim-off into database")?;
+            }
+        }
+
+        for entry in snapshot.finals_entries.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO finals_entries (
+                        competition_id, participant_id, seed, time_millis, status
+                    ) VALUES (
+                        $1, $2, $3, $4, $5
+                    );
+                "#,
+                entry.competition_id,
+                entry.participant_id,
+                entry.seed,
+                entry.time_millis,
+                entry.status as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals entry into database")?;
+        }
+
+        for payment in snapshot.payments.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO payments (
+                        id, participant_id, group_id, amount_cents, received_on, reference
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6
+                    );
+                "#,
+                payment.id,
+                payment.participant_id,
+                payment.group_id,
+                payment.amount_cents,
+                payment.received_on,
+                payment.reference,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert payment into database")?;
+        }
+
+        if let Some(event) = snapshot.event.as_ref() {
+            sqlx::query!("DELETE FROM event;")
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete event from database")?;
+            sqlx::query!(
+                r#"
+                    INSERT INTO event (
+                        id, card_secret, created_at, group_scoring, scoring_result_count,
+                        min_starts, shared_ranks, time_precision, group_tie_break,
+                        team_placing_points, team_max_scorers, max_individual_starts, course,
+                        phase, participant_fee_cents, start_fee_cents,
+                        late_entry_surcharge_cents, currency, name, organization,
+                        team_relay_weight
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                        $17, $18, $19, $20, $21
+                    );
+                "#,
+                event.id,
+                event.card_secret,
+                event.created_at,
+                event.group_scoring as _,
+                event.scoring_result_count,
+                event.min_starts,
+                event.shared_ranks as _,
+                event.time_precision as _,
+                event.group_tie_break as _,
+                event.team_placing_points,
+                event.team_max_scorers,
+                event.max_individual_starts,
+                event.course as _,
+                event.phase as _,
+                event.participant_fee_cents,
+                event.start_fee_cents,
+                event.late_entry_surcharge_cents,
+                event.currency,
+                event.name,
+                event.organization,
+                event.team_relay_weight,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert event into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+}
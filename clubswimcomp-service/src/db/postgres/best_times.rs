@@ -0,0 +1,116 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::db::best_times::{self, BestTime};
+use crate::db::{Course, Stroke};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl best_times::Repository for Repository {
+    async fn insert_best_times(&self, best_times: &[BestTime]) -> Result<u64> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let mut inserted = 0;
+        for best_time in best_times {
+            inserted += sqlx::query!(
+                r#"
+                    INSERT INTO best_times (
+                        id, first_name, last_name, birthday, stroke, distance, course,
+                        time_millis, swum_on, meet
+                    ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+                    ) ON CONFLICT DO NOTHING;
+                "#,
+                best_time.id,
+                best_time.first_name,
+                best_time.last_name,
+                best_time.birthday,
+                best_time.stroke as Stroke,
+                best_time.distance,
+                best_time.course as Course,
+                best_time.time_millis,
+                best_time.swum_on,
+                best_time.meet,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert best time into database")?
+            .rows_affected();
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(inserted)
+    }
+
+    async fn record_best_time(&self, best_time: &BestTime) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO best_times (
+                    id, first_name, last_name, birthday, stroke, distance, course,
+                    time_millis, swum_on, meet, registration_id
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+                ) ON CONFLICT (registration_id) DO UPDATE
+                SET time_millis = EXCLUDED.time_millis, swum_on = EXCLUDED.swum_on,
+                    meet = EXCLUDED.meet;
+            "#,
+            best_time.id,
+            best_time.first_name,
+            best_time.last_name,
+            best_time.birthday,
+            best_time.stroke as Stroke,
+            best_time.distance,
+            best_time.course as Course,
+            best_time.time_millis,
+            best_time.swum_on,
+            best_time.meet,
+            best_time.registration_id,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to record best time in database")?;
+
+        Ok(())
+    }
+
+    async fn best_times_of_swimmer(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        birthday: NaiveDate,
+    ) -> Result<Vec<BestTime>> {
+        sqlx::query_as!(
+            BestTime,
+            r#"
+                SELECT
+                    id, first_name, last_name, birthday, stroke AS "stroke: _", distance,
+                    course AS "course: _", time_millis, swum_on, meet, registration_id
+                FROM best_times
+                WHERE first_name = $1 AND last_name = $2 AND birthday = $3
+                ORDER BY swum_on, id;
+            "#,
+            first_name,
+            last_name,
+            birthday,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch best times of swimmer from database")
+    }
+}
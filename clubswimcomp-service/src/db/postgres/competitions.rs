@@ -0,0 +1,319 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::competitions::{self, Competition, Eligibility};
+use crate::db::{Gender, Stroke};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl competitions::Repository for Repository {
+    async fn create_competition(
+        &self,
+        gender: Gender,
+        stroke: Stroke,
+        distance: i32,
+        target_time: i64,
+        relay: bool,
+    ) -> Result<Uuid> {
+        sqlx::query_scalar!(
+            r#"
+                INSERT INTO competitions (
+                    gender, stroke, distance, target_time, relay
+                ) VALUES (
+                    $1, $2, $3, $4, $5
+                ) RETURNING id;
+            "#,
+            gender as Gender,
+            stroke as Stroke,
+            distance,
+            target_time as _,
+            relay,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to execute INSERT INTO query")
+    }
+
+    async fn search_competition(
+        &self,
+        gender: Option<Gender>,
+        stroke: Option<Stroke>,
+        distance: Option<i32>,
+    ) -> Result<Vec<Competition>> {
+        sqlx::query_as!(
+            Competition,
+            r#"
+                SELECT
+                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time,
+                    relay, round AS "round: _"
+                FROM competitions
+                WHERE
+                    (gender = $1 OR $1 IS NULL) AND
+                    (stroke = $2 OR $2 IS NULL) AND
+                    (distance = $3 OR $3 IS NULL);
+            "#,
+            gender as Option<Gender>,
+            stroke as Option<Stroke>,
+            distance
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch search results of competitions from database")
+    }
+
+    async fn all_competitions(&self) -> Result<Vec<Competition>> {
+        sqlx::query_as!(
+            Competition,
+            r#"
+                SELECT
+                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time,
+                    relay, round AS "round: _"
+                FROM competitions;
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all competitions from database")
+    }
+
+    async fn competition_by_id(&self, competition_id: Uuid) -> Result<Option<Competition>> {
+        sqlx::query_as!(
+            Competition,
+            r#"
+                SELECT
+                    id, gender AS "gender: _", stroke AS "stroke: _", distance, target_time,
+                    relay, round AS "round: _"
+                FROM competitions
+                WHERE id = $1;
+            "#,
+            competition_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch competition by id from database")
+    }
+
+    async fn delete_competition(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                DELETE FROM competitions
+                WHERE id = $1
+            "#,
+            competition_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete competition in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn eligibility_of_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Option<Eligibility>> {
+        let eligibility = sqlx::query!(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                WHERE competition_id = $1;
+            "#,
+            competition_id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch eligibility of competition from database")?;
+
+        let Some(eligibility) = eligibility else {
+            return Ok(None);
+        };
+
+        let group_ids = sqlx::query_scalar!(
+            r#"
+                SELECT group_id
+                FROM competition_eligible_groups
+                WHERE competition_id = $1
+                ORDER BY group_id;
+            "#,
+            competition_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch eligible groups of competition from database")?;
+
+        Ok(Some(Eligibility {
+            competition_id: eligibility.competition_id,
+            min_age: eligibility.min_age,
+            max_age: eligibility.max_age,
+            min_birth_year: eligibility.min_birth_year,
+            max_birth_year: eligibility.max_birth_year,
+            qualifying_time: eligibility.qualifying_time,
+            group_ids,
+        }))
+    }
+
+    async fn all_eligibilities(&self) -> Result<Vec<Eligibility>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let eligibilities = sqlx::query!(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                ORDER BY competition_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligibilities from database")?;
+
+        let groups = sqlx::query!(
+            r#"
+                SELECT competition_id, group_id
+                FROM competition_eligible_groups
+                ORDER BY competition_id, group_id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligible groups from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        let eligibilities = eligibilities
+            .into_iter()
+            .map(|e| Eligibility {
+                competition_id: e.competition_id,
+                min_age: e.min_age,
+                max_age: e.max_age,
+                min_birth_year: e.min_birth_year,
+                max_birth_year: e.max_birth_year,
+                qualifying_time: e.qualifying_time,
+                group_ids: groups
+                    .iter()
+                    .filter(|g| g.competition_id == e.competition_id)
+                    .map(|g| g.group_id)
+                    .collect(),
+            })
+            .collect();
+
+        Ok(eligibilities)
+    }
+
+    async fn set_eligibility(&self, eligibility: &Eligibility) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO competition_eligibility (
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6
+                ) ON CONFLICT (competition_id) DO UPDATE SET
+                    min_age = excluded.min_age,
+                    max_age = excluded.max_age,
+                    min_birth_year = excluded.min_birth_year,
+                    max_birth_year = excluded.max_birth_year,
+                    qualifying_time = excluded.qualifying_time;
+            "#,
+            eligibility.competition_id,
+            eligibility.min_age,
+            eligibility.max_age,
+            eligibility.min_birth_year,
+            eligibility.max_birth_year,
+            eligibility.qualifying_time,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to store eligibility in database")?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM competition_eligible_groups WHERE competition_id = $1;
+            "#,
+            eligibility.competition_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligible groups from database")?;
+
+        for group_id in eligibility.group_ids.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO competition_eligible_groups (
+                        competition_id, group_id
+                    ) VALUES (
+                        $1, $2
+                    );
+                "#,
+                eligibility.competition_id,
+                group_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert eligible group into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn delete_eligibility(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM competition_eligible_groups WHERE competition_id = $1;
+            "#,
+            competition_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligible groups from database")?;
+
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM competition_eligibility WHERE competition_id = $1;
+            "#,
+            competition_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligibility from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
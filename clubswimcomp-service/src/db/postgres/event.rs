@@ -0,0 +1,279 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+
+use crate::db::event::{
+    self, Event, GroupScoring, GroupTieBreak, PhaseChange, SharedRanks, TimePrecision,
+};
+use crate::db::Course;
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl event::Repository for Repository {
+    async fn event(&self) -> Result<Option<Event>> {
+        sqlx::query_as!(
+            Event,
+            r#"
+                SELECT
+                    id, card_secret, created_at, group_scoring AS "group_scoring: _",
+                    scoring_result_count, min_starts, shared_ranks AS "shared_ranks: _",
+                    time_precision AS "time_precision: _",
+                    group_tie_break AS "group_tie_break: _", team_placing_points,
+                    team_max_scorers, max_individual_starts, course AS "course: _",
+                    phase AS "phase: _", participant_fee_cents, start_fee_cents,
+                    late_entry_surcharge_cents, currency, name, organization,
+                    team_relay_weight
+                FROM event;
+            "#
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch event from database")
+    }
+
+    async fn init_event(&self, event: &Event) -> Result<Event> {
+        // The singleton index turns a second event into a conflict
+        sqlx::query!(
+            r#"
+                INSERT INTO event (
+                    id, card_secret, created_at, group_scoring, scoring_result_count,
+                    min_starts, shared_ranks, time_precision, group_tie_break,
+                    team_placing_points, team_max_scorers, max_individual_starts, course,
+                    phase, participant_fee_cents, start_fee_cents, late_entry_surcharge_cents,
+                    currency, name, organization, team_relay_weight
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+                    $18, $19, $20, $21
+                ) ON CONFLICT DO NOTHING;
+            "#,
+            event.id,
+            event.card_secret,
+            event.created_at,
+            event.group_scoring as _,
+            event.scoring_result_count,
+            event.min_starts,
+            event.shared_ranks as _,
+            event.time_precision as _,
+            event.group_tie_break as _,
+            event.team_placing_points,
+            event.team_max_scorers,
+            event.max_individual_starts,
+            event.course as _,
+            event.phase as _,
+            event.participant_fee_cents,
+            event.start_fee_cents,
+            event.late_entry_surcharge_cents,
+            event.currency,
+            event.name,
+            event.organization,
+            event.team_relay_weight,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert event into database")?;
+
+        self.event()
+            .await?
+            .context("Event is missing in database after inserting it")
+    }
+
+    async fn update_group_scoring(
+        &self,
+        group_scoring: GroupScoring,
+        scoring_result_count: Option<i32>,
+        min_starts: i32,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET
+                    group_scoring = $1, scoring_result_count = $2, min_starts = $3;
+            "#,
+            group_scoring as _,
+            scoring_result_count,
+            min_starts,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update group scoring in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_ranking_rules(
+        &self,
+        shared_ranks: SharedRanks,
+        time_precision: TimePrecision,
+        group_tie_break: GroupTieBreak,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET
+                    shared_ranks = $1, time_precision = $2, group_tie_break = $3;
+            "#,
+            shared_ranks as _,
+            time_precision as _,
+            group_tie_break as _,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update ranking rules in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_team_scoring(
+        &self,
+        team_placing_points: &str,
+        team_max_scorers: Option<i32>,
+        team_relay_weight: i32,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET
+                    team_placing_points = $1, team_max_scorers = $2, team_relay_weight = $3;
+            "#,
+            team_placing_points,
+            team_max_scorers,
+            team_relay_weight,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update team scoring in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_registration_limits(
+        &self,
+        max_individual_starts: Option<i32>,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET max_individual_starts = $1;
+            "#,
+            max_individual_starts,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update registration limits in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_entry_fees(
+        &self,
+        participant_fee_cents: i32,
+        start_fee_cents: i32,
+        late_entry_surcharge_cents: i32,
+        currency: &str,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET
+                    participant_fee_cents = $1,
+                    start_fee_cents = $2,
+                    late_entry_surcharge_cents = $3,
+                    currency = $4;
+            "#,
+            participant_fee_cents,
+            start_fee_cents,
+            late_entry_surcharge_cents,
+            currency,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update entry fees in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_info(&self, name: &str, organization: &str) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET name = $1, organization = $2;
+            "#,
+            name,
+            organization,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update event info in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_course(&self, course: Course) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET course = $1;
+            "#,
+            course as _,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update course in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_phase(&self, change: &PhaseChange) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query!(
+            r#"
+                UPDATE event SET phase = $1;
+            "#,
+            change.phase as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update phase in database")?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO event_phase_changes (id, phase, changed_at, reason)
+                VALUES ($1, $2, $3, $4);
+            "#,
+            change.id,
+            change.phase as _,
+            change.changed_at,
+            change.reason,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert phase change into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(Some(()))
+    }
+
+    async fn phase_changes(&self) -> Result<Vec<PhaseChange>> {
+        sqlx::query_as!(
+            PhaseChange,
+            r#"
+                SELECT id, phase AS "phase: _", changed_at, reason
+                FROM event_phase_changes
+                ORDER BY changed_at, id;
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch phase changes from database")
+    }
+}
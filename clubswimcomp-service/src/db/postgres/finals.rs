@@ -0,0 +1,278 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::competitions::{Competition, CompetitionRound};
+use crate::db::finals::{self, Finals, FinalsEntry, FinalsEntryStatus};
+use crate::db::{Gender, Stroke};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Insert a competition of any round.
+async fn insert_competition(
+    tx: &mut sqlx::Transaction<'_, super::Database>,
+    competition: &Competition,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO competitions (
+                id, gender, stroke, distance, target_time, relay, round
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7
+            );
+        "#,
+        competition.id,
+        competition.gender as Gender,
+        competition.stroke as Stroke,
+        competition.distance,
+        competition.target_time as _,
+        competition.relay,
+        competition.round as CompetitionRound,
+    )
+    .execute(&mut **tx)
+    .await
+    .context("Failed to insert competition into database")?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl finals::Repository for Repository {
+    async fn create_finals(&self, finals: &Finals, finals_competition: &Competition) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query!(
+            r#"
+                UPDATE competitions SET round = $2 WHERE id = $1;
+            "#,
+            finals.competition_id,
+            CompetitionRound::Prelims as CompetitionRound,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to turn competition into prelims in database")?;
+
+        insert_competition(&mut tx, finals_competition).await?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO competition_finals (
+                    competition_id, finals_competition_id, finalists, alternates
+                ) VALUES (
+                    $1, $2, $3, $4
+                );
+            "#,
+            finals.competition_id,
+            finals.finals_competition_id,
+            finals.finalists,
+            finals.alternates,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert finals into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn finals_of_competition(&self, competition_id: Uuid) -> Result<Option<Finals>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let finals = sqlx::query!(
+            r#"
+                SELECT competition_id, finals_competition_id, finalists, alternates
+                FROM competition_finals
+                WHERE competition_id = $1;
+            "#,
+            competition_id,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch finals of competition from database")?;
+
+        let Some(finals) = finals else {
+            return Ok(None);
+        };
+
+        let swim_off_ids = sqlx::query_scalar!(
+            r#"
+                SELECT competition_id
+                FROM swim_offs
+                WHERE prelims_id = $1
+                ORDER BY number;
+            "#,
+            competition_id,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch swim-offs of competition from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(Some(Finals {
+            competition_id: finals.competition_id,
+            finals_competition_id: finals.finals_competition_id,
+            finalists: finals.finalists,
+            alternates: finals.alternates,
+            swim_off_ids,
+        }))
+    }
+
+    async fn update_finals(&self, finals: &Finals) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                UPDATE competition_finals
+                SET finalists = $2, alternates = $3
+                WHERE competition_id = $1;
+            "#,
+            finals.competition_id,
+            finals.finalists,
+            finals.alternates,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update finals in database")?
+        .rows_affected();
+
+        Ok((rows > 0).then_some(()))
+    }
+
+    async fn finals_entries(&self, competition_id: Uuid) -> Result<Vec<FinalsEntry>> {
+        sqlx::query_as!(
+            FinalsEntry,
+            r#"
+                SELECT
+                    competition_id, participant_id, seed, time_millis,
+                    status AS "status: _"
+                FROM finals_entries
+                WHERE competition_id = $1
+                ORDER BY seed;
+            "#,
+            competition_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch finals entries from database")
+    }
+
+    async fn promote_finalists(
+        &self,
+        finals_competition_id: Uuid,
+        entries: &[FinalsEntry],
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for entry in entries.iter() {
+            sqlx::query!(
+                r#"
+                    INSERT INTO finals_entries (
+                        competition_id, participant_id, seed, time_millis, status
+                    ) VALUES (
+                        $1, $2, $3, $4, $5
+                    );
+                "#,
+                entry.competition_id,
+                entry.participant_id,
+                entry.seed,
+                entry.time_millis,
+                entry.status as FinalsEntryStatus,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals entry into database")?;
+
+            if entry.status != FinalsEntryStatus::Finalist {
+                continue;
+            }
+            sqlx::query!(
+                r#"
+                    INSERT INTO registrations (
+                        participant_id, competition_id, entry_time_millis, card_number, late_entry
+                    ) VALUES (
+                        $1, $2, $3, (SELECT COALESCE(MAX(card_number), 0) + 1 FROM registrations), FALSE
+                    );
+                "#,
+                entry.participant_id,
+                finals_competition_id,
+                entry.time_millis,
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration of finalist into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn update_finals_entry_status(
+        &self,
+        competition_id: Uuid,
+        participant_id: Uuid,
+        status: FinalsEntryStatus,
+    ) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                UPDATE finals_entries
+                SET status = $3
+                WHERE competition_id = $1 AND participant_id = $2;
+            "#,
+            competition_id,
+            participant_id,
+            status as FinalsEntryStatus,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update status of finals entry in database")?
+        .rows_affected();
+
+        Ok((rows > 0).then_some(()))
+    }
+
+    async fn create_swim_off(&self, competition_id: Uuid, swim_off: &Competition) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        insert_competition(&mut tx, swim_off).await?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO swim_offs (
+                    competition_id, prelims_id, number
+                ) VALUES (
+                    $1, $2,
+                    (SELECT COALESCE(MAX(number), 0) + 1 FROM swim_offs WHERE prelims_id = $2)
+                );
+            "#,
+            swim_off.id,
+            competition_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert swim-off into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+}
@@ -0,0 +1,30 @@
+//! Repositories backed by a PostgreSQL server.
+
+use anyhow::{Context, Result};
+
+pub mod assets;
+pub mod backups;
+pub mod best_times;
+pub mod competitions;
+pub mod event;
+pub mod finals;
+pub mod groups;
+pub mod participants;
+pub mod payments;
+pub mod records;
+pub mod registrations;
+pub mod sessions;
+
+pub type Database = sqlx::Postgres;
+pub type DatabasePool = sqlx::Pool<Database>;
+
+/// Connect to the PostgreSQL database behind `database_url`.
+///
+/// The database schema is expected to be migrated already.
+pub async fn connect(database_url: &str) -> Result<DatabasePool> {
+    sqlx::postgres::PgPoolOptions::new()
+        .max_connections(10)
+        .connect(database_url)
+        .await
+        .context("Failed to create database connection pool")
+}
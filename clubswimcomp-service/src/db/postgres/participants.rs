@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::db::participants::{self, Participant};
+use crate::db::Gender;
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl participants::Repository for Repository {
+    async fn list_participants(&self) -> Result<Vec<Participant>> {
+        sqlx::query_as!(
+            Participant,
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender AS "gender: _",
+                    birthday, group_id
+                FROM participants;
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all participants from database")
+    }
+
+    async fn participant_by_id(&self, participant_id: Uuid) -> Result<Option<Participant>> {
+        sqlx::query_as!(
+            Participant,
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender AS "gender: _",
+                    birthday, group_id
+                FROM participants
+                WHERE id = $1;
+            "#,
+            participant_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch list of all participants from database")
+    }
+
+    async fn participant_by_short_code(&self, short_id: i32) -> Result<Option<Participant>> {
+        sqlx::query_as!(
+            Participant,
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender AS "gender: _",
+                    birthday, group_id
+                FROM participants
+                WHERE short_id = $1;
+            "#,
+            short_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch participant by short id from database")
+    }
+
+    async fn create_participant(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        gender: Gender,
+        birthday: NaiveDate,
+        group_id: Uuid,
+    ) -> Result<Uuid> {
+        sqlx::query_scalar!(
+            r#"
+                INSERT INTO participants (
+                    first_name, last_name, gender, birthday, group_id
+                ) VALUES (
+                    $1, $2, $3, $4, $5
+                ) RETURNING id;
+            "#,
+            first_name,
+            last_name,
+            gender as Gender,
+            birthday,
+            group_id,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to create participant in database")
+    }
+
+    async fn delete_participant(&self, participant_id: Uuid) -> Result<bool> {
+        let rows = sqlx::query!(
+            r#"
+                DELETE FROM participants
+                WHERE id = $1
+            "#,
+            participant_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete participant from database")?
+        .rows_affected();
+
+        Ok(rows > 0)
+    }
+
+    async fn list_participants_in_group(&self, group_id: Uuid) -> Result<Vec<Participant>> {
+        sqlx::query_as!(
+            Participant,
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender AS "gender: _",
+                    birthday, group_id
+                FROM participants
+                WHERE group_id = $1;
+            "#,
+            group_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of participants in group from database")
+    }
+}
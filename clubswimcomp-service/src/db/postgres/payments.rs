@@ -0,0 +1,72 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::payments::{self, Payment};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl payments::Repository for Repository {
+    async fn create_payment(&self, payment: &Payment) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO payments (
+                    id, participant_id, group_id, amount_cents, received_on, reference
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6
+                );
+            "#,
+            payment.id,
+            payment.participant_id,
+            payment.group_id,
+            payment.amount_cents,
+            payment.received_on,
+            payment.reference,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert payment into database")?;
+
+        Ok(())
+    }
+
+    async fn all_payments(&self) -> Result<Vec<Payment>> {
+        sqlx::query_as!(
+            Payment,
+            r#"
+                SELECT id, participant_id, group_id, amount_cents, received_on, reference
+                FROM payments
+                ORDER BY received_on, id;
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch payments from database")
+    }
+
+    async fn delete_payment(&self, payment_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                DELETE FROM payments
+                WHERE id = $1;
+            "#,
+            payment_id,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete payment from database")?
+        .rows_affected();
+
+        Ok((rows > 0).then_some(()))
+    }
+}
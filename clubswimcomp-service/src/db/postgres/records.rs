@@ -0,0 +1,120 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::records::{self, Record, RecordStatus};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl records::Repository for Repository {
+    async fn create_record(&self, record: &Record) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO records (
+                    id, kind, gender, stroke, distance, course, age_class, time_millis,
+                    first_name, last_name, birthday, swum_on, meet, registration_id, status,
+                    created_at, decided_at, superseded_by
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                    $17, $18
+                );
+            "#,
+            record.id,
+            record.kind as _,
+            record.gender as _,
+            record.stroke as _,
+            record.distance,
+            record.course as _,
+            record.age_class,
+            record.time_millis,
+            record.first_name,
+            record.last_name,
+            record.birthday,
+            record.swum_on,
+            record.meet,
+            record.registration_id,
+            record.status as _,
+            record.created_at,
+            record.decided_at,
+            record.superseded_by,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert record into database")?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self) -> Result<Vec<Record>> {
+        sqlx::query_as!(
+            Record,
+            r#"
+                SELECT
+                    id, kind AS "kind: _", gender AS "gender: _", stroke AS "stroke: _",
+                    distance, course AS "course: _", age_class, time_millis, first_name,
+                    last_name, birthday, swum_on, meet, registration_id,
+                    status AS "status: _", created_at, decided_at, superseded_by
+                FROM records
+                ORDER BY created_at, id;
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch records from database")
+    }
+
+    async fn record_by_id(&self, record_id: Uuid) -> Result<Option<Record>> {
+        sqlx::query_as!(
+            Record,
+            r#"
+                SELECT
+                    id, kind AS "kind: _", gender AS "gender: _", stroke AS "stroke: _",
+                    distance, course AS "course: _", age_class, time_millis, first_name,
+                    last_name, birthday, swum_on, meet, registration_id,
+                    status AS "status: _", created_at, decided_at, superseded_by
+                FROM records
+                WHERE id = $1;
+            "#,
+            record_id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch record from database")
+    }
+
+    async fn update_record_status(
+        &self,
+        record_id: Uuid,
+        status: RecordStatus,
+        decided_at: DateTime<Utc>,
+        superseded_by: Option<Uuid>,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE records SET
+                    status = $2, decided_at = $3, superseded_by = $4
+                WHERE id = $1;
+            "#,
+            record_id,
+            status as _,
+            decided_at,
+            superseded_by,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to update status of record in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
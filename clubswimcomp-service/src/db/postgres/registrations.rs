@@ -0,0 +1,229 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::registrations::{self, Registration, RegistrationResult};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl registrations::Repository for Repository {
+    async fn registrations_of_participant(
+        &self,
+        participant_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        sqlx::query_as!(
+            Registration,
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE participant_id = $1;
+            "#,
+            participant_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all registrations for participant from database")
+    }
+
+    async fn result_for_registration(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<RegistrationResult>> {
+        sqlx::query_as!(
+            RegistrationResult,
+            r#"
+                SELECT
+                    rr.registration_id,
+                    rr.disqualified,
+                    rr.time_millis,
+                    CAST(FLOOR(1000 * POWER(CAST(c.target_time AS FLOAT) / CAST(rr.time_millis AS FLOAT), 3)) AS INT) AS "fina_points!"
+                FROM registration_results rr
+                INNER JOIN registrations r ON r.id = rr.registration_id
+                INNER JOIN competitions c ON c.id = r.competition_id
+                WHERE rr.registration_id = $1;
+            "#,
+            registration_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registration result rom database")
+    }
+
+    async fn delete_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                DELETE FROM registrations
+                WHERE id = $1
+            "#,
+            registration_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete registration in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_result_for_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query!(
+            r#"
+                DELETE FROM registration_results
+                WHERE registration_id = $1
+            "#,
+            registration_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete registration result in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn create_registration(
+        &self,
+        participant_id: Uuid,
+        competition_id: Uuid,
+        entry_time_millis: Option<i32>,
+        late_entry: bool,
+    ) -> Result<Uuid> {
+        let existing_registration_id = sqlx::query_scalar!(
+            r#"
+                SELECT
+                    id AS "id!"
+                FROM registrations
+                WHERE
+                    participant_id = $1 AND
+                    competition_id = $2;
+            "#,
+            participant_id,
+            competition_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to look for existing registration in database")?;
+
+        if let Some(registration_id) = existing_registration_id {
+            return Ok(registration_id);
+        }
+
+        let registration_id = sqlx::query_scalar!(
+            r#"
+                INSERT INTO registrations (
+                    participant_id, competition_id, entry_time_millis, card_number, late_entry
+                ) VALUES (
+                    $1, $2, $3, (SELECT COALESCE(MAX(card_number), 0) + 1 FROM registrations), $4
+                ) RETURNING id;
+            "#,
+            participant_id,
+            competition_id,
+            entry_time_millis,
+            late_entry
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to insert registration in database")?;
+
+        Ok(registration_id)
+    }
+
+    async fn registration_by_card_number(&self, card_number: i32) -> Result<Option<Registration>> {
+        sqlx::query_as!(
+            Registration,
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE card_number = $1;
+            "#,
+            card_number
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registration by card number from database")
+    }
+
+    async fn registration_by_id(&self, registration_id: Uuid) -> Result<Option<Registration>> {
+        sqlx::query_as!(
+            Registration,
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE id = $1;
+            "#,
+            registration_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registrations by id from database")
+    }
+
+    async fn create_registration_result(
+        &self,
+        registration_id: Uuid,
+        time_millis: i32,
+        disqualified: bool,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO registration_results (
+                    registration_id, time_millis, disqualified
+                ) VALUES (
+                    $1, $2, $3
+                );
+            "#,
+            registration_id,
+            time_millis,
+            disqualified
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert registration in database")?;
+
+        Ok(())
+    }
+
+    async fn registrations_for_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        sqlx::query_as!(
+            Registration,
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE competition_id = $1;
+            "#,
+            competition_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all registrations for competition from database")
+    }
+}
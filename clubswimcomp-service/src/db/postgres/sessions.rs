@@ -0,0 +1,225 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::sessions::{self, Session};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Store the competitions of the session in their order.
+async fn insert_competitions(
+    tx: &mut sqlx::Transaction<'_, super::Database>,
+    session: &Session,
+) -> Result<()> {
+    for (position, competition_id) in session.competition_ids.iter().enumerate() {
+        sqlx::query!(
+            r#"
+                INSERT INTO session_competitions (
+                    session_id, competition_id, position
+                ) VALUES (
+                    $1, $2, $3
+                );
+            "#,
+            session.id,
+            competition_id,
+            position as i32,
+        )
+        .execute(&mut **tx)
+        .await
+        .context("Failed to insert competition of session into database")?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl sessions::Repository for Repository {
+    async fn create_session(&self, session: &Session) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO sessions (
+                    id, name, starts_at, warm_up_minutes, turnaround_seconds
+                ) VALUES (
+                    $1, $2, $3, $4, $5
+                );
+            "#,
+            session.id,
+            session.name,
+            session.starts_at,
+            session.warm_up_minutes,
+            session.turnaround_seconds,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert session into database")?;
+
+        insert_competitions(&mut tx, session).await?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn all_sessions(&self) -> Result<Vec<Session>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let sessions = sqlx::query!(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                ORDER BY starts_at, id;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch sessions from database")?;
+
+        let competitions = sqlx::query!(
+            r#"
+                SELECT session_id, competition_id
+                FROM session_competitions
+                ORDER BY session_id, position;
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions of sessions from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        let sessions = sessions
+            .into_iter()
+            .map(|s| Session {
+                id: s.id,
+                name: s.name,
+                starts_at: s.starts_at,
+                warm_up_minutes: s.warm_up_minutes,
+                turnaround_seconds: s.turnaround_seconds,
+                competition_ids: competitions
+                    .iter()
+                    .filter(|c| c.session_id == s.id)
+                    .map(|c| c.competition_id)
+                    .collect(),
+            })
+            .collect();
+
+        Ok(sessions)
+    }
+
+    async fn session_by_id(&self, session_id: Uuid) -> Result<Option<Session>> {
+        let session = sqlx::query!(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                WHERE id = $1;
+            "#,
+            session_id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch session from database")?;
+
+        let Some(session) = session else {
+            return Ok(None);
+        };
+
+        let competition_ids = sqlx::query_scalar!(
+            r#"
+                SELECT competition_id
+                FROM session_competitions
+                WHERE session_id = $1
+                ORDER BY position;
+            "#,
+            session_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch competitions of session from database")?;
+
+        Ok(Some(Session {
+            id: session.id,
+            name: session.name,
+            starts_at: session.starts_at,
+            warm_up_minutes: session.warm_up_minutes,
+            turnaround_seconds: session.turnaround_seconds,
+            competition_ids,
+        }))
+    }
+
+    async fn update_session(&self, session: &Session) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query!(
+            r#"
+                UPDATE sessions SET
+                    name = $2,
+                    starts_at = $3,
+                    warm_up_minutes = $4,
+                    turnaround_seconds = $5
+                WHERE id = $1;
+            "#,
+            session.id,
+            session.name,
+            session.starts_at,
+            session.warm_up_minutes,
+            session.turnaround_seconds,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update session in database")?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            r#"
+                DELETE FROM session_competitions WHERE session_id = $1;
+            "#,
+            session.id,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete competitions of session from database")?;
+
+        insert_competitions(&mut tx, session).await?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(Some(()))
+    }
+
+    async fn delete_session(&self, session_id: Uuid) -> Result<Option<()>> {
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM sessions WHERE id = $1;
+            "#,
+            session_id,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete session from database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
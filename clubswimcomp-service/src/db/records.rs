@@ -0,0 +1,84 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::{Course, Gender, Stroke};
+
+/// Whether a record is kept for the club or for this meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "record_kind")]
+#[sqlx(rename_all = "lowercase")]
+pub enum RecordKind {
+    Club,
+    Meet,
+}
+
+/// Where a record is in the approval workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "record_status")]
+#[sqlx(rename_all = "lowercase")]
+pub enum RecordStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Superseded,
+}
+
+/// A record time, the records are kept per kind, gender, stroke, distance,
+/// course and age class.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Record {
+    pub id: Uuid,
+    pub kind: RecordKind,
+    pub gender: Gender,
+    pub stroke: Stroke,
+    pub distance: i32,
+    pub course: Course,
+    /// Age the swimmer turns in the year of the swim.
+    pub age_class: i32,
+    pub time_millis: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub swum_on: NaiveDate,
+    /// Name of an earlier meet, `None` for records swum at this event.
+    pub meet: Option<String>,
+    /// The result the record has been detected in.
+    pub registration_id: Option<Uuid>,
+    pub status: RecordStatus,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    /// The faster record that replaced this one.
+    pub superseded_by: Option<Uuid>,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn create_record(&self, record: &Record) -> Result<()>;
+
+    /// All records, ordered by the time they have been created.
+    async fn list_records(&self) -> Result<Vec<Record>>;
+
+    async fn record_by_id(&self, record_id: Uuid) -> Result<Option<Record>>;
+
+    /// Change the status of a record in the approval workflow.
+    ///
+    /// # Parameters:
+    /// - `record_id` - The id of the record
+    /// - `status` - The new status of the record
+    /// - `decided_at` - When the status has been changed
+    /// - `superseded_by` - The record that replaced this one, if superseded
+    ///
+    /// # Returns:
+    /// - `Ok(Some(()))` - if the record has been updated
+    /// - `Ok(None)` - if the record does not exist
+    /// - `Err(e)` - in case of a database error
+    async fn update_record_status(
+        &self,
+        record_id: Uuid,
+        status: RecordStatus,
+        decided_at: DateTime<Utc>,
+        superseded_by: Option<Uuid>,
+    ) -> Result<Option<()>>;
+}
@@ -1,12 +1,21 @@
-use anyhow::{Context as _, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct Registration {
     pub id: Uuid,
     pub participant_id: Uuid,
     pub competition_id: Uuid,
+    /// Time the participant expects to swim, used to seed the heats.
+    pub entry_time_millis: Option<i32>,
+    /// Number printed on the registration card, unique within the event.
+    pub card_number: i32,
+    /// Accepted after the registration has been closed.
+    pub late_entry: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct RegistrationResult {
     pub registration_id: Uuid,
     pub disqualified: bool,
@@ -14,58 +23,16 @@ pub struct RegistrationResult {
     pub fina_points: i64,
 }
 
-#[derive(Clone)]
-pub struct Repository {
-    pool: super::DatabasePool,
-}
-
-impl Repository {
-    pub fn new(pool: super::DatabasePool) -> Self {
-        Self { pool }
-    }
-
-    pub async fn registrations_of_participant(
-        &self,
-        participant_id: Uuid,
-    ) -> Result<Vec<Registration>> {
-        sqlx::query_as!(
-            Registration,
-            r#"
-                SELECT
-                    id, participant_id, competition_id
-                FROM registrations
-                WHERE participant_id = $1;
-            "#,
-            participant_id
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of all registrations for participant from database")
-    }
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn registrations_of_participant(&self, participant_id: Uuid)
+        -> Result<Vec<Registration>>;
 
-    pub async fn result_for_registration(
+    /// Get the result of a registration including the achieved FINA points.
+    async fn result_for_registration(
         &self,
         registration_id: Uuid,
-    ) -> Result<Option<RegistrationResult>> {
-        sqlx::query_as!(
-            RegistrationResult,
-            r#"
-                SELECT
-                    rr.registration_id,
-                    rr.disqualified,
-                    rr.time_millis,
-                    CAST(FLOOR(1000 * POWER(CAST(c.target_time AS FLOAT) / CAST(rr.time_millis AS FLOAT), 3)) AS INT) AS "fina_points!"
-                FROM registration_results rr
-                INNER JOIN registrations r ON r.id = rr.registration_id
-                INNER JOIN competitions c ON c.id = r.competition_id
-                WHERE rr.registration_id = $1;
-            "#,
-            registration_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to fetch registration result rom database")
-    }
+    ) -> Result<Option<RegistrationResult>>;
 
     /// Delete a registration.
     ///
@@ -80,25 +47,7 @@ impl Repository {
     /// - `Ok(Some(()))` - if the result has been deleted
     /// - `Ok(None)` - if the result did not exist
     /// - `Err(e)` - in case of an database error
-    pub async fn delete_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
-        let rows = sqlx::query!(
-            r#"
-                DELETE FROM registrations
-                WHERE id = $1
-            "#,
-            registration_id
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to delete registration in database")?
-        .rows_affected();
-
-        if rows > 0 {
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
-    }
+    async fn delete_registration(&self, registration_id: Uuid) -> Result<Option<()>>;
 
     /// Delete a result of a registration.
     ///
@@ -110,28 +59,7 @@ impl Repository {
     /// - `Ok(Some(()))` - if the result has been deleted
     /// - `Ok(None)` - if the result did not exist
     /// - `Err(e)` - in case of an database error
-    pub async fn delete_result_for_registration(
-        &self,
-        registration_id: Uuid,
-    ) -> Result<Option<()>> {
-        let rows = sqlx::query!(
-            r#"
-                DELETE FROM registration_results
-                WHERE registration_id = $1
-            "#,
-            registration_id
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to delete registration result in database")?
-        .rows_affected();
-
-        if rows > 0 {
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
-    }
+    async fn delete_result_for_registration(&self, registration_id: Uuid) -> Result<Option<()>>;
 
     /// Create a new registration.
     ///
@@ -141,94 +69,34 @@ impl Repository {
     /// # Parameters:
     /// - `participant_id` - The id of the participant that registers
     /// - `competition_id` - The id of the competition to register for
+    /// - `entry_time_millis` - The expected time of the participant, if known.
+    ///   It is ignored if the registration already exists.
+    /// - `late_entry` - Whether the registration is accepted after the
+    ///   registration has been closed. It is ignored if the registration
+    ///   already exists.
     ///
     /// # Returns:
     /// - `Ok(registration_id)` - If a new registration has been created or
     ///    already exists.
     /// - `Err(e)` - In case of a database error
-    pub async fn create_registration(
+    async fn create_registration(
         &self,
         participant_id: Uuid,
         competition_id: Uuid,
-    ) -> Result<Uuid> {
-        let existing_registration_id = sqlx::query_scalar!(
-            r#"
-                SELECT
-                    id AS "id!"
-                FROM registrations
-                WHERE
-                    participant_id = $1 AND
-                    competition_id = $2;
-            "#,
-            participant_id,
-            competition_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to look for existing registration in database")?;
+        entry_time_millis: Option<i32>,
+        late_entry: bool,
+    ) -> Result<Uuid>;
 
-        if let Some(registration_id) = existing_registration_id {
-            return Ok(registration_id);
-        }
+    async fn registration_by_id(&self, registration_id: Uuid) -> Result<Option<Registration>>;
 
-        let registration_id = sqlx::query_scalar!(
-            r#"
-                INSERT INTO registrations (
-                    participant_id, competition_id
-                ) VALUES (
-                    $1, $2
-                ) RETURNING id;
-            "#,
-            participant_id,
-            competition_id
-        )
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to insert registration in database")?;
+    async fn registration_by_card_number(&self, card_number: i32) -> Result<Option<Registration>>;
 
-        Ok(registration_id)
-    }
-
-    pub async fn registration_by_id(&self, registration_id: Uuid) -> Result<Option<Registration>> {
-        sqlx::query_as!(
-            Registration,
-            r#"
-                SELECT
-                    id, participant_id, competition_id
-                FROM registrations
-                WHERE id = $1;
-            "#,
-            registration_id
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .context("Failed to fetch registrations by id from database")
-    }
-
-    pub async fn create_registration_result(
+    async fn create_registration_result(
         &self,
         registration_id: Uuid,
         time_millis: i32,
         disqualified: bool,
-    ) -> Result<()> {
-        sqlx::query!(
-            r#"
-                INSERT INTO registration_results (
-                    registration_id, time_millis, disqualified
-                ) VALUES (
-                    $1, $2, $3
-                );
-            "#,
-            registration_id,
-            time_millis,
-            disqualified
-        )
-        .execute(&self.pool)
-        .await
-        .context("Failed to insert registration in database")?;
-
-        Ok(())
-    }
+    ) -> Result<()>;
 
     /// Get all registrations for the given competition.
     ///
@@ -237,22 +105,8 @@ impl Repository {
     ///
     /// # Parameters:
     /// - `competition_id` - The id of the competition to load the registrations for.
-    pub async fn registrations_for_competition(
+    async fn registrations_for_competition(
         &self,
         competition_id: Uuid,
-    ) -> Result<Vec<Registration>> {
-        sqlx::query_as!(
-            Registration,
-            r#"
-                SELECT
-                    id, participant_id, competition_id
-                FROM registrations
-                WHERE competition_id = $1;
-            "#,
-            competition_id
-        )
-        .fetch_all(&self.pool)
-        .await
-        .context("Failed to fetch list of all registrations for competition from database")
-    }
+    ) -> Result<Vec<Registration>>;
 }
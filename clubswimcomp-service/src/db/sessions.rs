@@ -0,0 +1,39 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// A part of the event with its own warm-up.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub name: String,
+    /// Local time the first heat starts.
+    pub starts_at: NaiveDateTime,
+    pub warm_up_minutes: i32,
+    /// Time between the end of a heat and the start of the next one.
+    pub turnaround_seconds: i32,
+    /// The competitions in the order they are swum.
+    #[sqlx(skip)]
+    pub competition_ids: Vec<Uuid>,
+}
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn create_session(&self, session: &Session) -> Result<()>;
+
+    /// All sessions, ordered by their start.
+    async fn all_sessions(&self) -> Result<Vec<Session>>;
+
+    async fn session_by_id(&self, session_id: Uuid) -> Result<Option<Session>>;
+
+    /// Replace the settings and the competitions of a session.
+    ///
+    /// # Returns:
+    /// - `Ok(Some(()))` - if the session has been updated
+    /// - `Ok(None)` - if the session does not exist
+    /// - `Err(e)` - in case of a database error
+    async fn update_session(&self, session: &Session) -> Result<Option<()>>;
+
+    async fn delete_session(&self, session_id: Uuid) -> Result<Option<()>>;
+}
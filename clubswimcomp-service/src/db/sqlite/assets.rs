@@ -0,0 +1,75 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::db::assets::{self, Asset};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl assets::Repository for Repository {
+    async fn upsert_asset(&self, name: &str, content: &[u8]) -> Result<()> {
+        sqlx::query(
+            r#"
+                INSERT INTO assets (
+                    name, content, updated_at
+                ) VALUES (
+                    ?, ?, ?
+                ) ON CONFLICT (name) DO UPDATE SET
+                    content = excluded.content,
+                    updated_at = excluded.updated_at;
+            "#,
+        )
+        .bind(name)
+        .bind(content)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .context("Failed to upsert asset in database")?;
+
+        Ok(())
+    }
+
+    async fn all_assets(&self) -> Result<Vec<Asset>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    name, content, updated_at
+                FROM assets
+                ORDER BY name;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all assets from database")
+    }
+
+    async fn delete_asset(&self, name: &str) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM assets
+                WHERE name = ?
+            "#,
+        )
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete asset in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,708 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+
+use uuid::Uuid;
+
+use crate::db::backups::{self, Snapshot};
+use crate::db::competitions::Eligibility;
+use crate::db::finals::Finals;
+use crate::db::sessions::Session;
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl backups::Repository for Repository {
+    async fn export_snapshot(&self) -> Result<Snapshot> {
+        // Reads within one transaction see the same state of the database
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let groups = sqlx::query_as(
+            r#"
+                SELECT id, name FROM groups ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch groups from database")?;
+
+        let participants = sqlx::query_as(
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                FROM participants
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch participants from database")?;
+
+        let competitions = sqlx::query_as(
+            r#"
+                SELECT
+                    id, gender, stroke, distance, target_time, relay, round
+                FROM competitions
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions from database")?;
+
+        let registrations = sqlx::query_as(
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch registrations from database")?;
+
+        let results = sqlx::query_as(
+            r#"
+                SELECT
+                    registration_id, disqualified, time_millis
+                FROM registration_results
+                ORDER BY registration_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch registration results from database")?;
+
+        let assets = sqlx::query_as(
+            r#"
+                SELECT
+                    name, content, updated_at
+                FROM assets
+                ORDER BY name;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch assets from database")?;
+
+        let best_times = sqlx::query_as(
+            r#"
+                SELECT
+                    id, first_name, last_name, birthday, stroke, distance, course,
+                    time_millis, swum_on, meet, registration_id
+                FROM best_times
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch best times from database")?;
+
+        let records = sqlx::query_as(
+            r#"
+                SELECT
+                    id, kind, gender, stroke, distance, course, age_class, time_millis,
+                    first_name, last_name, birthday, swum_on, meet, registration_id, status,
+                    created_at, decided_at, superseded_by
+                FROM records
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch records from database")?;
+
+        let eligible_groups: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+                SELECT competition_id, group_id
+                FROM competition_eligible_groups
+                ORDER BY competition_id, group_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligible groups from database")?;
+
+        let mut eligibilities: Vec<Eligibility> = sqlx::query_as(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                ORDER BY competition_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligibilities from database")?;
+        for eligibility in eligibilities.iter_mut() {
+            eligibility.group_ids = eligible_groups
+                .iter()
+                .filter(|(competition_id, _)| *competition_id == eligibility.competition_id)
+                .map(|(_, group_id)| *group_id)
+                .collect();
+        }
+
+        let phase_changes = sqlx::query_as(
+            r#"
+                SELECT id, phase, changed_at, reason
+                FROM event_phase_changes
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch phase changes from database")?;
+
+        let session_competitions: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+                SELECT session_id, competition_id
+                FROM session_competitions
+                ORDER BY session_id, position;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions of sessions from database")?;
+
+        let mut sessions: Vec<Session> = sqlx::query_as(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch sessions from database")?;
+        for session in sessions.iter_mut() {
+            session.competition_ids = session_competitions
+                .iter()
+                .filter(|(session_id, _)| *session_id == session.id)
+                .map(|(_, competition_id)| *competition_id)
+                .collect();
+        }
+
+        let swim_offs: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+                SELECT prelims_id, competition_id
+                FROM swim_offs
+                ORDER BY prelims_id, number;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch swim-offs from database")?;
+
+        let mut finals: Vec<Finals> = sqlx::query_as(
+            r#"
+                SELECT competition_id, finals_competition_id, finalists, alternates
+                FROM competition_finals
+                ORDER BY competition_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch finals from database")?;
+        for f in finals.iter_mut() {
+            f.swim_off_ids = swim_offs
+                .iter()
+                .filter(|(prelims_id, _)| *prelims_id == f.competition_id)
+                .map(|(_, competition_id)| *competition_id)
+                .collect();
+        }
+
+        let finals_entries = sqlx::query_as(
+            r#"
+                SELECT competition_id, participant_id, seed, time_millis, status
+                FROM finals_entries
+                ORDER BY competition_id, seed;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch finals entries from database")?;
+
+        let payments = sqlx::query_as(
+            r#"
+                SELECT id, participant_id, group_id, amount_cents, received_on, reference
+                FROM payments
+                ORDER BY id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch payments from database")?;
+
+        let event = sqlx::query_as(
+            r#"
+                SELECT
+                    id, card_secret, created_at, group_scoring, scoring_result_count,
+                    min_starts, shared_ranks, time_precision, group_tie_break,
+                    team_placing_points, team_max_scorers, max_individual_starts, course,
+                    phase, participant_fee_cents, start_fee_cents, late_entry_surcharge_cents,
+                    currency, name, organization, team_relay_weight
+                FROM event;
+            "#,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch event from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(Snapshot {
+            groups,
+            participants,
+            competitions,
+            registrations,
+            results,
+            assets,
+            best_times,
+            records,
+            eligibilities,
+            phase_changes,
+            sessions,
+            finals,
+            finals_entries,
+            payments,
+            event,
+        })
+    }
+
+    async fn is_empty(&self) -> Result<bool> {
+        sqlx::query_scalar(
+            r#"
+                SELECT
+                    NOT EXISTS (SELECT * FROM groups) AND
+                    NOT EXISTS (SELECT * FROM participants) AND
+                    NOT EXISTS (SELECT * FROM competitions) AND
+                    NOT EXISTS (SELECT * FROM registrations) AND
+                    NOT EXISTS (SELECT * FROM registration_results) AND
+                    NOT EXISTS (SELECT * FROM assets) AND
+                    NOT EXISTS (SELECT * FROM best_times) AND
+                    NOT EXISTS (SELECT * FROM records);
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check if database is empty")
+    }
+
+    async fn import_snapshot(&self, snapshot: &Snapshot) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for group in snapshot.groups.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO groups (id, name) VALUES (?, ?);
+                "#,
+            )
+            .bind(group.id)
+            .bind(&group.name)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert group into database")?;
+        }
+
+        for participant in snapshot.participants.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO participants (
+                        id, short_id, first_name, last_name, gender, birthday, group_id
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(participant.id)
+            .bind(participant.short_id)
+            .bind(&participant.first_name)
+            .bind(&participant.last_name)
+            .bind(participant.gender)
+            .bind(participant.birthday)
+            .bind(participant.group_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert participant into database")?;
+        }
+
+        for competition in snapshot.competitions.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO competitions (
+                        id, gender, stroke, distance, target_time, relay, round
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(competition.id)
+            .bind(competition.gender)
+            .bind(competition.stroke)
+            .bind(competition.distance)
+            .bind(competition.target_time)
+            .bind(competition.relay)
+            .bind(competition.round)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert competition into database")?;
+        }
+
+        for registration in snapshot.registrations.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO registrations (
+                        id, participant_id, competition_id, entry_time_millis, card_number,
+                        late_entry
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(registration.id)
+            .bind(registration.participant_id)
+            .bind(registration.competition_id)
+            .bind(registration.entry_time_millis)
+            .bind(registration.card_number)
+            .bind(registration.late_entry)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration into database")?;
+        }
+
+        for result in snapshot.results.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO registration_results (
+                        registration_id, disqualified, time_millis
+                    ) VALUES (
+                        ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(result.registration_id)
+            .bind(result.disqualified)
+            .bind(result.time_millis)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration result into database")?;
+        }
+
+        for asset in snapshot.assets.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO assets (
+                        name, content, updated_at
+                    ) VALUES (
+                        ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(&asset.name)
+            .bind(&asset.content)
+            .bind(asset.updated_at)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert asset into database")?;
+        }
+
+        for best_time in snapshot.best_times.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO best_times (
+                        id, first_name, last_name, birthday, stroke, distance, course,
+                        time_millis, swum_on, meet, registration_id
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(best_time.id)
+            .bind(&best_time.first_name)
+            .bind(&best_time.last_name)
+            .bind(best_time.birthday)
+            .bind(best_time.stroke)
+            .bind(best_time.distance)
+            .bind(best_time.course)
+            .bind(best_time.time_millis)
+            .bind(best_time.swum_on)
+            .bind(&best_time.meet)
+            .bind(best_time.registration_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert best time into database")?;
+        }
+
+        for record in snapshot.records.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO records (
+                        id, kind, gender, stroke, distance, course, age_class, time_millis,
+                        first_name, last_name, birthday, swum_on, meet, registration_id,
+                        status, created_at, decided_at, superseded_by
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(record.id)
+            .bind(record.kind)
+            .bind(record.gender)
+            .bind(record.stroke)
+            .bind(record.distance)
+            .bind(record.course)
+            .bind(record.age_class)
+            .bind(record.time_millis)
+            .bind(&record.first_name)
+            .bind(&record.last_name)
+            .bind(record.birthday)
+            .bind(record.swum_on)
+            .bind(&record.meet)
+            .bind(record.registration_id)
+            .bind(record.status)
+            .bind(record.created_at)
+            .bind(record.decided_at)
+            .bind(record.superseded_by)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert record into database")?;
+        }
+
+        for eligibility in snapshot.eligibilities.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO competition_eligibility (
+                        competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                        qualifying_time
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(eligibility.competition_id)
+            .bind(eligibility.min_age)
+            .bind(eligibility.max_age)
+            .bind(eligibility.min_birth_year)
+            .bind(eligibility.max_birth_year)
+            .bind(eligibility.qualifying_time)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert eligibility into database")?;
+
+            for group_id in eligibility.group_ids.iter() {
+                sqlx::query(
+                    r#"
+                        INSERT INTO competition_eligible_groups (
+                            competition_id, group_id
+                        ) VALUES (
+                            ?, ?
+                        );
+                    "#,
+                )
+                .bind(eligibility.competition_id)
+                .bind(group_id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert eligible group into database")?;
+            }
+        }
+
+        for change in snapshot.phase_changes.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO event_phase_changes (id, phase, changed_at, reason)
+                    VALUES (?, ?, ?, ?);
+                "#,
+            )
+            .bind(change.id)
+            .bind(change.phase)
+            .bind(change.changed_at)
+            .bind(&change.reason)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert phase change into database")?;
+        }
+
+        for session in snapshot.sessions.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO sessions (
+                        id, name, starts_at, warm_up_minutes, turnaround_seconds
+                    ) VALUES (
+                        ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(session.id)
+            .bind(&session.name)
+            .bind(session.starts_at)
+            .bind(session.warm_up_minutes)
+            .bind(session.turnaround_seconds)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert session into database")?;
+
+            for (position, competition_id) in session.competition_ids.iter().enumerate() {
+                sqlx::query(
+                    r#"
+                        INSERT INTO session_competitions (
+                            session_id, competition_id, position
+                        ) VALUES (
+                            ?, ?, ?
+                        );
+                    "#,
+                )
+                .bind(session.id)
+                .bind(competition_id)
+                .bind(position as i32)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert competition of session into database")?;
+            }
+        }
+
+        for finals in snapshot.finals.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO competition_finals (
+                        competition_id, finals_competition_id, finalists, alternates
+                    ) VALUES (
+                        ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(finals.competition_id)
+            .bind(finals.finals_competition_id)
+            .bind(finals.finalists)
+            .bind(finals.alternates)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals into database")?;
+
+            for (index, swim_off_id) in finals.swim_off_ids.iter().enumerate() {
+                sqlx::query(
+                    r#"
+                        INSERT INTO swim_offs (
+                            competition_id, prelims_id, number
+                        ) VALUES (
+                            ?, ?, ?
+                        );
+                    "#,
+                )
+                .bind(swim_off_id)
+                .bind(finals.competition_id)
+                .bind(index as i32 + 1)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert swim-off into database")?;
+            }
+        }
+
+        for entry in snapshot.finals_entries.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO finals_entries (
+                        competition_id, participant_id, seed, time_millis, status
+                    ) VALUES (
+                        ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(entry.competition_id)
+            .bind(entry.participant_id)
+            .bind(entry.seed)
+            .bind(entry.time_millis)
+            .bind(entry.status)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals entry into database")?;
+        }
+
+        for payment in snapshot.payments.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO payments (
+                        id, participant_id, group_id, amount_cents, received_on, reference
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(payment.id)
+            .bind(payment.participant_id)
+            .bind(payment.group_id)
+            .bind(payment.amount_cents)
+            .bind(payment.received_on)
+            .bind(&payment.reference)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert payment into database")?;
+        }
+
+        if let Some(event) = snapshot.event.as_ref() {
+            sqlx::query("DELETE FROM event;")
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete event from database")?;
+            sqlx::query(
+                r#"
+                    INSERT INTO event (
+                        id, card_secret, created_at, group_scoring, scoring_result_count,
+                        min_starts, shared_ranks, time_precision, group_tie_break,
+                        team_placing_points, team_max_scorers, max_individual_starts, course,
+                        phase, participant_fee_cents, start_fee_cents,
+                        late_entry_surcharge_cents, currency, name, organization,
+                        team_relay_weight
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(event.id)
+            .bind(&event.card_secret)
+            .bind(event.created_at)
+            .bind(event.group_scoring)
+            .bind(event.scoring_result_count)
+            .bind(event.min_starts)
+            .bind(event.shared_ranks)
+            .bind(event.time_precision)
+            .bind(event.group_tie_break)
+            .bind(&event.team_placing_points)
+            .bind(event.team_max_scorers)
+            .bind(event.max_individual_starts)
+            .bind(event.course)
+            .bind(event.phase)
+            .bind(event.participant_fee_cents)
+            .bind(event.start_fee_cents)
+            .bind(event.late_entry_surcharge_cents)
+            .bind(&event.currency)
+            .bind(&event.name)
+            .bind(&event.organization)
+            .bind(event.team_relay_weight)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert event into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+}
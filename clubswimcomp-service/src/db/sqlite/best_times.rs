@@ -0,0 +1,114 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::db::best_times::{self, BestTime};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl best_times::Repository for Repository {
+    async fn insert_best_times(&self, best_times: &[BestTime]) -> Result<u64> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let mut inserted = 0;
+        for best_time in best_times {
+            inserted += sqlx::query(
+                r#"
+                    INSERT INTO best_times (
+                        id, first_name, last_name, birthday, stroke, distance, course,
+                        time_millis, swum_on, meet
+                    ) VALUES (
+                        ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                    ) ON CONFLICT DO NOTHING;
+                "#,
+            )
+            .bind(best_time.id)
+            .bind(&best_time.first_name)
+            .bind(&best_time.last_name)
+            .bind(best_time.birthday)
+            .bind(best_time.stroke)
+            .bind(best_time.distance)
+            .bind(best_time.course)
+            .bind(best_time.time_millis)
+            .bind(best_time.swum_on)
+            .bind(&best_time.meet)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert best time into database")?
+            .rows_affected();
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(inserted)
+    }
+
+    async fn record_best_time(&self, best_time: &BestTime) -> Result<()> {
+        sqlx::query(
+            r#"
+                INSERT INTO best_times (
+                    id, first_name, last_name, birthday, stroke, distance, course,
+                    time_millis, swum_on, meet, registration_id
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                ) ON CONFLICT (registration_id) DO UPDATE
+                SET time_millis = excluded.time_millis, swum_on = excluded.swum_on,
+                    meet = excluded.meet;
+            "#,
+        )
+        .bind(best_time.id)
+        .bind(&best_time.first_name)
+        .bind(&best_time.last_name)
+        .bind(best_time.birthday)
+        .bind(best_time.stroke)
+        .bind(best_time.distance)
+        .bind(best_time.course)
+        .bind(best_time.time_millis)
+        .bind(best_time.swum_on)
+        .bind(&best_time.meet)
+        .bind(best_time.registration_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record best time in database")?;
+
+        Ok(())
+    }
+
+    async fn best_times_of_swimmer(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        birthday: NaiveDate,
+    ) -> Result<Vec<BestTime>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, first_name, last_name, birthday, stroke, distance, course,
+                    time_millis, swum_on, meet, registration_id
+                FROM best_times
+                WHERE first_name = ? AND last_name = ? AND birthday = ?
+                ORDER BY swum_on, id;
+            "#,
+        )
+        .bind(first_name)
+        .bind(last_name)
+        .bind(birthday)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch best times of swimmer from database")
+    }
+}
@@ -0,0 +1,301 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::competitions::{self, Competition, Eligibility};
+use crate::db::{Gender, Stroke};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl competitions::Repository for Repository {
+    async fn create_competition(
+        &self,
+        gender: Gender,
+        stroke: Stroke,
+        distance: i32,
+        target_time: i64,
+        relay: bool,
+    ) -> Result<Uuid> {
+        let competition_id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+                INSERT INTO competitions (
+                    id, gender, stroke, distance, target_time, relay
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?
+                );
+            "#,
+        )
+        .bind(competition_id)
+        .bind(gender)
+        .bind(stroke)
+        .bind(distance)
+        .bind(target_time)
+        .bind(relay)
+        .execute(&self.pool)
+        .await
+        .context("Failed to execute INSERT INTO query")?;
+
+        Ok(competition_id)
+    }
+
+    async fn search_competition(
+        &self,
+        gender: Option<Gender>,
+        stroke: Option<Stroke>,
+        distance: Option<i32>,
+    ) -> Result<Vec<Competition>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, gender, stroke, distance, target_time, relay, round
+                FROM competitions
+                WHERE
+                    (gender = ?1 OR ?1 IS NULL) AND
+                    (stroke = ?2 OR ?2 IS NULL) AND
+                    (distance = ?3 OR ?3 IS NULL);
+            "#,
+        )
+        .bind(gender)
+        .bind(stroke)
+        .bind(distance)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch search results of competitions from database")
+    }
+
+    async fn all_competitions(&self) -> Result<Vec<Competition>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, gender, stroke, distance, target_time, relay, round
+                FROM competitions;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all competitions from database")
+    }
+
+    async fn competition_by_id(&self, competition_id: Uuid) -> Result<Option<Competition>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, gender, stroke, distance, target_time, relay, round
+                FROM competitions
+                WHERE id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch competition by id from database")
+    }
+
+    async fn delete_competition(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM competitions
+                WHERE id = ?
+            "#,
+        )
+        .bind(competition_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete competition in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn eligibility_of_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Option<Eligibility>> {
+        let eligibility: Option<Eligibility> = sqlx::query_as(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                WHERE competition_id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch eligibility of competition from database")?;
+
+        let Some(mut eligibility) = eligibility else {
+            return Ok(None);
+        };
+
+        eligibility.group_ids = sqlx::query_scalar(
+            r#"
+                SELECT group_id
+                FROM competition_eligible_groups
+                WHERE competition_id = ?
+                ORDER BY group_id;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch eligible groups of competition from database")?;
+
+        Ok(Some(eligibility))
+    }
+
+    async fn all_eligibilities(&self) -> Result<Vec<Eligibility>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let mut eligibilities: Vec<Eligibility> = sqlx::query_as(
+            r#"
+                SELECT
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                FROM competition_eligibility
+                ORDER BY competition_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligibilities from database")?;
+
+        let groups: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+                SELECT competition_id, group_id
+                FROM competition_eligible_groups
+                ORDER BY competition_id, group_id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch eligible groups from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        for eligibility in eligibilities.iter_mut() {
+            eligibility.group_ids = groups
+                .iter()
+                .filter(|(competition_id, _)| *competition_id == eligibility.competition_id)
+                .map(|(_, group_id)| *group_id)
+                .collect();
+        }
+
+        Ok(eligibilities)
+    }
+
+    async fn set_eligibility(&self, eligibility: &Eligibility) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO competition_eligibility (
+                    competition_id, min_age, max_age, min_birth_year, max_birth_year,
+                    qualifying_time
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?
+                ) ON CONFLICT (competition_id) DO UPDATE SET
+                    min_age = excluded.min_age,
+                    max_age = excluded.max_age,
+                    min_birth_year = excluded.min_birth_year,
+                    max_birth_year = excluded.max_birth_year,
+                    qualifying_time = excluded.qualifying_time;
+            "#,
+        )
+        .bind(eligibility.competition_id)
+        .bind(eligibility.min_age)
+        .bind(eligibility.max_age)
+        .bind(eligibility.min_birth_year)
+        .bind(eligibility.max_birth_year)
+        .bind(eligibility.qualifying_time)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to store eligibility in database")?;
+
+        sqlx::query(
+            r#"
+                DELETE FROM competition_eligible_groups WHERE competition_id = ?;
+            "#,
+        )
+        .bind(eligibility.competition_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligible groups from database")?;
+
+        for group_id in eligibility.group_ids.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO competition_eligible_groups (
+                        competition_id, group_id
+                    ) VALUES (
+                        ?, ?
+                    );
+                "#,
+            )
+            .bind(eligibility.competition_id)
+            .bind(group_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert eligible group into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn delete_eligibility(&self, competition_id: Uuid) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query(
+            r#"
+                DELETE FROM competition_eligible_groups WHERE competition_id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligible groups from database")?;
+
+        let result = sqlx::query(
+            r#"
+                DELETE FROM competition_eligibility WHERE competition_id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete eligibility from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+
+use crate::db::event::{
+    self, Event, GroupScoring, GroupTieBreak, PhaseChange, SharedRanks, TimePrecision,
+};
+use crate::db::Course;
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl event::Repository for Repository {
+    async fn event(&self) -> Result<Option<Event>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, card_secret, created_at, group_scoring, scoring_result_count,
+                    min_starts, shared_ranks, time_precision, group_tie_break,
+                    team_placing_points, team_max_scorers, max_individual_starts, course,
+                    phase, participant_fee_cents, start_fee_cents, late_entry_surcharge_cents,
+                    currency, name, organization, team_relay_weight
+                FROM event;
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch event from database")
+    }
+
+    async fn init_event(&self, event: &Event) -> Result<Event> {
+        // The singleton index turns a second event into a conflict
+        sqlx::query(
+            r#"
+                INSERT INTO event (
+                    id, card_secret, created_at, group_scoring, scoring_result_count,
+                    min_starts, shared_ranks, time_precision, group_tie_break,
+                    team_placing_points, team_max_scorers, max_individual_starts, course,
+                    phase, participant_fee_cents, start_fee_cents, late_entry_surcharge_cents,
+                    currency, name, organization, team_relay_weight
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                ) ON CONFLICT DO NOTHING;
+            "#,
+        )
+        .bind(event.id)
+        .bind(&event.card_secret)
+        .bind(event.created_at)
+        .bind(event.group_scoring)
+        .bind(event.scoring_result_count)
+        .bind(event.min_starts)
+        .bind(event.shared_ranks)
+        .bind(event.time_precision)
+        .bind(event.group_tie_break)
+        .bind(&event.team_placing_points)
+        .bind(event.team_max_scorers)
+        .bind(event.max_individual_starts)
+        .bind(event.course)
+        .bind(event.phase)
+        .bind(event.participant_fee_cents)
+        .bind(event.start_fee_cents)
+        .bind(event.late_entry_surcharge_cents)
+        .bind(&event.currency)
+        .bind(&event.name)
+        .bind(&event.organization)
+        .bind(event.team_relay_weight)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert event into database")?;
+
+        self.event()
+            .await?
+            .context("Event is missing in database after inserting it")
+    }
+
+    async fn update_group_scoring(
+        &self,
+        group_scoring: GroupScoring,
+        scoring_result_count: Option<i32>,
+        min_starts: i32,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET
+                    group_scoring = ?, scoring_result_count = ?, min_starts = ?;
+            "#,
+        )
+        .bind(group_scoring)
+        .bind(scoring_result_count)
+        .bind(min_starts)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update group scoring in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_ranking_rules(
+        &self,
+        shared_ranks: SharedRanks,
+        time_precision: TimePrecision,
+        group_tie_break: GroupTieBreak,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET
+                    shared_ranks = ?, time_precision = ?, group_tie_break = ?;
+            "#,
+        )
+        .bind(shared_ranks)
+        .bind(time_precision)
+        .bind(group_tie_break)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update ranking rules in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_team_scoring(
+        &self,
+        team_placing_points: &str,
+        team_max_scorers: Option<i32>,
+        team_relay_weight: i32,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET
+                    team_placing_points = ?, team_max_scorers = ?, team_relay_weight = ?;
+            "#,
+        )
+        .bind(team_placing_points)
+        .bind(team_max_scorers)
+        .bind(team_relay_weight)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update team scoring in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_registration_limits(
+        &self,
+        max_individual_starts: Option<i32>,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET max_individual_starts = ?;
+            "#,
+        )
+        .bind(max_individual_starts)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update registration limits in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_entry_fees(
+        &self,
+        participant_fee_cents: i32,
+        start_fee_cents: i32,
+        late_entry_surcharge_cents: i32,
+        currency: &str,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET
+                    participant_fee_cents = ?,
+                    start_fee_cents = ?,
+                    late_entry_surcharge_cents = ?,
+                    currency = ?;
+            "#,
+        )
+        .bind(participant_fee_cents)
+        .bind(start_fee_cents)
+        .bind(late_entry_surcharge_cents)
+        .bind(currency)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update entry fees in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_info(&self, name: &str, organization: &str) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET name = ?, organization = ?;
+            "#,
+        )
+        .bind(name)
+        .bind(organization)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update event info in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_course(&self, course: Course) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET course = ?;
+            "#,
+        )
+        .bind(course)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update course in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+
+    async fn update_phase(&self, change: &PhaseChange) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query(
+            r#"
+                UPDATE event SET phase = ?;
+            "#,
+        )
+        .bind(change.phase)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update phase in database")?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query(
+            r#"
+                INSERT INTO event_phase_changes (id, phase, changed_at, reason)
+                VALUES (?, ?, ?, ?);
+            "#,
+        )
+        .bind(change.id)
+        .bind(change.phase)
+        .bind(change.changed_at)
+        .bind(&change.reason)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert phase change into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(Some(()))
+    }
+
+    async fn phase_changes(&self) -> Result<Vec<PhaseChange>> {
+        sqlx::query_as(
+            r#"
+                SELECT id, phase, changed_at, reason
+                FROM event_phase_changes
+                ORDER BY changed_at, id;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch phase changes from database")
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::competitions::{Competition, CompetitionRound};
+use crate::db::finals::{self, Finals, FinalsEntry, FinalsEntryStatus};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Insert a competition of any round.
+async fn insert_competition(
+    tx: &mut sqlx::Transaction<'_, super::Database>,
+    competition: &Competition,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+            INSERT INTO competitions (
+                id, gender, stroke, distance, target_time, relay, round
+            ) VALUES (
+                ?, ?, ?, ?, ?, ?, ?
+            );
+        "#,
+    )
+    .bind(competition.id)
+    .bind(competition.gender)
+    .bind(competition.stroke)
+    .bind(competition.distance)
+    .bind(competition.target_time)
+    .bind(competition.relay)
+    .bind(competition.round)
+    .execute(&mut **tx)
+    .await
+    .context("Failed to insert competition into database")?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl finals::Repository for Repository {
+    async fn create_finals(&self, finals: &Finals, finals_competition: &Competition) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query(
+            r#"
+                UPDATE competitions SET round = ? WHERE id = ?;
+            "#,
+        )
+        .bind(CompetitionRound::Prelims)
+        .bind(finals.competition_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to turn competition into prelims in database")?;
+
+        insert_competition(&mut tx, finals_competition).await?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO competition_finals (
+                    competition_id, finals_competition_id, finalists, alternates
+                ) VALUES (
+                    ?, ?, ?, ?
+                );
+            "#,
+        )
+        .bind(finals.competition_id)
+        .bind(finals.finals_competition_id)
+        .bind(finals.finalists)
+        .bind(finals.alternates)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert finals into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn finals_of_competition(&self, competition_id: Uuid) -> Result<Option<Finals>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let finals: Option<Finals> = sqlx::query_as(
+            r#"
+                SELECT competition_id, finals_competition_id, finalists, alternates
+                FROM competition_finals
+                WHERE competition_id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch finals of competition from database")?;
+
+        let Some(mut finals) = finals else {
+            return Ok(None);
+        };
+
+        finals.swim_off_ids = sqlx::query_scalar(
+            r#"
+                SELECT competition_id
+                FROM swim_offs
+                WHERE prelims_id = ?
+                ORDER BY number;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch swim-offs of competition from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(Some(finals))
+    }
+
+    async fn update_finals(&self, finals: &Finals) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                UPDATE competition_finals
+                SET finalists = ?, alternates = ?
+                WHERE competition_id = ?;
+            "#,
+        )
+        .bind(finals.finalists)
+        .bind(finals.alternates)
+        .bind(finals.competition_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update finals in database")?
+        .rows_affected();
+
+        Ok((rows > 0).then_some(()))
+    }
+
+    async fn finals_entries(&self, competition_id: Uuid) -> Result<Vec<FinalsEntry>> {
+        sqlx::query_as(
+            r#"
+                SELECT competition_id, participant_id, seed, time_millis, status
+                FROM finals_entries
+                WHERE competition_id = ?
+                ORDER BY seed;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch finals entries from database")
+    }
+
+    async fn promote_finalists(
+        &self,
+        finals_competition_id: Uuid,
+        entries: &[FinalsEntry],
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for entry in entries.iter() {
+            sqlx::query(
+                r#"
+                    INSERT INTO finals_entries (
+                        competition_id, participant_id, seed, time_millis, status
+                    ) VALUES (
+                        ?, ?, ?, ?, ?
+                    );
+                "#,
+            )
+            .bind(entry.competition_id)
+            .bind(entry.participant_id)
+            .bind(entry.seed)
+            .bind(entry.time_millis)
+            .bind(entry.status)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert finals entry into database")?;
+
+            if entry.status != FinalsEntryStatus::Finalist {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                    INSERT INTO registrations (
+                        id, participant_id, competition_id, entry_time_millis, card_number,
+                        late_entry
+                    ) VALUES (
+                        ?, ?, ?, ?, (SELECT COALESCE(MAX(card_number), 0) + 1 FROM registrations),
+                        FALSE
+                    );
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(entry.participant_id)
+            .bind(finals_competition_id)
+            .bind(entry.time_millis)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert registration of finalist into database")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn update_finals_entry_status(
+        &self,
+        competition_id: Uuid,
+        participant_id: Uuid,
+        status: FinalsEntryStatus,
+    ) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                UPDATE finals_entries
+                SET status = ?
+                WHERE competition_id = ? AND participant_id = ?;
+            "#,
+        )
+        .bind(status)
+        .bind(competition_id)
+        .bind(participant_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update status of finals entry in database")?
+        .rows_affected();
+
+        Ok((rows > 0).then_some(()))
+    }
+
+    async fn create_swim_off(&self, competition_id: Uuid, swim_off: &Competition) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        insert_competition(&mut tx, swim_off).await?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO swim_offs (
+                    competition_id, prelims_id, number
+                ) VALUES (
+                    ?1, ?2,
+                    (SELECT COALESCE(MAX(number), 0) + 1 FROM swim_offs WHERE prelims_id = ?2)
+                );
+            "#,
+        )
+        .bind(swim_off.id)
+        .bind(competition_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert swim-off into database")?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::groups::{self, Group};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl groups::Repository for Repository {
+    async fn create_group(&self, name: String) -> Result<Uuid> {
+        let group_id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+                INSERT INTO groups (
+                    id, name
+                ) VALUES (
+                    ?, ?
+                );
+            "#,
+        )
+        .bind(group_id)
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .context("Failed to execute INSERT INTO query")?;
+
+        Ok(group_id)
+    }
+
+    async fn all_groups(&self) -> Result<Vec<Group>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, name
+                FROM groups;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all groups from database")
+    }
+
+    async fn group_by_id(&self, group_id: Uuid) -> Result<Option<Group>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, name
+                FROM groups
+                WHERE id = ?;
+            "#,
+        )
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch group by id from database")
+    }
+
+    async fn delete_group(&self, group_id: Uuid) -> Result<Option<()>> {
+        // Runs in a transaction for the same reason as deleting a participant
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM groups
+                WHERE id = ?
+            "#,
+        )
+        .bind(group_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete group in database")?
+        .rows_affected();
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Repositories backed by a local SQLite database file.
+//!
+//! SQLite lacks the custom types and the PL/pgSQL function used by the
+//! PostgreSQL schema, so the enums are stored as checked `TEXT` columns and
+//! the participant short ids are generated by the repository itself.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+pub mod assets;
+pub mod backups;
+pub mod best_times;
+pub mod competitions;
+pub mod event;
+pub mod finals;
+pub mod groups;
+pub mod participants;
+pub mod payments;
+pub mod records;
+pub mod registrations;
+pub mod sessions;
+
+pub type Database = sqlx::Sqlite;
+pub type DatabasePool = sqlx::Pool<Database>;
+
+/// Open the SQLite database behind `database_url`.
+///
+/// The database file is created if it does not exist yet and the schema is
+/// migrated to the latest version.
+pub async fn connect(database_url: &str) -> Result<DatabasePool> {
+    let options = SqliteConnectOptions::from_str(database_url)
+        .context("Invalid SQLite database URL")?
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(10)
+        .connect_with(options)
+        .await
+        .context("Failed to create database connection pool")?;
+
+    sqlx::migrate!("./migrations-sqlite")
+        .run(&pool)
+        .await
+        .context("Failed to migrate SQLite database")?;
+
+    Ok(pool)
+}
@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::db::participants::{self, Participant};
+use crate::db::Gender;
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Find a random short id that is not in use by any participant yet.
+    ///
+    /// Equivalent of the `participant_short_id()` function of the PostgreSQL
+    /// schema: guesses a number between 1000 and 9998 until a free one is
+    /// found.
+    async fn participant_short_id(&self) -> Result<i32> {
+        loop {
+            let short_id = sqlx::query_scalar::<_, i32>(
+                r#"
+                    SELECT guess FROM (
+                        SELECT ABS(RANDOM()) % 8999 + 1000 AS guess
+                    )
+                    WHERE NOT EXISTS (
+                        SELECT * FROM participants p WHERE p.short_id = guess
+                    );
+                "#,
+            )
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to generate short id for participant")?;
+
+            if let Some(short_id) = short_id {
+                return Ok(short_id);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl participants::Repository for Repository {
+    async fn list_participants(&self) -> Result<Vec<Participant>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                FROM participants;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all participants from database")
+    }
+
+    async fn participant_by_id(&self, participant_id: Uuid) -> Result<Option<Participant>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                FROM participants
+                WHERE id = ?;
+            "#,
+        )
+        .bind(participant_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch list of all participants from database")
+    }
+
+    async fn participant_by_short_code(&self, short_id: i32) -> Result<Option<Participant>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                FROM participants
+                WHERE short_id = ?;
+            "#,
+        )
+        .bind(short_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch participant by short id from database")
+    }
+
+    async fn create_participant(
+        &self,
+        first_name: &str,
+        last_name: &str,
+        gender: Gender,
+        birthday: NaiveDate,
+        group_id: Uuid,
+    ) -> Result<Uuid> {
+        let participant_id = Uuid::new_v4();
+        let short_id = self.participant_short_id().await?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO participants (
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?
+                );
+            "#,
+        )
+        .bind(participant_id)
+        .bind(short_id)
+        .bind(first_name)
+        .bind(last_name)
+        .bind(gender)
+        .bind(birthday)
+        .bind(group_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to create participant in database")?;
+
+        Ok(participant_id)
+    }
+
+    async fn delete_participant(&self, participant_id: Uuid) -> Result<bool> {
+        // The payments restrict the delete. sqlx steps a statement that failed
+        // on a constraint again until the error is received, the transaction
+        // rolls back such a retry once the payments are gone.
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM participants
+                WHERE id = ?
+            "#,
+        )
+        .bind(participant_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete participant from database")?
+        .rows_affected();
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(rows > 0)
+    }
+
+    async fn list_participants_in_group(&self, group_id: Uuid) -> Result<Vec<Participant>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, short_id, first_name, last_name, gender, birthday, group_id
+                FROM participants
+                WHERE group_id = ?;
+            "#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of participants in group from database")
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::records::{self, Record, RecordStatus};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl records::Repository for Repository {
+    async fn create_record(&self, record: &Record) -> Result<()> {
+        sqlx::query(
+            r#"
+                INSERT INTO records (
+                    id, kind, gender, stroke, distance, course, age_class, time_millis,
+                    first_name, last_name, birthday, swum_on, meet, registration_id, status,
+                    created_at, decided_at, superseded_by
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                );
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.kind)
+        .bind(record.gender)
+        .bind(record.stroke)
+        .bind(record.distance)
+        .bind(record.course)
+        .bind(record.age_class)
+        .bind(record.time_millis)
+        .bind(&record.first_name)
+        .bind(&record.last_name)
+        .bind(record.birthday)
+        .bind(record.swum_on)
+        .bind(&record.meet)
+        .bind(record.registration_id)
+        .bind(record.status)
+        .bind(record.created_at)
+        .bind(record.decided_at)
+        .bind(record.superseded_by)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert record into database")?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self) -> Result<Vec<Record>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, kind, gender, stroke, distance, course, age_class, time_millis,
+                    first_name, last_name, birthday, swum_on, meet, registration_id, status,
+                    created_at, decided_at, superseded_by
+                FROM records
+                ORDER BY created_at, id;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch records from database")
+    }
+
+    async fn record_by_id(&self, record_id: Uuid) -> Result<Option<Record>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, kind, gender, stroke, distance, course, age_class, time_millis,
+                    first_name, last_name, birthday, swum_on, meet, registration_id, status,
+                    created_at, decided_at, superseded_by
+                FROM records
+                WHERE id = ?;
+            "#,
+        )
+        .bind(record_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch record from database")
+    }
+
+    async fn update_record_status(
+        &self,
+        record_id: Uuid,
+        status: RecordStatus,
+        decided_at: DateTime<Utc>,
+        superseded_by: Option<Uuid>,
+    ) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                UPDATE records SET
+                    status = ?, decided_at = ?, superseded_by = ?
+                WHERE id = ?;
+            "#,
+        )
+        .bind(status)
+        .bind(decided_at)
+        .bind(superseded_by)
+        .bind(record_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update status of record in database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
@@ -0,0 +1,235 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::registrations::{self, Registration, RegistrationResult};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl registrations::Repository for Repository {
+    async fn registrations_of_participant(
+        &self,
+        participant_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE participant_id = ?;
+            "#,
+        )
+        .bind(participant_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all registrations for participant from database")
+    }
+
+    async fn result_for_registration(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<RegistrationResult>> {
+        // SQLite has no POWER() by default, the ratio is cubed by hand instead.
+        sqlx::query_as(
+            r#"
+                SELECT
+                    registration_id,
+                    disqualified,
+                    time_millis,
+                    CAST(1000 * ratio * ratio * ratio AS INTEGER) AS fina_points
+                FROM (
+                    SELECT
+                        rr.registration_id,
+                        rr.disqualified,
+                        rr.time_millis,
+                        CAST(c.target_time AS REAL) / CAST(rr.time_millis AS REAL) AS ratio
+                    FROM registration_results rr
+                    INNER JOIN registrations r ON r.id = rr.registration_id
+                    INNER JOIN competitions c ON c.id = r.competition_id
+                    WHERE rr.registration_id = ?
+                );
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registration result rom database")
+    }
+
+    async fn delete_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM registrations
+                WHERE id = ?
+            "#,
+        )
+        .bind(registration_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete registration in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_result_for_registration(&self, registration_id: Uuid) -> Result<Option<()>> {
+        let rows = sqlx::query(
+            r#"
+                DELETE FROM registration_results
+                WHERE registration_id = ?
+            "#,
+        )
+        .bind(registration_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete registration result in database")?
+        .rows_affected();
+
+        if rows > 0 {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn create_registration(
+        &self,
+        participant_id: Uuid,
+        competition_id: Uuid,
+        entry_time_millis: Option<i32>,
+        late_entry: bool,
+    ) -> Result<Uuid> {
+        let existing_registration_id = sqlx::query_scalar(
+            r#"
+                SELECT
+                    id
+                FROM registrations
+                WHERE
+                    participant_id = ? AND
+                    competition_id = ?;
+            "#,
+        )
+        .bind(participant_id)
+        .bind(competition_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to look for existing registration in database")?;
+
+        if let Some(registration_id) = existing_registration_id {
+            return Ok(registration_id);
+        }
+
+        let registration_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+                INSERT INTO registrations (
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                ) VALUES (
+                    ?, ?, ?, ?, (SELECT COALESCE(MAX(card_number), 0) + 1 FROM registrations), ?
+                );
+            "#,
+        )
+        .bind(registration_id)
+        .bind(participant_id)
+        .bind(competition_id)
+        .bind(entry_time_millis)
+        .bind(late_entry)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert registration in database")?;
+
+        Ok(registration_id)
+    }
+
+    async fn registration_by_card_number(&self, card_number: i32) -> Result<Option<Registration>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE card_number = ?;
+            "#,
+        )
+        .bind(card_number)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registration by card number from database")
+    }
+
+    async fn registration_by_id(&self, registration_id: Uuid) -> Result<Option<Registration>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE id = ?;
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch registrations by id from database")
+    }
+
+    async fn create_registration_result(
+        &self,
+        registration_id: Uuid,
+        time_millis: i32,
+        disqualified: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+                INSERT INTO registration_results (
+                    registration_id, time_millis, disqualified
+                ) VALUES (
+                    ?, ?, ?
+                );
+            "#,
+        )
+        .bind(registration_id)
+        .bind(time_millis)
+        .bind(disqualified)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert registration in database")?;
+
+        Ok(())
+    }
+
+    async fn registrations_for_competition(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<Vec<Registration>> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id, participant_id, competition_id, entry_time_millis, card_number,
+                    late_entry
+                FROM registrations
+                WHERE competition_id = ?;
+            "#,
+        )
+        .bind(competition_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch list of all registrations for competition from database")
+    }
+}
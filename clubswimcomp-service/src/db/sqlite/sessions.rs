@@ -0,0 +1,210 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::sessions::{self, Session};
+
+#[derive(Clone)]
+pub struct Repository {
+    pool: super::DatabasePool,
+}
+
+impl Repository {
+    pub fn new(pool: super::DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Store the competitions of the session in their order.
+async fn insert_competitions(
+    tx: &mut sqlx::Transaction<'_, super::Database>,
+    session: &Session,
+) -> Result<()> {
+    for (position, competition_id) in session.competition_ids.iter().enumerate() {
+        sqlx::query(
+            r#"
+                INSERT INTO session_competitions (
+                    session_id, competition_id, position
+                ) VALUES (
+                    ?, ?, ?
+                );
+            "#,
+        )
+        .bind(session.id)
+        .bind(competition_id)
+        .bind(position as i32)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to insert competition of session into database")?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl sessions::Repository for Repository {
+    async fn create_session(&self, session: &Session) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO sessions (
+                    id, name, starts_at, warm_up_minutes, turnaround_seconds
+                ) VALUES (
+                    ?, ?, ?, ?, ?
+                );
+            "#,
+        )
+        .bind(session.id)
+        .bind(&session.name)
+        .bind(session.starts_at)
+        .bind(session.warm_up_minutes)
+        .bind(session.turnaround_seconds)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert session into database")?;
+
+        insert_competitions(&mut tx, session).await?;
+
+        tx.commit().await.context("Failed to commit transaction")
+    }
+
+    async fn all_sessions(&self) -> Result<Vec<Session>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let mut sessions: Vec<Session> = sqlx::query_as(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                ORDER BY starts_at, id;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch sessions from database")?;
+
+        let competitions: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+                SELECT session_id, competition_id
+                FROM session_competitions
+                ORDER BY session_id, position;
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch competitions of sessions from database")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        for session in sessions.iter_mut() {
+            session.competition_ids = competitions
+                .iter()
+                .filter(|(session_id, _)| *session_id == session.id)
+                .map(|(_, competition_id)| *competition_id)
+                .collect();
+        }
+
+        Ok(sessions)
+    }
+
+    async fn session_by_id(&self, session_id: Uuid) -> Result<Option<Session>> {
+        let session: Option<Session> = sqlx::query_as(
+            r#"
+                SELECT id, name, starts_at, warm_up_minutes, turnaround_seconds
+                FROM sessions
+                WHERE id = ?;
+            "#,
+        )
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch session from database")?;
+
+        let Some(mut session) = session else {
+            return Ok(None);
+        };
+
+        session.competition_ids = sqlx::query_scalar(
+            r#"
+                SELECT competition_id
+                FROM session_competitions
+                WHERE session_id = ?
+                ORDER BY position;
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch competitions of session from database")?;
+
+        Ok(Some(session))
+    }
+
+    async fn update_session(&self, session: &Session) -> Result<Option<()>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = sqlx::query(
+            r#"
+                UPDATE sessions SET
+                    name = ?,
+                    starts_at = ?,
+                    warm_up_minutes = ?,
+                    turnaround_seconds = ?
+                WHERE id = ?;
+            "#,
+        )
+        .bind(&session.name)
+        .bind(session.starts_at)
+        .bind(session.warm_up_minutes)
+        .bind(session.turnaround_seconds)
+        .bind(session.id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update session in database")?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query(
+            r#"
+                DELETE FROM session_competitions WHERE session_id = ?;
+            "#,
+        )
+        .bind(session.id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete competitions of session from database")?;
+
+        insert_competitions(&mut tx, session).await?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(Some(()))
+    }
+
+    async fn delete_session(&self, session_id: Uuid) -> Result<Option<()>> {
+        let result = sqlx::query(
+            r#"
+                DELETE FROM sessions WHERE id = ?;
+            "#,
+        )
+        .bind(session_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete session from database")?;
+
+        Ok((result.rows_affected() > 0).then_some(()))
+    }
+}
@@ -0,0 +1,1743 @@
+//! Test suite for the repository implementations.
+//!
+//! Every test is written once against [`Repositories`] and then run against
+//! each of the database backends. The PostgreSQL tests use the server behind
+//! `DATABASE_URL`, the SQLite tests use temporary database files. The
+//! in-memory fakes run the same suite to ensure they behave like the real
+//! thing.
+
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use uuid::Uuid;
+
+use super::assets::Asset;
+use super::backups::{Snapshot, StoredResult};
+use super::best_times::BestTime;
+use super::competitions::{Competition, CompetitionRound, Eligibility};
+use super::event::{
+    Event, EventPhase, GroupScoring, GroupTieBreak, PhaseChange, SharedRanks, TimePrecision,
+};
+use super::finals::{Finals, FinalsEntry, FinalsEntryStatus};
+use super::groups::Group;
+use super::participants::Participant;
+use super::payments::Payment;
+use super::records::{Record, RecordKind, RecordStatus};
+use super::registrations::Registration;
+use super::sessions::Session;
+use super::{Course, Gender, Repositories, Stroke};
+
+macro_rules! backend_tests {
+    ($($test:ident),* $(,)?) => {
+        mod postgres {
+            $(
+                #[sqlx::test]
+                async fn $test(pool: sqlx::PgPool) {
+                    super::$test(super::Repositories::postgres(pool)).await;
+                }
+            )*
+        }
+
+        mod sqlite {
+            $(
+                #[sqlx::test(migrations = "./migrations-sqlite")]
+                async fn $test(pool: sqlx::SqlitePool) {
+                    super::$test(super::Repositories::sqlite(pool)).await;
+                }
+            )*
+        }
+
+        mod memory {
+            $(
+                #[tokio::test]
+                async fn $test() {
+                    super::$test(super::Repositories::in_memory()).await;
+                }
+            )*
+        }
+    };
+}
+
+backend_tests!(
+    groups_can_be_created_and_deleted,
+    participants_can_be_created_and_deleted,
+    participants_get_unique_short_ids,
+    participants_are_listed_by_group,
+    competitions_can_be_searched,
+    competitions_can_be_deleted,
+    relays_are_another_kind_of_competition,
+    eligibility_is_stored_with_competition,
+    registrations_are_created_once,
+    registrations_get_sequential_card_numbers,
+    registration_results_have_fina_points,
+    registrations_can_be_deleted,
+    assets_can_be_replaced_and_deleted,
+    event_is_only_created_once,
+    group_scoring_is_stored_with_event,
+    ranking_rules_are_stored_with_event,
+    team_scoring_is_stored_with_event,
+    course_is_stored_with_event,
+    registration_limits_are_stored_with_event,
+    phase_changes_are_stored_with_event,
+    entry_fees_are_stored_with_event,
+    info_is_stored_with_event,
+    best_times_are_stored_once_per_swim,
+    recorded_best_times_belong_to_their_result,
+    records_keep_their_status_history,
+    sessions_keep_their_competition_order,
+    finals_keep_their_entries_and_swim_offs,
+    payers_with_payments_can_not_be_deleted,
+    snapshots_can_be_imported_and_exported,
+    snapshot_imports_are_atomic,
+);
+
+async fn create_participant(repos: &Repositories, group_id: Uuid, gender: Gender) -> Uuid {
+    repos
+        .participants
+        .create_participant(
+            "Erika",
+            "Mustermann",
+            gender,
+            NaiveDate::from_ymd_opt(2012, 4, 1).unwrap(),
+            group_id,
+        )
+        .await
+        .unwrap()
+}
+
+async fn groups_can_be_created_and_deleted(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+
+    let group = repos.groups.group_by_id(group_id).await.unwrap().unwrap();
+    assert_eq!(group.name, "Dolphins");
+    assert_eq!(repos.groups.all_groups().await.unwrap(), vec![group]);
+
+    assert_eq!(repos.groups.delete_group(group_id).await.unwrap(), Some(()));
+    assert_eq!(repos.groups.delete_group(group_id).await.unwrap(), None);
+    assert!(repos.groups.group_by_id(group_id).await.unwrap().is_none());
+}
+
+async fn participants_can_be_created_and_deleted(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+
+    let participant = repos
+        .participants
+        .participant_by_id(participant_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(participant.id, participant_id);
+    assert_eq!(participant.first_name, "Erika");
+    assert_eq!(participant.last_name, "Mustermann");
+    assert_eq!(participant.gender, Gender::Female);
+    assert_eq!(
+        participant.birthday,
+        NaiveDate::from_ymd_opt(2012, 4, 1).unwrap()
+    );
+    assert_eq!(participant.group_id, group_id);
+    assert!((1000..=9998).contains(&participant.short_id));
+
+    assert_eq!(
+        repos
+            .participants
+            .participant_by_short_code(participant.short_id)
+            .await
+            .unwrap(),
+        Some(participant.clone())
+    );
+
+    let short_id = participant.short_id;
+    let participants = repos.participants.list_participants().await.unwrap();
+    assert_eq!(participants, vec![participant]);
+
+    assert!(repos
+        .participants
+        .delete_participant(participant_id)
+        .await
+        .unwrap());
+    assert!(!repos
+        .participants
+        .delete_participant(participant_id)
+        .await
+        .unwrap());
+    assert!(repos
+        .participants
+        .participant_by_id(participant_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repos
+        .participants
+        .participant_by_short_code(short_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn participants_get_unique_short_ids(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    for _ in 0..50 {
+        create_participant(&repos, group_id, Gender::Male).await;
+    }
+
+    let short_ids = repos
+        .participants
+        .list_participants()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.short_id)
+        .collect::<HashSet<_>>();
+    assert_eq!(short_ids.len(), 50);
+}
+
+async fn participants_are_listed_by_group(repos: Repositories) {
+    let dolphins = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let sharks = repos
+        .groups
+        .create_group("Sharks".to_string())
+        .await
+        .unwrap();
+    let dolphin = create_participant(&repos, dolphins, Gender::Female).await;
+    let shark = create_participant(&repos, sharks, Gender::Male).await;
+
+    let in_group = |group_id| {
+        let repos = repos.clone();
+        async move {
+            repos
+                .participants
+                .list_participants_in_group(group_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|p| p.id)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(in_group(dolphins).await, vec![dolphin]);
+    assert_eq!(in_group(sharks).await, vec![shark]);
+    assert!(in_group(Uuid::new_v4()).await.is_empty());
+}
+
+async fn competitions_can_be_searched(repos: Repositories) {
+    let female_back = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Back, 50, 40_000, false)
+        .await
+        .unwrap();
+    let male_back = repos
+        .competitions
+        .create_competition(Gender::Male, Stroke::Back, 50, 38_000, false)
+        .await
+        .unwrap();
+    let male_breast = repos
+        .competitions
+        .create_competition(Gender::Male, Stroke::Breast, 25, 20_000, false)
+        .await
+        .unwrap();
+
+    let search = |gender, stroke, distance| {
+        let repos = repos.clone();
+        async move {
+            let mut ids = repos
+                .competitions
+                .search_competition(gender, stroke, distance)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        }
+    };
+    let sorted = |mut ids: Vec<Uuid>| {
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(
+        search(None, None, None).await,
+        sorted(vec![female_back, male_back, male_breast])
+    );
+    assert_eq!(
+        search(Some(Gender::Male), None, None).await,
+        sorted(vec![male_back, male_breast])
+    );
+    assert_eq!(
+        search(None, Some(Stroke::Back), None).await,
+        sorted(vec![female_back, male_back])
+    );
+    assert_eq!(search(None, None, Some(25)).await, vec![male_breast]);
+    assert_eq!(
+        search(Some(Gender::Female), Some(Stroke::Back), Some(50)).await,
+        vec![female_back]
+    );
+    assert!(search(Some(Gender::Female), Some(Stroke::Breast), None)
+        .await
+        .is_empty());
+
+    let competition = repos
+        .competitions
+        .competition_by_id(male_breast)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(competition.gender, Gender::Male);
+    assert_eq!(competition.stroke, Stroke::Breast);
+    assert_eq!(competition.distance, 25);
+    assert_eq!(competition.target_time, 20_000);
+    assert_eq!(
+        repos.competitions.all_competitions().await.unwrap().len(),
+        3
+    );
+}
+
+async fn relays_are_another_kind_of_competition(repos: Repositories) {
+    let single = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 50, 30_000, false)
+        .await
+        .unwrap();
+    let relay = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 50, 30_000, true)
+        .await
+        .unwrap();
+    assert!(repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 50, 30_000, true)
+        .await
+        .is_err());
+
+    let is_relay = |competition_id| {
+        let repos = repos.clone();
+        async move {
+            repos
+                .competitions
+                .competition_by_id(competition_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .relay
+        }
+    };
+    assert!(!is_relay(single).await);
+    assert!(is_relay(relay).await);
+}
+
+async fn competitions_can_be_deleted(repos: Repositories) {
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 100, 80_000, false)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repos
+            .competitions
+            .delete_competition(competition_id)
+            .await
+            .unwrap(),
+        Some(())
+    );
+    assert_eq!(
+        repos
+            .competitions
+            .delete_competition(competition_id)
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(repos
+        .competitions
+        .competition_by_id(competition_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn eligibility_is_stored_with_competition(repos: Repositories) {
+    let dolphins = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let sharks = repos
+        .groups
+        .create_group("Sharks".to_string())
+        .await
+        .unwrap();
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 100, 80_000, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        repos
+            .competitions
+            .eligibility_of_competition(competition_id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let mut group_ids = vec![dolphins, sharks];
+    group_ids.sort();
+    let eligibility = Eligibility {
+        competition_id,
+        min_age: Some(10),
+        max_age: Some(12),
+        min_birth_year: None,
+        max_birth_year: Some(2016),
+        qualifying_time: Some(90_000),
+        group_ids,
+    };
+    repos
+        .competitions
+        .set_eligibility(&eligibility)
+        .await
+        .unwrap();
+    assert_eq!(
+        repos
+            .competitions
+            .eligibility_of_competition(competition_id)
+            .await
+            .unwrap(),
+        Some(eligibility.clone())
+    );
+
+    // Replacing the eligibility replaces the groups, deleted groups are dropped
+    repos
+        .competitions
+        .set_eligibility(&Eligibility {
+            min_age: None,
+            group_ids: vec![dolphins],
+            ..eligibility.clone()
+        })
+        .await
+        .unwrap();
+    repos.groups.delete_group(dolphins).await.unwrap().unwrap();
+    assert_eq!(
+        repos.competitions.all_eligibilities().await.unwrap(),
+        vec![Eligibility {
+            min_age: None,
+            group_ids: vec![],
+            ..eligibility
+        }]
+    );
+
+    assert_eq!(
+        repos
+            .competitions
+            .delete_eligibility(competition_id)
+            .await
+            .unwrap(),
+        Some(())
+    );
+    assert_eq!(
+        repos
+            .competitions
+            .delete_eligibility(competition_id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    // The eligibility is deleted with the competition
+    repos
+        .competitions
+        .set_eligibility(&Eligibility {
+            competition_id,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    repos
+        .competitions
+        .delete_competition(competition_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(repos
+        .competitions
+        .all_eligibilities()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn registrations_are_created_once(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Butterfly, 25, 20_000, false)
+        .await
+        .unwrap();
+
+    let registration_id = repos
+        .registrations
+        .create_registration(participant_id, competition_id, None, false)
+        .await
+        .unwrap();
+    let again = repos
+        .registrations
+        .create_registration(participant_id, competition_id, None, false)
+        .await
+        .unwrap();
+    assert_eq!(registration_id, again);
+
+    let registration = repos
+        .registrations
+        .registration_by_id(registration_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registration.participant_id, participant_id);
+    assert_eq!(registration.competition_id, competition_id);
+
+    let of_participant = repos
+        .registrations
+        .registrations_of_participant(participant_id)
+        .await
+        .unwrap();
+    assert_eq!(of_participant, vec![registration.clone()]);
+
+    let for_competition = repos
+        .registrations
+        .registrations_for_competition(competition_id)
+        .await
+        .unwrap();
+    assert_eq!(for_competition, vec![registration]);
+}
+
+async fn registrations_get_sequential_card_numbers(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+    let mut registration_ids = Vec::new();
+    for stroke in [Stroke::Butterfly, Stroke::Back, Stroke::Breast] {
+        let competition_id = repos
+            .competitions
+            .create_competition(Gender::Female, stroke, 25, 20_000, false)
+            .await
+            .unwrap();
+        let registration_id = repos
+            .registrations
+            .create_registration(participant_id, competition_id, None, false)
+            .await
+            .unwrap();
+        registration_ids.push(registration_id);
+    }
+
+    for (card_number, registration_id) in (1..).zip(&registration_ids) {
+        let registration = repos
+            .registrations
+            .registration_by_card_number(card_number)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(registration.id, *registration_id);
+        assert_eq!(registration.card_number, card_number);
+    }
+    assert!(repos
+        .registrations
+        .registration_by_card_number(4)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Numbers of deleted registrations are not handed out again while later
+    // cards exist
+    repos
+        .registrations
+        .delete_registration(registration_ids[1])
+        .await
+        .unwrap();
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Freestyle, 25, 20_000, false)
+        .await
+        .unwrap();
+    let registration_id = repos
+        .registrations
+        .create_registration(participant_id, competition_id, None, false)
+        .await
+        .unwrap();
+    let registration = repos
+        .registrations
+        .registration_by_id(registration_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registration.card_number, 4);
+}
+
+async fn registration_results_have_fina_points(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let first = create_participant(&repos, group_id, Gender::Male).await;
+    let second = create_participant(&repos, group_id, Gender::Male).await;
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Male, Stroke::Freestyle, 50, 30_000, false)
+        .await
+        .unwrap();
+
+    let on_target = repos
+        .registrations
+        .create_registration(first, competition_id, None, false)
+        .await
+        .unwrap();
+    let twice_as_slow = repos
+        .registrations
+        .create_registration(second, competition_id, None, false)
+        .await
+        .unwrap();
+    assert!(repos
+        .registrations
+        .result_for_registration(on_target)
+        .await
+        .unwrap()
+        .is_none());
+
+    repos
+        .registrations
+        .create_registration_result(on_target, 30_000, false)
+        .await
+        .unwrap();
+    repos
+        .registrations
+        .create_registration_result(twice_as_slow, 60_000, true)
+        .await
+        .unwrap();
+
+    let result = repos
+        .registrations
+        .result_for_registration(on_target)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.registration_id, on_target);
+    assert!(!result.disqualified);
+    assert_eq!(result.time_millis, 30_000);
+    assert_eq!(result.fina_points, 1000);
+
+    let result = repos
+        .registrations
+        .result_for_registration(twice_as_slow)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(result.disqualified);
+    assert_eq!(result.time_millis, 60_000);
+    assert_eq!(result.fina_points, 125);
+}
+
+async fn registrations_can_be_deleted(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Breast, 50, 45_000, false)
+        .await
+        .unwrap();
+    let registration_id = repos
+        .registrations
+        .create_registration(participant_id, competition_id, None, false)
+        .await
+        .unwrap();
+    repos
+        .registrations
+        .create_registration_result(registration_id, 50_000, false)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repos
+            .registrations
+            .delete_result_for_registration(registration_id)
+            .await
+            .unwrap(),
+        Some(())
+    );
+    assert_eq!(
+        repos
+            .registrations
+            .delete_result_for_registration(registration_id)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        repos
+            .registrations
+            .delete_registration(registration_id)
+            .await
+            .unwrap(),
+        Some(())
+    );
+    assert_eq!(
+        repos
+            .registrations
+            .delete_registration(registration_id)
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(repos
+        .registrations
+        .registrations_of_participant(participant_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn assets_can_be_replaced_and_deleted(repos: Repositories) {
+    repos
+        .assets
+        .upsert_asset("logo.svg", b"<svg/>")
+        .await
+        .unwrap();
+    repos
+        .assets
+        .upsert_asset("labels.typst", b"old")
+        .await
+        .unwrap();
+    repos
+        .assets
+        .upsert_asset("labels.typst", b"new")
+        .await
+        .unwrap();
+
+    let assets = repos.assets.all_assets().await.unwrap();
+    let names = assets.iter().map(|a| a.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["labels.typst", "logo.svg"]);
+    assert_eq!(assets[0].content, b"new");
+
+    assert_eq!(
+        repos.assets.delete_asset("labels.typst").await.unwrap(),
+        Some(())
+    );
+    assert_eq!(
+        repos.assets.delete_asset("labels.typst").await.unwrap(),
+        None
+    );
+    assert_eq!(repos.assets.all_assets().await.unwrap().len(), 1);
+}
+
+fn event() -> Event {
+    Event {
+        id: Uuid::new_v4(),
+        card_secret: Uuid::new_v4().as_bytes().to_vec(),
+        created_at: Utc.with_ymd_and_hms(2026, 10, 18, 14, 0, 0).unwrap(),
+        group_scoring: GroupScoring::BestResults,
+        scoring_result_count: Some(2),
+        min_starts: 1,
+        shared_ranks: SharedRanks::Dense,
+        time_precision: TimePrecision::Thousandths,
+        group_tie_break: GroupTieBreak::BestSingleResult,
+        team_placing_points: "7,5,4,3,2,1".to_string(),
+        team_max_scorers: Some(3),
+        team_relay_weight: 3,
+        max_individual_starts: Some(4),
+        course: Course::Long,
+        phase: EventPhase::RegistrationOpen,
+        participant_fee_cents: 500,
+        start_fee_cents: 350,
+        late_entry_surcharge_cents: 200,
+        currency: "EUR".to_string(),
+        name: "Autumn Cup".to_string(),
+        organization: "SC Example".to_string(),
+    }
+}
+
+async fn event_is_only_created_once(repos: Repositories) {
+    assert_eq!(repos.event.event().await.unwrap(), None);
+    let first = event();
+
+    assert_eq!(repos.event.init_event(&first).await.unwrap(), first);
+    assert_eq!(repos.event.init_event(&event()).await.unwrap(), first);
+
+    assert_eq!(repos.event.event().await.unwrap(), Some(first));
+    assert!(repos.backups.is_empty().await.unwrap());
+}
+
+async fn group_scoring_is_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_group_scoring(GroupScoring::Average, None, 3)
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_group_scoring(GroupScoring::OnePerStroke, None, 2)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            group_scoring: GroupScoring::OnePerStroke,
+            scoring_result_count: None,
+            min_starts: 2,
+            ..event
+        })
+    );
+}
+
+async fn ranking_rules_are_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_ranking_rules(
+                SharedRanks::Skip,
+                TimePrecision::Hundredths,
+                GroupTieBreak::None
+            )
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_ranking_rules(
+            SharedRanks::Skip,
+            TimePrecision::Hundredths,
+            GroupTieBreak::None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            shared_ranks: SharedRanks::Skip,
+            time_precision: TimePrecision::Hundredths,
+            group_tie_break: GroupTieBreak::None,
+            ..event
+        })
+    );
+}
+
+async fn team_scoring_is_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_team_scoring("3,2,1", None, 2)
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_team_scoring("3,2,1", None, 2)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            team_placing_points: "3,2,1".to_string(),
+            team_max_scorers: None,
+            team_relay_weight: 2,
+            ..event
+        })
+    );
+}
+
+async fn course_is_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos.event.update_course(Course::Short).await.unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_course(Course::Short)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            course: Course::Short,
+            ..event
+        })
+    );
+}
+
+async fn registration_limits_are_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_registration_limits(Some(2))
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_registration_limits(None)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            max_individual_starts: None,
+            ..event
+        })
+    );
+}
+
+fn phase_change(phase: EventPhase, hour: u32, reason: Option<&str>) -> PhaseChange {
+    PhaseChange {
+        id: Uuid::new_v4(),
+        phase,
+        changed_at: Utc.with_ymd_and_hms(2026, 10, 18, hour, 0, 0).unwrap(),
+        reason: reason.map(str::to_string),
+    }
+}
+
+async fn phase_changes_are_stored_with_event(repos: Repositories) {
+    let closed = phase_change(EventPhase::RegistrationClosed, 12, None);
+    assert_eq!(repos.event.update_phase(&closed).await.unwrap(), None);
+    assert!(repos.event.phase_changes().await.unwrap().is_empty());
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    let reopened = phase_change(EventPhase::MeetInProgress, 19, Some("Wrong time"));
+    repos.event.update_phase(&reopened).await.unwrap().unwrap();
+    repos.event.update_phase(&closed).await.unwrap().unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            phase: EventPhase::RegistrationClosed,
+            ..event
+        })
+    );
+    assert_eq!(
+        repos.event.phase_changes().await.unwrap(),
+        vec![closed, reopened]
+    );
+}
+
+async fn entry_fees_are_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_entry_fees(0, 400, 0, "CHF")
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_entry_fees(0, 400, 0, "CHF")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            participant_fee_cents: 0,
+            start_fee_cents: 400,
+            late_entry_surcharge_cents: 0,
+            currency: "CHF".to_string(),
+            ..event
+        })
+    );
+}
+
+async fn info_is_stored_with_event(repos: Repositories) {
+    assert_eq!(
+        repos
+            .event
+            .update_info("Spring Cup", "SV Example")
+            .await
+            .unwrap(),
+        None
+    );
+    let event = repos.event.init_event(&event()).await.unwrap();
+
+    repos
+        .event
+        .update_info("Spring Cup", "SV Example")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        repos.event.event().await.unwrap(),
+        Some(Event {
+            name: "Spring Cup".to_string(),
+            organization: "SV Example".to_string(),
+            ..event
+        })
+    );
+}
+
+fn best_time(last_name: &str, swum_on: NaiveDate, time_millis: i32) -> BestTime {
+    BestTime {
+        id: Uuid::new_v4(),
+        first_name: "Kim".to_string(),
+        last_name: last_name.to_string(),
+        birthday: NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+        stroke: Stroke::Back,
+        distance: 50,
+        course: Course::Short,
+        time_millis,
+        swum_on,
+        meet: "Autumn Cup".to_string(),
+        registration_id: None,
+    }
+}
+
+async fn best_times_are_stored_once_per_swim(repos: Repositories) {
+    let spring = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+    let autumn = NaiveDate::from_ymd_opt(2025, 11, 8).unwrap();
+    let later = best_time("Swimmer", spring, 41_000);
+    let earlier = best_time("Swimmer", autumn, 43_000);
+    let other_swimmer = best_time("Diver", autumn, 39_000);
+
+    assert_eq!(
+        repos
+            .best_times
+            .insert_best_times(&[later.clone(), earlier.clone(), other_swimmer])
+            .await
+            .unwrap(),
+        3
+    );
+    // The same swim imported again is skipped, even with another time
+    assert_eq!(
+        repos
+            .best_times
+            .insert_best_times(&[best_time("Swimmer", spring, 40_000)])
+            .await
+            .unwrap(),
+        0
+    );
+
+    assert_eq!(
+        repos
+            .best_times
+            .best_times_of_swimmer("Kim", "Swimmer", later.birthday)
+            .await
+            .unwrap(),
+        vec![earlier, later]
+    );
+    assert!(!repos.backups.is_empty().await.unwrap());
+}
+
+async fn recorded_best_times_belong_to_their_result(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+    let prelims_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Back, 50, 40_000, false)
+        .await
+        .unwrap();
+    let finals_id = repos
+        .competitions
+        .create_competition(Gender::Male, Stroke::Back, 50, 40_000, false)
+        .await
+        .unwrap();
+    let mut recorded = Vec::new();
+    for (competition_id, time_millis) in [(prelims_id, 42_000), (finals_id, 41_000)] {
+        let registration_id = repos
+            .registrations
+            .create_registration(participant_id, competition_id, None, false)
+            .await
+            .unwrap();
+        repos
+            .registrations
+            .create_registration_result(registration_id, time_millis, false)
+            .await
+            .unwrap();
+        recorded.push(BestTime {
+            registration_id: Some(registration_id),
+            ..best_time(
+                "Swimmer",
+                NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+                time_millis,
+            )
+        });
+    }
+
+    // Both races of the day are kept, a result recorded again replaces its time
+    for best_time in recorded.iter() {
+        repos.best_times.record_best_time(best_time).await.unwrap();
+    }
+    let slower_prelims = BestTime {
+        id: Uuid::new_v4(),
+        time_millis: 43_000,
+        ..recorded[0].clone()
+    };
+    repos
+        .best_times
+        .record_best_time(&slower_prelims)
+        .await
+        .unwrap();
+    let mut best_times = repos
+        .best_times
+        .best_times_of_swimmer("Kim", "Swimmer", recorded[0].birthday)
+        .await
+        .unwrap();
+    best_times.sort_by_key(|b| b.time_millis);
+    assert_eq!(
+        best_times,
+        vec![
+            recorded[1].clone(),
+            BestTime {
+                time_millis: 43_000,
+                ..recorded[0].clone()
+            }
+        ]
+    );
+
+    // The time is removed with its result
+    repos
+        .registrations
+        .delete_result_for_registration(recorded[1].registration_id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let best_times = repos
+        .best_times
+        .best_times_of_swimmer("Kim", "Swimmer", recorded[0].birthday)
+        .await
+        .unwrap();
+    assert_eq!(
+        best_times,
+        vec![BestTime {
+            time_millis: 43_000,
+            ..recorded[0].clone()
+        }]
+    );
+}
+
+fn record(time_millis: i32, status: RecordStatus, hour: u32) -> Record {
+    Record {
+        id: Uuid::new_v4(),
+        kind: RecordKind::Club,
+        gender: Gender::Female,
+        stroke: Stroke::Back,
+        distance: 50,
+        course: Course::Short,
+        age_class: 12,
+        time_millis,
+        first_name: "Kim".to_string(),
+        last_name: "Swimmer".to_string(),
+        birthday: NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+        swum_on: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+        meet: None,
+        registration_id: Some(Uuid::new_v4()),
+        status,
+        created_at: Utc.with_ymd_and_hms(2026, 10, 18, hour, 0, 0).unwrap(),
+        decided_at: None,
+        superseded_by: None,
+    }
+}
+
+async fn records_keep_their_status_history(repos: Repositories) {
+    let former = Record {
+        meet: Some("Club Championships 2019".to_string()),
+        registration_id: None,
+        decided_at: Some(Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap()),
+        ..record(42_000, RecordStatus::Approved, 9)
+    };
+    let new = record(41_000, RecordStatus::Pending, 10);
+    repos.records.create_record(&new).await.unwrap();
+    repos.records.create_record(&former).await.unwrap();
+    assert_eq!(
+        repos.records.list_records().await.unwrap(),
+        vec![former.clone(), new.clone()]
+    );
+
+    let decided_at = Utc.with_ymd_and_hms(2026, 10, 18, 11, 0, 0).unwrap();
+    repos
+        .records
+        .update_record_status(new.id, RecordStatus::Approved, decided_at, None)
+        .await
+        .unwrap()
+        .unwrap();
+    repos
+        .records
+        .update_record_status(
+            former.id,
+            RecordStatus::Superseded,
+            decided_at,
+            Some(new.id),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        repos.records.record_by_id(former.id).await.unwrap(),
+        Some(Record {
+            status: RecordStatus::Superseded,
+            decided_at: Some(decided_at),
+            superseded_by: Some(new.id),
+            ..former
+        })
+    );
+    assert_eq!(
+        repos
+            .records
+            .record_by_id(new.id)
+            .await
+            .unwrap()
+            .map(|r| r.status),
+        Some(RecordStatus::Approved)
+    );
+
+    assert_eq!(
+        repos
+            .records
+            .update_record_status(Uuid::new_v4(), RecordStatus::Rejected, decided_at, None)
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(!repos.backups.is_empty().await.unwrap());
+}
+
+fn session(name: &str, hour: u32, competition_ids: Vec<Uuid>) -> Session {
+    Session {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        starts_at: starts_at(hour),
+        warm_up_minutes: 30,
+        turnaround_seconds: 45,
+        competition_ids,
+    }
+}
+
+fn starts_at(hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 10, 18)
+        .unwrap()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap()
+}
+
+async fn sessions_keep_their_competition_order(repos: Repositories) {
+    let mut competition_ids = Vec::new();
+    for distance in [50, 100, 200] {
+        competition_ids.push(
+            repos
+                .competitions
+                .create_competition(Gender::Female, Stroke::Freestyle, distance, 40_000, false)
+                .await
+                .unwrap(),
+        );
+    }
+    let afternoon = session(
+        "Afternoon",
+        14,
+        vec![competition_ids[2], competition_ids[0]],
+    );
+    let morning = session("Morning", 9, vec![competition_ids[1]]);
+    repos.sessions.create_session(&afternoon).await.unwrap();
+    repos.sessions.create_session(&morning).await.unwrap();
+
+    assert_eq!(
+        repos.sessions.all_sessions().await.unwrap(),
+        vec![morning.clone(), afternoon.clone()]
+    );
+
+    // A competition is swum in only one session
+    let taken = session("Evening", 18, vec![competition_ids[1]]);
+    assert!(repos.sessions.create_session(&taken).await.is_err());
+
+    let afternoon = Session {
+        starts_at: starts_at(15),
+        competition_ids: vec![competition_ids[0], competition_ids[2]],
+        ..afternoon
+    };
+    repos
+        .sessions
+        .update_session(&afternoon)
+        .await
+        .unwrap()
+        .unwrap();
+    repos
+        .competitions
+        .delete_competition(competition_ids[2])
+        .await
+        .unwrap();
+    assert_eq!(
+        repos.sessions.session_by_id(afternoon.id).await.unwrap(),
+        Some(Session {
+            competition_ids: vec![competition_ids[0]],
+            ..afternoon
+        })
+    );
+
+    repos
+        .sessions
+        .delete_session(morning.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        repos.sessions.session_by_id(morning.id).await.unwrap(),
+        None
+    );
+    assert_eq!(repos.sessions.update_session(&morning).await.unwrap(), None);
+    assert_eq!(
+        repos.sessions.delete_session(morning.id).await.unwrap(),
+        None
+    );
+}
+
+async fn finals_keep_their_entries_and_swim_offs(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_ids = [
+        create_participant(&repos, group_id, Gender::Female).await,
+        create_participant(&repos, group_id, Gender::Female).await,
+    ];
+    let competition_id = repos
+        .competitions
+        .create_competition(Gender::Female, Stroke::Breast, 100, 90_000, false)
+        .await
+        .unwrap();
+    let prelims = repos
+        .competitions
+        .competition_by_id(competition_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(prelims.round, CompetitionRound::TimedFinal);
+
+    let finals_competition = Competition {
+        id: Uuid::new_v4(),
+        round: CompetitionRound::Finals,
+        ..prelims.clone()
+    };
+    let mut finals = Finals {
+        competition_id,
+        finals_competition_id: finals_competition.id,
+        finalists: 1,
+        alternates: 0,
+        swim_off_ids: Vec::new(),
+    };
+    repos
+        .finals
+        .create_finals(&finals, &finals_competition)
+        .await
+        .unwrap();
+    assert!(repos
+        .finals
+        .create_finals(&finals, &finals_competition)
+        .await
+        .is_err());
+    assert_eq!(
+        repos
+            .competitions
+            .competition_by_id(competition_id)
+            .await
+            .unwrap()
+            .map(|c| c.round),
+        Some(CompetitionRound::Prelims)
+    );
+    assert_eq!(
+        repos
+            .competitions
+            .competition_by_id(finals_competition.id)
+            .await
+            .unwrap(),
+        Some(finals_competition.clone())
+    );
+
+    finals.alternates = 1;
+    repos.finals.update_finals(&finals).await.unwrap().unwrap();
+
+    // Both swim-offs are of the same kind as the prelims
+    let mut swim_off_ids = Vec::new();
+    for _ in 0..2 {
+        let swim_off = Competition {
+            id: Uuid::new_v4(),
+            round: CompetitionRound::SwimOff,
+            ..prelims.clone()
+        };
+        repos
+            .finals
+            .create_swim_off(competition_id, &swim_off)
+            .await
+            .unwrap();
+        swim_off_ids.push(swim_off.id);
+    }
+    assert_eq!(
+        repos
+            .finals
+            .finals_of_competition(competition_id)
+            .await
+            .unwrap(),
+        Some(Finals {
+            swim_off_ids,
+            ..finals.clone()
+        })
+    );
+
+    let entries: Vec<FinalsEntry> = participant_ids
+        .iter()
+        .enumerate()
+        .map(|(index, participant_id)| FinalsEntry {
+            competition_id,
+            participant_id: *participant_id,
+            seed: index as i32 + 1,
+            time_millis: 90_000 + index as i32,
+            status: FinalsEntryStatus::Finalist,
+        })
+        .collect();
+    let mut duplicate_seed = entries.clone();
+    duplicate_seed[1].seed = 1;
+    assert!(repos
+        .finals
+        .promote_finalists(finals_competition.id, &duplicate_seed)
+        .await
+        .is_err());
+    assert_eq!(
+        repos.finals.finals_entries(competition_id).await.unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        repos
+            .registrations
+            .registrations_for_competition(finals_competition.id)
+            .await
+            .unwrap(),
+        vec![]
+    );
+
+    repos
+        .finals
+        .promote_finalists(finals_competition.id, &entries)
+        .await
+        .unwrap();
+    let registrations = repos
+        .registrations
+        .registrations_for_competition(finals_competition.id)
+        .await
+        .unwrap();
+    assert_eq!(registrations.len(), entries.len());
+    assert!(entries
+        .iter()
+        .all(|e| registrations
+            .iter()
+            .any(|r| r.participant_id == e.participant_id
+                && r.entry_time_millis == Some(e.time_millis))));
+    repos
+        .finals
+        .update_finals_entry_status(
+            competition_id,
+            participant_ids[1],
+            FinalsEntryStatus::Scratched,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        repos
+            .finals
+            .update_finals_entry_status(
+                competition_id,
+                Uuid::new_v4(),
+                FinalsEntryStatus::Alternate
+            )
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        repos.finals.finals_entries(competition_id).await.unwrap(),
+        vec![
+            entries[0].clone(),
+            FinalsEntry {
+                status: FinalsEntryStatus::Scratched,
+                ..entries[1].clone()
+            },
+        ]
+    );
+
+    repos
+        .competitions
+        .delete_competition(competition_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        repos
+            .finals
+            .finals_of_competition(competition_id)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        repos.finals.finals_entries(competition_id).await.unwrap(),
+        vec![]
+    );
+}
+
+fn payment(
+    participant_id: Option<Uuid>,
+    group_id: Option<Uuid>,
+    amount_cents: i32,
+    day: u32,
+) -> Payment {
+    Payment {
+        id: Uuid::new_v4(),
+        participant_id,
+        group_id,
+        amount_cents,
+        received_on: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+        reference: format!("Transfer {day}"),
+    }
+}
+
+async fn payers_with_payments_can_not_be_deleted(repos: Repositories) {
+    let group_id = repos
+        .groups
+        .create_group("Dolphins".to_string())
+        .await
+        .unwrap();
+    let participant_id = create_participant(&repos, group_id, Gender::Female).await;
+    let late = payment(Some(participant_id), None, 850, 20);
+    let early = payment(None, Some(group_id), 2_000, 12);
+    repos.payments.create_payment(&late).await.unwrap();
+    repos.payments.create_payment(&early).await.unwrap();
+    let unknown = payment(Some(Uuid::new_v4()), None, 100, 1);
+    assert!(repos.payments.create_payment(&unknown).await.is_err());
+
+    assert_eq!(
+        repos.payments.all_payments().await.unwrap(),
+        vec![early.clone(), late.clone()]
+    );
+
+    assert!(repos
+        .participants
+        .delete_participant(participant_id)
+        .await
+        .is_err());
+    assert_eq!(
+        repos.payments.delete_payment(late.id).await.unwrap(),
+        Some(())
+    );
+    assert!(repos
+        .participants
+        .delete_participant(participant_id)
+        .await
+        .unwrap());
+
+    assert!(repos.groups.delete_group(group_id).await.is_err());
+    assert_eq!(
+        repos.payments.all_payments().await.unwrap(),
+        vec![early.clone()]
+    );
+
+    assert_eq!(
+        repos.payments.delete_payment(early.id).await.unwrap(),
+        Some(())
+    );
+    assert_eq!(repos.payments.delete_payment(early.id).await.unwrap(), None);
+    assert!(repos.payments.all_payments().await.unwrap().is_empty());
+    assert_eq!(repos.groups.delete_group(group_id).await.unwrap(), Some(()));
+}
+
+/// Snapshot with one entity of each kind, ordered by id.
+fn snapshot() -> Snapshot {
+    let group_id = Uuid::new_v4();
+    let participant_id = Uuid::new_v4();
+    let competition_id = Uuid::new_v4();
+    let finals_competition_id = Uuid::new_v4();
+    let swim_off_id = Uuid::new_v4();
+    let registration_id = Uuid::new_v4();
+
+    let mut competitions: Vec<Competition> = [
+        (competition_id, CompetitionRound::Prelims),
+        (finals_competition_id, CompetitionRound::Finals),
+        (swim_off_id, CompetitionRound::SwimOff),
+    ]
+    .into_iter()
+    .map(|(id, round)| Competition {
+        id,
+        gender: Gender::Female,
+        stroke: Stroke::Back,
+        distance: 50,
+        target_time: 40_000,
+        relay: false,
+        round,
+    })
+    .collect();
+    competitions.sort_by_key(|c| c.id);
+    let mut payments = vec![
+        payment(Some(participant_id), None, 850, 20),
+        payment(None, Some(group_id), 2_000, 12),
+    ];
+    payments.sort_by_key(|p| p.id);
+
+    Snapshot {
+        groups: vec![Group {
+            id: group_id,
+            name: "Dolphins".to_string(),
+        }],
+        participants: vec![Participant {
+            id: participant_id,
+            short_id: 4711,
+            first_name: "Kim".to_string(),
+            last_name: "Swimmer".to_string(),
+            gender: Gender::Female,
+            birthday: NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+            group_id,
+        }],
+        competitions,
+        eligibilities: vec![Eligibility {
+            competition_id,
+            min_age: Some(10),
+            max_age: None,
+            min_birth_year: Some(2012),
+            max_birth_year: None,
+            qualifying_time: Some(45_000),
+            group_ids: vec![group_id],
+        }],
+        registrations: vec![Registration {
+            id: registration_id,
+            participant_id,
+            competition_id,
+            entry_time_millis: Some(42_000),
+            card_number: 17,
+            late_entry: true,
+        }],
+        results: vec![StoredResult {
+            registration_id,
+            disqualified: false,
+            time_millis: 40_000,
+        }],
+        assets: vec![Asset {
+            name: "labels.typst".to_string(),
+            content: b"#let lang = \"en\"".to_vec(),
+            updated_at: Utc.with_ymd_and_hms(2026, 10, 18, 13, 0, 0).unwrap(),
+        }],
+        best_times: vec![best_time(
+            "Swimmer",
+            NaiveDate::from_ymd_opt(2025, 11, 8).unwrap(),
+            41_000,
+        )],
+        records: vec![record(40_000, RecordStatus::Pending, 14)],
+        phase_changes: vec![phase_change(EventPhase::RegistrationClosed, 12, None)],
+        sessions: vec![session("Morning", 9, vec![competition_id])],
+        finals: vec![Finals {
+            competition_id,
+            finals_competition_id,
+            finalists: 8,
+            alternates: 2,
+            swim_off_ids: vec![swim_off_id],
+        }],
+        finals_entries: vec![FinalsEntry {
+            competition_id,
+            participant_id,
+            seed: 1,
+            time_millis: 40_000,
+            status: FinalsEntryStatus::Finalist,
+        }],
+        payments,
+        event: Some(event()),
+    }
+}
+
+async fn snapshots_can_be_imported_and_exported(repos: Repositories) {
+    let snapshot = snapshot();
+    repos.event.init_event(&event()).await.unwrap();
+    assert!(repos.backups.is_empty().await.unwrap());
+
+    repos.backups.import_snapshot(&snapshot).await.unwrap();
+
+    assert!(!repos.backups.is_empty().await.unwrap());
+    assert_eq!(repos.backups.export_snapshot().await.unwrap(), snapshot);
+
+    let participant = repos
+        .participants
+        .participant_by_id(snapshot.participants[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(participant.short_id, 4711);
+    let result = repos
+        .registrations
+        .result_for_registration(snapshot.results[0].registration_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.fina_points, 1000);
+}
+
+async fn snapshot_imports_are_atomic(repos: Repositories) {
+    let mut snapshot = snapshot();
+    snapshot.registrations[0].participant_id = Uuid::new_v4();
+
+    assert!(repos.backups.import_snapshot(&snapshot).await.is_err());
+
+    assert!(repos.backups.is_empty().await.unwrap());
+}
@@ -0,0 +1,74 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::typst_compiler::{TypstCompiler, TypstOutput};
+
+/// Report of the balances that are still outstanding, for the treasurer to
+/// reconcile the payments. Amounts are formatted as they are printed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceReport {
+    pub event_name: String,
+    pub organization: String,
+    pub date: String,
+
+    /// Groups that have not paid exactly what they owe.
+    pub groups: Vec<GroupBalance>,
+    pub owed: String,
+    pub paid: String,
+    pub outstanding: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupBalance {
+    pub name: String,
+    pub participants: u32,
+    pub owed: String,
+    pub paid: String,
+    /// Negative if the group paid too much.
+    pub outstanding: String,
+}
+
+impl BalanceReport {
+    /// Report with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            date: "18.10.2026".to_string(),
+            groups: vec![
+                GroupBalance {
+                    name: "Delphine".to_string(),
+                    participants: 2,
+                    owed: "26,00 EUR".to_string(),
+                    paid: "20,00 EUR".to_string(),
+                    outstanding: "6,00 EUR".to_string(),
+                },
+                GroupBalance {
+                    name: "Seepferdchen".to_string(),
+                    participants: 1,
+                    owed: "8,50 EUR".to_string(),
+                    paid: "10,00 EUR".to_string(),
+                    outstanding: "-1,50 EUR".to_string(),
+                },
+            ],
+            owed: "34,50 EUR".to_string(),
+            paid: "30,00 EUR".to_string(),
+            outstanding: "4,50 EUR".to_string(),
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data =
+            serde_json::to_vec(self).context("Invalid balance report, serialization failed")?;
+
+        let template = "balance_report.typst";
+        typst_compiler
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst balance report")
+    }
+}
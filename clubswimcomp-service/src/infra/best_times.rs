@@ -0,0 +1,522 @@
+//! Import of the times swimmers swam at earlier events.
+//!
+//! The times are read from a CSV file with one time per line or from the
+//! results of a meet in the Lenex format, the XML format of the swimming
+//! meet software, uncompressed or zipped. Only single strokes in short and long course pools are
+//! imported, relays and medley have no competitions to compare with.
+
+use std::{collections::HashMap, io::Read};
+
+use chrono::NaiveDate;
+use clubswimcomp_types::model;
+use serde::Deserialize;
+
+/// A time of a swimmer read from an import file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedTime {
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub best_time: model::BestTime,
+}
+
+/// Format of an import file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Lenex,
+}
+
+/// Read the times of an import file, the error describes the invalid line
+/// or element.
+pub fn parse(format: ImportFormat, content: &[u8]) -> Result<Vec<ImportedTime>, String> {
+    match format {
+        ImportFormat::Csv => parse_csv(content),
+        ImportFormat::Lenex => parse_lenex(content),
+    }
+}
+
+/// A line of the CSV file, with the header
+/// `first_name,last_name,birthday,stroke,distance,course,time,date,meet`.
+#[derive(Debug, Deserialize)]
+struct CsvLine {
+    first_name: String,
+    last_name: String,
+    birthday: NaiveDate,
+    stroke: String,
+    distance: u32,
+    course: String,
+    time: String,
+    date: NaiveDate,
+    meet: String,
+}
+
+/// Read the times of a CSV file.
+///
+/// Strokes are `freestyle`, `back`, `breast` or `butterfly`, courses are
+/// `short` or `long` and times are written like `1:05.32` or `38.45`.
+pub fn parse_csv(content: &[u8]) -> Result<Vec<ImportedTime>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content);
+
+    let mut times = Vec::new();
+    for (index, line) in reader.deserialize::<CsvLine>().enumerate() {
+        // The header is the first line
+        let number = index + 2;
+        let line = line.map_err(|e| format!("Line {number} is invalid: {e}"))?;
+
+        let stroke = parse_stroke(&line.stroke)
+            .ok_or_else(|| format!("Line {number} has the unknown stroke {}", line.stroke))?;
+        let course = parse_course(&line.course)
+            .ok_or_else(|| format!("Line {number} has the unknown course {}", line.course))?;
+        let time = parse_time(&line.time)
+            .ok_or_else(|| format!("Line {number} has the invalid time {}", line.time))?;
+        if line.distance == 0 {
+            return Err(format!("Line {number} has no distance"));
+        }
+
+        times.push(ImportedTime {
+            first_name: line.first_name,
+            last_name: line.last_name,
+            birthday: line.birthday,
+            best_time: model::BestTime {
+                stroke,
+                distance: line.distance,
+                course,
+                time,
+                swum_on: line.date,
+                meet: line.meet,
+            },
+        });
+    }
+
+    Ok(times)
+}
+
+/// An event of a Lenex meet, referenced by the results.
+struct LenexEvent {
+    /// Missing for relays, medley and other strokes that are not imported.
+    stroke: Option<model::Stroke>,
+    distance: u32,
+    /// Missing for pools that are neither short nor long course meters.
+    course: Option<model::Course>,
+    date: NaiveDate,
+}
+
+/// Read the valid results of all meets in a Lenex file.
+///
+/// Results of disqualified swimmers, swimmers that did not start or finish
+/// and results without a time are skipped.
+pub fn parse_lenex(content: &[u8]) -> Result<Vec<ImportedTime>, String> {
+    let unzipped;
+    let content = if content.starts_with(ZIP_SIGNATURE) {
+        unzipped = unzip_lenex(content)?;
+        &unzipped
+    } else {
+        content
+    };
+
+    let content =
+        std::str::from_utf8(content).map_err(|_| "The file is not UTF-8 encoded".to_string())?;
+    let document =
+        roxmltree::Document::parse(content).map_err(|e| format!("The file is no XML: {e}"))?;
+    if !document.root_element().has_tag_name("LENEX") {
+        return Err("The file is no Lenex file".to_string());
+    }
+
+    let mut times = Vec::new();
+    for meet in document.descendants().filter(|n| n.has_tag_name("MEET")) {
+        let meet_name = meet.attribute("name").unwrap_or_default();
+        let meet_course = meet.attribute("course");
+
+        let mut events = HashMap::new();
+        for session in meet.descendants().filter(|n| n.has_tag_name("SESSION")) {
+            let date = required_attribute(session, "date")?;
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Session has the invalid date {date}"))?;
+            // Sessions in another pool override the course of the meet
+            let course = session.attribute("course").or(meet_course);
+
+            for event in session.descendants().filter(|n| n.has_tag_name("EVENT")) {
+                let event_id = required_attribute(event, "eventid")?;
+                let swim_style = event
+                    .children()
+                    .find(|n| n.has_tag_name("SWIMSTYLE"))
+                    .ok_or_else(|| format!("Event {event_id} has no swim style"))?;
+                let relay_count = swim_style.attribute("relaycount").unwrap_or("1");
+                let distance = required_attribute(swim_style, "distance")?;
+                let distance = distance
+                    .parse()
+                    .map_err(|_| format!("Event {event_id} has the invalid distance {distance}"))?;
+
+                events.insert(
+                    event_id,
+                    LenexEvent {
+                        stroke: swim_style
+                            .attribute("stroke")
+                            .filter(|_| relay_count == "1")
+                            .and_then(lenex_stroke),
+                        distance,
+                        course: course.and_then(lenex_course),
+                        date,
+                    },
+                );
+            }
+        }
+
+        for athlete in meet.descendants().filter(|n| n.has_tag_name("ATHLETE")) {
+            let first_name = required_attribute(athlete, "firstname")?;
+            let last_name = required_attribute(athlete, "lastname")?;
+            let birthday = required_attribute(athlete, "birthdate")?;
+            let birthday = NaiveDate::parse_from_str(birthday, "%Y-%m-%d").map_err(|_| {
+                format!("Athlete {first_name} {last_name} has the invalid birthdate {birthday}")
+            })?;
+
+            for result in athlete.descendants().filter(|n| n.has_tag_name("RESULT")) {
+                let event_id = required_attribute(result, "eventid")?;
+                let event = events
+                    .get(event_id)
+                    .ok_or_else(|| format!("Result references the unknown event {event_id}"))?;
+                let (Some(stroke), Some(course)) = (event.stroke, event.course) else {
+                    continue;
+                };
+                if result.attribute("status").is_some_and(|s| !s.is_empty()) {
+                    continue;
+                }
+                let Some(time) = result.attribute("swimtime").and_then(parse_time) else {
+                    continue;
+                };
+
+                times.push(ImportedTime {
+                    first_name: first_name.to_string(),
+                    last_name: last_name.to_string(),
+                    birthday,
+                    best_time: model::BestTime {
+                        stroke,
+                        distance: event.distance,
+                        course,
+                        time,
+                        swum_on: event.date,
+                        meet: meet_name.to_string(),
+                    },
+                });
+            }
+        }
+    }
+
+    Ok(times)
+}
+
+/// Start of a zip file, the usual `.lxf` Lenex files are zipped.
+const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+/// Read the single `.lef` file in a zipped Lenex file.
+///
+/// The entries are found in the central directory at the end of the zip file,
+/// the local headers may miss the sizes of the entries.
+fn unzip_lenex(content: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "The zip file is invalid".to_string();
+    let u16_at = |offset: usize| {
+        content
+            .get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(invalid)
+    };
+    let u32_at = |offset: usize| {
+        content
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(invalid)
+    };
+
+    // The end of the central directory is followed by a comment of at most
+    // 64 KiB
+    let end = (0..content.len().saturating_sub(21))
+        .rev()
+        .take(u16::MAX as usize + 1)
+        .find(|&offset| content[offset..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(invalid)?;
+    let entry_count = u16_at(end + 10)?;
+    let mut offset = u32_at(end + 16)?;
+
+    let mut lenex_entries = Vec::new();
+    for _ in 0..entry_count {
+        if !content[offset.min(content.len())..].starts_with(b"PK\x01\x02") {
+            return Err(invalid());
+        }
+        let name_length = u16_at(offset + 28)?;
+        let name = content
+            .get(offset + 46..offset + 46 + name_length)
+            .ok_or_else(invalid)?;
+        if name.to_ascii_lowercase().ends_with(b".lef") {
+            lenex_entries.push(offset);
+        }
+        offset += 46 + name_length + u16_at(offset + 30)? + u16_at(offset + 32)?;
+    }
+    let [entry] = lenex_entries[..] else {
+        return Err("The zip file does not contain exactly one Lenex file".to_string());
+    };
+
+    let method = u16_at(entry + 10)?;
+    let compressed_size = u32_at(entry + 20)?;
+    let size = u32_at(entry + 24)?;
+    let header = u32_at(entry + 42)?;
+    if !content[header.min(content.len())..].starts_with(ZIP_SIGNATURE) {
+        return Err(invalid());
+    }
+    let start = header + 30 + u16_at(header + 26)? + u16_at(header + 28)?;
+    let data = content
+        .get(start..start + compressed_size)
+        .ok_or_else(invalid)?;
+
+    match method {
+        // Stored
+        0 => Ok(data.to_vec()),
+        // Deflated
+        8 => {
+            let mut lenex = Vec::with_capacity(size);
+            flate2::read::DeflateDecoder::new(data)
+                .take(size as u64)
+                .read_to_end(&mut lenex)
+                .map_err(|_| invalid())?;
+            Ok(lenex)
+        }
+        _ => Err("The Lenex file in the zip file is compressed with an unknown method".to_string()),
+    }
+}
+
+fn required_attribute<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Result<&'a str, String> {
+    node.attribute(name)
+        .ok_or_else(|| format!("Element {} has no attribute {name}", node.tag_name().name()))
+}
+
+fn parse_stroke(stroke: &str) -> Option<model::Stroke> {
+    match stroke.to_lowercase().as_str() {
+        "freestyle" => Some(model::Stroke::Freestyle),
+        "back" => Some(model::Stroke::Back),
+        "breast" => Some(model::Stroke::Breast),
+        "butterfly" => Some(model::Stroke::Butterfly),
+        _ => None,
+    }
+}
+
+fn parse_course(course: &str) -> Option<model::Course> {
+    match course.to_lowercase().as_str() {
+        "short" => Some(model::Course::Short),
+        "long" => Some(model::Course::Long),
+        _ => None,
+    }
+}
+
+fn lenex_stroke(stroke: &str) -> Option<model::Stroke> {
+    match stroke {
+        "FREE" => Some(model::Stroke::Freestyle),
+        "BACK" => Some(model::Stroke::Back),
+        "BREAST" => Some(model::Stroke::Breast),
+        "FLY" => Some(model::Stroke::Butterfly),
+        _ => None,
+    }
+}
+
+fn lenex_course(course: &str) -> Option<model::Course> {
+    match course {
+        "SCM" => Some(model::Course::Short),
+        "LCM" => Some(model::Course::Long),
+        _ => None,
+    }
+}
+
+/// Time in milliseconds of a text like `38.45`, `1:05.32` or the Lenex
+/// format `00:01:05.32`.
+///
+/// `None` for invalid times, zero times and `NT`, no time.
+fn parse_time(time: &str) -> Option<u32> {
+    let (whole, fraction) = time.trim().split_once(['.', ','])?;
+    if fraction.is_empty() || fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let millis = fraction.parse::<u32>().ok()? * 10u32.pow(3 - fraction.len() as u32);
+
+    let mut seconds = 0u32;
+    for (index, part) in whole.split(':').enumerate() {
+        let value = part.parse::<u32>().ok()?;
+        // Only the hours are not limited to less than 60
+        if index > 0 && value >= 60 {
+            return None;
+        }
+        seconds = seconds.checked_mul(60)?.checked_add(value)?;
+    }
+
+    let time = seconds.checked_mul(1000)?.checked_add(millis)?;
+    (time > 0 && time <= i32::MAX as u32).then_some(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn times_are_read_with_and_without_minutes() {
+        assert_eq!(parse_time("38.45"), Some(38_450));
+        assert_eq!(parse_time("1:05.32"), Some(65_320));
+        assert_eq!(parse_time("1:05,3"), Some(65_300));
+        assert_eq!(parse_time("00:01:05.321"), Some(65_321));
+        assert_eq!(parse_time("NT"), None);
+        assert_eq!(parse_time("00:00:00.00"), None);
+        assert_eq!(parse_time("1:65.00"), None);
+        assert_eq!(parse_time("38"), None);
+    }
+
+    #[test]
+    fn csv_lines_are_read_as_times() {
+        let content = "\
+first_name,last_name,birthday,stroke,distance,course,time,date,meet
+Anna,Schwimmer,2014-05-03,Freestyle,50,short,38.45,2025-11-08,Herbstpokal
+Anna, Schwimmer ,2014-05-03,back,100,long,1:32.10,2026-03-14,\"Frühjahr, Finale\"
+";
+
+        let times = parse_csv(content.as_bytes()).unwrap();
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[1].last_name, "Schwimmer");
+        assert_eq!(times[1].birthday, date("2014-05-03"));
+        assert_eq!(
+            times[1].best_time,
+            model::BestTime {
+                stroke: model::Stroke::Back,
+                distance: 100,
+                course: model::Course::Long,
+                time: 92_100,
+                swum_on: date("2026-03-14"),
+                meet: "Frühjahr, Finale".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_csv_lines_are_reported_with_their_number() {
+        let content = "\
+first_name,last_name,birthday,stroke,distance,course,time,date,meet
+Anna,Schwimmer,2014-05-03,freestyle,50,short,38.45,2025-11-08,Herbstpokal
+Anna,Schwimmer,2014-05-03,medley,100,short,1:32.10,2026-03-14,Frühjahr
+";
+
+        let error = parse_csv(content.as_bytes()).unwrap_err();
+        assert_eq!(error, "Line 3 has the unknown stroke medley");
+        let error = parse_csv(b"first_name,last_name\nAnna,Schwimmer\n").unwrap_err();
+        assert!(error.starts_with("Line 2 is invalid"), "{error}");
+    }
+
+    #[test]
+    fn valid_lenex_results_of_single_strokes_are_read() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LENEX version="3.0">
+  <MEETS>
+    <MEET name="Herbstpokal" course="SCM" city="Musterstadt">
+      <SESSIONS>
+        <SESSION number="1" date="2025-11-08">
+          <EVENTS>
+            <EVENT eventid="1" number="1"><SWIMSTYLE distance="50" relaycount="1" stroke="FREE" /></EVENT>
+            <EVENT eventid="2" number="2"><SWIMSTYLE distance="100" relaycount="1" stroke="MEDLEY" /></EVENT>
+            <EVENT eventid="3" number="3"><SWIMSTYLE distance="50" relaycount="4" stroke="FREE" /></EVENT>
+          </EVENTS>
+        </SESSION>
+        <SESSION number="2" date="2025-11-09" course="LCM">
+          <EVENTS>
+            <EVENT eventid="4" number="4"><SWIMSTYLE distance="50" relaycount="1" stroke="FLY" /></EVENT>
+          </EVENTS>
+        </SESSION>
+      </SESSIONS>
+      <CLUBS>
+        <CLUB name="SV Musterstadt">
+          <ATHLETES>
+            <ATHLETE athleteid="1" firstname="Anna" lastname="Schwimmer" birthdate="2014-05-03" gender="F">
+              <RESULTS>
+                <RESULT resultid="1" eventid="1" swimtime="00:00:38.45" />
+                <RESULT resultid="2" eventid="2" swimtime="00:01:32.10" />
+                <RESULT resultid="3" eventid="3" swimtime="00:02:40.00" />
+                <RESULT resultid="4" eventid="4" swimtime="00:00:45.12" />
+              </RESULTS>
+            </ATHLETE>
+            <ATHLETE athleteid="2" firstname="Ben" lastname="Taucher" birthdate="2013-01-20" gender="M">
+              <RESULTS>
+                <RESULT resultid="5" eventid="1" swimtime="00:00:40.00" status="DSQ" />
+                <RESULT resultid="6" eventid="4" swimtime="NT" />
+              </RESULTS>
+            </ATHLETE>
+          </ATHLETES>
+        </CLUB>
+      </CLUBS>
+    </MEET>
+  </MEETS>
+</LENEX>
+"#;
+
+        let times = parse_lenex(content.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.first_name, t.best_time))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            vec![
+                (
+                    "Anna".to_string(),
+                    model::BestTime {
+                        stroke: model::Stroke::Freestyle,
+                        distance: 50,
+                        course: model::Course::Short,
+                        time: 38_450,
+                        swum_on: date("2025-11-08"),
+                        meet: "Herbstpokal".to_string(),
+                    }
+                ),
+                (
+                    "Anna".to_string(),
+                    model::BestTime {
+                        stroke: model::Stroke::Butterfly,
+                        distance: 50,
+                        course: model::Course::Long,
+                        time: 45_120,
+                        swum_on: date("2025-11-09"),
+                        meet: "Herbstpokal".to_string(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn zipped_lenex_files_are_read() {
+        let content = include_bytes!("../../fixtures/winterpokal.lxf");
+
+        let times = parse_lenex(content).unwrap();
+        assert_eq!(
+            times,
+            vec![ImportedTime {
+                first_name: "Anna".to_string(),
+                last_name: "Schwimmer".to_string(),
+                birthday: date("2014-05-03"),
+                best_time: model::BestTime {
+                    stroke: model::Stroke::Back,
+                    distance: 100,
+                    course: model::Course::Short,
+                    time: 88_640,
+                    swum_on: date("2026-01-17"),
+                    meet: "Winterpokal".to_string(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn files_other_than_lenex_are_rejected() {
+        assert!(parse_lenex(b"<BACKUP />").is_err());
+        assert!(parse_lenex(b"first_name,last_name").is_err());
+        assert!(parse_lenex(b"PK\x03\x04").is_err());
+    }
+}
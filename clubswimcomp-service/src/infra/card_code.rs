@@ -0,0 +1,164 @@
+//! Content of the QR codes on the registration cards.
+//!
+//! Codes are signed with the secret of the event, so that cards of other
+//! events and forged cards are rejected when the results are entered. Cards
+//! printed before the codes were signed only contain the registration id and
+//! are still accepted.
+
+use base64::prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// Prefix of the first version of signed codes.
+const PREFIX_V1: &str = "CSC1";
+/// The signature is truncated to 128 bits to keep the QR codes small.
+const SIGNATURE_LEN: usize = 16;
+
+/// The registration a card belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCode {
+    pub event_id: Uuid,
+    pub registration_id: Uuid,
+    /// Short code of the participant, readable without the service.
+    pub short_code: String,
+}
+
+/// A code read from a registration card, not verified yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScannedCode {
+    Signed {
+        code: CardCode,
+        signature: Vec<u8>,
+    },
+    /// Code of a card printed before the codes were signed.
+    Legacy {
+        registration_id: Uuid,
+    },
+}
+
+impl CardCode {
+    /// The signed content of the QR code.
+    pub fn encode(&self, secret: &[u8]) -> String {
+        let payload = self.payload();
+        let signature = mac(secret, &payload).finalize().into_bytes();
+        let signature = BASE64_URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_LEN]);
+        format!("{payload}.{signature}")
+    }
+
+    /// Check the signature in constant time.
+    pub fn verify(&self, signature: &[u8], secret: &[u8]) -> bool {
+        signature.len() == SIGNATURE_LEN
+            && mac(secret, &self.payload())
+                .verify_truncated_left(signature)
+                .is_ok()
+    }
+
+    fn payload(&self) -> String {
+        format!(
+            "{PREFIX_V1}.{}.{}.{}",
+            self.event_id, self.registration_id, self.short_code
+        )
+    }
+}
+
+/// Read the content of a QR code, `None` if it is no registration card.
+pub fn parse(content: &str) -> Option<ScannedCode> {
+    let content = content.trim();
+    if let Ok(registration_id) = Uuid::parse_str(content) {
+        return Some(ScannedCode::Legacy { registration_id });
+    }
+
+    let mut parts = content.split('.');
+    let (
+        Some(PREFIX_V1),
+        Some(event_id),
+        Some(registration_id),
+        Some(short_code),
+        Some(signature),
+        None,
+    ) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    )
+    else {
+        return None;
+    };
+
+    Some(ScannedCode::Signed {
+        code: CardCode {
+            event_id: Uuid::parse_str(event_id).ok()?,
+            registration_id: Uuid::parse_str(registration_id).ok()?,
+            short_code: short_code.to_string(),
+        },
+        signature: BASE64_URL_SAFE_NO_PAD.decode(signature).ok()?,
+    })
+}
+
+fn mac(secret: &[u8], payload: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"secret of the event";
+
+    fn code() -> CardCode {
+        CardCode {
+            event_id: Uuid::new_v4(),
+            registration_id: Uuid::new_v4(),
+            short_code: "1234".to_string(),
+        }
+    }
+
+    #[test]
+    fn encoded_codes_can_be_verified() {
+        let code = code();
+
+        let Some(ScannedCode::Signed {
+            code: parsed,
+            signature,
+        }) = parse(&code.encode(SECRET))
+        else {
+            panic!("The encoded code can not be parsed");
+        };
+
+        assert_eq!(parsed, code);
+        assert!(parsed.verify(&signature, SECRET));
+        assert!(!parsed.verify(&signature, b"secret of another event"));
+    }
+
+    #[test]
+    fn changed_codes_are_rejected() {
+        let code = code();
+        let encoded = code.encode(SECRET);
+        let forged = encoded.replace(&code.registration_id.to_string(), &Uuid::nil().to_string());
+
+        let Some(ScannedCode::Signed { code, signature }) = parse(&forged) else {
+            panic!("The forged code can not be parsed");
+        };
+
+        assert!(!code.verify(&signature, SECRET));
+        assert!(!code.verify(&signature[..8], SECRET));
+    }
+
+    #[test]
+    fn bare_registration_ids_are_legacy_codes() {
+        let registration_id = Uuid::new_v4();
+
+        assert_eq!(
+            parse(&format!("{registration_id}\n")),
+            Some(ScannedCode::Legacy { registration_id })
+        );
+        assert_eq!(parse("CSC2.something"), None);
+        assert_eq!(parse("https://example.com"), None);
+    }
+}
@@ -2,23 +2,32 @@ use anyhow::Context;
 use clubswimcomp_types::model;
 use serde::{Deserialize, Serialize};
 
-use crate::infra;
-
-use super::typst_compiler::{self, TypstCompiler};
+use super::typst_compiler::{TypstCompiler, TypstOutput};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Certificates(pub Vec<Certificate>);
+pub struct Certificates {
+    pub event_name: String,
+    pub organization: String,
+    /// Labels of the signature lines below the results, e.g. the referee.
+    pub signatures: Vec<String>,
+
+    pub certificates: Vec<Certificate>,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Certificate {
     pub first_name: String,
     pub last_name: String,
     pub birthyear: u32,
+    pub group: String,
 
     pub group_points: u32,
-    pub group_rank: u32,
+    /// Missing if the participant has fewer starts than required.
+    pub group_rank: Option<u32>,
 
     pub results: Vec<CompetitionResult>,
+    /// Not all results of the participant have been entered yet.
+    pub results_missing: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,6 +36,8 @@ pub struct CompetitionResult {
     pub stroke: Stroke,
     pub millis: u32,
     pub rank: u32,
+    /// Faster than the best time the participant swam before.
+    pub personal_best: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
@@ -49,21 +60,89 @@ impl From<model::Stroke> for Stroke {
 }
 
 impl Certificates {
-    pub async fn generate_pdf(mut self, typst_compiler: &TypstCompiler) -> anyhow::Result<Vec<u8>> {
-        self.0
+    /// Certificate with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            signatures: vec!["Wettkampfleitung".to_string(), "Vorstand".to_string()],
+            certificates: vec![Certificate {
+                first_name: "Anna".to_string(),
+                last_name: "Schwimmer".to_string(),
+                birthyear: 2014,
+                group: "Delphine".to_string(),
+                group_points: 412,
+                group_rank: Some(2),
+                results: vec![
+                    CompetitionResult {
+                        distance: 50,
+                        stroke: Stroke::Freestyle,
+                        millis: 38_450,
+                        rank: 1,
+                        personal_best: true,
+                    },
+                    CompetitionResult {
+                        distance: 25,
+                        stroke: Stroke::Breast,
+                        millis: 24_120,
+                        rank: 3,
+                        personal_best: false,
+                    },
+                ],
+                results_missing: true,
+            }],
+        }
+    }
+
+    pub async fn generate(
+        mut self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.certificates
             .iter_mut()
             .for_each(|cert| cert.results.sort_by_key(|r| r.stroke));
 
-        let input_data = serde_json::to_string(&self.0)
-            .context("Invalid certificate data, serialization failed")?;
+        let data =
+            serde_json::to_vec(&self).context("Invalid certificate data, serialization failed")?;
 
         let template = "certificate.typst";
-        let inputs = [("certificates".to_string(), input_data)]
-            .into_iter()
-            .collect();
         typst_compiler
-            .compile(template, infra::typst_compiler::TypstOutput::Pdf, &inputs)
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst certificates")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/golden/certificate.svg");
+
+    /// The template must render the sample certificate exactly like the
+    /// checked in SVG.
+    ///
+    /// Run with `UPDATE_GOLDEN=1` to update the checked in file.
+    #[tokio::test]
+    async fn template_renders_like_golden_file() {
+        let typst_compiler = TypstCompiler::new(None);
+
+        let data = serde_json::to_vec(&Certificates::sample()).unwrap();
+        let rendered = typst_compiler
+            .compile("certificate.typst", TypstOutput::Svg { page: 1 }, data)
             .await
-            .context("Failed to compile typst registration cards")
+            .unwrap();
+        let rendered = String::from_utf8(rendered).unwrap();
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(GOLDEN_PATH, &rendered).unwrap();
+        }
+
+        let golden = std::fs::read_to_string(GOLDEN_PATH).unwrap_or_default();
+        assert!(
+            golden == rendered,
+            "certificate.svg is outdated, run the tests with UPDATE_GOLDEN=1 to update it"
+        );
     }
 }
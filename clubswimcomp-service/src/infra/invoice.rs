@@ -0,0 +1,103 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::typst_compiler::{TypstCompiler, TypstOutput};
+
+/// Invoices of the entry fees, one for every group. Amounts are formatted as
+/// they are printed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invoices {
+    pub event_name: String,
+    pub organization: String,
+    pub date: String,
+
+    pub invoices: Vec<Invoice>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invoice {
+    pub group: String,
+    pub lines: Vec<InvoiceLine>,
+    pub payments: Vec<InvoicePayment>,
+    pub owed: String,
+    pub paid: String,
+    pub outstanding: String,
+}
+
+/// The fees of one participant of the group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvoiceLine {
+    pub first_name: String,
+    pub last_name: String,
+    pub participant_fee: String,
+    pub starts: u32,
+    pub start_fees: String,
+    pub late_entries: u32,
+    pub surcharges: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvoicePayment {
+    pub received_on: String,
+    pub reference: String,
+    pub amount: String,
+}
+
+impl Invoices {
+    /// Invoice with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            date: "18.10.2026".to_string(),
+            invoices: vec![Invoice {
+                group: "Delphine".to_string(),
+                lines: vec![
+                    InvoiceLine {
+                        first_name: "Anna".to_string(),
+                        last_name: "Schwimmer".to_string(),
+                        participant_fee: "5,00 EUR".to_string(),
+                        starts: 3,
+                        start_fees: "10,50 EUR".to_string(),
+                        late_entries: 1,
+                        surcharges: "2,00 EUR".to_string(),
+                        amount: "17,50 EUR".to_string(),
+                    },
+                    InvoiceLine {
+                        first_name: "Ben".to_string(),
+                        last_name: "Taucher".to_string(),
+                        participant_fee: "5,00 EUR".to_string(),
+                        starts: 1,
+                        start_fees: "3,50 EUR".to_string(),
+                        late_entries: 0,
+                        surcharges: "0,00 EUR".to_string(),
+                        amount: "8,50 EUR".to_string(),
+                    },
+                ],
+                payments: vec![InvoicePayment {
+                    received_on: "12.10.2026".to_string(),
+                    reference: "Überweisung Delphine".to_string(),
+                    amount: "20,00 EUR".to_string(),
+                }],
+                owed: "26,00 EUR".to_string(),
+                paid: "20,00 EUR".to_string(),
+                outstanding: "6,00 EUR".to_string(),
+            }],
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data = serde_json::to_vec(self).context("Invalid invoices, serialization failed")?;
+
+        let template = "invoice.typst";
+        typst_compiler
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst invoices")
+    }
+}
@@ -1,3 +1,11 @@
+pub mod balance_report;
+pub mod best_times;
+pub mod card_code;
 pub mod certificate;
+pub mod invoice;
 pub mod registration_card;
+pub mod results_protocol;
+pub mod start_list;
+pub mod templates;
+pub mod timetable;
 pub mod typst_compiler;
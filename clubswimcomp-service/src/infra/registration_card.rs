@@ -1,19 +1,43 @@
 use anyhow::Context;
 use clubswimcomp_types::model;
+use qrcode::{render::svg, QrCode};
 use serde::{Deserialize, Serialize};
 
-use crate::infra;
-
-use super::typst_compiler::TypstCompiler;
+use super::typst_compiler::{TypstCompiler, TypstOutput};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RegistrationCards {
     pub event_name: String,
     pub organization: String,
+    pub layout: CardLayout,
+    /// Print the test grid of the layout instead of the cards.
+    pub calibration: bool,
 
     pub cards: Vec<RegistrationCard>,
 }
 
+/// How the registration cards are arranged on the paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum CardLayout {
+    /// One card per A6 page.
+    A6,
+    /// Eight cards per A4 page, two columns of four cards.
+    A4Grid,
+    /// One label per page, for label printers.
+    Label { width_mm: u32, height_mm: u32 },
+}
+
+impl CardLayout {
+    pub fn template(&self) -> &'static str {
+        match self {
+            CardLayout::A6 => "registration_card.typst",
+            CardLayout::A4Grid => "registration_card_grid.typst",
+            CardLayout::Label { .. } => "registration_card_label.typst",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RegistrationCard {
     pub first_name: String,
@@ -22,6 +46,8 @@ pub struct RegistrationCard {
     pub stroke: Stroke,
     pub gender: Gender,
     pub participant_number: String,
+    /// Number to type in when the QR code can not be scanned.
+    pub card_number: u32,
     pub qr_code: String,
 }
 
@@ -60,16 +86,39 @@ impl From<model::Gender> for Gender {
 }
 
 impl RegistrationCards {
-    pub async fn generate_pdf(&self, typst_compiler: &TypstCompiler) -> anyhow::Result<Vec<u8>> {
-        let input_data = serde_json::to_string(self)
-            .context("Invalid registration card, serialization failed")?;
+    /// Registration card with sample data, used to preview the template.
+    pub fn sample(layout: CardLayout) -> Self {
+        let qr_code = QrCode::new(uuid::Uuid::nil().to_string().as_bytes()).unwrap();
+
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            layout,
+            calibration: false,
+            cards: vec![RegistrationCard {
+                first_name: "Anna".to_string(),
+                last_name: "Schwimmer".to_string(),
+                distance: 50,
+                stroke: Stroke::Freestyle,
+                gender: Gender::Female,
+                participant_number: "1234".to_string(),
+                card_number: 42,
+                qr_code: qr_code.render::<svg::Color>().build(),
+            }],
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data =
+            serde_json::to_vec(self).context("Invalid registration card, serialization failed")?;
 
-        let template = "registration_card.typst";
-        let inputs = [("registration_cards".to_string(), input_data)]
-            .into_iter()
-            .collect();
+        let template = self.layout.template();
         typst_compiler
-            .compile(template, infra::typst_compiler::TypstOutput::Pdf, &inputs)
+            .compile(template, output, data)
             .await
             .context("Failed to compile typst registration cards")
     }
@@ -0,0 +1,131 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::registration_card::{Gender, Stroke};
+use super::typst_compiler::{TypstCompiler, TypstOutput};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultsProtocol {
+    pub event_name: String,
+    pub organization: String,
+
+    pub competitions: Vec<CompetitionResults>,
+    pub groups: Vec<GroupStandings>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompetitionResults {
+    pub distance: u32,
+    pub stroke: Stroke,
+    pub gender: Gender,
+
+    pub results: Vec<RankedResult>,
+    /// Rankings of the results within each birth year.
+    pub age_classes: Vec<AgeClass>,
+    pub disqualifications: Vec<Swimmer>,
+    pub did_not_start: Vec<Swimmer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgeClass {
+    pub birthyear: u32,
+    pub results: Vec<RankedResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankedResult {
+    pub rank: u32,
+    #[serde(flatten)]
+    pub swimmer: Swimmer,
+    pub millis: u32,
+    pub fina_points: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Swimmer {
+    pub first_name: String,
+    pub last_name: String,
+    pub birthyear: u32,
+    pub group: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupStandings {
+    pub name: String,
+    pub scores: Vec<GroupStanding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupStanding {
+    /// Missing if the participant has fewer starts than required.
+    pub rank: Option<u32>,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthyear: u32,
+    pub fina_points: u32,
+}
+
+impl ResultsProtocol {
+    /// Results protocol with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        let swimmer = Swimmer {
+            first_name: "Anna".to_string(),
+            last_name: "Schwimmer".to_string(),
+            birthyear: 2014,
+            group: "Delphine".to_string(),
+        };
+        let result = RankedResult {
+            rank: 1,
+            swimmer: swimmer.clone(),
+            millis: 38_450,
+            fina_points: 412,
+        };
+
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            competitions: vec![CompetitionResults {
+                distance: 50,
+                stroke: Stroke::Freestyle,
+                gender: Gender::Female,
+                results: vec![result.clone()],
+                age_classes: vec![AgeClass {
+                    birthyear: 2014,
+                    results: vec![result],
+                }],
+                disqualifications: Vec::new(),
+                did_not_start: vec![Swimmer {
+                    first_name: "Mia".to_string(),
+                    last_name: "Taucher".to_string(),
+                    birthyear: 2015,
+                    group: "Seepferdchen".to_string(),
+                }],
+            }],
+            groups: vec![GroupStandings {
+                name: "Delphine".to_string(),
+                scores: vec![GroupStanding {
+                    rank: Some(1),
+                    first_name: swimmer.first_name,
+                    last_name: swimmer.last_name,
+                    birthyear: swimmer.birthyear,
+                    fina_points: 412,
+                }],
+            }],
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data =
+            serde_json::to_vec(self).context("Invalid results protocol, serialization failed")?;
+
+        let template = "results_protocol.typst";
+        typst_compiler
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst results protocol")
+    }
+}
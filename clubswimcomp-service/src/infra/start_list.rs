@@ -0,0 +1,87 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::registration_card::{Gender, Stroke};
+use super::typst_compiler::{TypstCompiler, TypstOutput};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartLists {
+    pub event_name: String,
+    pub organization: String,
+
+    pub start_lists: Vec<StartList>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartList {
+    pub distance: u32,
+    pub stroke: Stroke,
+    pub gender: Gender,
+    pub heats: Vec<Heat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heat {
+    pub number: u32,
+    pub lanes: Vec<Lane>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lane {
+    pub lane: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthyear: u32,
+    pub group: String,
+    pub entry_time_millis: Option<u32>,
+}
+
+impl StartLists {
+    /// Start list with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            start_lists: vec![StartList {
+                distance: 50,
+                stroke: Stroke::Freestyle,
+                gender: Gender::Female,
+                heats: vec![Heat {
+                    number: 1,
+                    lanes: vec![
+                        Lane {
+                            lane: 3,
+                            first_name: "Anna".to_string(),
+                            last_name: "Schwimmer".to_string(),
+                            birthyear: 2014,
+                            group: "Delphine".to_string(),
+                            entry_time_millis: Some(38_450),
+                        },
+                        Lane {
+                            lane: 4,
+                            first_name: "Mia".to_string(),
+                            last_name: "Taucher".to_string(),
+                            birthyear: 2015,
+                            group: "Seepferdchen".to_string(),
+                            entry_time_millis: None,
+                        },
+                    ],
+                }],
+            }],
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data = serde_json::to_vec(self).context("Invalid start list, serialization failed")?;
+
+        let template = "start_list.typst";
+        typst_compiler
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst start lists")
+    }
+}
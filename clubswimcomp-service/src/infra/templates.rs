@@ -0,0 +1,68 @@
+use anyhow::Context;
+use clubswimcomp_types::model::DocumentTemplate;
+
+use super::{
+    balance_report::BalanceReport,
+    certificate::Certificates,
+    invoice::Invoices,
+    registration_card::{CardLayout, RegistrationCards},
+    results_protocol::ResultsProtocol,
+    start_list::StartLists,
+    timetable::Timetable,
+};
+
+/// All documents generated from typst templates.
+pub const DOCUMENT_TEMPLATES: [DocumentTemplate; 9] = [
+    DocumentTemplate::Certificate,
+    DocumentTemplate::RegistrationCard,
+    DocumentTemplate::RegistrationCardGrid,
+    DocumentTemplate::RegistrationCardLabel,
+    DocumentTemplate::ResultsProtocol,
+    DocumentTemplate::StartList,
+    DocumentTemplate::Timetable,
+    DocumentTemplate::Invoice,
+    DocumentTemplate::BalanceReport,
+];
+
+/// Size of the labels in previews of the label layout.
+const SAMPLE_LABEL: CardLayout = CardLayout::Label {
+    width_mm: 100,
+    height_mm: 50,
+};
+
+/// Name of the typst file a document is compiled from.
+pub fn template_file(template: DocumentTemplate) -> &'static str {
+    match template {
+        DocumentTemplate::Certificate => "certificate.typst",
+        DocumentTemplate::RegistrationCard => CardLayout::A6.template(),
+        DocumentTemplate::RegistrationCardGrid => CardLayout::A4Grid.template(),
+        DocumentTemplate::RegistrationCardLabel => SAMPLE_LABEL.template(),
+        DocumentTemplate::ResultsProtocol => "results_protocol.typst",
+        DocumentTemplate::StartList => "start_list.typst",
+        DocumentTemplate::Timetable => "timetable.typst",
+        DocumentTemplate::Invoice => "invoice.typst",
+        DocumentTemplate::BalanceReport => "balance_report.typst",
+    }
+}
+
+/// Sample data of a document, used to preview and validate its template.
+pub fn sample_data(template: DocumentTemplate) -> anyhow::Result<Vec<u8>> {
+    match template {
+        DocumentTemplate::Certificate => serde_json::to_vec(&Certificates::sample()),
+        DocumentTemplate::RegistrationCard => {
+            serde_json::to_vec(&RegistrationCards::sample(CardLayout::A6))
+        }
+        DocumentTemplate::RegistrationCardGrid => {
+            serde_json::to_vec(&RegistrationCards::sample(CardLayout::A4Grid))
+        }
+        DocumentTemplate::RegistrationCardLabel => {
+            serde_json::to_vec(&RegistrationCards::sample(SAMPLE_LABEL))
+        }
+        DocumentTemplate::ResultsProtocol => serde_json::to_vec(&ResultsProtocol::sample()),
+        DocumentTemplate::StartList => serde_json::to_vec(&StartLists::sample()),
+        DocumentTemplate::Timetable => serde_json::to_vec(&Timetable::sample()),
+        DocumentTemplate::Invoice => serde_json::to_vec(&Invoices::sample()),
+        DocumentTemplate::BalanceReport => serde_json::to_vec(&BalanceReport::sample()),
+    }
+    .context("Invalid sample data, serialization failed")
+}
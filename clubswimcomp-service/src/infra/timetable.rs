@@ -0,0 +1,80 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::registration_card::{Gender, Stroke};
+use super::typst_compiler::{TypstCompiler, TypstOutput};
+
+/// Printable program of the event, times are formatted as they are printed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timetable {
+    pub event_name: String,
+    pub organization: String,
+
+    pub sessions: Vec<Session>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub name: String,
+    pub date: String,
+    pub warm_up_at: String,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub competitions: Vec<Competition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Competition {
+    pub starts_at: String,
+    pub distance: u32,
+    pub stroke: Stroke,
+    pub gender: Gender,
+    pub heats: u32,
+}
+
+impl Timetable {
+    /// Timetable with sample data, used to preview the template.
+    pub fn sample() -> Self {
+        Self {
+            event_name: "Vereinsmeisterschaften 2026".to_string(),
+            organization: "SV Musterstadt".to_string(),
+            sessions: vec![Session {
+                name: "1. Abschnitt".to_string(),
+                date: "18.10.2026".to_string(),
+                warm_up_at: "08:30".to_string(),
+                starts_at: "09:00".to_string(),
+                ends_at: "09:24".to_string(),
+                competitions: vec![
+                    Competition {
+                        starts_at: "09:00".to_string(),
+                        distance: 50,
+                        stroke: Stroke::Freestyle,
+                        gender: Gender::Female,
+                        heats: 3,
+                    },
+                    Competition {
+                        starts_at: "09:12".to_string(),
+                        distance: 100,
+                        stroke: Stroke::Breast,
+                        gender: Gender::Male,
+                        heats: 0,
+                    },
+                ],
+            }],
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        typst_compiler: &TypstCompiler,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let data = serde_json::to_vec(self).context("Invalid timetable, serialization failed")?;
+
+        let template = "timetable.typst";
+        typst_compiler
+            .compile(template, output, data)
+            .await
+            .context("Failed to compile typst timetable")
+    }
+}
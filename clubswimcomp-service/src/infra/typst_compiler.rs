@@ -1,109 +1,430 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+};
 
 use anyhow::Context;
+use chrono::Datelike;
+use comemo::Prehashed;
+use thiserror::Error;
 use tracing::instrument;
+use typst::{
+    diag::{FileError, FileResult, SourceDiagnostic},
+    eval::Tracer,
+    foundations::{Bytes, Datetime, Smart},
+    layout::Page,
+    model::Document,
+    syntax::{FileId, Source, VirtualPath},
+    text::{Font, FontBook},
+    visualize::Color,
+    Library, World,
+};
 
 /// Output format to be generated by the Typst compiler
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TypstOutput {
     Pdf,
-    Png,
-    Svg,
+    /// A single page of the document, starting at 1.
+    Png {
+        page: usize,
+    },
+    /// A single page of the document, starting at 1.
+    Svg {
+        page: usize,
+    },
 }
 
-impl TypstOutput {
-    fn as_cli_arg(&self) -> &'static str {
-        match self {
-            TypstOutput::Pdf => "pdf",
-            TypstOutput::Png => "png",
-            TypstOutput::Svg => "svg",
-        }
-    }
+/// The requested page is not part of the compiled document.
+#[derive(Debug, Error)]
+#[error("The document has no page {page}, it only has {pages} pages")]
+pub struct PageDoesNotExist {
+    pub page: usize,
+    pub pages: usize,
 }
 
-#[derive(Debug, Clone)]
+/// Path of the virtual file holding the data of a document, templates read it
+/// using `json("/data.json")`.
+pub const DATA_PATH: &str = "/data.json";
+
+/// Resolution of rendered PNG images.
+const PNG_PIXEL_PER_PT: f32 = 2.0;
+
+/// Templates and images delivered with the service.
+pub const BUNDLED_ASSETS: &[(&str, &[u8])] = &[
+    (
+        "balance_report.typst",
+        include_bytes!("../../assets/balance_report.typst"),
+    ),
+    (
+        "certificate.typst",
+        include_bytes!("../../assets/certificate.typst"),
+    ),
+    (
+        "invoice.typst",
+        include_bytes!("../../assets/invoice.typst"),
+    ),
+    (
+        "card_calibration.typst",
+        include_bytes!("../../assets/card_calibration.typst"),
+    ),
+    (
+        "registration_card.typst",
+        include_bytes!("../../assets/registration_card.typst"),
+    ),
+    (
+        "registration_card_grid.typst",
+        include_bytes!("../../assets/registration_card_grid.typst"),
+    ),
+    (
+        "registration_card_label.typst",
+        include_bytes!("../../assets/registration_card_label.typst"),
+    ),
+    (
+        "results_protocol.typst",
+        include_bytes!("../../assets/results_protocol.typst"),
+    ),
+    (
+        "start_list.typst",
+        include_bytes!("../../assets/start_list.typst"),
+    ),
+    (
+        "timetable.typst",
+        include_bytes!("../../assets/timetable.typst"),
+    ),
+    ("labels.typst", include_bytes!("../../assets/labels.typst")),
+    ("logo.svg", include_bytes!("../../assets/logo.svg")),
+];
+
+/// Fonts delivered with the service, in addition to the fonts of Typst itself.
+const BUNDLED_FONTS: &[&[u8]] = &[
+    include_bytes!("../../assets/fonts/DejaVuSans.ttf"),
+    include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../../assets/fonts/DejaVuSans-Oblique.ttf"),
+    include_bytes!("../../assets/fonts/DejaVuSans-BoldOblique.ttf"),
+    include_bytes!("../../assets/fonts/DejaVuSans-ExtraLight.ttf"),
+];
+
+/// Compiles the typst templates within the service.
+///
+/// Templates, images and fonts are bundled with the service. Files in the
+/// optional assets directory take precedence over the bundled ones and fonts
+/// in its `fonts` subdirectory are added to the bundled fonts. The custom
+/// assets of the event take precedence over both. System fonts are never
+/// used, so documents look the same on every machine.
+#[derive(Clone)]
 pub struct TypstCompiler {
-    typst_bin: String,
-    working_dir: String,
+    resources: Arc<Resources>,
+}
+
+struct Resources {
+    library: Prehashed<Library>,
+    book: Prehashed<FontBook>,
+    fonts: Vec<Font>,
+    assets_dir: Option<PathBuf>,
+    /// Assets uploaded for the event, by their name.
+    custom_assets: RwLock<Arc<HashMap<String, Bytes>>>,
+    /// Parsed templates, only parsed again when their text changes.
+    sources: Mutex<HashMap<FileId, Source>>,
+}
+
+impl std::fmt::Debug for TypstCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypstCompiler")
+            .field("assets_dir", &self.resources.assets_dir)
+            .field("fonts", &self.resources.fonts.len())
+            .finish()
+    }
 }
 
 impl TypstCompiler {
-    pub fn new(typst_bin: String, working_dir: String) -> Self {
+    pub fn new(assets_dir: Option<PathBuf>) -> Self {
+        let mut fonts = typst_assets::fonts()
+            .chain(BUNDLED_FONTS.iter().copied())
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .collect::<Vec<_>>();
+
+        if let Some(fonts_dir) = assets_dir.as_ref().map(|dir| dir.join("fonts")) {
+            fonts.extend(load_fonts_from_dir(&fonts_dir));
+        }
+
+        let book = FontBook::from_fonts(&fonts);
+
         Self {
-            typst_bin,
-            working_dir,
+            resources: Arc::new(Resources {
+                library: Prehashed::new(Library::default()),
+                book: Prehashed::new(book),
+                fonts,
+                assets_dir,
+                custom_assets: RwLock::new(Arc::new(HashMap::new())),
+                sources: Mutex::new(HashMap::new()),
+            }),
         }
     }
 
     /// Compile a typst template to the given output format.
     ///
+    /// PNG and SVG output only contain a single page of the document, a
+    /// missing page fails with [`PageDoesNotExist`].
+    ///
     /// # Parameters:
     /// - `template` - The typst template
     /// - `output_format` - The format to compile the document to
-    /// - `inputs` - List of key-value pairs accessible in typst using `sys.inputs.<key>`
-    #[instrument]
+    /// - `data` - Data of the document, accessible in typst using `json("/data.json")`
+    #[instrument(skip(data))]
     pub async fn compile(
         &self,
         template_path: &str,
         output_format: TypstOutput,
-        inputs: &HashMap<String, String>,
+        data: Vec<u8>,
     ) -> anyhow::Result<Vec<u8>> {
-        use std::process::Stdio;
-        use tokio::process::Command;
-
-        let args = build_cli_args(template_path, output_format, inputs);
-
-        let typst_process = Command::new(&self.typst_bin)
-            .env_clear()
-            .current_dir(&self.working_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .args(args)
-            .spawn()
-            .context("Could not spawn typst as a command")?;
-
-        let max_compile_time = Duration::from_secs(10);
-        let typst_output =
-            match tokio::time::timeout(max_compile_time, typst_process.wait_with_output()).await {
-                Ok(Ok(typst_output)) => Ok(typst_output),
-                Ok(Err(e)) => Err(e).context("Typst process run into a error"),
-                Err(e) => Err(e).context("Compilation did not finish within timeout limit"),
-            }?;
-
-        // Typst compilation failed
-        if !typst_output.status.success() {
-            let typst_error_output = &String::from_utf8_lossy(&typst_output.stderr)[..];
-            tracing::warn!(typst_error_output, "Typst compilation failed with error");
-            anyhow::bail!("Typst compilation failed with error")
+        let custom_assets = self.resources.custom_assets.read().unwrap().clone();
+        self.compile_with(template_path, output_format, data, custom_assets)
+            .await
+    }
+
+    /// Compile a typst template with the given custom assets instead of the
+    /// active ones, to validate assets before activating them.
+    #[instrument(skip(data, custom_assets))]
+    pub async fn compile_with_assets(
+        &self,
+        template_path: &str,
+        output_format: TypstOutput,
+        data: Vec<u8>,
+        custom_assets: HashMap<String, Vec<u8>>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let custom_assets = custom_assets
+            .into_iter()
+            .map(|(name, content)| (name, Bytes::from(content)))
+            .collect();
+        self.compile_with(template_path, output_format, data, Arc::new(custom_assets))
+            .await
+    }
+
+    /// Replace the custom assets used by all following compilations.
+    pub fn set_custom_assets(&self, custom_assets: HashMap<String, Vec<u8>>) {
+        let custom_assets = custom_assets
+            .into_iter()
+            .map(|(name, content)| (name, Bytes::from(content)))
+            .collect();
+        *self.resources.custom_assets.write().unwrap() = Arc::new(custom_assets);
+    }
+
+    /// The content of an asset as it is used for compilation.
+    pub fn asset(&self, name: &str) -> Option<Vec<u8>> {
+        let custom_assets = self.resources.custom_assets.read().unwrap().clone();
+        self.resources
+            .read_asset(&custom_assets, name)
+            .ok()
+            .map(|content| content.to_vec())
+    }
+
+    async fn compile_with(
+        &self,
+        template_path: &str,
+        output_format: TypstOutput,
+        data: Vec<u8>,
+        custom_assets: Arc<HashMap<String, Bytes>>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let resources = self.resources.clone();
+        let template_path = template_path.to_string();
+
+        // Compilation is CPU bound and would block the runtime otherwise
+        tokio::task::spawn_blocking(move || {
+            let mut world = DocumentWorld {
+                resources: &resources,
+                custom_assets,
+                main: Source::detached(""),
+                data: Bytes::from(data),
+                today: chrono::Local::now().date_naive(),
+            };
+            world.main = world
+                .source(FileId::new(None, VirtualPath::new(&template_path)))
+                .map_err(|e| anyhow::anyhow!("{e}"))
+                .context("Failed to load typst template")?;
+            let output = world.compile(output_format);
+
+            // Drop cached results that have not been used in a while
+            comemo::evict(10);
+
+            output
+        })
+        .await
+        .context("Typst compilation panicked")?
+    }
+}
+
+/// Load all fonts within a directory, unreadable files are skipped.
+fn load_fonts_from_dir(fonts_dir: &std::path::Path) -> Vec<Font> {
+    let Ok(entries) = std::fs::read_dir(fonts_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            match std::fs::read(&path) {
+                Ok(data) => Some(Font::iter(Bytes::from(data)).collect::<Vec<_>>()),
+                Err(error) => {
+                    tracing::warn!(?path, ?error, "Failed to read font file");
+                    None
+                }
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+fn page_of(document: &Document, page: usize) -> Result<&Page, PageDoesNotExist> {
+    page.checked_sub(1)
+        .and_then(|index| document.pages.get(index))
+        .ok_or(PageDoesNotExist {
+            page,
+            pages: document.pages.len(),
+        })
+}
+
+/// The world of a single document compilation.
+struct DocumentWorld<'a> {
+    resources: &'a Resources,
+    custom_assets: Arc<HashMap<String, Bytes>>,
+    main: Source,
+    data: Bytes,
+    today: chrono::NaiveDate,
+}
+
+impl DocumentWorld<'_> {
+    fn compile(&self, output_format: TypstOutput) -> anyhow::Result<Vec<u8>> {
+        let mut tracer = Tracer::new();
+        let document = match typst::compile(self, &mut tracer) {
+            Ok(document) => document,
+            Err(errors) => {
+                let typst_errors = errors
+                    .iter()
+                    .map(|error| self.describe_error(error))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                tracing::warn!(typst_errors, "Typst compilation failed with error");
+                anyhow::bail!("Typst compilation failed with error:\n{typst_errors}")
+            }
+        };
+
+        match output_format {
+            TypstOutput::Pdf => Ok(typst_pdf::pdf(&document, Smart::Auto, None)),
+            TypstOutput::Png { page } => {
+                let page = page_of(&document, page)?;
+                typst_render::render(&page.frame, PNG_PIXEL_PER_PT, Color::WHITE)
+                    .encode_png()
+                    .context("Failed to encode page as PNG")
+            }
+            TypstOutput::Svg { page } => {
+                let page = page_of(&document, page)?;
+                Ok(typst_svg::svg(&page.frame).into_bytes())
+            }
         }
+    }
 
-        Ok(typst_output.stdout)
+    /// Describe a compilation error including the file and line it occurred in.
+    fn describe_error(&self, error: &SourceDiagnostic) -> String {
+        let location = error.span.id().and_then(|id| {
+            let source = self.source(id).ok()?;
+            let line = source.byte_to_line(source.range(error.span)?.start)?;
+            Some(format!(
+                "{}:{}: ",
+                id.vpath().as_rootless_path().display(),
+                line + 1
+            ))
+        });
+
+        format!("{}{}", location.unwrap_or_default(), error.message)
+    }
+
+    fn read_asset(&self, id: FileId) -> FileResult<Bytes> {
+        let name = id.vpath().as_rootless_path().to_string_lossy();
+        self.resources.read_asset(&self.custom_assets, &name)
     }
 }
 
-#[instrument]
-fn build_cli_args(
-    input_file: &str,
-    output_format: TypstOutput,
-    inputs: &HashMap<String, String>,
-) -> Vec<String> {
-    let mut args = vec![
-        "compile".to_string(),
-        "--format".to_string(),
-        output_format.as_cli_arg().to_string(),
-    ];
+impl Resources {
+    /// Read an asset from the custom assets or the assets directory, falling
+    /// back to the bundled assets.
+    fn read_asset(&self, custom_assets: &HashMap<String, Bytes>, name: &str) -> FileResult<Bytes> {
+        if let Some(content) = custom_assets.get(name) {
+            return Ok(content.clone());
+        }
+
+        if let Some(assets_dir) = &self.assets_dir {
+            let file_path = VirtualPath::new(name)
+                .resolve(assets_dir)
+                .ok_or(FileError::AccessDenied)?;
+            if file_path.is_file() {
+                return std::fs::read(&file_path)
+                    .map(Bytes::from)
+                    .map_err(|e| FileError::from_io(e, &file_path));
+            }
+        }
 
-    // Append the input parameters to the args
-    for (key, value) in inputs.iter() {
-        args.push("--input".to_string());
-        args.push(format!("{key}={value}"));
+        BUNDLED_ASSETS
+            .iter()
+            .find(|(bundled_name, _)| *bundled_name == name)
+            .map(|(_, data)| Bytes::from_static(data))
+            .ok_or_else(|| FileError::NotFound(name.into()))
     }
+}
 
-    // Use STDIN as the input file
-    args.push(input_file.to_string());
+impl World for DocumentWorld<'_> {
+    fn library(&self) -> &Prehashed<Library> {
+        &self.resources.library
+    }
 
-    // Use STDOUT as the output file
-    args.push("/dev/stdout".to_string());
+    fn book(&self) -> &Prehashed<FontBook> {
+        &self.resources.book
+    }
+
+    fn main(&self) -> Source {
+        self.main.clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        let data = self.read_asset(id)?;
+        let text = std::str::from_utf8(&data).map_err(|_| FileError::InvalidUtf8)?;
+
+        let mut sources = self.resources.sources.lock().unwrap();
+        match sources.get(&id) {
+            Some(source) if source.text() == text => Ok(source.clone()),
+            _ => {
+                let source = Source::new(id, text.to_string());
+                sources.insert(id, source.clone());
+                Ok(source)
+            }
+        }
+    }
 
-    args
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        if id.vpath() == &VirtualPath::new(DATA_PATH) {
+            return Ok(self.data.clone());
+        }
+
+        self.read_asset(id)
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.resources.fonts.get(index).cloned()
+    }
+
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        let today = match offset {
+            None => self.today,
+            Some(offset) => {
+                (chrono::Utc::now() + chrono::Duration::try_hours(offset)?).date_naive()
+            }
+        };
+
+        Datetime::from_ymd(
+            today.year(),
+            today.month().try_into().ok()?,
+            today.day().try_into().ok()?,
+        )
+    }
 }
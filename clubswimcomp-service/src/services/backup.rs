@@ -0,0 +1,887 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clubswimcomp_types::{backup, model};
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::db;
+
+use super::ServiceRepositoryError;
+
+/// Prefix of the files written by the scheduled backups, backups downloaded
+/// by hand into the same directory are never pruned.
+const SCHEDULED_BACKUP_PREFIX: &str = "clubswimcomp-scheduled-backup-";
+
+#[derive(Debug, Error)]
+pub enum RestoreBackupError {
+    #[error("Backups of version {0} are not supported")]
+    UnsupportedVersion(u32),
+
+    #[error("Backups can only be restored into an empty database")]
+    DatabaseNotEmpty,
+
+    #[error("The backup is invalid: {0}")]
+    InvalidBackup(String),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+pub struct BackupService {
+    backup_repo: Arc<dyn db::backups::Repository>,
+}
+
+impl BackupService {
+    pub fn new(backup_repo: Arc<dyn db::backups::Repository>) -> Self {
+        Self { backup_repo }
+    }
+
+    /// Create a backup of the complete dataset.
+    #[instrument(skip(self))]
+    pub async fn create_backup(&self) -> Result<backup::Backup, ServiceRepositoryError> {
+        tracing::debug!("Exporting snapshot from repository");
+        let snapshot = self
+            .backup_repo
+            .export_snapshot()
+            .await
+            .context("Failed to export snapshot from repository")?;
+
+        let finals = snapshot
+            .finals
+            .into_iter()
+            .map(|f| backup::FinalsRecord {
+                competition_id: f.competition_id,
+                finals_competition_id: f.finals_competition_id,
+                finalists: f.finalists as _,
+                alternates: f.alternates as _,
+                swim_off_ids: f.swim_off_ids,
+                entries: snapshot
+                    .finals_entries
+                    .iter()
+                    .filter(|e| e.competition_id == f.competition_id)
+                    .cloned()
+                    .map(From::from)
+                    .collect(),
+            })
+            .collect();
+
+        Ok(backup::Backup {
+            version: backup::VERSION,
+            created_at: Utc::now(),
+            groups: snapshot.groups.into_iter().map(From::from).collect(),
+            participants: snapshot.participants.into_iter().map(From::from).collect(),
+            competitions: snapshot.competitions.into_iter().map(From::from).collect(),
+            registrations: snapshot.registrations.into_iter().map(From::from).collect(),
+            results: snapshot.results.into_iter().map(From::from).collect(),
+            assets: snapshot.assets.into_iter().map(From::from).collect(),
+            best_times: snapshot.best_times.into_iter().map(From::from).collect(),
+            records: snapshot.records.into_iter().map(From::from).collect(),
+            eligibilities: snapshot.eligibilities.into_iter().map(From::from).collect(),
+            phase_changes: snapshot.phase_changes.into_iter().map(From::from).collect(),
+            sessions: snapshot.sessions.into_iter().map(From::from).collect(),
+            finals,
+            payments: snapshot.payments.into_iter().map(From::from).collect(),
+            event: snapshot.event.map(From::from),
+        })
+    }
+
+    /// Restore a backup into the empty database.
+    ///
+    /// The backup is validated before anything is stored. Either the complete
+    /// backup is restored or nothing at all.
+    #[instrument(skip(self, backup))]
+    pub async fn restore_backup(
+        &self,
+        mut backup: backup::Backup,
+    ) -> Result<(), RestoreBackupError> {
+        tracing::debug!("Ensuring the backup version is supported");
+        if backup.version != backup::VERSION {
+            return Err(RestoreBackupError::UnsupportedVersion(backup.version));
+        }
+
+        tracing::debug!("Numbering registrations of backups without card numbers");
+        number_cards(&mut backup.registrations);
+
+        tracing::debug!("Validating the referential integrity of the backup");
+        validate_backup(&backup).map_err(RestoreBackupError::InvalidBackup)?;
+
+        tracing::debug!("Ensuring the database is empty");
+        let is_empty = self
+            .backup_repo
+            .is_empty()
+            .await
+            .context("Failed to check if repository is empty")?;
+        if !is_empty {
+            return Err(RestoreBackupError::DatabaseNotEmpty);
+        }
+
+        let assets = backup
+            .assets
+            .into_iter()
+            .map(|asset| {
+                let name = asset.name.clone();
+                db::assets::Asset::try_from(asset)
+                    .map_err(|_| format!("Asset {name} is not valid base64"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RestoreBackupError::InvalidBackup)?;
+        let event = backup
+            .event
+            .map(db::event::Event::try_from)
+            .transpose()
+            .map_err(|_| {
+                RestoreBackupError::InvalidBackup("Event secret is not valid base64".to_string())
+            })?;
+
+        let finals_entries = backup
+            .finals
+            .iter()
+            .flat_map(|f| {
+                f.entries.iter().map(|e| db::finals::FinalsEntry {
+                    competition_id: f.competition_id,
+                    participant_id: e.participant_id,
+                    seed: e.seed as _,
+                    time_millis: e.time_millis as _,
+                    status: e.status.into(),
+                })
+            })
+            .collect();
+        let finals = backup
+            .finals
+            .into_iter()
+            .map(|f| db::finals::Finals {
+                competition_id: f.competition_id,
+                finals_competition_id: f.finals_competition_id,
+                finalists: f.finalists as _,
+                alternates: f.alternates as _,
+                swim_off_ids: f.swim_off_ids,
+            })
+            .collect();
+
+        let snapshot = db::backups::Snapshot {
+            groups: backup.groups.into_iter().map(From::from).collect(),
+            participants: backup.participants.into_iter().map(From::from).collect(),
+            competitions: backup.competitions.into_iter().map(From::from).collect(),
+            registrations: backup.registrations.into_iter().map(From::from).collect(),
+            results: backup.results.into_iter().map(From::from).collect(),
+            assets,
+            best_times: backup.best_times.into_iter().map(From::from).collect(),
+            records: backup.records.into_iter().map(From::from).collect(),
+            eligibilities: backup.eligibilities.into_iter().map(From::from).collect(),
+            phase_changes: backup.phase_changes.into_iter().map(From::from).collect(),
+            sessions: backup.sessions.into_iter().map(From::from).collect(),
+            finals,
+            finals_entries,
+            payments: backup.payments.into_iter().map(From::from).collect(),
+            event,
+        };
+
+        tracing::debug!("Importing snapshot into repository");
+        self.backup_repo
+            .import_snapshot(&snapshot)
+            .await
+            .context("Failed to import snapshot into repository")?;
+
+        Ok(())
+    }
+
+    /// Write a scheduled backup of the complete dataset into the given
+    /// directory.
+    ///
+    /// The file names sort from the oldest to the newest backup and are
+    /// unique, even for backups written within the same second. Returns the
+    /// path of the backup file.
+    #[instrument(skip(self))]
+    pub async fn write_backup(&self, directory: &Path) -> Result<PathBuf> {
+        let backup = self.create_backup().await?;
+        let content =
+            serde_json::to_vec_pretty(&backup).context("Failed to serialize backup to JSON")?;
+
+        tokio::fs::create_dir_all(directory)
+            .await
+            .context("Failed to create backup directory")?;
+
+        // Written to a temporary file first, so there are never partial backups
+        let file_name = format!(
+            "{SCHEDULED_BACKUP_PREFIX}{}-{}.json",
+            backup.created_at.format("%Y%m%d-%H%M%S-%3f"),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let path = directory.join(file_name);
+        let temporary_path = path.with_extension("json.tmp");
+        tokio::fs::write(&temporary_path, content)
+            .await
+            .context("Failed to write backup file")?;
+        tokio::fs::rename(&temporary_path, &path)
+            .await
+            .context("Failed to move backup file into place")?;
+
+        Ok(path)
+    }
+
+    /// Remove all but the newest `keep` scheduled backups from the given
+    /// directory.
+    ///
+    /// Only the files written by [`BackupService::write_backup`] are removed,
+    /// returns their paths.
+    #[instrument(skip(self))]
+    pub async fn prune_backups(&self, directory: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+        let mut entries = tokio::fs::read_dir(directory)
+            .await
+            .context("Failed to read backup directory")?;
+        let mut backups = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("Failed to read backup directory")?
+        {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if file_name.starts_with(SCHEDULED_BACKUP_PREFIX) && file_name.ends_with(".json") {
+                backups.push(entry.path());
+            }
+        }
+
+        // The timestamp in the file names sorts them from the oldest to the newest
+        backups.sort();
+        let removed = backups.len().saturating_sub(keep);
+        backups.truncate(removed);
+        for path in &backups {
+            tokio::fs::remove_file(path)
+                .await
+                .context("Failed to remove old backup file")?;
+        }
+
+        Ok(backups)
+    }
+
+    /// Write a backup into the given directory in a fixed interval, keeping
+    /// only the newest `keep` backups.
+    ///
+    /// Failed backups are logged and retried in the next interval.
+    pub async fn run_periodic_backups(self, directory: PathBuf, interval: Duration, keep: usize) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            match self.write_backup(&directory).await {
+                Ok(path) => tracing::info!(path = %path.display(), "Wrote periodic backup"),
+                Err(err) => {
+                    tracing::error!(error = ?err, "Failed to write periodic backup");
+                    continue;
+                }
+            }
+            match self.prune_backups(&directory, keep).await {
+                Ok(removed) => tracing::debug!(count = removed.len(), "Removed old backups"),
+                Err(err) => tracing::error!(error = ?err, "Failed to remove old backups"),
+            }
+        }
+    }
+}
+
+/// Assign card numbers following the highest one in the backup to all
+/// registrations without a number.
+fn number_cards(registrations: &mut [backup::RegistrationRecord]) {
+    let mut next = registrations
+        .iter()
+        .filter_map(|r| r.card_number)
+        .max()
+        .unwrap_or_default();
+    for registration in registrations.iter_mut() {
+        if registration.card_number.is_none() {
+            next += 1;
+            registration.card_number = Some(next);
+        }
+    }
+}
+
+/// Check that all references within the backup are valid and that it
+/// satisfies the constraints of the database.
+fn validate_backup(backup: &backup::Backup) -> Result<(), String> {
+    let mut group_ids = HashSet::new();
+    for group in backup.groups.iter() {
+        if !group_ids.insert(group.id) {
+            return Err(format!("Group {} exists more than once", group.id));
+        }
+    }
+
+    let mut participant_ids = HashSet::new();
+    let mut short_ids = HashSet::new();
+    for participant in backup.participants.iter() {
+        if !participant_ids.insert(participant.id) {
+            return Err(format!(
+                "Participant {} exists more than once",
+                participant.id
+            ));
+        }
+        if participant.short_id > i32::MAX as u32 || !short_ids.insert(participant.short_id) {
+            return Err(format!(
+                "Participant {} has an invalid or duplicate short id",
+                participant.id
+            ));
+        }
+        if !group_ids.contains(&participant.group_id) {
+            return Err(format!(
+                "Participant {} references unknown group {}",
+                participant.id, participant.group_id
+            ));
+        }
+    }
+
+    let mut competition_ids = HashSet::new();
+    let mut competition_kinds = HashSet::new();
+    for competition in backup.competitions.iter() {
+        if !competition_ids.insert(competition.id) {
+            return Err(format!(
+                "Competition {} exists more than once",
+                competition.id
+            ));
+        }
+        if competition.distance == 0 || competition.distance % 25 != 0 {
+            return Err(format!(
+                "Competition {} has an invalid distance",
+                competition.id
+            ));
+        }
+        if competition.target_time == 0 || competition.target_time > i32::MAX as u32 {
+            return Err(format!(
+                "Competition {} has an invalid target time",
+                competition.id
+            ));
+        }
+        let kind = (
+            competition.gender,
+            competition.stroke,
+            competition.distance,
+            competition.relay,
+            competition.round,
+        );
+        // A tie at the cutoff can need more than one swim-off
+        if competition.round != model::CompetitionRound::SwimOff && !competition_kinds.insert(kind)
+        {
+            return Err(format!(
+                "Competition {} exists more than once",
+                competition.id
+            ));
+        }
+    }
+
+    let mut registration_ids = HashSet::new();
+    let mut registered = HashSet::new();
+    let mut card_numbers = HashSet::new();
+    for registration in backup.registrations.iter() {
+        if !registration_ids.insert(registration.id) {
+            return Err(format!(
+                "Registration {} exists more than once",
+                registration.id
+            ));
+        }
+        if !participant_ids.contains(&registration.participant_id) {
+            return Err(format!(
+                "Registration {} references unknown participant {}",
+                registration.id, registration.participant_id
+            ));
+        }
+        if !competition_ids.contains(&registration.competition_id) {
+            return Err(format!(
+                "Registration {} references unknown competition {}",
+                registration.id, registration.competition_id
+            ));
+        }
+        if !registered.insert((registration.participant_id, registration.competition_id)) {
+            return Err(format!(
+                "Participant {} is registered more than once for competition {}",
+                registration.participant_id, registration.competition_id
+            ));
+        }
+        if let Some(card_number) = registration.card_number {
+            if card_number == 0 || !card_numbers.insert(card_number) {
+                return Err(format!(
+                    "Card number {card_number} of registration {} is invalid or used more than once",
+                    registration.id
+                ));
+            }
+        }
+    }
+
+    let mut result_ids = HashSet::new();
+    for result in backup.results.iter() {
+        if !registration_ids.contains(&result.registration_id) {
+            return Err(format!(
+                "Result references unknown registration {}",
+                result.registration_id
+            ));
+        }
+        if !result_ids.insert(result.registration_id) {
+            return Err(format!(
+                "Registration {} has more than one result",
+                result.registration_id
+            ));
+        }
+        if result.time_millis == 0 || result.time_millis > i32::MAX as u32 {
+            return Err(format!(
+                "Result of registration {} has an invalid time",
+                result.registration_id
+            ));
+        }
+    }
+
+    let mut asset_names = HashSet::new();
+    for asset in backup.assets.iter() {
+        if !asset_names.insert(&asset.name) {
+            return Err(format!("Asset {} exists more than once", asset.name));
+        }
+    }
+
+    let mut best_time_ids = HashSet::new();
+    let mut best_time_results = HashSet::new();
+    for best_time in backup.best_times.iter() {
+        if !best_time_ids.insert(best_time.id) {
+            return Err(format!("Best time {} exists more than once", best_time.id));
+        }
+        if best_time.time_millis == 0 || best_time.time_millis > i32::MAX as u32 {
+            return Err(format!("Best time {} has an invalid time", best_time.id));
+        }
+        if let Some(registration_id) = best_time.registration_id {
+            if !result_ids.contains(&registration_id) {
+                return Err(format!(
+                    "Best time {} references unknown result {registration_id}",
+                    best_time.id
+                ));
+            }
+            if !best_time_results.insert(registration_id) {
+                return Err(format!(
+                    "Result {registration_id} has more than one best time"
+                ));
+            }
+        }
+    }
+
+    let mut record_ids = HashSet::new();
+    for record in backup.records.iter() {
+        if !record_ids.insert(record.id) {
+            return Err(format!("Record {} exists more than once", record.id));
+        }
+        if record.time_millis == 0 || record.time_millis > i32::MAX as u32 {
+            return Err(format!("Record {} has an invalid time", record.id));
+        }
+        if record.distance == 0 || record.distance > i32::MAX as u32 {
+            return Err(format!("Record {} has an invalid distance", record.id));
+        }
+        if record.age_class > i32::MAX as u32 {
+            return Err(format!("Record {} has an invalid age class", record.id));
+        }
+    }
+
+    let mut eligible_competitions = HashSet::new();
+    for eligibility in backup.eligibilities.iter() {
+        let competition_id = eligibility.competition_id;
+        if !competition_ids.contains(&competition_id) {
+            return Err(format!(
+                "Eligibility references unknown competition {competition_id}"
+            ));
+        }
+        if !eligible_competitions.insert(competition_id) {
+            return Err(format!(
+                "Competition {competition_id} has more than one eligibility"
+            ));
+        }
+        let rules = &eligibility.eligibility;
+        let mut eligible_groups = HashSet::new();
+        for group_id in rules.group_ids.iter() {
+            if !group_ids.contains(group_id) || !eligible_groups.insert(group_id) {
+                return Err(format!(
+                    "Eligibility of competition {competition_id} references unknown or duplicate group {group_id}"
+                ));
+            }
+        }
+        if super::competition::validate_eligibility(rules).is_err() {
+            return Err(format!(
+                "Eligibility of competition {competition_id} is invalid"
+            ));
+        }
+    }
+
+    let mut phase_change_ids = HashSet::new();
+    for change in backup.phase_changes.iter() {
+        if !phase_change_ids.insert(change.id) {
+            return Err(format!("Phase change {} exists more than once", change.id));
+        }
+    }
+
+    let mut session_ids = HashSet::new();
+    let mut scheduled = HashSet::new();
+    for session in backup.sessions.iter() {
+        if !session_ids.insert(session.id) {
+            return Err(format!("Session {} exists more than once", session.id));
+        }
+        if session.warm_up_minutes > i32::MAX as u32 || session.turnaround_seconds > i32::MAX as u32
+        {
+            return Err(format!("Session {} has invalid durations", session.id));
+        }
+        for competition_id in session.competition_ids.iter() {
+            if !competition_ids.contains(competition_id) || !scheduled.insert(competition_id) {
+                return Err(format!(
+                    "Session {} references unknown or already scheduled competition {competition_id}",
+                    session.id
+                ));
+            }
+        }
+    }
+
+    let mut finals_competition_ids = HashSet::new();
+    for finals in backup.finals.iter() {
+        let competition_id = finals.competition_id;
+        let referenced = [competition_id, finals.finals_competition_id]
+            .iter()
+            .chain(finals.swim_off_ids.iter())
+            .all(|c| competition_ids.contains(c) && finals_competition_ids.insert(*c));
+        if !referenced {
+            return Err(format!(
+                "Finals of competition {competition_id} reference unknown or already used competitions"
+            ));
+        }
+        if finals.finalists == 0
+            || finals.finalists > i32::MAX as u32
+            || finals.alternates > i32::MAX as u32
+        {
+            return Err(format!(
+                "Finals of competition {competition_id} have an invalid number of finalists"
+            ));
+        }
+
+        let mut entered = HashSet::new();
+        let mut seeds = HashSet::new();
+        for entry in finals.entries.iter() {
+            if !participant_ids.contains(&entry.participant_id)
+                || !entered.insert(entry.participant_id)
+            {
+                return Err(format!(
+                    "Finals of competition {competition_id} reference unknown or already entered participant {}",
+                    entry.participant_id
+                ));
+            }
+            if entry.seed == 0
+                || entry.seed > i32::MAX as u32
+                || !seeds.insert(entry.seed)
+                || entry.time_millis == 0
+                || entry.time_millis > i32::MAX as u32
+            {
+                return Err(format!(
+                    "Finals of competition {competition_id} have an invalid entry of participant {}",
+                    entry.participant_id
+                ));
+            }
+        }
+    }
+
+    let mut payment_ids = HashSet::new();
+    for payment in backup.payments.iter() {
+        if !payment_ids.insert(payment.id) {
+            return Err(format!("Payment {} exists more than once", payment.id));
+        }
+        let payer_exists = match (payment.participant_id, payment.group_id) {
+            (Some(participant_id), None) => participant_ids.contains(&participant_id),
+            (None, Some(group_id)) => group_ids.contains(&group_id),
+            _ => false,
+        };
+        if !payer_exists {
+            return Err(format!(
+                "Payment {} references no, more than one or an unknown payer",
+                payment.id
+            ));
+        }
+        if payment.amount == 0 || payment.amount > i32::MAX as u32 {
+            return Err(format!("Payment {} has an invalid amount", payment.id));
+        }
+    }
+
+    if let Some(event) = backup.event.as_ref() {
+        let max_starts = event.registration_limits.max_individual_starts;
+        if max_starts.is_some_and(|m| m == 0 || m > i32::MAX as u32) {
+            return Err("Maximum number of individual starts is invalid".to_string());
+        }
+        let fees = &event.entry_fees;
+        if [
+            fees.participant_fee,
+            fees.start_fee,
+            fees.late_entry_surcharge,
+        ]
+        .iter()
+        .any(|fee| *fee > i32::MAX as u32)
+        {
+            return Err("Entry fees are invalid".to_string());
+        }
+        let relay_weight = event.team_scoring.relay_weight;
+        if relay_weight == 0 || relay_weight > i32::MAX as u32 {
+            return Err("Relay weight of the team scoring is invalid".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Fixture with one registration that has a result, a competition only
+    /// the group may register for and a payment of the group.
+    async fn event() -> Fixture {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture
+            .competition_service()
+            .set_competition_eligibility(
+                competition_id,
+                clubswimcomp_types::model::Eligibility {
+                    group_ids: vec![group_id],
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let registration_id = fixture.register(participant_id, competition_id).await;
+        fixture.result(registration_id, 41_000, false).await;
+        fixture
+            .fee_service()
+            .add_payment(clubswimcomp_types::api::AddPaymentRequest {
+                participant_id: None,
+                group_id: Some(group_id),
+                amount: 850,
+                received_on: chrono::NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+                reference: "Transfer".to_string(),
+            })
+            .await
+            .unwrap();
+        fixture
+    }
+
+    #[tokio::test]
+    async fn restored_backups_keep_the_printed_cards_valid() {
+        let fixture = event().await;
+        let event = crate::services::current_event(fixture.repos.event.as_ref())
+            .await
+            .unwrap();
+        let backup = fixture.backup_service().create_backup().await.unwrap();
+        let empty = Fixture::new();
+        // Scanning a card on the new server already created another event
+        crate::services::current_event(empty.repos.event.as_ref())
+            .await
+            .unwrap();
+
+        empty.backup_service().restore_backup(backup).await.unwrap();
+
+        assert_eq!(
+            crate::services::current_event(empty.repos.event.as_ref())
+                .await
+                .unwrap(),
+            event
+        );
+    }
+
+    #[tokio::test]
+    async fn backups_can_be_restored_into_empty_database() {
+        let backup = event()
+            .await
+            .backup_service()
+            .create_backup()
+            .await
+            .unwrap();
+        let empty = Fixture::new();
+
+        empty
+            .backup_service()
+            .restore_backup(backup.clone())
+            .await
+            .unwrap();
+
+        let restored = empty.backup_service().create_backup().await.unwrap();
+        assert_eq!(
+            backup::Backup {
+                created_at: backup.created_at,
+                ..restored
+            },
+            backup
+        );
+    }
+
+    #[tokio::test]
+    async fn registrations_of_older_backups_get_card_numbers() {
+        let mut backup = event()
+            .await
+            .backup_service()
+            .create_backup()
+            .await
+            .unwrap();
+        backup.registrations[0].card_number = None;
+        let empty = Fixture::new();
+
+        empty.backup_service().restore_backup(backup).await.unwrap();
+
+        let restored = empty.backup_service().create_backup().await.unwrap();
+        assert_eq!(restored.registrations[0].card_number, Some(1));
+    }
+
+    #[tokio::test]
+    async fn backups_are_not_restored_into_used_database() {
+        let fixture = event().await;
+        let backup = fixture.backup_service().create_backup().await.unwrap();
+
+        let result = fixture.backup_service().restore_backup(backup).await;
+
+        assert!(matches!(result, Err(RestoreBackupError::DatabaseNotEmpty)));
+    }
+
+    #[tokio::test]
+    async fn backups_of_other_versions_are_rejected() {
+        let mut backup = event()
+            .await
+            .backup_service()
+            .create_backup()
+            .await
+            .unwrap();
+        backup.version = backup::VERSION + 1;
+
+        let result = Fixture::new().backup_service().restore_backup(backup).await;
+
+        assert!(matches!(
+            result,
+            Err(RestoreBackupError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn backups_with_dangling_references_are_rejected() {
+        let backup = event()
+            .await
+            .backup_service()
+            .create_backup()
+            .await
+            .unwrap();
+        let empty = Fixture::new();
+
+        let mut invalid = backup.clone();
+        invalid.participants[0].group_id = uuid::Uuid::new_v4();
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.registrations[0].competition_id = uuid::Uuid::new_v4();
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.results.push(invalid.results[0].clone());
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.eligibilities[0].eligibility.group_ids = vec![uuid::Uuid::new_v4()];
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.payments[0].participant_id = invalid.payments[0].group_id;
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.records.push(backup::RecordRecord {
+            id: uuid::Uuid::new_v4(),
+            kind: model::RecordKind::Club,
+            gender: model::Gender::Female,
+            stroke: model::Stroke::Back,
+            distance: 50,
+            course: model::Course::Short,
+            age_class: u32::MAX,
+            time_millis: 40_000,
+            first_name: "Alex".to_string(),
+            last_name: "Former".to_string(),
+            birthday: chrono::NaiveDate::from_ymd_opt(2009, 3, 1).unwrap(),
+            swum_on: chrono::NaiveDate::from_ymd_opt(2019, 11, 9).unwrap(),
+            meet: None,
+            registration_id: None,
+            status: model::RecordStatus::Approved,
+            created_at: Utc::now(),
+            decided_at: Some(Utc::now()),
+            superseded_by: None,
+        });
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup.clone();
+        invalid.event.as_mut().unwrap().team_scoring.relay_weight = 0;
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        let mut invalid = backup;
+        invalid.registrations.clear();
+        let result = empty.backup_service().restore_backup(invalid).await;
+        assert!(matches!(result, Err(RestoreBackupError::InvalidBackup(_))));
+
+        assert!(empty.repos.backups.is_empty().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn only_the_newest_backups_are_kept() {
+        let fixture = event().await;
+        let backup_service = fixture.backup_service();
+        let directory =
+            std::env::temp_dir().join(format!("clubswimcomp-backups-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        for name in [
+            "clubswimcomp-scheduled-backup-20261018-120000-000-0a1b2c3d.json",
+            "clubswimcomp-scheduled-backup-20261018-120500-000-4e5f6a7b.json",
+            "clubswimcomp-scheduled-backup-20261018-121000-000-8c9d0e1f.json",
+            "clubswimcomp-backup-20261018-115500.json",
+            "notes.txt",
+        ] {
+            tokio::fs::write(directory.join(name), "{}").await.unwrap();
+        }
+        let newest = backup_service.write_backup(&directory).await.unwrap();
+        // Backups within the same second still get a file of their own
+        let second_newest = backup_service.write_backup(&directory).await.unwrap();
+        assert_ne!(newest, second_newest);
+
+        let removed = backup_service.prune_backups(&directory, 3).await;
+        let mut remaining = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(
+            removed.unwrap(),
+            vec![
+                directory.join("clubswimcomp-scheduled-backup-20261018-120000-000-0a1b2c3d.json"),
+                directory.join("clubswimcomp-scheduled-backup-20261018-120500-000-4e5f6a7b.json"),
+            ]
+        );
+        let mut expected = vec![
+            directory.join("clubswimcomp-backup-20261018-115500.json"),
+            directory.join("clubswimcomp-scheduled-backup-20261018-121000-000-8c9d0e1f.json"),
+            newest,
+            second_newest,
+            directory.join("notes.txt"),
+        ];
+        expected.sort();
+        assert_eq!(remaining, expected);
+    }
+}
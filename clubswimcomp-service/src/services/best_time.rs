@@ -0,0 +1,312 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::db;
+use crate::infra::best_times::{self, ImportFormat};
+
+use super::ServiceRepositoryError;
+
+/// Times the swimmers swam at earlier events and this one, to flag their
+/// personal bests.
+pub struct BestTimeService {
+    participant_repo: Arc<dyn db::participants::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+}
+
+/// Outcome of an import of best times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestTimesImport {
+    pub imported: u64,
+    /// Times of swims that have already been imported before.
+    pub duplicates: u64,
+}
+
+#[derive(Debug, Error)]
+pub enum ImportBestTimesError {
+    #[error("The file is invalid: {0}")]
+    InvalidFile(String),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetCourseError {
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ParticipantBestTimesError {
+    #[error("The participant does not exist")]
+    ParticipantDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl BestTimeService {
+    pub fn new(
+        participant_repo: Arc<dyn db::participants::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+    ) -> Self {
+        Self {
+            participant_repo,
+            best_time_repo,
+            event_repo,
+        }
+    }
+
+    /// Import the times of a CSV or Lenex file.
+    ///
+    /// Either all times of the file are imported or none. Times of swims
+    /// that have already been imported are skipped, so the results of a
+    /// meet can be imported again.
+    #[instrument(skip(self, content))]
+    pub async fn import_best_times(
+        &self,
+        format: ImportFormat,
+        content: &[u8],
+    ) -> Result<BestTimesImport, ImportBestTimesError> {
+        tracing::debug!("Reading the times of the file");
+        let times =
+            best_times::parse(format, content).map_err(ImportBestTimesError::InvalidFile)?;
+        let times = times
+            .into_iter()
+            .map(|t| db::best_times::BestTime {
+                id: Uuid::new_v4(),
+                first_name: t.first_name,
+                last_name: t.last_name,
+                birthday: t.birthday,
+                stroke: t.best_time.stroke.into(),
+                distance: t.best_time.distance.min(i32::MAX as u32) as _,
+                course: t.best_time.course.into(),
+                time_millis: t.best_time.time as _,
+                swum_on: t.best_time.swum_on,
+                meet: t.best_time.meet,
+                registration_id: None,
+            })
+            .collect::<Vec<_>>();
+
+        tracing::debug!(count = times.len(), "Storing the times in repository");
+        let imported = self
+            .best_time_repo
+            .insert_best_times(&times)
+            .await
+            .context("Failed to store best times in repository")?;
+
+        Ok(BestTimesImport {
+            imported,
+            duplicates: times.len() as u64 - imported,
+        })
+    }
+
+    /// All times the participant swam, including the results of this event,
+    /// oldest first.
+    #[instrument(skip(self))]
+    pub async fn participant_best_times(
+        &self,
+        participant_id: Uuid,
+    ) -> Result<Vec<model::BestTime>, ParticipantBestTimesError> {
+        tracing::debug!("Fetching participant from repository");
+        let participant = self
+            .participant_repo
+            .participant_by_id(participant_id)
+            .await
+            .context("Failed to fetch participant from repository")?
+            .ok_or(ParticipantBestTimesError::ParticipantDoesNotExist)?;
+
+        tracing::debug!("Fetching best times of participant from repository");
+        let best_times = self
+            .best_time_repo
+            .best_times_of_swimmer(
+                &participant.first_name,
+                &participant.last_name,
+                participant.birthday,
+            )
+            .await
+            .context("Failed to fetch best times of participant from repository")?;
+
+        Ok(best_times.into_iter().map(model::BestTime::from).collect())
+    }
+
+    /// The pool of the event, only times in this course are compared.
+    #[instrument(skip(self))]
+    pub async fn course(&self) -> Result<model::Course, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(event.course.into())
+    }
+
+    /// Change the pool of the event, applies to all scoreboards and
+    /// certificates generated afterwards.
+    #[instrument(skip(self))]
+    pub async fn set_course(&self, course: model::Course) -> Result<(), SetCourseError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetCourseError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the course with the event");
+        self.event_repo
+            .update_course(course.into())
+            .await
+            .context("Failed to update course in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    const BEST_TIMES: &str = "\
+first_name,last_name,birthday,stroke,distance,course,time,date,meet
+Kim,Fast,2014-06-01,back,50,short,42.00,2025-11-08,Autumn Cup
+Kim,Fast,2014-06-01,back,50,short,41.00,2026-03-14,Spring Cup
+Kim,Fast,2014-06-01,back,50,long,38.00,2026-06-20,Summer Cup
+Kim,Same,2014-06-01,back,50,short,40.00,2026-03-14,Spring Cup
+";
+
+    #[tokio::test]
+    async fn faster_times_than_before_are_personal_bests() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        for last_name in ["Fast", "Same", "New"] {
+            let participant_id = fixture
+                .participant(group_id, Gender::Female, last_name)
+                .await;
+            let registration_id = fixture.register(participant_id, competition_id).await;
+            fixture.result(registration_id, 40_000, false).await;
+        }
+        fixture
+            .best_time_service()
+            .import_best_times(ImportFormat::Csv, BEST_TIMES.as_bytes())
+            .await
+            .unwrap();
+
+        let score_service = fixture.score_service();
+        let personal_bests = || async {
+            let mut scores = score_service
+                .competition_scoreboard(competition_id)
+                .await
+                .unwrap()
+                .scores
+                .into_iter()
+                .map(|s| (s.participant.last_name, s.personal_best, s.previous_best))
+                .collect::<Vec<_>>();
+            scores.sort();
+            scores
+        };
+
+        // The long course time is not compared in a short course pool
+        assert_eq!(
+            personal_bests().await,
+            vec![
+                ("Fast".to_string(), true, Some(41_000)),
+                ("New".to_string(), false, None),
+                ("Same".to_string(), false, Some(40_000)),
+            ]
+        );
+
+        fixture
+            .best_time_service()
+            .set_course(model::Course::Long)
+            .await
+            .unwrap();
+        assert_eq!(
+            personal_bests().await,
+            vec![
+                ("Fast".to_string(), false, Some(38_000)),
+                ("New".to_string(), false, None),
+                ("Same".to_string(), false, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn imported_times_are_only_stored_once() {
+        let fixture = Fixture::new();
+        let best_time_service = fixture.best_time_service();
+
+        let import = best_time_service
+            .import_best_times(ImportFormat::Csv, BEST_TIMES.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(
+            import,
+            BestTimesImport {
+                imported: 4,
+                duplicates: 0
+            }
+        );
+        let import = best_time_service
+            .import_best_times(ImportFormat::Csv, BEST_TIMES.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(
+            import,
+            BestTimesImport {
+                imported: 0,
+                duplicates: 4
+            }
+        );
+
+        let result = best_time_service
+            .import_best_times(ImportFormat::Lenex, BEST_TIMES.as_bytes())
+            .await;
+        assert!(
+            matches!(result, Err(ImportBestTimesError::InvalidFile(_))),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn best_times_of_participants_are_listed_oldest_first() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Female, "Fast").await;
+        let best_time_service = fixture.best_time_service();
+        best_time_service
+            .import_best_times(ImportFormat::Csv, BEST_TIMES.as_bytes())
+            .await
+            .unwrap();
+
+        let meets = best_time_service
+            .participant_best_times(participant_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.meet)
+            .collect::<Vec<_>>();
+        assert_eq!(meets, vec!["Autumn Cup", "Spring Cup", "Summer Cup"]);
+
+        let result = best_time_service
+            .participant_best_times(Uuid::new_v4())
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(ParticipantBestTimesError::ParticipantDoesNotExist)
+            ),
+            "{result:?}"
+        );
+    }
+}
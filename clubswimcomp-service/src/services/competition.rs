@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use clubswimcomp_types::model;
 use thiserror::Error;
@@ -9,9 +11,11 @@ use crate::db;
 use super::ServiceRepositoryError;
 
 pub struct CompetitionService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
 }
 
 #[derive(Debug, Error)]
@@ -22,6 +26,9 @@ pub enum AddCompetitionError {
     #[error("There is already the same competition")]
     SameCompetitionExists,
 
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -34,6 +41,9 @@ pub enum DeleteCompetitionError {
     #[error("The competition can not be deleted while there are still registrations for it")]
     CompetitionHasRegistrations,
 
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -47,16 +57,44 @@ pub enum CompetitionDetailsError {
     RepositoryError(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum SetEligibilityError {
+    #[error("The competition does not exist")]
+    CompetitionDoesNotExist,
+
+    #[error("The group {0} does not exist")]
+    GroupDoesNotExist(Uuid),
+
+    #[error("The minimum age must not be above the maximum age")]
+    InvalidAgeRange,
+
+    #[error("The first birth year must not be after the last one")]
+    InvalidBirthYearRange,
+
+    #[error("The qualifying time must be above zero")]
+    InvalidQualifyingTime,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
 impl CompetitionService {
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
     ) -> Self {
         Self {
             participant_repo,
             registration_repo,
             competition_repo,
+            group_repo,
+            event_repo,
         }
     }
 
@@ -84,6 +122,7 @@ impl CompetitionService {
         gender: model::Gender,
         stroke: model::Stroke,
         target_time: u32,
+        relay: bool,
     ) -> Result<Uuid, AddCompetitionError> {
         tracing::debug!("Checking if the provided distance is valid");
         if distance % 25 != 0 {
@@ -91,8 +130,13 @@ impl CompetitionService {
             return Err(AddCompetitionError::InvalidDistance);
         }
 
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(AddCompetitionError::EventFinalized);
+        }
+
         tracing::debug!("Checking if there already exists the same competition");
-        let already_exists = !self
+        let already_exists = self
             .competition_repo
             .search_competition(
                 Some(gender.into()),
@@ -101,7 +145,8 @@ impl CompetitionService {
             )
             .await
             .context("Failed to fetch competitions from repository")?
-            .is_empty();
+            .iter()
+            .any(|c| c.relay == relay);
 
         if already_exists {
             tracing::debug!("The same competition already exists");
@@ -116,6 +161,7 @@ impl CompetitionService {
                 stroke.into(),
                 distance as _,
                 target_time as _,
+                relay,
             )
             .await
             .context("Failed to create competition in repository")?;
@@ -129,6 +175,11 @@ impl CompetitionService {
         competition_id: Uuid,
         force_delete: bool,
     ) -> Result<(), DeleteCompetitionError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(DeleteCompetitionError::EventFinalized);
+        }
+
         tracing::debug!("Ensuring the competition actually exists");
         let _competition = self
             .competition_repo
@@ -229,4 +280,344 @@ impl CompetitionService {
             registrations,
         })
     }
+
+    /// Who may register for the competition besides the gender.
+    #[instrument(skip(self))]
+    pub async fn competition_eligibility(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<model::Eligibility, CompetitionDetailsError> {
+        tracing::debug!("Ensuring the competition actually exists");
+        self.competition_repo
+            .competition_by_id(competition_id)
+            .await
+            .context("Failed to fetch competition from repository")?
+            .ok_or(CompetitionDetailsError::CompetitionDoesNotExist)?;
+
+        tracing::debug!("Fetching eligibility of competition from repository");
+        let eligibility = self
+            .competition_repo
+            .eligibility_of_competition(competition_id)
+            .await
+            .context("Failed to fetch eligibility of competition from repository")?
+            .map(model::Eligibility::from)
+            .unwrap_or_default();
+
+        Ok(eligibility)
+    }
+
+    /// Change who may register for the competition.
+    ///
+    /// Existing registrations are kept, the rules apply to the following
+    /// registrations only.
+    #[instrument(skip(self))]
+    pub async fn set_competition_eligibility(
+        &self,
+        competition_id: Uuid,
+        mut eligibility: model::Eligibility,
+    ) -> Result<(), SetEligibilityError> {
+        validate_eligibility(&eligibility)?;
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetEligibilityError::EventFinalized);
+        }
+
+        tracing::debug!("Ensuring the competition actually exists");
+        self.competition_repo
+            .competition_by_id(competition_id)
+            .await
+            .context("Failed to fetch competition from repository")?
+            .ok_or(SetEligibilityError::CompetitionDoesNotExist)?;
+
+        eligibility.group_ids.sort();
+        eligibility.group_ids.dedup();
+        for group_id in eligibility.group_ids.iter() {
+            tracing::debug!(?group_id, "Ensuring the eligible group exists");
+            self.group_repo
+                .group_by_id(*group_id)
+                .await
+                .context("Failed to fetch group from repository")?
+                .ok_or(SetEligibilityError::GroupDoesNotExist(*group_id))?;
+        }
+
+        if eligibility == model::Eligibility::default() {
+            tracing::debug!("Removing the eligibility of the competition from repository");
+            self.competition_repo
+                .delete_eligibility(competition_id)
+                .await
+                .context("Failed to delete eligibility from repository")?;
+        } else {
+            tracing::debug!("Storing the eligibility of the competition in repository");
+            self.competition_repo
+                .set_eligibility(&crate::conversions::eligibility(
+                    competition_id,
+                    eligibility,
+                ))
+                .await
+                .context("Failed to store eligibility in repository")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that the limits of the eligibility can be met and stored.
+pub(super) fn validate_eligibility(
+    eligibility: &model::Eligibility,
+) -> Result<(), SetEligibilityError> {
+    let ages = [eligibility.min_age, eligibility.max_age];
+    if ages.iter().flatten().any(|age| *age > i32::MAX as u32) {
+        return Err(SetEligibilityError::InvalidAgeRange);
+    }
+    if let (Some(min_age), Some(max_age)) = (eligibility.min_age, eligibility.max_age) {
+        if min_age > max_age {
+            return Err(SetEligibilityError::InvalidAgeRange);
+        }
+    }
+    if let (Some(min_year), Some(max_year)) =
+        (eligibility.min_birth_year, eligibility.max_birth_year)
+    {
+        if min_year > max_year {
+            return Err(SetEligibilityError::InvalidBirthYearRange);
+        }
+    }
+    if let Some(qualifying_time) = eligibility.qualifying_time {
+        if qualifying_time == 0 || qualifying_time > i32::MAX as u32 {
+            return Err(SetEligibilityError::InvalidQualifyingTime);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Competition with one registration that already has a result.
+    async fn competition_with_registration(fixture: &Fixture) -> (Uuid, Uuid) {
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Male, "Swimmer").await;
+        let competition_id = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let registration_id = fixture.register(participant_id, competition_id).await;
+        fixture.result(registration_id, 31_000, false).await;
+        (competition_id, registration_id)
+    }
+
+    #[tokio::test]
+    async fn delete_competition_without_registrations() {
+        let fixture = Fixture::new();
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+
+        fixture
+            .competition_service()
+            .delete_competition(competition_id, false)
+            .await
+            .unwrap();
+
+        let competition = fixture
+            .repos
+            .competitions
+            .competition_by_id(competition_id)
+            .await
+            .unwrap();
+        assert!(competition.is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_competition_with_registrations_requires_force() {
+        let fixture = Fixture::new();
+        let (competition_id, registration_id) = competition_with_registration(&fixture).await;
+
+        let result = fixture
+            .competition_service()
+            .delete_competition(competition_id, false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DeleteCompetitionError::CompetitionHasRegistrations)
+        ));
+        let repos = &fixture.repos;
+        assert!(repos
+            .competitions
+            .competition_by_id(competition_id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repos
+            .registrations
+            .registration_by_id(registration_id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repos
+            .registrations
+            .result_for_registration(registration_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn force_delete_competition_removes_registrations_and_results() {
+        let fixture = Fixture::new();
+        let (competition_id, registration_id) = competition_with_registration(&fixture).await;
+
+        fixture
+            .competition_service()
+            .delete_competition(competition_id, true)
+            .await
+            .unwrap();
+
+        let repos = &fixture.repos;
+        assert!(repos
+            .competitions
+            .competition_by_id(competition_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repos
+            .registrations
+            .registration_by_id(registration_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repos
+            .registrations
+            .result_for_registration(registration_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn force_delete_is_rejected_after_finalization() {
+        let fixture = Fixture::new();
+        let (competition_id, registration_id) = competition_with_registration(&fixture).await;
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+
+        let result = fixture
+            .competition_service()
+            .delete_competition(competition_id, true)
+            .await;
+
+        assert!(
+            matches!(result, Err(DeleteCompetitionError::EventFinalized)),
+            "{result:?}"
+        );
+        let repos = &fixture.repos;
+        assert!(repos
+            .competitions
+            .competition_by_id(competition_id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repos
+            .registrations
+            .result_for_registration(registration_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn delete_unknown_competition() {
+        let fixture = Fixture::new();
+
+        let result = fixture
+            .competition_service()
+            .delete_competition(Uuid::new_v4(), true)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DeleteCompetitionError::CompetitionDoesNotExist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_eligibilities_are_rejected() {
+        let fixture = Fixture::new();
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let service = fixture.competition_service();
+
+        let result = service
+            .set_competition_eligibility(
+                competition_id,
+                model::Eligibility {
+                    min_age: Some(12),
+                    max_age: Some(10),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(SetEligibilityError::InvalidAgeRange)),
+            "{result:?}"
+        );
+        let result = service
+            .set_competition_eligibility(
+                competition_id,
+                model::Eligibility {
+                    min_birth_year: Some(2016),
+                    max_birth_year: Some(2014),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(SetEligibilityError::InvalidBirthYearRange)),
+            "{result:?}"
+        );
+        let result = service
+            .set_competition_eligibility(
+                competition_id,
+                model::Eligibility {
+                    qualifying_time: Some(0),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(SetEligibilityError::InvalidQualifyingTime)),
+            "{result:?}"
+        );
+        let group_id = Uuid::new_v4();
+        let result = service
+            .set_competition_eligibility(
+                competition_id,
+                model::Eligibility {
+                    group_ids: vec![group_id],
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(SetEligibilityError::GroupDoesNotExist(id)) if id == group_id),
+            "{result:?}"
+        );
+
+        assert_eq!(
+            service
+                .competition_eligibility(competition_id)
+                .await
+                .unwrap(),
+            model::Eligibility::default()
+        );
+    }
 }
@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::Utc;
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::db;
+use crate::db::event::EventPhase;
+
+use super::ServiceRepositoryError;
+
+/// The phases of the event, which decide when registrations and results may
+/// be changed.
+pub struct EventService {
+    event_repo: Arc<dyn db::event::Repository>,
+}
+
+#[derive(Debug, Error)]
+pub enum ChangePhaseError {
+    #[error("The event is finalized and has to be re-opened")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetEventInfoError {
+    #[error("The name of the event is missing")]
+    MissingName,
+
+    #[error("The event is finalized and has to be re-opened")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ReopenEventError {
+    #[error("Only finalized events can be re-opened")]
+    EventNotFinalized,
+
+    #[error("A re-opened event can't be finalized again at once")]
+    InvalidPhase,
+
+    #[error("The reason for re-opening the event is missing")]
+    MissingReason,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl EventService {
+    pub fn new(event_repo: Arc<dyn db::event::Repository>) -> Self {
+        Self { event_repo }
+    }
+
+    /// The current phase of the event with all changes.
+    #[instrument(skip(self))]
+    pub async fn event_phase(&self) -> Result<model::EventPhaseDetails, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+
+        tracing::debug!("Loading the phase changes from repository");
+        let changes = self
+            .event_repo
+            .phase_changes()
+            .await
+            .context("Failed to fetch phase changes from repository")?
+            .into_iter()
+            .map(model::PhaseChange::from)
+            .collect();
+
+        Ok(model::EventPhaseDetails {
+            phase: event.phase.into(),
+            changes,
+        })
+    }
+
+    /// Name and organizer of the event, printed on the documents.
+    #[instrument(skip(self))]
+    pub async fn event_info(&self) -> Result<model::EventInfo, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::EventInfo::from(&event))
+    }
+
+    /// Change the name and organizer of the event.
+    #[instrument(skip(self))]
+    pub async fn set_event_info(&self, info: model::EventInfo) -> Result<(), SetEventInfoError> {
+        let name = info.name.trim();
+        if name.is_empty() {
+            return Err(SetEventInfoError::MissingName);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == EventPhase::Finalized {
+            return Err(SetEventInfoError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the name and organizer with the event");
+        self.event_repo
+            .update_info(name, info.organization.trim())
+            .await
+            .context("Failed to update event info in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
+    /// Move the event into another phase.
+    ///
+    /// Finalized events can only be changed with [`EventService::reopen_event`].
+    #[instrument(skip(self))]
+    pub async fn change_phase(&self, phase: model::EventPhase) -> Result<(), ChangePhaseError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == EventPhase::Finalized {
+            return Err(ChangePhaseError::EventFinalized);
+        }
+        if event.phase == phase.into() {
+            tracing::debug!("The event already is in the phase");
+            return Ok(());
+        }
+
+        self.store_phase(phase.into(), None).await?;
+        Ok(())
+    }
+
+    /// Re-open a finalized event, the reason is kept with the change.
+    #[instrument(skip(self))]
+    pub async fn reopen_event(
+        &self,
+        phase: model::EventPhase,
+        reason: &str,
+    ) -> Result<(), ReopenEventError> {
+        if phase == model::EventPhase::Finalized {
+            return Err(ReopenEventError::InvalidPhase);
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(ReopenEventError::MissingReason);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase != EventPhase::Finalized {
+            return Err(ReopenEventError::EventNotFinalized);
+        }
+
+        tracing::info!(?phase, reason, "Re-opening the finalized event");
+        self.store_phase(phase.into(), Some(reason.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    async fn store_phase(&self, phase: EventPhase, reason: Option<String>) -> anyhow::Result<()> {
+        tracing::debug!(?phase, "Storing the phase of the event");
+        self.event_repo
+            .update_phase(&db::event::PhaseChange {
+                id: Uuid::new_v4(),
+                phase,
+                changed_at: Utc::now(),
+                reason,
+            })
+            .await
+            .context("Failed to update phase in repository")?
+            .context("Event is missing in repository even though it has been created")
+    }
+}
+
+/// Which changes of the registrations the phase of the event allows.
+pub(super) enum RegistrationWindow {
+    Open,
+    /// Only late entries are accepted.
+    Closed,
+    Finalized,
+}
+
+impl From<EventPhase> for RegistrationWindow {
+    fn from(phase: EventPhase) -> Self {
+        match phase {
+            EventPhase::RegistrationOpen => Self::Open,
+            EventPhase::RegistrationClosed | EventPhase::MeetInProgress => Self::Closed,
+            EventPhase::Finalized => Self::Finalized,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn phases_are_changed_until_the_event_is_finalized() {
+        let event_service = Fixture::new().event_service();
+
+        event_service
+            .change_phase(model::EventPhase::MeetInProgress)
+            .await
+            .unwrap();
+        event_service
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+        let result = event_service
+            .change_phase(model::EventPhase::RegistrationOpen)
+            .await;
+
+        assert!(
+            matches!(result, Err(ChangePhaseError::EventFinalized)),
+            "{result:?}"
+        );
+        let details = event_service.event_phase().await.unwrap();
+        assert_eq!(details.phase, model::EventPhase::Finalized);
+        let phases = details.changes.iter().map(|c| c.phase).collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            vec![
+                model::EventPhase::MeetInProgress,
+                model::EventPhase::Finalized
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn event_info_is_trimmed_and_needs_a_name() {
+        let event_service = Fixture::new().event_service();
+        let result = event_service
+            .set_event_info(model::EventInfo {
+                name: " ".to_string(),
+                organization: "SC Example".to_string(),
+            })
+            .await;
+        assert!(
+            matches!(result, Err(SetEventInfoError::MissingName)),
+            "{result:?}"
+        );
+
+        event_service
+            .set_event_info(model::EventInfo {
+                name: " Autumn Cup ".to_string(),
+                organization: "SC Example ".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            event_service.event_info().await.unwrap(),
+            model::EventInfo {
+                name: "Autumn Cup".to_string(),
+                organization: "SC Example".to_string(),
+            }
+        );
+
+        event_service
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+        let result = event_service
+            .set_event_info(model::EventInfo {
+                name: "Spring Cup".to_string(),
+                organization: String::new(),
+            })
+            .await;
+        assert!(
+            matches!(result, Err(SetEventInfoError::EventFinalized)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reopened_events_keep_the_reason() {
+        let event_service = Fixture::new().event_service();
+        let result = event_service
+            .reopen_event(model::EventPhase::MeetInProgress, "Wrong time")
+            .await;
+        assert!(
+            matches!(result, Err(ReopenEventError::EventNotFinalized)),
+            "{result:?}"
+        );
+        event_service
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+
+        let result = event_service
+            .reopen_event(model::EventPhase::MeetInProgress, " ")
+            .await;
+        assert!(
+            matches!(result, Err(ReopenEventError::MissingReason)),
+            "{result:?}"
+        );
+        event_service
+            .reopen_event(model::EventPhase::MeetInProgress, " Wrong time in heat 3 ")
+            .await
+            .unwrap();
+
+        let details = event_service.event_phase().await.unwrap();
+        assert_eq!(details.phase, model::EventPhase::MeetInProgress);
+        let reopened = details.changes.last().unwrap();
+        assert_eq!(reopened.phase, model::EventPhase::MeetInProgress);
+        assert_eq!(reopened.reason.as_deref(), Some("Wrong time in heat 3"));
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::Utc;
+use clubswimcomp_types::{api, model};
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::infra::typst_compiler::TypstOutput;
+use crate::{db, infra};
+
+use super::ServiceRepositoryError;
+
+pub struct FeeService {
+    payment_repo: Arc<dyn db::payments::Repository>,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
+}
+
+#[derive(Debug, Error)]
+pub enum SetEntryFeesError {
+    #[error("The fees are too high")]
+    InvalidFee,
+
+    #[error("The currency must be a three letter code")]
+    InvalidCurrency,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum AddPaymentError {
+    #[error("The payment must be received from either a participant or a group")]
+    InvalidPayer,
+
+    #[error("The amount must be positive and not too high")]
+    InvalidAmount,
+
+    #[error("The participant does not exist")]
+    ParticipantDoesNotExist,
+
+    #[error("The group does not exist")]
+    GroupDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RemovePaymentError {
+    #[error("The payment does not exist")]
+    PaymentDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum InvoiceError {
+    #[error("The group does not exist")]
+    GroupDoesNotExist,
+
+    #[error("Failed to generate the invoice PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum BalanceReportError {
+    #[error("Failed to generate the balance report PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl FeeService {
+    pub fn new(
+        payment_repo: Arc<dyn db::payments::Repository>,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
+    ) -> Self {
+        Self {
+            payment_repo,
+            participant_repo,
+            registration_repo,
+            competition_repo,
+            group_repo,
+            event_repo,
+            typst_compiler,
+        }
+    }
+
+    /// The fees every participant pays for the entries.
+    #[instrument(skip(self))]
+    pub async fn entry_fees(&self) -> Result<model::EntryFees, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::EntryFees::from(&event))
+    }
+
+    /// Change the fees of the entries.
+    ///
+    /// The amounts owed are always calculated from the current fees, so the
+    /// new fees apply to the existing registrations as well.
+    #[instrument(skip(self))]
+    pub async fn set_entry_fees(&self, fees: model::EntryFees) -> Result<(), SetEntryFeesError> {
+        let [participant_fee, start_fee, late_entry_surcharge] = [
+            fees.participant_fee,
+            fees.start_fee,
+            fees.late_entry_surcharge,
+        ]
+        .map(i32::try_from)
+        .map(|fee| fee.map_err(|_| SetEntryFeesError::InvalidFee));
+        let currency = fees.currency.trim().to_uppercase();
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(SetEntryFeesError::InvalidCurrency);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetEntryFeesError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the entry fees with the event");
+        self.event_repo
+            .update_entry_fees(
+                participant_fee?,
+                start_fee?,
+                late_entry_surcharge?,
+                &currency,
+            )
+            .await
+            .context("Failed to update entry fees in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
+    /// All payments, ordered by the day they have been received.
+    #[instrument(skip(self))]
+    pub async fn list_payments(&self) -> Result<Vec<model::Payment>, ServiceRepositoryError> {
+        Ok(self
+            .payment_repo
+            .all_payments()
+            .await
+            .context("Failed to fetch payments from repository")?
+            .into_iter()
+            .map(model::Payment::from)
+            .collect())
+    }
+
+    /// Record a payment of a participant or for a whole group.
+    #[instrument(skip(self))]
+    pub async fn add_payment(
+        &self,
+        payment: api::AddPaymentRequest,
+    ) -> Result<Uuid, AddPaymentError> {
+        let amount_cents = i32::try_from(payment.amount)
+            .ok()
+            .filter(|a| *a > 0)
+            .ok_or(AddPaymentError::InvalidAmount)?;
+
+        match (payment.participant_id, payment.group_id) {
+            (Some(participant_id), None) => {
+                tracing::debug!("Ensuring participant actually exists");
+                self.participant_repo
+                    .participant_by_id(participant_id)
+                    .await
+                    .context("Failed to fetch participant from repository")?
+                    .ok_or(AddPaymentError::ParticipantDoesNotExist)?;
+            }
+            (None, Some(group_id)) => {
+                tracing::debug!("Ensuring group actually exists");
+                self.group_repo
+                    .group_by_id(group_id)
+                    .await
+                    .context("Failed to fetch group from repository")?
+                    .ok_or(AddPaymentError::GroupDoesNotExist)?;
+            }
+            _ => return Err(AddPaymentError::InvalidPayer),
+        }
+
+        let payment = db::payments::Payment {
+            id: Uuid::new_v4(),
+            participant_id: payment.participant_id,
+            group_id: payment.group_id,
+            amount_cents,
+            received_on: payment.received_on,
+            reference: payment.reference.trim().to_string(),
+        };
+        self.payment_repo
+            .create_payment(&payment)
+            .await
+            .context("Failed to create payment in repository")?;
+
+        Ok(payment.id)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn remove_payment(&self, payment_id: Uuid) -> Result<(), RemovePaymentError> {
+        self.payment_repo
+            .delete_payment(payment_id)
+            .await
+            .context("Failed to delete payment from repository")?
+            .ok_or(RemovePaymentError::PaymentDoesNotExist)
+    }
+
+    /// What every participant and group owes and has paid.
+    ///
+    /// Every start in a timed final or in the prelims is charged, the finals
+    /// and swim-offs are free. Participants without a charged start do not
+    /// pay the participant fee.
+    #[instrument(skip(self))]
+    pub async fn balances(&self) -> Result<model::Balances, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let fees = model::EntryFees::from(&event);
+
+        tracing::debug!("Counting the charged starts of all participants");
+        let competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?;
+        let mut starts: HashMap<Uuid, (u32, u32)> = HashMap::new();
+        for competition in competitions
+            .iter()
+            .filter(|c| c.round.is_individual_start())
+        {
+            let registrations = self
+                .registration_repo
+                .registrations_for_competition(competition.id)
+                .await
+                .context("Failed to fetch registrations of competition from repository")?;
+            for registration in registrations {
+                let (starts, late_entries) = starts.entry(registration.participant_id).or_default();
+                *starts += 1;
+                *late_entries += registration.late_entry as u32;
+            }
+        }
+
+        let payments = self
+            .payment_repo
+            .all_payments()
+            .await
+            .context("Failed to fetch payments from repository")?;
+        // Summed up as u64, the payments are each limited to the range of i32
+        let paid_by = |payer: fn(&db::payments::Payment) -> Option<Uuid>, id: Uuid| -> u64 {
+            payments
+                .iter()
+                .filter(|p| payer(p) == Some(id))
+                .map(|p| p.amount_cents as u64)
+                .sum()
+        };
+
+        let mut participants = self
+            .participant_repo
+            .list_participants()
+            .await
+            .context("Failed to fetch participants from repository")?;
+        participants.sort_by(|a, b| {
+            (&a.last_name, &a.first_name, a.short_id).cmp(&(
+                &b.last_name,
+                &b.first_name,
+                b.short_id,
+            ))
+        });
+        let participants = participants
+            .into_iter()
+            .map(|participant| {
+                let (starts, late_entries) =
+                    starts.get(&participant.id).copied().unwrap_or_default();
+                let owed = amount_owed(&fees, starts, late_entries);
+                let paid = paid_by(|p| p.participant_id, participant.id);
+                model::ParticipantBalance {
+                    participant: participant.into(),
+                    starts,
+                    late_entries,
+                    owed,
+                    paid,
+                    outstanding: owed as i64 - paid as i64,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut groups = self
+            .group_repo
+            .all_groups()
+            .await
+            .context("Failed to fetch groups from repository")?;
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let members = participants
+                    .iter()
+                    .filter(|p| p.participant.group_id == group.id);
+                let (owed, members_paid) =
+                    members.fold((0, 0), |(owed, paid), p| (owed + p.owed, paid + p.paid));
+                let paid = members_paid + paid_by(|p| p.group_id, group.id);
+                model::GroupBalance {
+                    group: group.into(),
+                    owed,
+                    paid,
+                    outstanding: owed as i64 - paid as i64,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(model::Balances {
+            owed: groups.iter().map(|g| g.owed).sum(),
+            paid: payments.iter().map(|p| p.amount_cents as u64).sum(),
+            currency: fees.currency,
+            participants,
+            groups,
+        })
+    }
+
+    /// Generate the invoice of the entry fees of the group.
+    #[instrument(skip(self))]
+    pub async fn group_invoice(
+        &self,
+        group_id: Uuid,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, InvoiceError> {
+        tracing::debug!("Ensuring group actually exists");
+        self.group_repo
+            .group_by_id(group_id)
+            .await
+            .context("Failed to fetch group from repository")?
+            .ok_or(InvoiceError::GroupDoesNotExist)?;
+
+        self.generate_invoices(Some(group_id), output).await
+    }
+
+    /// Generate the invoices of all groups that owe or paid anything.
+    #[instrument(skip(self))]
+    pub async fn invoices(&self, output: TypstOutput) -> Result<Vec<u8>, InvoiceError> {
+        self.generate_invoices(None, output).await
+    }
+
+    /// Generate the report of all groups that have not paid exactly what
+    /// they owe.
+    #[instrument(skip(self))]
+    pub async fn balance_report(&self, output: TypstOutput) -> Result<Vec<u8>, BalanceReportError> {
+        let balances = self.balances().await.map_err(|e| match e {
+            ServiceRepositoryError::RepositoryError(e) => BalanceReportError::RepositoryError(e),
+        })?;
+        let currency = balances.currency.as_str();
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+
+        let report = infra::balance_report::BalanceReport {
+            event_name: event.name,
+            organization: event.organization,
+            date: Utc::now().format("%d.%m.%Y").to_string(),
+            groups: balances
+                .groups
+                .iter()
+                .filter(|g| g.outstanding != 0)
+                .map(|g| infra::balance_report::GroupBalance {
+                    name: g.group.name.clone(),
+                    participants: balances
+                        .participants
+                        .iter()
+                        .filter(|p| p.participant.group_id == g.group.id && p.starts > 0)
+                        .count() as u32,
+                    owed: format_amount(g.owed as i64, currency),
+                    paid: format_amount(g.paid as i64, currency),
+                    outstanding: format_amount(g.outstanding, currency),
+                })
+                .collect(),
+            owed: format_amount(balances.owed as i64, currency),
+            paid: format_amount(balances.paid as i64, currency),
+            outstanding: format_amount(balances.owed as i64 - balances.paid as i64, currency),
+        };
+
+        tracing::debug!("Generating balance report PDF");
+        report
+            .generate(&self.typst_compiler, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    BalanceReportError::PageDoesNotExist,
+                    BalanceReportError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    /// Generate the invoice of a single group or of all groups.
+    async fn generate_invoices(
+        &self,
+        group_id: Option<Uuid>,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, InvoiceError> {
+        let balances = self.balances().await.map_err(|e| match e {
+            ServiceRepositoryError::RepositoryError(e) => InvoiceError::RepositoryError(e),
+        })?;
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let fees = model::EntryFees::from(&event);
+        let payments = self
+            .payment_repo
+            .all_payments()
+            .await
+            .context("Failed to fetch payments from repository")?;
+        let currency = fees.currency.as_str();
+
+        let invoices = balances
+            .groups
+            .iter()
+            .filter(|g| match group_id {
+                Some(group_id) => g.group.id == group_id,
+                None => g.owed > 0 || g.paid > 0,
+            })
+            .map(|g| {
+                let members = balances
+                    .participants
+                    .iter()
+                    .filter(|p| p.participant.group_id == g.group.id);
+                infra::invoice::Invoice {
+                    group: g.group.name.clone(),
+                    lines: members
+                        .clone()
+                        .filter(|p| p.starts > 0)
+                        .map(|p| infra::invoice::InvoiceLine {
+                            first_name: p.participant.first_name.clone(),
+                            last_name: p.participant.last_name.clone(),
+                            participant_fee: format_amount(fees.participant_fee as i64, currency),
+                            starts: p.starts,
+                            start_fees: format_amount(
+                                p.starts as i64 * fees.start_fee as i64,
+                                currency,
+                            ),
+                            late_entries: p.late_entries,
+                            surcharges: format_amount(
+                                p.late_entries as i64 * fees.late_entry_surcharge as i64,
+                                currency,
+                            ),
+                            amount: format_amount(p.owed as i64, currency),
+                        })
+                        .collect(),
+                    payments: payments
+                        .iter()
+                        .filter(|payment| {
+                            payment.group_id == Some(g.group.id)
+                                || members
+                                    .clone()
+                                    .any(|p| payment.participant_id == Some(p.participant.id))
+                        })
+                        .map(|payment| infra::invoice::InvoicePayment {
+                            received_on: payment.received_on.format("%d.%m.%Y").to_string(),
+                            reference: payment.reference.clone(),
+                            amount: format_amount(payment.amount_cents as i64, currency),
+                        })
+                        .collect(),
+                    owed: format_amount(g.owed as i64, currency),
+                    paid: format_amount(g.paid as i64, currency),
+                    outstanding: format_amount(g.outstanding, currency),
+                }
+            })
+            .collect();
+
+        let invoices = infra::invoice::Invoices {
+            event_name: event.name,
+            organization: event.organization,
+            date: Utc::now().format("%d.%m.%Y").to_string(),
+            invoices,
+        };
+
+        tracing::debug!("Generating invoice PDF");
+        invoices
+            .generate(&self.typst_compiler, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    InvoiceError::PageDoesNotExist,
+                    InvoiceError::PdfGenerationFailed,
+                )
+            })
+    }
+}
+
+/// The amount a participant owes for the charged starts.
+fn amount_owed(fees: &model::EntryFees, starts: u32, late_entries: u32) -> u64 {
+    if starts == 0 {
+        return 0;
+    }
+    u64::from(fees.participant_fee)
+        .saturating_add(u64::from(starts) * u64::from(fees.start_fee))
+        .saturating_add(u64::from(late_entries) * u64::from(fees.late_entry_surcharge))
+}
+
+/// Format an amount of cents as it is printed, e.g. `12,50 EUR`.
+fn format_amount(cents: i64, currency: &str) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    format!("{sign}{},{:02} {currency}", cents / 100, cents % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    fn fees() -> model::EntryFees {
+        model::EntryFees {
+            participant_fee: 500,
+            start_fee: 350,
+            late_entry_surcharge: 200,
+            currency: "eur".to_string(),
+        }
+    }
+
+    fn payment(
+        participant_id: Option<Uuid>,
+        group_id: Option<Uuid>,
+        amount: u32,
+    ) -> api::AddPaymentRequest {
+        api::AddPaymentRequest {
+            participant_id,
+            group_id,
+            amount,
+            received_on: NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+            reference: " Transfer ".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn entry_fees_are_kept_after_finalization() {
+        let fixture = Fixture::new();
+        let service = fixture.fee_service();
+        service.set_entry_fees(fees()).await.unwrap();
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+
+        let result = service
+            .set_entry_fees(model::EntryFees {
+                start_fee: 400,
+                ..fees()
+            })
+            .await;
+
+        assert!(
+            matches!(result, Err(SetEntryFeesError::EventFinalized)),
+            "{result:?}"
+        );
+        assert_eq!(service.entry_fees().await.unwrap().start_fee, 350);
+    }
+
+    #[tokio::test]
+    async fn balances_charge_starts_but_not_finals() {
+        let fixture = Fixture::new();
+        let service = fixture.fee_service();
+        service.set_entry_fees(fees()).await.unwrap();
+        let group_id = fixture.group("Dolphins").await;
+        let swimmer = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let idle = fixture.participant(group_id, Gender::Female, "Idle").await;
+        let prelims = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        let timed_final = fixture
+            .competition(Gender::Female, Stroke::Breast, 50, 45_000)
+            .await;
+        fixture.register(swimmer, prelims).await;
+        fixture
+            .repos
+            .registrations
+            .create_registration(swimmer, timed_final, None, true)
+            .await
+            .unwrap();
+        let finals_id = fixture
+            .finals_service()
+            .set_finals(
+                prelims,
+                api::FinalsRequest {
+                    finalists: 1,
+                    alternates: 0,
+                },
+            )
+            .await
+            .unwrap();
+        fixture.register(swimmer, finals_id).await;
+        service
+            .add_payment(payment(Some(swimmer), None, 1_000))
+            .await
+            .unwrap();
+        service
+            .add_payment(payment(None, Some(group_id), 500))
+            .await
+            .unwrap();
+
+        let balances = service.balances().await.unwrap();
+
+        assert_eq!(balances.currency, "EUR");
+        let owed = balances
+            .participants
+            .iter()
+            .map(|p| {
+                (
+                    p.participant.id,
+                    p.starts,
+                    p.late_entries,
+                    p.owed,
+                    p.outstanding,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(owed, vec![(idle, 0, 0, 0, 0), (swimmer, 2, 1, 1_400, 400)]);
+        assert_eq!(balances.groups[0].owed, 1_400);
+        assert_eq!(balances.groups[0].paid, 1_500);
+        assert_eq!(balances.groups[0].outstanding, -100);
+        assert_eq!((balances.owed, balances.paid), (1_400, 1_500));
+    }
+
+    #[tokio::test]
+    async fn large_payments_are_summed_up_without_overflow() {
+        let fixture = Fixture::new();
+        let service = fixture.fee_service();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let amount = i32::MAX as u32;
+        service
+            .add_payment(payment(Some(participant_id), None, amount))
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            service
+                .add_payment(payment(None, Some(group_id), amount))
+                .await
+                .unwrap();
+        }
+
+        let balances = service.balances().await.unwrap();
+
+        assert_eq!(balances.groups[0].paid, 3 * amount as u64);
+        assert_eq!(balances.groups[0].outstanding, -3 * amount as i64);
+        assert_eq!(balances.paid, 3 * amount as u64);
+    }
+
+    #[tokio::test]
+    async fn payments_need_exactly_one_existing_payer() {
+        let fixture = Fixture::new();
+        let service = fixture.fee_service();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+
+        assert!(matches!(
+            service
+                .add_payment(payment(Some(participant_id), Some(group_id), 100))
+                .await,
+            Err(AddPaymentError::InvalidPayer)
+        ));
+        assert!(matches!(
+            service.add_payment(payment(None, None, 100)).await,
+            Err(AddPaymentError::InvalidPayer)
+        ));
+        assert!(matches!(
+            service.add_payment(payment(None, Some(group_id), 0)).await,
+            Err(AddPaymentError::InvalidAmount)
+        ));
+        assert!(matches!(
+            service
+                .add_payment(payment(Some(Uuid::new_v4()), None, 100))
+                .await,
+            Err(AddPaymentError::ParticipantDoesNotExist)
+        ));
+
+        let payment_id = service
+            .add_payment(payment(None, Some(group_id), 100))
+            .await
+            .unwrap();
+        let payments = service.list_payments().await.unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].reference, "Transfer");
+
+        service.remove_payment(payment_id).await.unwrap();
+        assert!(matches!(
+            service.remove_payment(payment_id).await,
+            Err(RemovePaymentError::PaymentDoesNotExist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn currencies_are_three_letter_codes() {
+        let fixture = Fixture::new();
+        let service = fixture.fee_service();
+
+        assert!(matches!(
+            service
+                .set_entry_fees(model::EntryFees {
+                    currency: "Euro".to_string(),
+                    ..fees()
+                })
+                .await,
+            Err(SetEntryFeesError::InvalidCurrency)
+        ));
+        assert_eq!(service.entry_fees().await.unwrap(), Default::default());
+
+        service.set_entry_fees(fees()).await.unwrap();
+        assert_eq!(
+            service.entry_fees().await.unwrap(),
+            model::EntryFees {
+                currency: "EUR".to_string(),
+                ..fees()
+            }
+        );
+    }
+
+    #[test]
+    fn amounts_are_formatted_with_decimal_comma() {
+        assert_eq!(format_amount(1_250, "EUR"), "12,50 EUR");
+        assert_eq!(format_amount(5, "CHF"), "0,05 CHF");
+        assert_eq!(format_amount(-150, "EUR"), "-1,50 EUR");
+    }
+}
@@ -0,0 +1,998 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use clubswimcomp_types::{api, model};
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::db::competitions::CompetitionRound;
+use crate::db::finals::FinalsEntryStatus;
+use crate::{db, infra, services::score::ScoreService};
+
+use super::CompetitionScoreboardError;
+
+/// Promotion of the fastest swimmers of the prelims into the finals.
+pub struct FinalsService {
+    finals_repo: Arc<dyn db::finals::Repository>,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
+}
+
+#[derive(Debug, Error)]
+pub enum FinalsDetailsError {
+    #[error("The competition has no finals")]
+    CompetitionHasNoFinals,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetFinalsError {
+    #[error("The competition does not exist")]
+    CompetitionDoesNotExist,
+
+    #[error("At least one swimmer must advance into the finals")]
+    InvalidFinalists,
+
+    #[error("Only competitions swum in prelims can have finals")]
+    NotPrelims,
+
+    #[error("The finalists have already been promoted")]
+    FinalistsAlreadyPromoted,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PromoteFinalistsError {
+    #[error("The competition has no finals")]
+    CompetitionHasNoFinals,
+
+    #[error("Not every swimmer of the prelims has a result yet")]
+    PrelimsNotFinished,
+
+    #[error("The finalists have already been promoted")]
+    FinalistsAlreadyPromoted,
+
+    #[error("Swimmers are tied for the last place in the finals, a swim-off is required")]
+    SwimOffRequired,
+
+    #[error("The swim-off of the tie at the cutoff has no results yet")]
+    SwimOffNotFinished,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum AddSwimOffError {
+    #[error("The competition has no finals")]
+    CompetitionHasNoFinals,
+
+    #[error("Not every swimmer of the prelims has a result yet")]
+    PrelimsNotFinished,
+
+    #[error("The finalists have already been promoted")]
+    FinalistsAlreadyPromoted,
+
+    #[error("No swimmers are tied for the last place in the finals")]
+    NoTieAtCutoff,
+
+    #[error("The swim-off of the tie at the cutoff has no results yet")]
+    SwimOffNotFinished,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ScratchFinalistError {
+    #[error("The competition has no finals")]
+    CompetitionHasNoFinals,
+
+    #[error("The finalists have not been promoted yet")]
+    FinalistsNotPromoted,
+
+    #[error("The participant is not a finalist")]
+    NotAFinalist,
+
+    #[error("The finalist already has a result in the finals")]
+    ResultAlreadyExists,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+/// The swimmers of the prelims grouped by their place, best first, with the
+/// results of the swim-offs applied.
+struct Standings {
+    places: Vec<Vec<model::CompetitionScore>>,
+    /// Swimmers of the swim-offs that have no results yet.
+    pending_swim_offs: Vec<HashSet<Uuid>>,
+}
+
+impl Standings {
+    /// The swimmers that are tied across the last place in the finals.
+    fn tie_at_cutoff(&self, finalists: usize) -> Option<&[model::CompetitionScore]> {
+        let mut placed = 0;
+        for place in self.places.iter() {
+            if placed < finalists && placed + place.len() > finalists {
+                return Some(place);
+            }
+            placed += place.len();
+        }
+        None
+    }
+
+    /// A swim-off of exactly these swimmers is still waiting for its results.
+    fn swim_off_pending(&self, tie: &[model::CompetitionScore]) -> bool {
+        let tied = participant_ids(tie);
+        self.pending_swim_offs.contains(&tied)
+    }
+}
+
+fn participant_ids(scores: &[model::CompetitionScore]) -> HashSet<Uuid> {
+    scores.iter().map(|s| s.participant.id).collect()
+}
+
+impl FinalsService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        finals_repo: Arc<dyn db::finals::Repository>,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
+    ) -> Self {
+        Self {
+            finals_repo,
+            participant_repo,
+            registration_repo,
+            competition_repo,
+            group_repo,
+            event_repo,
+            best_time_repo,
+            payment_repo,
+            typst_compiler,
+        }
+    }
+
+    fn score_service(&self) -> ScoreService {
+        ScoreService::new(
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    /// The finals of the competition with the promoted swimmers.
+    ///
+    /// # Parameters:
+    /// - `competition_id` - The id of the prelims
+    #[instrument(skip(self))]
+    pub async fn finals(&self, competition_id: Uuid) -> Result<model::Finals, FinalsDetailsError> {
+        tracing::debug!("Fetching finals of competition from repository");
+        let finals = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?
+            .ok_or(FinalsDetailsError::CompetitionHasNoFinals)?;
+
+        tracing::debug!("Fetching entries of the finals from repository");
+        let db_entries = self
+            .finals_repo
+            .finals_entries(competition_id)
+            .await
+            .context("Failed to fetch finals entries from repository")?;
+
+        let mut entries = Vec::with_capacity(db_entries.len());
+        for entry in db_entries.into_iter() {
+            let participant = self
+                .participant_repo
+                .participant_by_id(entry.participant_id)
+                .await
+                .context("Failed to fetch participant from repository")?
+                .map(model::Participant::from)
+                .context(
+                    "Participant is referenced in the finals but does not exist in the repository",
+                )?;
+
+            entries.push(model::FinalsEntry {
+                participant,
+                seed: entry.seed as _,
+                time_millis: entry.time_millis as _,
+                status: entry.status.into(),
+            });
+        }
+
+        Ok(model::Finals {
+            competition_id: finals.competition_id,
+            finals_competition_id: finals.finals_competition_id,
+            finalists: finals.finalists as _,
+            alternates: finals.alternates as _,
+            entries,
+            swim_off_ids: finals.swim_off_ids,
+        })
+    }
+
+    /// Swim the competition in prelims and finals, or change the number of
+    /// finalists of the existing finals.
+    ///
+    /// The finals are created as a competition of the same kind, the
+    /// competition itself becomes the prelims.
+    ///
+    /// # Returns:
+    /// The id of the finals competition.
+    #[instrument(skip(self))]
+    pub async fn set_finals(
+        &self,
+        competition_id: Uuid,
+        request: api::FinalsRequest,
+    ) -> Result<Uuid, SetFinalsError> {
+        if request.finalists == 0
+            || request.finalists > i32::MAX as u32
+            || request.alternates > i32::MAX as u32
+        {
+            return Err(SetFinalsError::InvalidFinalists);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetFinalsError::EventFinalized);
+        }
+
+        tracing::debug!("Fetching competition from repository");
+        let competition = self
+            .competition_repo
+            .competition_by_id(competition_id)
+            .await
+            .context("Failed to fetch competition from repository")?
+            .ok_or(SetFinalsError::CompetitionDoesNotExist)?;
+        if !matches!(
+            competition.round,
+            CompetitionRound::TimedFinal | CompetitionRound::Prelims
+        ) {
+            tracing::debug!(round = ?competition.round, "Competition is not swum in prelims");
+            return Err(SetFinalsError::NotPrelims);
+        }
+
+        let existing = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?;
+
+        if let Some(mut finals) = existing {
+            let promoted = !self
+                .finals_repo
+                .finals_entries(competition_id)
+                .await
+                .context("Failed to fetch finals entries from repository")?
+                .is_empty();
+            if promoted {
+                return Err(SetFinalsError::FinalistsAlreadyPromoted);
+            }
+
+            tracing::debug!("Updating the number of finalists in repository");
+            finals.finalists = request.finalists as _;
+            finals.alternates = request.alternates as _;
+            self.finals_repo
+                .update_finals(&finals)
+                .await
+                .context("Failed to update finals in repository")?
+                .context("Finals are missing in repository even though they exist")?;
+
+            return Ok(finals.finals_competition_id);
+        }
+
+        let finals_competition = db::competitions::Competition {
+            id: Uuid::new_v4(),
+            round: CompetitionRound::Finals,
+            ..competition
+        };
+        let finals = db::finals::Finals {
+            competition_id,
+            finals_competition_id: finals_competition.id,
+            finalists: request.finalists as _,
+            alternates: request.alternates as _,
+            swim_off_ids: Vec::new(),
+        };
+
+        tracing::debug!("Creating the finals in repository");
+        self.finals_repo
+            .create_finals(&finals, &finals_competition)
+            .await
+            .context("Failed to create finals in repository")?;
+
+        Ok(finals_competition.id)
+    }
+
+    /// Promote the fastest swimmers of the prelims into the finals.
+    ///
+    /// The swimmers after the finalists become the alternates. A tie across
+    /// the last place in the finals has to be decided by a swim-off first,
+    /// ties across the last alternate place are promoted as alternates
+    /// together.
+    #[instrument(skip(self))]
+    pub async fn promote_finalists(
+        &self,
+        competition_id: Uuid,
+    ) -> Result<(), PromoteFinalistsError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(PromoteFinalistsError::EventFinalized);
+        }
+
+        let finals = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?
+            .ok_or(PromoteFinalistsError::CompetitionHasNoFinals)?;
+
+        let promoted = !self
+            .finals_repo
+            .finals_entries(competition_id)
+            .await
+            .context("Failed to fetch finals entries from repository")?
+            .is_empty();
+        if promoted {
+            return Err(PromoteFinalistsError::FinalistsAlreadyPromoted);
+        }
+
+        let standings = self
+            .standings(&finals)
+            .await?
+            .ok_or(PromoteFinalistsError::PrelimsNotFinished)?;
+
+        let finalists = finals.finalists as usize;
+        if let Some(tie) = standings.tie_at_cutoff(finalists) {
+            tracing::debug!(tied = tie.len(), "Swimmers are tied at the cutoff");
+            return if standings.swim_off_pending(tie) {
+                Err(PromoteFinalistsError::SwimOffNotFinished)
+            } else {
+                Err(PromoteFinalistsError::SwimOffRequired)
+            };
+        }
+
+        let mut entries = Vec::new();
+        for place in standings.places.iter() {
+            let placed = entries.len();
+            let status = if placed < finalists {
+                FinalsEntryStatus::Finalist
+            } else if placed < finalists + finals.alternates as usize {
+                FinalsEntryStatus::Alternate
+            } else {
+                break;
+            };
+
+            for score in place.iter() {
+                entries.push(db::finals::FinalsEntry {
+                    competition_id,
+                    participant_id: score.participant.id,
+                    seed: entries.len() as i32 + 1,
+                    time_millis: score.time as _,
+                    status,
+                });
+            }
+        }
+
+        tracing::debug!(
+            "Storing the entries of the finals and registering the finalists in repository"
+        );
+        self.finals_repo
+            .promote_finalists(finals.finals_competition_id, &entries)
+            .await
+            .context("Failed to promote finalists in repository")?;
+
+        Ok(())
+    }
+
+    /// Add a swim-off for the swimmers tied across the last place in the
+    /// finals.
+    ///
+    /// The tied swimmers are registered with their time of the prelims.
+    ///
+    /// # Returns:
+    /// The id of the swim-off competition.
+    #[instrument(skip(self))]
+    pub async fn add_swim_off(&self, competition_id: Uuid) -> Result<Uuid, AddSwimOffError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(AddSwimOffError::EventFinalized);
+        }
+
+        let finals = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?
+            .ok_or(AddSwimOffError::CompetitionHasNoFinals)?;
+
+        let promoted = !self
+            .finals_repo
+            .finals_entries(competition_id)
+            .await
+            .context("Failed to fetch finals entries from repository")?
+            .is_empty();
+        if promoted {
+            return Err(AddSwimOffError::FinalistsAlreadyPromoted);
+        }
+
+        let standings = self
+            .standings(&finals)
+            .await?
+            .ok_or(AddSwimOffError::PrelimsNotFinished)?;
+        let tie = standings
+            .tie_at_cutoff(finals.finalists as usize)
+            .ok_or(AddSwimOffError::NoTieAtCutoff)?;
+        if standings.swim_off_pending(tie) {
+            return Err(AddSwimOffError::SwimOffNotFinished);
+        }
+
+        let prelims = self
+            .competition_repo
+            .competition_by_id(competition_id)
+            .await
+            .context("Failed to fetch competition from repository")?
+            .context("Competition is referenced by the finals but does not exist")?;
+        let swim_off = db::competitions::Competition {
+            id: Uuid::new_v4(),
+            round: CompetitionRound::SwimOff,
+            ..prelims
+        };
+
+        tracing::debug!(tied = tie.len(), "Creating swim-off in repository");
+        self.finals_repo
+            .create_swim_off(competition_id, &swim_off)
+            .await
+            .context("Failed to create swim-off in repository")?;
+
+        for score in tie.iter() {
+            self.registration_repo
+                .create_registration(
+                    score.participant.id,
+                    swim_off.id,
+                    Some(score.time as _),
+                    false,
+                )
+                .await
+                .context("Failed to create registration in repository")?;
+        }
+
+        Ok(swim_off.id)
+    }
+
+    /// Withdraw a finalist from the finals.
+    ///
+    /// The best placed alternate moves up into the finals in their place.
+    ///
+    /// # Returns:
+    /// The participant id of the alternate that moved up, if there is one
+    /// left.
+    #[instrument(skip(self))]
+    pub async fn scratch_finalist(
+        &self,
+        competition_id: Uuid,
+        participant_id: Uuid,
+    ) -> Result<Option<Uuid>, ScratchFinalistError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(ScratchFinalistError::EventFinalized);
+        }
+
+        let finals = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?
+            .ok_or(ScratchFinalistError::CompetitionHasNoFinals)?;
+
+        let entries = self
+            .finals_repo
+            .finals_entries(competition_id)
+            .await
+            .context("Failed to fetch finals entries from repository")?;
+        if entries.is_empty() {
+            return Err(ScratchFinalistError::FinalistsNotPromoted);
+        }
+        if !entries
+            .iter()
+            .any(|e| e.participant_id == participant_id && e.status == FinalsEntryStatus::Finalist)
+        {
+            return Err(ScratchFinalistError::NotAFinalist);
+        }
+
+        let registration = self
+            .registration_repo
+            .registrations_for_competition(finals.finals_competition_id)
+            .await
+            .context("Failed to fetch registrations for finals from repository")?
+            .into_iter()
+            .find(|r| r.participant_id == participant_id);
+
+        if let Some(registration) = registration {
+            let has_result = self
+                .registration_repo
+                .result_for_registration(registration.id)
+                .await
+                .context("Failed to fetch result for registration from repository")?
+                .is_some();
+            if has_result {
+                return Err(ScratchFinalistError::ResultAlreadyExists);
+            }
+
+            tracing::debug!("Deleting the registration of the finalist from repository");
+            self.registration_repo
+                .delete_registration(registration.id)
+                .await
+                .context("Failed to delete registration in repository")?;
+        }
+
+        self.finals_repo
+            .update_finals_entry_status(
+                competition_id,
+                participant_id,
+                FinalsEntryStatus::Scratched,
+            )
+            .await
+            .context("Failed to update finals entry in repository")?
+            .context("Finals entry is missing in repository even though it exists")?;
+
+        // The entries are ordered by their seed
+        let Some(alternate) = entries
+            .iter()
+            .find(|e| e.status == FinalsEntryStatus::Alternate)
+        else {
+            tracing::debug!("No alternate left to move up into the finals");
+            return Ok(None);
+        };
+
+        tracing::debug!(participant_id = ?alternate.participant_id, "Moving alternate up into the finals");
+        self.registration_repo
+            .create_registration(
+                alternate.participant_id,
+                finals.finals_competition_id,
+                Some(alternate.time_millis),
+                false,
+            )
+            .await
+            .context("Failed to create registration in repository")?;
+        self.finals_repo
+            .update_finals_entry_status(
+                competition_id,
+                alternate.participant_id,
+                FinalsEntryStatus::Finalist,
+            )
+            .await
+            .context("Failed to update finals entry in repository")?
+            .context("Finals entry is missing in repository even though it exists")?;
+
+        Ok(Some(alternate.participant_id))
+    }
+
+    /// The places of the prelims with the results of the finished swim-offs
+    /// applied, `None` if not every swimmer of the prelims has a result.
+    ///
+    /// A swim-off decides the order of the tied swimmers it has been swum by.
+    /// Disqualified swimmers of a swim-off are placed behind the others.
+    async fn standings(&self, finals: &db::finals::Finals) -> Result<Option<Standings>> {
+        let prelims = self.scoreboard(finals.competition_id).await?;
+        if !prelims.missing_results.is_empty() {
+            tracing::debug!(
+                missing = prelims.missing_results.len(),
+                "Prelims are missing results"
+            );
+            return Ok(None);
+        }
+
+        let mut standings = Standings {
+            places: group_by_rank(prelims.scores),
+            pending_swim_offs: Vec::new(),
+        };
+
+        for swim_off_id in finals.swim_off_ids.iter() {
+            let swim_off = self.scoreboard(*swim_off_id).await?;
+            let swimmers: HashSet<Uuid> = swim_off
+                .scores
+                .iter()
+                .map(|s| s.participant.id)
+                .chain(swim_off.disqualifications.iter().map(|r| r.participant.id))
+                .chain(swim_off.missing_results.iter().map(|r| r.participant.id))
+                .collect();
+
+            if !swim_off.missing_results.is_empty() {
+                standings.pending_swim_offs.push(swimmers);
+                continue;
+            }
+
+            let Some(index) = standings
+                .places
+                .iter()
+                .position(|p| participant_ids(p) == swimmers)
+            else {
+                tracing::warn!(?swim_off_id, "Swim-off does not match a tie of the prelims");
+                continue;
+            };
+
+            let tied = standings.places.remove(index);
+            let mut decided: Vec<Vec<model::CompetitionScore>> = group_by_rank(swim_off.scores)
+                .into_iter()
+                .map(|place| {
+                    place
+                        .iter()
+                        .filter_map(|s| tied.iter().find(|t| t.participant.id == s.participant.id))
+                        .cloned()
+                        .collect()
+                })
+                .collect();
+            let disqualified: Vec<model::CompetitionScore> = tied
+                .iter()
+                .filter(|t| {
+                    swim_off
+                        .disqualifications
+                        .iter()
+                        .any(|r| r.participant.id == t.participant.id)
+                })
+                .cloned()
+                .collect();
+            if !disqualified.is_empty() {
+                decided.push(disqualified);
+            }
+
+            standings.places.splice(index..index, decided);
+        }
+
+        Ok(Some(standings))
+    }
+
+    async fn scoreboard(&self, competition_id: Uuid) -> Result<model::CompetitionScoreboard> {
+        self.score_service()
+            .competition_scoreboard(competition_id)
+            .await
+            .map_err(|err| match err {
+                CompetitionScoreboardError::CompetitionDoesNotExist => anyhow::anyhow!(
+                    "Competition {competition_id} is referenced by the finals but does not exist"
+                ),
+                CompetitionScoreboardError::RepositoryError(err) => err,
+            })
+    }
+}
+
+/// Group the scores by their rank, best first.
+fn group_by_rank(mut scores: Vec<model::CompetitionScore>) -> Vec<Vec<model::CompetitionScore>> {
+    scores.sort_by(|a, b| {
+        a.rank
+            .cmp(&b.rank)
+            .then_with(|| a.participant.last_name.cmp(&b.participant.last_name))
+            .then_with(|| a.participant.first_name.cmp(&b.participant.first_name))
+    });
+
+    let mut places: Vec<Vec<model::CompetitionScore>> = Vec::new();
+    for score in scores.into_iter() {
+        match places.last_mut() {
+            Some(place) if place[0].rank == score.rank => place.push(score),
+            _ => places.push(vec![score]),
+        }
+    }
+    places
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Prelims with a finals of two finalists and one alternate, the
+    /// swimmers swim the given times in the order of their names.
+    async fn prelims(fixture: &Fixture, times: &[i32]) -> (Uuid, Vec<Uuid>) {
+        let group_id = fixture.group("Dolphins").await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+
+        let mut participant_ids = Vec::new();
+        for (index, time) in times.iter().enumerate() {
+            let participant_id = fixture
+                .participant(group_id, Gender::Female, &format!("Swimmer {index}"))
+                .await;
+            let registration_id = fixture.register(participant_id, competition_id).await;
+            fixture.result(registration_id, *time, false).await;
+            participant_ids.push(participant_id);
+        }
+
+        fixture
+            .finals_service()
+            .set_finals(
+                competition_id,
+                api::FinalsRequest {
+                    finalists: 2,
+                    alternates: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        (competition_id, participant_ids)
+    }
+
+    async fn statuses(
+        fixture: &Fixture,
+        competition_id: Uuid,
+    ) -> Vec<(Uuid, model::FinalsEntryStatus)> {
+        fixture
+            .finals_service()
+            .finals(competition_id)
+            .await
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|e| (e.participant.id, e.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn fastest_swimmers_are_promoted_into_the_finals() {
+        let fixture = Fixture::new();
+        let (competition_id, swimmers) = prelims(&fixture, &[33_000, 31_000, 32_000, 34_000]).await;
+        let finals_service = fixture.finals_service();
+
+        finals_service
+            .promote_finalists(competition_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            statuses(&fixture, competition_id).await,
+            vec![
+                (swimmers[1], model::FinalsEntryStatus::Finalist),
+                (swimmers[2], model::FinalsEntryStatus::Finalist),
+                (swimmers[0], model::FinalsEntryStatus::Alternate),
+            ]
+        );
+
+        let finals = finals_service.finals(competition_id).await.unwrap();
+        let registrations = fixture
+            .repos
+            .registrations
+            .registrations_for_competition(finals.finals_competition_id)
+            .await
+            .unwrap();
+        assert_eq!(registrations.len(), 2);
+        assert!(registrations
+            .iter()
+            .any(|r| r.participant_id == swimmers[1] && r.entry_time_millis == Some(31_000)));
+
+        let prelims = fixture
+            .competition_service()
+            .competition_details(competition_id)
+            .await
+            .unwrap();
+        assert_eq!(prelims.competition.round, model::CompetitionRound::Prelims);
+        assert!(matches!(
+            finals_service.promote_finalists(competition_id).await,
+            Err(PromoteFinalistsError::FinalistsAlreadyPromoted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn finalists_are_promoted_with_the_last_result_of_the_prelims() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        fixture
+            .finals_service()
+            .set_finals(
+                competition_id,
+                api::FinalsRequest {
+                    finalists: 2,
+                    alternates: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        let registration_service = fixture.registration_service();
+        let mut swimmers = Vec::new();
+        for (index, time) in [33_000, 31_000, 32_000].into_iter().enumerate() {
+            let participant_id = fixture
+                .participant(group_id, Gender::Female, &format!("Swimmer {index}"))
+                .await;
+            let registration_id = fixture.register(participant_id, competition_id).await;
+            swimmers.push((participant_id, registration_id, time));
+        }
+        for (_, registration_id, time) in swimmers.iter().take(2) {
+            registration_service
+                .add_result_for_registration(*registration_id, false, *time)
+                .await
+                .unwrap();
+        }
+        assert_eq!(statuses(&fixture, competition_id).await, vec![]);
+
+        let (_, registration_id, time) = swimmers[2];
+        registration_service
+            .add_result_for_registration(registration_id, false, time)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            statuses(&fixture, competition_id).await,
+            vec![
+                (swimmers[1].0, model::FinalsEntryStatus::Finalist),
+                (swimmers[2].0, model::FinalsEntryStatus::Finalist),
+                (swimmers[0].0, model::FinalsEntryStatus::Alternate),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn results_of_the_prelims_are_kept_once_the_finalists_are_promoted() {
+        let fixture = Fixture::new();
+        let (competition_id, swimmers) = prelims(&fixture, &[31_000, 32_000, 33_000]).await;
+        fixture
+            .finals_service()
+            .promote_finalists(competition_id)
+            .await
+            .unwrap();
+        let registration_id = fixture
+            .repos
+            .registrations
+            .registrations_for_competition(competition_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|r| r.participant_id == swimmers[2])
+            .unwrap()
+            .id;
+
+        let registration_service = fixture.registration_service();
+        assert!(matches!(
+            registration_service
+                .remove_registration_result(registration_id)
+                .await,
+            Err(crate::services::RemoveRegistrationResultError::FinalistsAlreadyPromoted)
+        ));
+        assert!(matches!(
+            registration_service
+                .add_result_for_registration(registration_id, false, 30_000)
+                .await,
+            Err(crate::services::AddRegistrationResultError::FinalistsAlreadyPromoted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn tie_at_the_cutoff_is_decided_by_a_swim_off() {
+        let fixture = Fixture::new();
+        let (competition_id, swimmers) = prelims(&fixture, &[31_000, 32_000, 32_000, 34_000]).await;
+        let finals_service = fixture.finals_service();
+
+        assert!(matches!(
+            finals_service.promote_finalists(competition_id).await,
+            Err(PromoteFinalistsError::SwimOffRequired)
+        ));
+
+        let swim_off_id = finals_service.add_swim_off(competition_id).await.unwrap();
+        assert!(matches!(
+            finals_service.promote_finalists(competition_id).await,
+            Err(PromoteFinalistsError::SwimOffNotFinished)
+        ));
+
+        let registrations = fixture
+            .repos
+            .registrations
+            .registrations_for_competition(swim_off_id)
+            .await
+            .unwrap();
+        assert_eq!(registrations.len(), 2);
+        for registration in registrations.iter() {
+            let time = if registration.participant_id == swimmers[2] {
+                31_500
+            } else {
+                31_800
+            };
+            fixture.result(registration.id, time, false).await;
+        }
+
+        finals_service
+            .promote_finalists(competition_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            statuses(&fixture, competition_id).await,
+            vec![
+                (swimmers[0], model::FinalsEntryStatus::Finalist),
+                (swimmers[2], model::FinalsEntryStatus::Finalist),
+                (swimmers[1], model::FinalsEntryStatus::Alternate),
+            ]
+        );
+        assert!(matches!(
+            finals_service.add_swim_off(competition_id).await,
+            Err(AddSwimOffError::FinalistsAlreadyPromoted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn scratched_finalist_is_replaced_by_the_alternate() {
+        let fixture = Fixture::new();
+        let (competition_id, swimmers) = prelims(&fixture, &[31_000, 32_000, 33_000]).await;
+        let finals_service = fixture.finals_service();
+        finals_service
+            .promote_finalists(competition_id)
+            .await
+            .unwrap();
+
+        let promoted = finals_service
+            .scratch_finalist(competition_id, swimmers[0])
+            .await
+            .unwrap();
+        assert_eq!(promoted, Some(swimmers[2]));
+        assert_eq!(
+            statuses(&fixture, competition_id).await,
+            vec![
+                (swimmers[0], model::FinalsEntryStatus::Scratched),
+                (swimmers[1], model::FinalsEntryStatus::Finalist),
+                (swimmers[2], model::FinalsEntryStatus::Finalist),
+            ]
+        );
+
+        let finals = finals_service.finals(competition_id).await.unwrap();
+        let registered: HashSet<Uuid> = fixture
+            .repos
+            .registrations
+            .registrations_for_competition(finals.finals_competition_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.participant_id)
+            .collect();
+        assert_eq!(registered, HashSet::from([swimmers[1], swimmers[2]]));
+
+        let promoted = finals_service
+            .scratch_finalist(competition_id, swimmers[1])
+            .await
+            .unwrap();
+        assert_eq!(promoted, None);
+        assert!(matches!(
+            finals_service
+                .scratch_finalist(competition_id, swimmers[0])
+                .await,
+            Err(ScratchFinalistError::NotAFinalist)
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+//! Helpers to set up services on top of in-memory repositories in tests.
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::{db, infra};
+
+use super::{
+    BackupService, BestTimeService, CompetitionService, EventService, FeeService, FinalsService,
+    GroupService, ParticipantService, RecordService, RegistrationCardService, RegistrationService,
+    ScheduleService, ScoreService, TeamService, TemplateService,
+};
+
+pub struct Fixture {
+    pub repos: db::Repositories,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Self {
+            repos: db::Repositories::in_memory(),
+        }
+    }
+
+    pub fn participant_service(&self) -> ParticipantService {
+        ParticipantService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+        )
+    }
+
+    pub fn competition_service(&self) -> CompetitionService {
+        CompetitionService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+        )
+    }
+
+    pub fn group_service(&self) -> GroupService {
+        GroupService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn score_service(&self) -> ScoreService {
+        ScoreService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn finals_service(&self) -> FinalsService {
+        FinalsService::new(
+            self.repos.finals.clone(),
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn team_service(&self) -> TeamService {
+        TeamService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn registration_card_service(&self) -> RegistrationCardService {
+        RegistrationCardService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.event.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn registration_service(&self) -> RegistrationService {
+        RegistrationService::new(
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.event.clone(),
+            self.repos.records.clone(),
+            self.repos.finals.clone(),
+            self.repos.groups.clone(),
+            self.repos.best_times.clone(),
+            self.repos.payments.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn record_service(&self) -> RecordService {
+        RecordService::new(self.repos.records.clone(), self.repos.event.clone())
+    }
+
+    pub fn event_service(&self) -> EventService {
+        EventService::new(self.repos.event.clone())
+    }
+
+    pub fn best_time_service(&self) -> BestTimeService {
+        BestTimeService::new(
+            self.repos.participants.clone(),
+            self.repos.best_times.clone(),
+            self.repos.event.clone(),
+        )
+    }
+
+    pub fn backup_service(&self) -> BackupService {
+        BackupService::new(self.repos.backups.clone())
+    }
+
+    pub fn fee_service(&self) -> FeeService {
+        FeeService::new(
+            self.repos.payments.clone(),
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.groups.clone(),
+            self.repos.event.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub fn schedule_service(&self, lanes: u32) -> ScheduleService {
+        ScheduleService::new(
+            self.repos.sessions.clone(),
+            self.repos.participants.clone(),
+            self.repos.registrations.clone(),
+            self.repos.competitions.clone(),
+            self.repos.event.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+            lanes,
+        )
+    }
+
+    pub fn template_service(&self) -> TemplateService {
+        TemplateService::new(
+            self.repos.assets.clone(),
+            self.repos.event.clone(),
+            infra::typst_compiler::TypstCompiler::new(None),
+        )
+    }
+
+    pub async fn group(&self, name: &str) -> Uuid {
+        self.repos
+            .groups
+            .create_group(name.to_string())
+            .await
+            .unwrap()
+    }
+
+    pub async fn participant(&self, group_id: Uuid, gender: db::Gender, last_name: &str) -> Uuid {
+        self.repos
+            .participants
+            .create_participant(
+                "Kim",
+                last_name,
+                gender,
+                NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+                group_id,
+            )
+            .await
+            .unwrap()
+    }
+
+    pub async fn competition(
+        &self,
+        gender: db::Gender,
+        stroke: db::Stroke,
+        distance: i32,
+        target_time: i64,
+    ) -> Uuid {
+        self.repos
+            .competitions
+            .create_competition(gender, stroke, distance, target_time, false)
+            .await
+            .unwrap()
+    }
+
+    pub async fn relay(
+        &self,
+        gender: db::Gender,
+        stroke: db::Stroke,
+        distance: i32,
+        target_time: i64,
+    ) -> Uuid {
+        self.repos
+            .competitions
+            .create_competition(gender, stroke, distance, target_time, true)
+            .await
+            .unwrap()
+    }
+
+    pub async fn register(&self, participant_id: Uuid, competition_id: Uuid) -> Uuid {
+        self.repos
+            .registrations
+            .create_registration(participant_id, competition_id, None, false)
+            .await
+            .unwrap()
+    }
+
+    pub async fn result(&self, registration_id: Uuid, time_millis: i32, disqualified: bool) {
+        self.repos
+            .registrations
+            .create_registration_result(registration_id, time_millis, disqualified)
+            .await
+            .unwrap()
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use clubswimcomp_types::model;
@@ -8,19 +8,31 @@ use uuid::Uuid;
 
 use crate::{
     db, infra,
-    services::{score::ScoreService, ParticipantService},
+    services::score::{GroupScoreboardError, ScoreService},
 };
 
 use super::ServiceRepositoryError;
 
 pub struct GroupService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
-    group_repo: db::groups::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
     typst_compiler: infra::typst_compiler::TypstCompiler,
 }
 
+#[derive(Debug, Error)]
+pub enum AddGroupError {
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum GroupResultError {
     #[error("The group does not exist")]
@@ -31,11 +43,15 @@ pub enum GroupResultError {
 }
 
 impl GroupService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
-        group_repo: db::groups::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
         typst_compiler: infra::typst_compiler::TypstCompiler,
     ) -> Self {
         Self {
@@ -43,6 +59,9 @@ impl GroupService {
             registration_repo,
             competition_repo,
             group_repo,
+            event_repo,
+            best_time_repo,
+            payment_repo,
             typst_compiler,
         }
     }
@@ -60,7 +79,12 @@ impl GroupService {
     }
 
     #[instrument(skip(self))]
-    pub async fn add_group(&self, group_name: String) -> Result<Uuid, ServiceRepositoryError> {
+    pub async fn add_group(&self, group_name: String) -> Result<Uuid, AddGroupError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(AddGroupError::EventFinalized);
+        }
+
         Ok(self
             .group_repo
             .create_group(group_name)
@@ -68,6 +92,8 @@ impl GroupService {
             .context("Failed to create group in repository")?)
     }
 
+    /// The group with the ranking of its participants, scored like the group
+    /// scoreboard.
     #[instrument(skip(self))]
     pub async fn group_details(
         &self,
@@ -78,60 +104,57 @@ impl GroupService {
             self.registration_repo.clone(),
             self.competition_repo.clone(),
             self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
             self.typst_compiler.clone(),
         );
 
-        tracing::debug!("Ensuring group actually exists");
-        let group = self
-            .group_repo
-            .group_by_id(group_id)
-            .await
-            .context("Failed to fetch group from repository")?
-            .map(model::Group::from)
-            .ok_or(GroupResultError::GroupDoesNotExist)?;
+        tracing::debug!("Loading the scoreboard of the group");
+        let scoreboard =
+            score_service
+                .group_scoreboard(group_id)
+                .await
+                .map_err(|err| match err {
+                    GroupScoreboardError::GroupDoesNotExist => GroupResultError::GroupDoesNotExist,
+                    GroupScoreboardError::RepositoryError(e) => {
+                        GroupResultError::RepositoryError(e)
+                    }
+                })?;
+
+        Ok(model::GroupDetails {
+            group: scoreboard.group,
+            scores: scoreboard.scores,
+        })
+    }
+}
 
-        tracing::debug!("Loading participants in the group");
-        let mut participants = self
-            .participant_repo
-            .list_participants_in_group(group.id)
-            .await
-            .context("Failed to fetch participants for group from repository")?
-            .into_iter()
-            .map(|p| (p.id, model::Participant::from(p)))
-            .collect::<HashMap<_, _>>();
+#[cfg(test)]
+mod tests {
+    use crate::services::fixtures::Fixture;
 
-        tracing::debug!("Fetching participant scores");
-        let participant_scores = score_service
-            .participants_fina_points()
-            .await
-            .context("Failed to fetch participants FINA points")?
-            .into_iter()
-            .filter(|r| participants.contains_key(&r.0))
-            .collect::<HashMap<_, _>>();
+    use super::*;
 
-        if participant_scores.len() == participants.len() {
-            return Err(GroupResultError::RepositoryError(anyhow::anyhow!(
-                "Participants scores and participants do not match up in length"
-            )));
-        }
+    #[tokio::test]
+    async fn groups_are_not_added_after_finalization() {
+        let fixture = Fixture::new();
+        let group_service = fixture.group_service();
+        group_service
+            .add_group("Dolphins".to_string())
+            .await
+            .unwrap();
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
 
-        let mut scores = Vec::with_capacity(participants.len());
-        for (participant_id, (results_missing, own_fina_points)) in participant_scores.iter() {
-            let participants_with_more_points = participant_scores
-                .values()
-                .filter(|ps| ps.1 > *own_fina_points)
-                .count();
-
-            // 0 with more means 1st rank
-            let rank = participants_with_more_points as u32 + 1;
-
-            scores.push(model::GroupScore {
-                participant: participants.remove(participant_id).unwrap(),
-                fina_points: *own_fina_points,
-                rank,
-            });
-        }
+        let result = group_service.add_group("Sharks".to_string()).await;
 
-        Ok(model::GroupDetails { group, scores })
+        assert!(
+            matches!(result, Err(AddGroupError::EventFinalized)),
+            "{result:?}"
+        );
+        assert_eq!(group_service.list_groups().await.unwrap().len(), 1);
     }
 }
@@ -1,21 +1,105 @@
+mod backup;
+mod best_time;
 mod competition;
+mod event;
+mod fee;
+mod finals;
+#[cfg(test)]
+mod fixtures;
 mod group;
 mod participant;
+mod ranking;
+mod record;
 mod registration;
 mod registration_card;
+mod schedule;
 mod score;
+mod start_list;
+mod team;
+mod template;
 
+pub use backup::*;
+pub use best_time::*;
 pub use competition::*;
+pub use event::*;
+pub use fee::*;
+pub use finals::*;
 pub use group::*;
 pub use participant::*;
+pub use record::*;
 pub use registration::*;
 pub use registration_card::*;
+pub use schedule::*;
 pub use score::*;
+pub use start_list::*;
+pub use team::*;
+pub use template::*;
 
+use anyhow::Context;
+use chrono::Utc;
+use clubswimcomp_types::model;
 use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db;
+use crate::infra::typst_compiler::PageDoesNotExist;
 
 #[derive(Debug, Error)]
 pub enum ServiceRepositoryError {
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
+
+/// Split a missing page off the other failures of generating a document.
+fn document_error<E>(
+    err: anyhow::Error,
+    page_does_not_exist: impl FnOnce(usize) -> E,
+    generation_failed: impl FnOnce(anyhow::Error) -> E,
+) -> E {
+    match err.downcast_ref::<PageDoesNotExist>() {
+        Some(missing) => page_does_not_exist(missing.page),
+        None => generation_failed(err),
+    }
+}
+
+/// The event of the database, created with a new card secret on first use.
+async fn current_event(event_repo: &dyn db::event::Repository) -> anyhow::Result<db::event::Event> {
+    if let Some(event) = event_repo
+        .event()
+        .await
+        .context("Failed to fetch event from repository")?
+    {
+        return Ok(event);
+    }
+
+    tracing::info!("Creating the event");
+    let event = db::event::Event {
+        id: Uuid::new_v4(),
+        card_secret: rand::random::<[u8; 32]>().to_vec(),
+        created_at: Utc::now(),
+        group_scoring: db::event::GroupScoring::Sum,
+        scoring_result_count: None,
+        min_starts: 0,
+        shared_ranks: db::event::SharedRanks::Skip,
+        time_precision: db::event::TimePrecision::Hundredths,
+        group_tie_break: db::event::GroupTieBreak::None,
+        team_placing_points: crate::conversions::team_placing_points(
+            &model::TeamScoring::default().placing_points,
+        ),
+        team_max_scorers: None,
+        team_relay_weight: model::TeamScoring::default().relay_weight as _,
+        max_individual_starts: None,
+        course: db::Course::Short,
+        phase: db::event::EventPhase::RegistrationOpen,
+        participant_fee_cents: 0,
+        start_fee_cents: 0,
+        late_entry_surcharge_cents: 0,
+        currency: model::EntryFees::default().currency,
+        name: String::new(),
+        organization: String::new(),
+    };
+    event_repo
+        .init_event(&event)
+        .await
+        .context("Failed to create event in repository")
+}
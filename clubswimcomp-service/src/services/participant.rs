@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use clubswimcomp_types::model;
 use thiserror::Error;
 use tracing::instrument;
@@ -7,13 +9,25 @@ use uuid::Uuid;
 
 use crate::db;
 
-use super::ServiceRepositoryError;
+use super::{RegistrationWindow, ServiceRepositoryError};
 
 pub struct ParticipantService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
-    group_repo: db::groups::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
+}
+
+#[derive(Debug, Error)]
+pub enum AddParticipantError {
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
 }
 
 #[derive(Debug, Error)]
@@ -33,6 +47,12 @@ pub enum RemoveParticipantError {
     #[error("Participant can't be deleted while still registered to competitions")]
     ParticipantHasRegistrations,
 
+    #[error("Participant can't be deleted while payments of the participant are recorded")]
+    ParticipantHasPayments,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -57,8 +77,57 @@ pub enum RegisterForCompetitionsError {
     #[error("Participant is already registered for the competition")]
     AlreadyRegistered,
 
-    #[error("Participant is not eligible to register for the competition")]
-    NotEligible,
+    #[error("Participant is not eligible to register for the competition: {0}")]
+    NotEligible(Ineligibility),
+
+    #[error("The registration is closed, only late entries are accepted")]
+    RegistrationClosed,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+/// Why a participant may not register for a competition.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Ineligibility {
+    #[error("Swimmers are promoted into the competition from its prelims")]
+    PromotedRound,
+
+    #[error("The competition is for another gender")]
+    OtherGender,
+
+    #[error("The participant is younger than {0} years")]
+    TooYoung(u32),
+
+    #[error("The participant is older than {0} years")]
+    TooOld(u32),
+
+    #[error("The participant is born before {0}")]
+    BornTooEarly(i32),
+
+    #[error("The participant is born after {0}")]
+    BornTooLate(i32),
+
+    #[error("The group of the participant may not register")]
+    GroupNotEligible,
+
+    #[error("The participant has no best time that meets the qualifying time")]
+    NoQualifyingTime,
+
+    #[error("The participant already has the maximum of {0} individual starts")]
+    TooManyStarts(u32),
+}
+
+#[derive(Debug, Error)]
+pub enum SetRegistrationLimitsError {
+    #[error("The maximum number of individual starts must be above zero")]
+    InvalidMaxIndividualStarts,
+
+    #[error("The event is finalized")]
+    EventFinalized,
 
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
@@ -69,22 +138,34 @@ pub enum UnregisterFromCompetitionError {
     #[error("The registration does not exist")]
     RegistrationDoesNotExist,
 
+    #[error("The registration is closed, only late entries are accepted")]
+    RegistrationClosed,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
 
 impl ParticipantService {
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
-        group_repo: db::groups::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
     ) -> Self {
         Self {
             participant_repo,
             registration_repo,
             competition_repo,
             group_repo,
+            event_repo,
+            best_time_repo,
+            payment_repo,
         }
     }
 
@@ -112,12 +193,19 @@ impl ParticipantService {
         gender: model::Gender,
         birthday: NaiveDate,
         group_id: Uuid,
-    ) -> Result<Uuid, ServiceRepositoryError> {
-        self.participant_repo
+    ) -> Result<Uuid, AddParticipantError> {
+        if let RegistrationWindow::Finalized = self.registration_window().await? {
+            tracing::debug!("Participants of a finalized event can't be added");
+            return Err(AddParticipantError::EventFinalized);
+        }
+
+        let participant_id = self
+            .participant_repo
             .create_participant(first_name, last_name, gender.into(), birthday, group_id)
             .await
-            .context("Failed to add participant to repository")
-            .map_err(ServiceRepositoryError::from)
+            .context("Failed to add participant to repository")?;
+
+        Ok(participant_id)
     }
 
     /// Fetch the details of the given participant.
@@ -199,7 +287,8 @@ impl ParticipantService {
     ///
     /// Before a participant can be deleted, all registrations of the
     /// participant must be removed. As an alternative they can be removed
-    /// automatically by setting `force_delete` to `true`.
+    /// automatically by setting `force_delete` to `true`. Participants with
+    /// recorded payments are never deleted.
     ///
     /// # Parameters
     /// - `participant_id` - The id of the participant that shall be removed.
@@ -219,6 +308,24 @@ impl ParticipantService {
             .context("Failed to fetch participant from repository")?
             .ok_or(RemoveParticipantError::ParticipantDoesNotExist)?;
 
+        if let RegistrationWindow::Finalized = self.registration_window().await? {
+            tracing::debug!("Participants of a finalized event can't be removed");
+            return Err(RemoveParticipantError::EventFinalized);
+        }
+
+        // Payments are kept for the accounts, even with force delete
+        let has_payments = self
+            .payment_repo
+            .all_payments()
+            .await
+            .context("Failed to fetch payments from repository")?
+            .iter()
+            .any(|p| p.participant_id == Some(participant_id));
+        if has_payments {
+            tracing::debug!("Participants with payments can't be removed");
+            return Err(RemoveParticipantError::ParticipantHasPayments);
+        }
+
         tracing::debug!("Loading registrations for the participant");
         let registrations = self
             .registration_repo
@@ -257,6 +364,8 @@ impl ParticipantService {
 
     /// Get a list of competitions for which registrations are still available.
     ///
+    /// Only competitions the participant is eligible for are listed.
+    ///
     /// # Parameters:
     /// - `participant_id` - The id of the participant
     #[instrument(skip(self))]
@@ -286,13 +395,25 @@ impl ParticipantService {
             .await
             .context("Failed to load competitions from repository")?;
 
-        let not_registered_yet = applicable_competitions
+        tracing::debug!("Loading the eligibility rules of the competitions");
+        let eligibilities = self
+            .competition_repo
+            .all_eligibilities()
+            .await
+            .context("Failed to load eligibilities from repository")?;
+        let check = self.eligibility_check(participant, &registrations).await?;
+
+        let available = applicable_competitions
             .into_iter()
             .filter(|c| !registrations.iter().any(|r| r.competition_id == c.id))
+            .filter(|c| {
+                let eligibility = eligibilities.iter().find(|e| e.competition_id == c.id);
+                check.check(c, eligibility).is_ok()
+            })
             .map(model::Competition::from)
             .collect();
 
-        Ok(not_registered_yet)
+        Ok(available)
     }
 
     /// Register participant for a competition.
@@ -300,12 +421,32 @@ impl ParticipantService {
     /// # Parameters:
     /// - `participant_id` - The id of the participant
     /// - `competition_id` - The id of the competition
+    /// - `entry_time_millis` - The expected time of the participant, if known
+    /// - `late_entry` - If set to `true`, the registration is accepted after
+    ///   the registration has been closed
     #[instrument(skip(self))]
     pub async fn register_for_competition(
         &self,
         participant_id: Uuid,
         competition_id: Uuid,
+        entry_time_millis: Option<u32>,
+        late_entry: bool,
     ) -> Result<Uuid, RegisterForCompetitionsError> {
+        // Late entries are charged with the late-entry surcharge
+        let is_late = match self.registration_window().await? {
+            RegistrationWindow::Open => false,
+            RegistrationWindow::Closed if late_entry => {
+                tracing::info!("Accepting late entry after the registration has been closed");
+                true
+            }
+            RegistrationWindow::Closed => {
+                return Err(RegisterForCompetitionsError::RegistrationClosed)
+            }
+            RegistrationWindow::Finalized => {
+                return Err(RegisterForCompetitionsError::EventFinalized)
+            }
+        };
+
         tracing::debug!("Ensuring participant actually exists");
         let participant = self
             .participant_repo
@@ -322,16 +463,6 @@ impl ParticipantService {
             .context("Failed to fetch competition from repository")?
             .ok_or(RegisterForCompetitionsError::CompetitionDoesNotExist)?;
 
-        tracing::debug!("Ensuring participant is eligible for the competition");
-        if participant.gender != competition.gender {
-            tracing::debug!(
-                participant_gender = ?participant.gender,
-                competition_gender = ?competition.gender,
-                "Tried to register for a competition with different gender than participant"
-            );
-            return Err(RegisterForCompetitionsError::NotEligible);
-        }
-
         tracing::debug!("Loading existing registrations for participant");
         let registrations = self
             .registration_repo
@@ -348,24 +479,146 @@ impl ParticipantService {
             return Err(RegisterForCompetitionsError::AlreadyRegistered);
         }
 
+        tracing::debug!("Ensuring participant is eligible for the competition");
+        let eligibility = self
+            .competition_repo
+            .eligibility_of_competition(competition_id)
+            .await
+            .context("Failed to fetch eligibility of competition from repository")?;
+        self.eligibility_check(participant, &registrations)
+            .await?
+            .check(&competition, eligibility.as_ref())
+            .map_err(|reason| {
+                tracing::debug!(%reason, "Participant is not eligible for the competition");
+                RegisterForCompetitionsError::NotEligible(reason)
+            })?;
+
         let registration_id = self
             .registration_repo
-            .create_registration(participant_id, competition_id)
+            .create_registration(
+                participant_id,
+                competition_id,
+                entry_time_millis.map(|t| t as i32),
+                is_late,
+            )
             .await
             .context("Failed to create registration in repository")?;
 
         Ok(registration_id)
     }
 
+    /// The limits of the registrations of each participant.
+    #[instrument(skip(self))]
+    pub async fn registration_limits(
+        &self,
+    ) -> Result<model::RegistrationLimits, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::RegistrationLimits::from(&event))
+    }
+
+    /// Change the limits of the registrations of each participant.
+    ///
+    /// Existing registrations are kept, the limits apply to the following
+    /// registrations only.
+    #[instrument(skip(self))]
+    pub async fn set_registration_limits(
+        &self,
+        limits: model::RegistrationLimits,
+    ) -> Result<(), SetRegistrationLimitsError> {
+        if let Some(max_starts) = limits.max_individual_starts {
+            if max_starts == 0 || max_starts > i32::MAX as u32 {
+                return Err(SetRegistrationLimitsError::InvalidMaxIndividualStarts);
+            }
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetRegistrationLimitsError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the registration limits with the event");
+        self.event_repo
+            .update_registration_limits(limits.max_individual_starts.map(|m| m as _))
+            .await
+            .context("Failed to update registration limits in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
+    /// Load everything needed to check the eligibility of the participant.
+    ///
+    /// # Parameters:
+    /// - `participant` - The participant that wants to register
+    /// - `registrations` - The existing registrations of the participant
+    async fn eligibility_check(
+        &self,
+        participant: db::participants::Participant,
+        registrations: &[db::registrations::Registration],
+    ) -> Result<EligibilityCheck> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+
+        let competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?;
+        let starts = registrations
+            .iter()
+            .filter(|r| {
+                competitions
+                    .iter()
+                    .any(|c| c.id == r.competition_id && c.round.is_individual_start())
+            })
+            .count();
+
+        tracing::debug!("Loading best times of participant from repository");
+        let best_times = self
+            .best_time_repo
+            .best_times_of_swimmer(
+                &participant.first_name,
+                &participant.last_name,
+                participant.birthday,
+            )
+            .await
+            .context("Failed to fetch best times of participant from repository")?
+            .into_iter()
+            .filter(|b| b.course == event.course)
+            .collect();
+
+        Ok(EligibilityCheck {
+            participant: model::Participant::from(participant),
+            starts,
+            max_starts: model::RegistrationLimits::from(&event).max_individual_starts,
+            best_times,
+        })
+    }
+
     /// Unregister from a competition.
     ///
     /// # Parameters:
     /// - `registration_id` - The id of the registration
+    /// - `late_entry` - If set to `true`, the registration is removed after
+    ///   the registration has been closed
     #[instrument(skip(self))]
     pub async fn unregister_from_competition(
         &self,
         registration_id: Uuid,
+        late_entry: bool,
     ) -> Result<(), UnregisterFromCompetitionError> {
+        match self.registration_window().await? {
+            RegistrationWindow::Open => {}
+            RegistrationWindow::Closed if late_entry => {
+                tracing::info!("Removing registration after the registration has been closed");
+            }
+            RegistrationWindow::Closed => {
+                return Err(UnregisterFromCompetitionError::RegistrationClosed)
+            }
+            RegistrationWindow::Finalized => {
+                return Err(UnregisterFromCompetitionError::EventFinalized)
+            }
+        }
+
         tracing::debug!("Deleting registration result from repository");
         self.registration_repo
             .delete_result_for_registration(registration_id)
@@ -381,4 +634,669 @@ impl ParticipantService {
 
         Ok(())
     }
+
+    /// The changes of registrations the current phase of the event allows.
+    async fn registration_window(&self) -> Result<RegistrationWindow> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(event.phase.into())
+    }
+}
+
+/// A participant with everything the eligibility rules are checked against.
+struct EligibilityCheck {
+    participant: model::Participant,
+    /// Number of existing registrations of the participant.
+    starts: usize,
+    max_starts: Option<u32>,
+    /// Best times in the course of the event.
+    best_times: Vec<db::best_times::BestTime>,
+}
+
+impl EligibilityCheck {
+    /// Check if the participant may register for the competition, the first
+    /// rule that is not met is returned.
+    fn check(
+        &self,
+        competition: &db::competitions::Competition,
+        eligibility: Option<&db::competitions::Eligibility>,
+    ) -> Result<(), Ineligibility> {
+        if matches!(
+            competition.round,
+            db::competitions::CompetitionRound::Finals
+                | db::competitions::CompetitionRound::SwimOff
+        ) {
+            return Err(Ineligibility::PromotedRound);
+        }
+
+        if db::Gender::from(self.participant.gender) != competition.gender {
+            return Err(Ineligibility::OtherGender);
+        }
+
+        if let Some(max_starts) = self.max_starts {
+            if self.starts >= max_starts as usize {
+                return Err(Ineligibility::TooManyStarts(max_starts));
+            }
+        }
+
+        let Some(eligibility) = eligibility else {
+            return Ok(());
+        };
+
+        let age = self.participant.age;
+        if let Some(min_age) = eligibility.min_age {
+            if age < min_age as u32 {
+                return Err(Ineligibility::TooYoung(min_age as _));
+            }
+        }
+        if let Some(max_age) = eligibility.max_age {
+            if age > max_age as u32 {
+                return Err(Ineligibility::TooOld(max_age as _));
+            }
+        }
+
+        let birth_year = self.participant.birthday.year();
+        if let Some(min_birth_year) = eligibility.min_birth_year {
+            if birth_year < min_birth_year {
+                return Err(Ineligibility::BornTooEarly(min_birth_year));
+            }
+        }
+        if let Some(max_birth_year) = eligibility.max_birth_year {
+            if birth_year > max_birth_year {
+                return Err(Ineligibility::BornTooLate(max_birth_year));
+            }
+        }
+
+        if !eligibility.group_ids.is_empty()
+            && !eligibility.group_ids.contains(&self.participant.group_id)
+        {
+            return Err(Ineligibility::GroupNotEligible);
+        }
+
+        if let Some(qualifying_time) = eligibility.qualifying_time {
+            let qualified = self.best_times.iter().any(|b| {
+                b.stroke == competition.stroke
+                    && b.distance == competition.distance
+                    && b.time_millis <= qualifying_time
+            });
+            if !qualified {
+                return Err(Ineligibility::NoQualifyingTime);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clubswimcomp_types::api;
+
+    use crate::db::{Course, Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Try to register a swimmer of the dolphins, born in 2014, for a female
+    /// backstroke competition with the eligibility.
+    async fn register_with_eligibility(
+        fixture: &Fixture,
+        eligibility: model::Eligibility,
+    ) -> Result<Uuid, RegisterForCompetitionsError> {
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture
+            .competition_service()
+            .set_competition_eligibility(competition_id, eligibility)
+            .await
+            .unwrap();
+
+        fixture
+            .participant_service()
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await
+    }
+
+    fn backstroke_best_time(course: Course, time_millis: i32) -> db::best_times::BestTime {
+        db::best_times::BestTime {
+            id: Uuid::new_v4(),
+            first_name: "Kim".to_string(),
+            last_name: "Swimmer".to_string(),
+            birthday: NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+            stroke: Stroke::Back,
+            distance: 50,
+            course,
+            time_millis,
+            swum_on: NaiveDate::from_ymd_opt(2026, 3, 14).unwrap(),
+            meet: "Spring Cup".to_string(),
+            registration_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn participants_with_payments_are_not_removed() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        fixture
+            .fee_service()
+            .add_payment(api::AddPaymentRequest {
+                participant_id: Some(participant_id),
+                group_id: None,
+                amount: 850,
+                received_on: NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+                reference: "Cash".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let result = fixture
+            .participant_service()
+            .remove_participant(participant_id, true)
+            .await;
+
+        assert!(
+            matches!(result, Err(RemoveParticipantError::ParticipantHasPayments)),
+            "{result:?}"
+        );
+        assert!(fixture
+            .repos
+            .participants
+            .participant_by_id(participant_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn register_for_competition_of_same_gender() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Butterfly, 25, 20_000)
+            .await;
+
+        let registration_id = fixture
+            .participant_service()
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await
+            .unwrap();
+
+        let registration = fixture
+            .repos
+            .registrations
+            .registration_by_id(registration_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(registration.participant_id, participant_id);
+        assert_eq!(registration.competition_id, competition_id);
+    }
+
+    #[tokio::test]
+    async fn register_for_competition_of_other_gender_is_not_eligible() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Male, Stroke::Butterfly, 25, 20_000)
+            .await;
+
+        let result = fixture
+            .participant_service()
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RegisterForCompetitionsError::NotEligible(
+                Ineligibility::OtherGender
+            ))
+        ));
+        let registrations = fixture
+            .repos
+            .registrations
+            .registrations_of_participant(participant_id)
+            .await
+            .unwrap();
+        assert!(registrations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn register_for_competition_twice() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Male, "Swimmer").await;
+        let competition_id = fixture
+            .competition(Gender::Male, Stroke::Breast, 50, 40_000)
+            .await;
+        let service = fixture.participant_service();
+
+        service
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await
+            .unwrap();
+        let result = service
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RegisterForCompetitionsError::AlreadyRegistered)
+        ));
+    }
+
+    #[tokio::test]
+    async fn register_for_competition_with_unknown_ids() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Male, "Swimmer").await;
+        let competition_id = fixture
+            .competition(Gender::Male, Stroke::Breast, 50, 40_000)
+            .await;
+        let service = fixture.participant_service();
+
+        let result = service
+            .register_for_competition(Uuid::new_v4(), competition_id, None, false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(RegisterForCompetitionsError::ParticipantDoesNotExist)
+        ));
+
+        let result = service
+            .register_for_competition(participant_id, Uuid::new_v4(), None, false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(RegisterForCompetitionsError::CompetitionDoesNotExist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn available_competitions_exclude_other_gender_and_registered() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let registered = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let available = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 35_000)
+            .await;
+        fixture
+            .competition(Gender::Male, Stroke::Back, 50, 33_000)
+            .await;
+        fixture.register(participant_id, registered).await;
+
+        let competitions = fixture
+            .participant_service()
+            .available_competitions_for_registration(participant_id)
+            .await
+            .unwrap();
+
+        let ids = competitions.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![available]);
+    }
+
+    #[tokio::test]
+    async fn register_outside_of_the_eligibility_names_the_reason() {
+        let cases = [
+            (
+                model::Eligibility {
+                    min_age: Some(99),
+                    ..Default::default()
+                },
+                Ineligibility::TooYoung(99),
+            ),
+            (
+                model::Eligibility {
+                    max_age: Some(1),
+                    ..Default::default()
+                },
+                Ineligibility::TooOld(1),
+            ),
+            (
+                model::Eligibility {
+                    min_birth_year: Some(2015),
+                    ..Default::default()
+                },
+                Ineligibility::BornTooEarly(2015),
+            ),
+            (
+                model::Eligibility {
+                    max_birth_year: Some(2013),
+                    ..Default::default()
+                },
+                Ineligibility::BornTooLate(2013),
+            ),
+            (
+                model::Eligibility {
+                    qualifying_time: Some(45_000),
+                    ..Default::default()
+                },
+                Ineligibility::NoQualifyingTime,
+            ),
+        ];
+
+        for (eligibility, reason) in cases {
+            let result = register_with_eligibility(&Fixture::new(), eligibility).await;
+            assert!(
+                matches!(&result, Err(RegisterForCompetitionsError::NotEligible(r)) if *r == reason),
+                "{result:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn register_for_competition_of_other_groups_is_not_eligible() {
+        let fixture = Fixture::new();
+        let sharks = fixture.group("Sharks").await;
+
+        let result = register_with_eligibility(
+            &fixture,
+            model::Eligibility {
+                group_ids: vec![sharks],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(RegisterForCompetitionsError::NotEligible(
+                    Ineligibility::GroupNotEligible
+                ))
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn register_with_best_time_in_course_of_event_meets_qualifying_time() {
+        let fixture = Fixture::new();
+        fixture
+            .repos
+            .best_times
+            .insert_best_times(&[backstroke_best_time(Course::Long, 44_000)])
+            .await
+            .unwrap();
+        let eligibility = model::Eligibility {
+            min_birth_year: Some(2013),
+            max_birth_year: Some(2014),
+            qualifying_time: Some(45_000),
+            ..Default::default()
+        };
+
+        // The event is swum in the short course
+        let result = register_with_eligibility(&fixture, eligibility.clone()).await;
+        assert!(
+            matches!(
+                result,
+                Err(RegisterForCompetitionsError::NotEligible(
+                    Ineligibility::NoQualifyingTime
+                ))
+            ),
+            "{result:?}"
+        );
+
+        let fixture = Fixture::new();
+        fixture
+            .repos
+            .best_times
+            .insert_best_times(&[backstroke_best_time(Course::Short, 45_000)])
+            .await
+            .unwrap();
+        register_with_eligibility(&fixture, eligibility)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn register_beyond_max_individual_starts_is_not_eligible() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Male, "Swimmer").await;
+        let breast = fixture
+            .competition(Gender::Male, Stroke::Breast, 50, 40_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Male, Stroke::Back, 50, 40_000)
+            .await;
+        let service = fixture.participant_service();
+        service
+            .set_registration_limits(model::RegistrationLimits {
+                max_individual_starts: Some(1),
+            })
+            .await
+            .unwrap();
+
+        service
+            .register_for_competition(participant_id, breast, None, false)
+            .await
+            .unwrap();
+        assert!(service
+            .available_competitions_for_registration(participant_id)
+            .await
+            .unwrap()
+            .is_empty());
+        let result = service
+            .register_for_competition(participant_id, back, None, false)
+            .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(RegisterForCompetitionsError::NotEligible(
+                    Ineligibility::TooManyStarts(1)
+                ))
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn finals_do_not_count_as_individual_starts() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture.participant(group_id, Gender::Male, "Swimmer").await;
+        let breast = fixture
+            .competition(Gender::Male, Stroke::Breast, 50, 40_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Male, Stroke::Back, 50, 40_000)
+            .await;
+        let registration_id = fixture.register(participant_id, breast).await;
+        fixture.result(registration_id, 41_000, false).await;
+        let finals_service = fixture.finals_service();
+        finals_service
+            .set_finals(
+                breast,
+                api::FinalsRequest {
+                    finalists: 1,
+                    alternates: 0,
+                },
+            )
+            .await
+            .unwrap();
+        finals_service.promote_finalists(breast).await.unwrap();
+        let service = fixture.participant_service();
+        service
+            .set_registration_limits(model::RegistrationLimits {
+                max_individual_starts: Some(2),
+            })
+            .await
+            .unwrap();
+
+        service
+            .register_for_competition(participant_id, back, None, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn invalid_registration_limits_are_rejected() {
+        let service = Fixture::new().participant_service();
+
+        let result = service
+            .set_registration_limits(model::RegistrationLimits {
+                max_individual_starts: Some(0),
+            })
+            .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(SetRegistrationLimitsError::InvalidMaxIndividualStarts)
+            ),
+            "{result:?}"
+        );
+        assert_eq!(
+            service.registration_limits().await.unwrap(),
+            model::RegistrationLimits::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn available_competitions_exclude_ineligible() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let eligible = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let ineligible = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 35_000)
+            .await;
+        fixture
+            .competition_service()
+            .set_competition_eligibility(
+                ineligible,
+                model::Eligibility {
+                    max_birth_year: Some(2010),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let competitions = fixture
+            .participant_service()
+            .available_competitions_for_registration(participant_id)
+            .await
+            .unwrap();
+
+        let ids = competitions.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![eligible]);
+    }
+
+    #[tokio::test]
+    async fn closed_registration_only_accepts_late_entries() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::RegistrationClosed)
+            .await
+            .unwrap();
+        let participant_service = fixture.participant_service();
+
+        let result = participant_service
+            .register_for_competition(participant_id, competition_id, None, false)
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(RegisterForCompetitionsError::RegistrationClosed)
+            ),
+            "{result:?}"
+        );
+        let registration_id = participant_service
+            .register_for_competition(participant_id, competition_id, None, true)
+            .await
+            .unwrap();
+
+        let result = participant_service
+            .unregister_from_competition(registration_id, false)
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(UnregisterFromCompetitionError::RegistrationClosed)
+            ),
+            "{result:?}"
+        );
+        participant_service
+            .unregister_from_competition(registration_id, true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn finalized_event_is_read_only() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+        let participant_service = fixture.participant_service();
+
+        let result = participant_service
+            .register_for_competition(participant_id, competition_id, None, true)
+            .await;
+        assert!(
+            matches!(result, Err(RegisterForCompetitionsError::EventFinalized)),
+            "{result:?}"
+        );
+        let result = participant_service
+            .add_participant(
+                "Kim",
+                "Swimmer",
+                model::Gender::Female,
+                NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+                group_id,
+            )
+            .await;
+        assert!(
+            matches!(result, Err(AddParticipantError::EventFinalized)),
+            "{result:?}"
+        );
+        let result = participant_service
+            .remove_participant(participant_id, true)
+            .await;
+        assert!(
+            matches!(result, Err(RemoveParticipantError::EventFinalized)),
+            "{result:?}"
+        );
+    }
 }
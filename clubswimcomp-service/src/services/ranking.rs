@@ -0,0 +1,150 @@
+//! Ranking of the scores in the competition and group scoreboards.
+
+use std::cmp::Reverse;
+
+use clubswimcomp_types::model;
+
+/// The place of a score in a ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placing {
+    pub rank: u32,
+    /// Shares the rank with other scores.
+    pub tied: bool,
+}
+
+/// Rank scores by their keys, lower keys rank better and equal keys share
+/// the rank.
+///
+/// The placings are returned in the order of the keys.
+pub fn rank<K: Ord>(keys: &[K], shared_ranks: model::SharedRanks) -> Vec<Placing> {
+    let mut sorted = keys.iter().collect::<Vec<_>>();
+    sorted.sort();
+    let mut distinct = sorted.clone();
+    distinct.dedup();
+
+    keys.iter()
+        .map(|key| {
+            let better = match shared_ranks {
+                model::SharedRanks::Skip => sorted.partition_point(|other| *other < key),
+                model::SharedRanks::Dense => distinct.partition_point(|other| *other < key),
+            };
+            let equal = sorted.iter().filter(|other| **other == key).count();
+
+            // Nobody better than you means you are first
+            Placing {
+                rank: better as u32 + 1,
+                tied: equal > 1,
+            }
+        })
+        .collect()
+}
+
+/// Key of a time in the competition rankings.
+///
+/// Like in the official timing, the thousandths are cut off and not rounded
+/// when comparing hundredths.
+pub fn time_key(time_millis: u32, precision: model::TimePrecision) -> u32 {
+    match precision {
+        model::TimePrecision::Hundredths => time_millis / 10,
+        model::TimePrecision::Thousandths => time_millis,
+    }
+}
+
+/// Key of a participant in the group rankings, more points rank better.
+///
+/// # Parameters:
+/// - `points` - the points of the participant with the group scoring
+/// - `result_points` - the points of the single results, best first
+/// - `tie_break` - how participants with the same points are ordered
+pub fn group_key(
+    points: u32,
+    result_points: Vec<u32>,
+    tie_break: model::GroupTieBreak,
+) -> (Reverse<u32>, Reverse<Vec<u32>>) {
+    let tie_break = match tie_break {
+        model::GroupTieBreak::None => Vec::new(),
+        model::GroupTieBreak::BestSingleResult => result_points,
+    };
+
+    (Reverse(points), Reverse(tie_break))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placings<K: Ord>(keys: &[K], shared_ranks: model::SharedRanks) -> Vec<(u32, bool)> {
+        rank(keys, shared_ranks)
+            .into_iter()
+            .map(|p| (p.rank, p.tied))
+            .collect()
+    }
+
+    #[test]
+    fn ranks_follow_the_order_of_the_keys() {
+        assert_eq!(
+            placings(&[30, 10, 20], model::SharedRanks::Skip),
+            vec![(3, false), (1, false), (2, false)]
+        );
+        assert!(rank::<u32>(&[], model::SharedRanks::Skip).is_empty());
+    }
+
+    #[test]
+    fn ties_skip_the_following_ranks() {
+        assert_eq!(
+            placings(&[10, 20, 10, 30, 20], model::SharedRanks::Skip),
+            vec![(1, true), (3, true), (1, true), (5, false), (3, true)]
+        );
+    }
+
+    #[test]
+    fn dense_ranks_follow_ties_directly() {
+        assert_eq!(
+            placings(&[10, 20, 10, 30, 20], model::SharedRanks::Dense),
+            vec![(1, true), (2, true), (1, true), (3, false), (2, true)]
+        );
+    }
+
+    #[test]
+    fn hundredths_ignore_the_thousandths() {
+        let keys = [60_004, 60_009, 60_010]
+            .map(|millis| time_key(millis, model::TimePrecision::Hundredths));
+        assert_eq!(
+            placings(&keys, model::SharedRanks::Skip),
+            vec![(1, true), (1, true), (3, false)]
+        );
+
+        let keys = [60_004, 60_009, 60_010]
+            .map(|millis| time_key(millis, model::TimePrecision::Thousandths));
+        assert_eq!(
+            placings(&keys, model::SharedRanks::Skip),
+            vec![(1, false), (2, false), (3, false)]
+        );
+    }
+
+    #[test]
+    fn better_single_result_breaks_group_ties() {
+        let results = [
+            (300, vec![200, 100]),
+            (300, vec![150, 150]),
+            (300, vec![200, 100]),
+            (400, vec![400]),
+        ];
+
+        let keys = results
+            .clone()
+            .map(|(points, singles)| group_key(points, singles, model::GroupTieBreak::None));
+        assert_eq!(
+            placings(&keys, model::SharedRanks::Skip),
+            vec![(2, true), (2, true), (2, true), (1, false)]
+        );
+
+        let keys = results.map(|(points, singles)| {
+            group_key(points, singles, model::GroupTieBreak::BestSingleResult)
+        });
+        assert_eq!(
+            placings(&keys, model::SharedRanks::Skip),
+            vec![(2, true), (4, false), (2, true), (1, false)]
+        );
+    }
+}
@@ -0,0 +1,762 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::{Datelike, NaiveDate, Utc};
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::db;
+use crate::db::records::{Record, RecordKind, RecordStatus};
+
+use super::ranking;
+
+/// The club and meet records with their approval workflow.
+pub struct RecordService {
+    record_repo: Arc<dyn db::records::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+}
+
+/// A record from the paper list of the club.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewRecord {
+    pub kind: model::RecordKind,
+    pub gender: model::Gender,
+    pub stroke: model::Stroke,
+    pub distance: u32,
+    pub course: model::Course,
+    pub age_class: u32,
+    pub time_millis: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub swum_on: NaiveDate,
+    pub meet: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum AddRecordError {
+    #[error("The distance must be positive")]
+    InvalidDistance,
+
+    #[error("The time must be positive")]
+    InvalidTime,
+
+    #[error("The time does not beat the current record")]
+    NotFasterThanRecord,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ApproveRecordError {
+    #[error("The record does not exist")]
+    RecordDoesNotExist,
+
+    #[error("Only pending records can be approved")]
+    RecordNotPending,
+
+    #[error("The record has been beaten by another record in the meantime")]
+    NotFasterThanRecord,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RejectRecordError {
+    #[error("The record does not exist")]
+    RecordDoesNotExist,
+
+    #[error("Only pending records can be rejected")]
+    RecordNotPending,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl RecordService {
+    pub fn new(
+        record_repo: Arc<dyn db::records::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+    ) -> Self {
+        Self {
+            record_repo,
+            event_repo,
+        }
+    }
+
+    /// The records, optionally only the ones of a kind or status.
+    ///
+    /// The superseded records are the history of the current ones, the
+    /// records are ordered by the time they have been entered.
+    #[instrument(skip(self))]
+    pub async fn records(
+        &self,
+        kind: Option<model::RecordKind>,
+        status: Option<model::RecordStatus>,
+    ) -> Result<Vec<model::Record>, super::ServiceRepositoryError> {
+        tracing::debug!("Fetching records from repository");
+        let records = self
+            .record_repo
+            .list_records()
+            .await
+            .context("Failed to fetch records from repository")?;
+
+        Ok(records
+            .into_iter()
+            .map(model::Record::from)
+            .filter(|r| kind.is_none_or(|kind| r.kind == kind))
+            .filter(|r| status.is_none_or(|status| r.status == status))
+            .collect())
+    }
+
+    /// Enter a record from the paper list, it replaces the current record.
+    #[instrument(skip(self))]
+    pub async fn add_record(&self, record: NewRecord) -> Result<Uuid, AddRecordError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(AddRecordError::EventFinalized);
+        }
+
+        if record.distance == 0 || record.distance > i32::MAX as u32 {
+            return Err(AddRecordError::InvalidDistance);
+        }
+        if record.time_millis == 0 || record.time_millis > i32::MAX as u32 {
+            return Err(AddRecordError::InvalidTime);
+        }
+
+        let now = Utc::now();
+        let record = Record {
+            id: Uuid::new_v4(),
+            kind: record.kind.into(),
+            gender: record.gender.into(),
+            stroke: record.stroke.into(),
+            distance: record.distance as _,
+            course: record.course.into(),
+            age_class: record.age_class.min(i32::MAX as u32) as _,
+            time_millis: record.time_millis as _,
+            first_name: record.first_name,
+            last_name: record.last_name,
+            birthday: record.birthday,
+            swum_on: record.swum_on,
+            meet: record.meet,
+            registration_id: None,
+            status: RecordStatus::Approved,
+            created_at: now,
+            decided_at: Some(now),
+            superseded_by: None,
+        };
+
+        tracing::debug!("Fetching the current record from repository");
+        let precision = self.time_precision().await?;
+        let current = self.current_record(&record).await?;
+        if current
+            .as_ref()
+            .is_some_and(|current| !is_faster(&record, current, precision))
+        {
+            return Err(AddRecordError::NotFasterThanRecord);
+        }
+
+        tracing::debug!("Storing the record in repository");
+        self.record_repo
+            .create_record(&record)
+            .await
+            .context("Failed to store record in repository")?;
+
+        if let Some(current) = current {
+            tracing::debug!(record_id = ?current.id, "Superseding the former record");
+            self.supersede(current.id, record.id).await?;
+        }
+
+        Ok(record.id)
+    }
+
+    /// Approve a new record, the former record is kept as superseded.
+    ///
+    /// The slower pending records of the same table are superseded as well,
+    /// they can not become the record anymore.
+    #[instrument(skip(self))]
+    pub async fn approve_record(&self, record_id: Uuid) -> Result<(), ApproveRecordError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(ApproveRecordError::EventFinalized);
+        }
+
+        tracing::debug!("Fetching the record from repository");
+        let record = self
+            .record_repo
+            .record_by_id(record_id)
+            .await
+            .context("Failed to fetch record from repository")?
+            .ok_or(ApproveRecordError::RecordDoesNotExist)?;
+        if record.status != RecordStatus::Pending {
+            return Err(ApproveRecordError::RecordNotPending);
+        }
+
+        tracing::debug!("Fetching the current record from repository");
+        let precision = self.time_precision().await?;
+        let current = self.current_record(&record).await?;
+        if current
+            .as_ref()
+            .is_some_and(|current| !is_faster(&record, current, precision))
+        {
+            return Err(ApproveRecordError::NotFasterThanRecord);
+        }
+
+        tracing::debug!("Approving the record in repository");
+        self.record_repo
+            .update_record_status(record_id, RecordStatus::Approved, Utc::now(), None)
+            .await
+            .context("Failed to approve record in repository")?
+            .context("Record is missing in repository even though it has been fetched")?;
+
+        if let Some(current) = current {
+            tracing::debug!(record_id = ?current.id, "Superseding the former record");
+            self.supersede(current.id, record_id).await?;
+        }
+
+        let records = self
+            .record_repo
+            .list_records()
+            .await
+            .context("Failed to fetch records from repository")?;
+        for pending in records.iter().filter(|r| {
+            r.status == RecordStatus::Pending
+                && same_key(r, &record)
+                && !is_faster(r, &record, precision)
+        }) {
+            tracing::debug!(record_id = ?pending.id, "Superseding the slower pending record");
+            self.supersede(pending.id, record_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reject a new record, e.g. because the time has been taken by hand.
+    #[instrument(skip(self))]
+    pub async fn reject_record(&self, record_id: Uuid) -> Result<(), RejectRecordError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(RejectRecordError::EventFinalized);
+        }
+
+        tracing::debug!("Fetching the record from repository");
+        let record = self
+            .record_repo
+            .record_by_id(record_id)
+            .await
+            .context("Failed to fetch record from repository")?
+            .ok_or(RejectRecordError::RecordDoesNotExist)?;
+        if record.status != RecordStatus::Pending {
+            return Err(RejectRecordError::RecordNotPending);
+        }
+
+        tracing::debug!("Rejecting the record in repository");
+        self.record_repo
+            .update_record_status(record_id, RecordStatus::Rejected, Utc::now(), None)
+            .await
+            .context("Failed to reject record in repository")?
+            .context("Record is missing in repository even though it has been fetched")?;
+
+        Ok(())
+    }
+
+    async fn time_precision(&self) -> anyhow::Result<model::TimePrecision> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::RankingRules::from(&event).time_precision)
+    }
+
+    /// The approved record with the same key as the given record.
+    async fn current_record(&self, record: &Record) -> anyhow::Result<Option<Record>> {
+        let records = self
+            .record_repo
+            .list_records()
+            .await
+            .context("Failed to fetch records from repository")?;
+
+        Ok(records
+            .into_iter()
+            .find(|r| r.status == RecordStatus::Approved && same_key(r, record)))
+    }
+
+    async fn supersede(&self, record_id: Uuid, superseded_by: Uuid) -> anyhow::Result<()> {
+        self.record_repo
+            .update_record_status(
+                record_id,
+                RecordStatus::Superseded,
+                Utc::now(),
+                Some(superseded_by),
+            )
+            .await
+            .context("Failed to supersede record in repository")?
+            .context("Record is missing in repository even though it has been fetched")
+    }
+}
+
+/// Age class of a swimmer, the age turned in the year of the swim.
+fn age_class(birthday: NaiveDate, swum_on: NaiveDate) -> i32 {
+    (swum_on.year() - birthday.year()).max(0)
+}
+
+/// Both records are kept in the same record table.
+fn same_key(a: &Record, b: &Record) -> bool {
+    (
+        a.kind,
+        a.gender,
+        a.stroke,
+        a.distance,
+        a.course,
+        a.age_class,
+    ) == (
+        b.kind,
+        b.gender,
+        b.stroke,
+        b.distance,
+        b.course,
+        b.age_class,
+    )
+}
+
+/// The record is faster at the precision the event compares times with.
+fn is_faster(record: &Record, other: &Record, precision: model::TimePrecision) -> bool {
+    ranking::time_key(record.time_millis as _, precision)
+        < ranking::time_key(other.time_millis as _, precision)
+}
+
+/// Check a result for new club and meet records.
+///
+/// A pending record is created for every record table in which the result
+/// beats the approved record, they are returned so that the result desk can
+/// announce them. Pending records do not hide each other, the slower ones
+/// are superseded once a faster record is approved.
+///
+/// Meet records are only set by results swum at the meet, results entered
+/// before the meet is in progress are only checked for club records.
+pub(super) async fn check_records(
+    record_repo: &dyn db::records::Repository,
+    event: &db::event::Event,
+    participant: &db::participants::Participant,
+    competition: &db::competitions::Competition,
+    registration_id: Uuid,
+    time_millis: u32,
+) -> anyhow::Result<Vec<model::Record>> {
+    let precision = model::RankingRules::from(event).time_precision;
+    let records = record_repo
+        .list_records()
+        .await
+        .context("Failed to fetch records from repository")?;
+
+    let now = Utc::now();
+    let swum_on = now.date_naive();
+    let kinds = if event.phase == db::event::EventPhase::MeetInProgress {
+        &[RecordKind::Club, RecordKind::Meet][..]
+    } else {
+        &[RecordKind::Club][..]
+    };
+    let mut new_records = Vec::new();
+    for &kind in kinds {
+        let record = Record {
+            id: Uuid::new_v4(),
+            kind,
+            gender: participant.gender,
+            stroke: competition.stroke,
+            distance: competition.distance,
+            course: event.course,
+            age_class: age_class(participant.birthday, swum_on),
+            time_millis: time_millis.min(i32::MAX as u32) as _,
+            first_name: participant.first_name.clone(),
+            last_name: participant.last_name.clone(),
+            birthday: participant.birthday,
+            swum_on,
+            meet: None,
+            registration_id: Some(registration_id),
+            status: RecordStatus::Pending,
+            created_at: now,
+            decided_at: None,
+            superseded_by: None,
+        };
+
+        let beaten = records
+            .iter()
+            .filter(|r| r.status == RecordStatus::Approved && same_key(r, &record))
+            .all(|r| is_faster(&record, r, precision));
+        if !beaten {
+            continue;
+        }
+
+        tracing::info!(?kind, "New record");
+        record_repo
+            .create_record(&record)
+            .await
+            .context("Failed to store new record in repository")?;
+        new_records.push(record.into());
+    }
+
+    Ok(new_records)
+}
+
+/// Reject the pending records of a result that has been removed.
+pub(super) async fn reject_records_of_result(
+    record_repo: &dyn db::records::Repository,
+    registration_id: Uuid,
+) -> anyhow::Result<()> {
+    let records = record_repo
+        .list_records()
+        .await
+        .context("Failed to fetch records from repository")?;
+
+    for record in records
+        .into_iter()
+        .filter(|r| r.status == RecordStatus::Pending && r.registration_id == Some(registration_id))
+    {
+        record_repo
+            .update_record_status(record.id, RecordStatus::Rejected, Utc::now(), None)
+            .await
+            .context("Failed to reject record in repository")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Fixture of a meet in progress with one 50m back competition and a
+    /// club record of 41.00.
+    async fn fixture() -> (Fixture, Uuid) {
+        let fixture = Fixture::new();
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::MeetInProgress)
+            .await
+            .unwrap();
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture
+            .record_service()
+            .add_record(paper_record(41_000))
+            .await
+            .unwrap();
+        (fixture, competition_id)
+    }
+
+    fn paper_record(time_millis: u32) -> NewRecord {
+        NewRecord {
+            kind: model::RecordKind::Club,
+            gender: model::Gender::Female,
+            stroke: model::Stroke::Back,
+            distance: 50,
+            course: model::Course::Short,
+            // The fixture participants are born in 2014
+            age_class: (Utc::now().year() - 2014) as _,
+            time_millis,
+            first_name: "Alex".to_string(),
+            last_name: "Former".to_string(),
+            birthday: NaiveDate::from_ymd_opt(2009, 3, 1).unwrap(),
+            swum_on: NaiveDate::from_ymd_opt(2019, 11, 9).unwrap(),
+            meet: Some("Club Championships 2019".to_string()),
+        }
+    }
+
+    /// Enter a result and return the kinds of the new records.
+    async fn enter_result(
+        fixture: &Fixture,
+        competition_id: Uuid,
+        last_name: &str,
+        time_millis: u32,
+        disqualified: bool,
+    ) -> (Uuid, Vec<model::RecordKind>) {
+        let group_id = fixture.group(last_name).await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, last_name)
+            .await;
+        let registration_id = fixture.register(participant_id, competition_id).await;
+        let new_records = fixture
+            .registration_service()
+            .add_result_for_registration(registration_id, disqualified, time_millis)
+            .await
+            .unwrap();
+        (
+            registration_id,
+            new_records.into_iter().map(|r| r.kind).collect(),
+        )
+    }
+
+    async fn records_of(fixture: &Fixture, last_name: &str) -> Vec<model::Record> {
+        fixture
+            .record_service()
+            .records(None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.last_name == last_name)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn results_beating_the_records_are_pending_records() {
+        let (fixture, competition_id) = fixture().await;
+
+        // There is no meet record yet, so any valid time sets one
+        let (_, kinds) = enter_result(&fixture, competition_id, "Slow", 42_000, false).await;
+        assert_eq!(kinds, vec![model::RecordKind::Meet]);
+        let (_, kinds) = enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        assert_eq!(
+            kinds,
+            vec![model::RecordKind::Club, model::RecordKind::Meet]
+        );
+        // Not faster than the approved club record, the pending meet records
+        // of the others are no record yet
+        let (_, kinds) = enter_result(&fixture, competition_id, "Middle", 41_000, false).await;
+        assert_eq!(kinds, vec![model::RecordKind::Meet]);
+        let (_, kinds) = enter_result(&fixture, competition_id, "Early", 30_000, true).await;
+        assert_eq!(kinds, vec![]);
+
+        // The slower pending records wait until the faster one is decided
+        let pending = fixture
+            .record_service()
+            .records(None, Some(model::RecordStatus::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 4);
+        assert!(pending
+            .iter()
+            .all(|r| r.meet.is_none() && r.registration_id.is_some()));
+        let slow = records_of(&fixture, "Slow").await;
+        assert_eq!(slow[0].status, model::RecordStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn slower_results_after_a_pending_record_remain_when_it_is_rejected() {
+        let (fixture, competition_id) = fixture().await;
+        let record_service = fixture.record_service();
+        enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        let (_, kinds) = enter_result(&fixture, competition_id, "Slow", 40_500, false).await;
+        assert_eq!(
+            kinds,
+            vec![model::RecordKind::Club, model::RecordKind::Meet]
+        );
+
+        for record in records_of(&fixture, "Fast").await {
+            record_service.reject_record(record.id).await.unwrap();
+        }
+
+        let slow = records_of(&fixture, "Slow").await;
+        assert_eq!(slow.len(), 2);
+        assert!(slow
+            .iter()
+            .all(|r| r.status == model::RecordStatus::Pending));
+        let club_record = slow
+            .iter()
+            .find(|r| r.kind == model::RecordKind::Club)
+            .unwrap();
+        record_service.approve_record(club_record.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn approved_records_supersede_the_slower_pending_records() {
+        let (fixture, competition_id) = fixture().await;
+        let record_service = fixture.record_service();
+        enter_result(&fixture, competition_id, "Slow", 42_000, false).await;
+        enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        let fast_record = records_of(&fixture, "Fast")
+            .await
+            .into_iter()
+            .find(|r| r.kind == model::RecordKind::Meet)
+            .unwrap();
+
+        record_service.approve_record(fast_record.id).await.unwrap();
+
+        let slow = records_of(&fixture, "Slow").await;
+        assert_eq!(slow[0].status, model::RecordStatus::Superseded);
+        assert_eq!(slow[0].superseded_by, Some(fast_record.id));
+    }
+
+    #[tokio::test]
+    async fn slower_pending_records_remain_when_the_faster_one_is_rejected() {
+        let (fixture, competition_id) = fixture().await;
+        let record_service = fixture.record_service();
+        enter_result(&fixture, competition_id, "Slow", 42_000, false).await;
+        enter_result(&fixture, competition_id, "Hand", 40_500, false).await;
+        let (registration_id, _) =
+            enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        let hand_record = records_of(&fixture, "Hand")
+            .await
+            .into_iter()
+            .find(|r| r.kind == model::RecordKind::Meet)
+            .unwrap();
+
+        record_service.reject_record(hand_record.id).await.unwrap();
+        fixture
+            .registration_service()
+            .remove_registration_result(registration_id)
+            .await
+            .unwrap();
+
+        let slow = records_of(&fixture, "Slow").await;
+        assert_eq!(slow[0].status, model::RecordStatus::Pending);
+        record_service.approve_record(slow[0].id).await.unwrap();
+        let current = record_service
+            .records(
+                Some(model::RecordKind::Meet),
+                Some(model::RecordStatus::Approved),
+            )
+            .await
+            .unwrap();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].last_name, "Slow");
+    }
+
+    #[tokio::test]
+    async fn meet_records_are_only_set_during_the_meet() {
+        let (fixture, competition_id) = fixture().await;
+        fixture
+            .event_service()
+            .change_phase(model::EventPhase::RegistrationClosed)
+            .await
+            .unwrap();
+
+        let (_, kinds) = enter_result(&fixture, competition_id, "Early", 40_000, false).await;
+        assert_eq!(kinds, vec![model::RecordKind::Club]);
+    }
+
+    #[tokio::test]
+    async fn approved_records_supersede_the_former_record() {
+        let (fixture, competition_id) = fixture().await;
+        let record_service = fixture.record_service();
+        enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        let new_record = records_of(&fixture, "Fast")
+            .await
+            .into_iter()
+            .find(|r| r.kind == model::RecordKind::Club)
+            .unwrap();
+
+        record_service.approve_record(new_record.id).await.unwrap();
+
+        let former_record = records_of(&fixture, "Former").await[0].clone();
+        assert_eq!(former_record.status, model::RecordStatus::Superseded);
+        assert_eq!(former_record.superseded_by, Some(new_record.id));
+        let current = record_service
+            .records(
+                Some(model::RecordKind::Club),
+                Some(model::RecordStatus::Approved),
+            )
+            .await
+            .unwrap();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].id, new_record.id);
+
+        let result = record_service.approve_record(new_record.id).await;
+        assert!(
+            matches!(result, Err(ApproveRecordError::RecordNotPending)),
+            "{result:?}"
+        );
+        let result = record_service.approve_record(Uuid::new_v4()).await;
+        assert!(
+            matches!(result, Err(ApproveRecordError::RecordDoesNotExist)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn records_beaten_before_their_approval_can_not_be_approved() {
+        let (fixture, competition_id) = fixture().await;
+        let record_service = fixture.record_service();
+        enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+        let club_record = records_of(&fixture, "Fast")
+            .await
+            .into_iter()
+            .find(|r| r.kind == model::RecordKind::Club)
+            .unwrap();
+        record_service
+            .add_record(paper_record(39_500))
+            .await
+            .unwrap();
+
+        let result = record_service.approve_record(club_record.id).await;
+        assert!(
+            matches!(result, Err(ApproveRecordError::NotFasterThanRecord)),
+            "{result:?}"
+        );
+        record_service.reject_record(club_record.id).await.unwrap();
+        let result = record_service.reject_record(club_record.id).await;
+        assert!(
+            matches!(result, Err(RejectRecordError::RecordNotPending)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn removed_results_reject_their_pending_records() {
+        let (fixture, competition_id) = fixture().await;
+        let (registration_id, _) =
+            enter_result(&fixture, competition_id, "Fast", 40_000, false).await;
+
+        fixture
+            .registration_service()
+            .remove_registration_result(registration_id)
+            .await
+            .unwrap();
+
+        let statuses = records_of(&fixture, "Fast")
+            .await
+            .into_iter()
+            .map(|r| r.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![model::RecordStatus::Rejected, model::RecordStatus::Rejected]
+        );
+    }
+
+    #[tokio::test]
+    async fn paper_records_must_beat_the_current_record() {
+        let (fixture, _) = fixture().await;
+        let record_service = fixture.record_service();
+
+        let result = record_service.add_record(paper_record(41_000)).await;
+        assert!(
+            matches!(result, Err(AddRecordError::NotFasterThanRecord)),
+            "{result:?}"
+        );
+        let result = record_service.add_record(paper_record(0)).await;
+        assert!(
+            matches!(result, Err(AddRecordError::InvalidTime)),
+            "{result:?}"
+        );
+
+        record_service
+            .add_record(paper_record(40_500))
+            .await
+            .unwrap();
+        let statuses = records_of(&fixture, "Former")
+            .await
+            .into_iter()
+            .map(|r| (r.time, r.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                (41_000, model::RecordStatus::Superseded),
+                (40_500, model::RecordStatus::Approved)
+            ]
+        );
+    }
+}
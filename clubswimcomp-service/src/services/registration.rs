@@ -1,10 +1,15 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
+use chrono::Utc;
 use clubswimcomp_types::model;
 use thiserror::Error;
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::db;
+use crate::infra::card_code::{self, ScannedCode};
+use crate::services::{FinalsService, PromoteFinalistsError};
+use crate::{db, infra};
 
 #[derive(Debug, Error)]
 pub enum AddRegistrationResultError {
@@ -14,6 +19,12 @@ pub enum AddRegistrationResultError {
     #[error("The registration does not exist")]
     RegistrationDoesNotExist,
 
+    #[error("The finalists of the prelims have already been promoted")]
+    FinalistsAlreadyPromoted,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -26,6 +37,12 @@ pub enum RemoveRegistrationResultError {
     #[error("The registration does not have any result")]
     RegistrationHasNoResult,
 
+    #[error("The finalists of the prelims have already been promoted")]
+    FinalistsAlreadyPromoted,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -39,46 +56,252 @@ pub enum RegistrationDetailsError {
     RepositoryError(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum ScanCardError {
+    #[error("The code is not from a registration card")]
+    InvalidCode,
+
+    #[error("The registration card belongs to another event")]
+    CardOfOtherEvent,
+
+    #[error("The signature of the registration card is invalid")]
+    InvalidSignature,
+
+    #[error("The registration does not exist")]
+    RegistrationDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindRegistrationError {
+    #[error("No participant has the short code")]
+    ParticipantDoesNotExist,
+
+    #[error("The registration does not exist")]
+    RegistrationDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+/// The ways to identify a registration without scanning its card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationLookup {
+    /// The number printed on the registration card.
+    CardNumber(u32),
+    /// The short code of the participant and the competition swum.
+    ShortCode {
+        short_code: String,
+        competition_id: Uuid,
+    },
+}
+
+/// The registration of a scanned registration card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedCard {
+    pub registration_id: Uuid,
+    /// The card has been printed before the codes were signed and could
+    /// belong to any event.
+    pub legacy: bool,
+}
+
 pub struct RegistrationService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    record_repo: Arc<dyn db::records::Repository>,
+    finals_repo: Arc<dyn db::finals::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
 }
 
 impl RegistrationService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        record_repo: Arc<dyn db::records::Repository>,
+        finals_repo: Arc<dyn db::finals::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
     ) -> Self {
         Self {
             participant_repo,
             registration_repo,
             competition_repo,
+            event_repo,
+            record_repo,
+            finals_repo,
+            group_repo,
+            best_time_repo,
+            payment_repo,
+            typst_compiler,
         }
     }
 
+    fn finals_service(&self) -> FinalsService {
+        FinalsService::new(
+            self.finals_repo.clone(),
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    /// Find the registration of a scanned registration card.
+    ///
+    /// Signed codes must belong to this event and have a valid signature,
+    /// codes that only contain the registration id are accepted as legacy
+    /// cards.
+    #[instrument(skip(self))]
+    pub async fn scan_card(&self, content: &str) -> Result<ScannedCard, ScanCardError> {
+        let scanned = match card_code::parse(content).ok_or(ScanCardError::InvalidCode)? {
+            ScannedCode::Legacy { registration_id } => ScannedCard {
+                registration_id,
+                legacy: true,
+            },
+            ScannedCode::Signed { code, signature } => {
+                tracing::debug!("Verifying the signature of the card");
+                let event = super::current_event(self.event_repo.as_ref()).await?;
+                if code.event_id != event.id {
+                    return Err(ScanCardError::CardOfOtherEvent);
+                }
+                if !code.verify(&signature, &event.card_secret) {
+                    return Err(ScanCardError::InvalidSignature);
+                }
+
+                ScannedCard {
+                    registration_id: code.registration_id,
+                    legacy: false,
+                }
+            }
+        };
+
+        tracing::debug!(registration_id = ?scanned.registration_id, "Ensuring the registration exists");
+        self.registration_repo
+            .registration_by_id(scanned.registration_id)
+            .await
+            .context("Failed to search registration by id in repository")?
+            .ok_or(ScanCardError::RegistrationDoesNotExist)?;
+
+        Ok(scanned)
+    }
+
+    /// Find a registration by what is typed in at the result desk when a
+    /// card can not be scanned.
+    #[instrument(skip(self))]
+    pub async fn find_registration(
+        &self,
+        lookup: RegistrationLookup,
+    ) -> Result<model::RegistrationDetails, FindRegistrationError> {
+        let registration_id = match lookup {
+            RegistrationLookup::CardNumber(card_number) => {
+                tracing::debug!("Searching registration by card number");
+                let card_number = i32::try_from(card_number)
+                    .map_err(|_| FindRegistrationError::RegistrationDoesNotExist)?;
+                self.registration_repo
+                    .registration_by_card_number(card_number)
+                    .await
+                    .context("Failed to search registration by card number in repository")?
+                    .ok_or(FindRegistrationError::RegistrationDoesNotExist)?
+                    .id
+            }
+            RegistrationLookup::ShortCode {
+                short_code,
+                competition_id,
+            } => {
+                tracing::debug!("Searching participant by short code");
+                let short_id = short_code
+                    .trim()
+                    .parse::<i32>()
+                    .map_err(|_| FindRegistrationError::ParticipantDoesNotExist)?;
+                let participant = self
+                    .participant_repo
+                    .participant_by_short_code(short_id)
+                    .await
+                    .context("Failed to search participant by short id in repository")?
+                    .ok_or(FindRegistrationError::ParticipantDoesNotExist)?;
+
+                tracing::debug!(
+                    "Searching the registration of the participant for the competition"
+                );
+                self.registration_repo
+                    .registrations_of_participant(participant.id)
+                    .await
+                    .context("Failed to fetch registrations of participant from repository")?
+                    .into_iter()
+                    .find(|r| r.competition_id == competition_id)
+                    .ok_or(FindRegistrationError::RegistrationDoesNotExist)?
+                    .id
+            }
+        };
+
+        self.registration_details(registration_id)
+            .await
+            .map_err(|err| match err {
+                RegistrationDetailsError::RegistrationDoesNotExist => {
+                    FindRegistrationError::RegistrationDoesNotExist
+                }
+                RegistrationDetailsError::RepositoryError(e) => e.into(),
+            })
+    }
+
     /// Enter results for a registration.
     ///
+    /// Results that are not disqualified are checked for new club and meet
+    /// records, which wait for an approval. With the last result of prelims
+    /// the finalists are promoted into the finals, unless a swim-off has to
+    /// decide a tie first. Once they are promoted the results of the prelims
+    /// can't be changed anymore.
+    ///
     /// # Parameters:
     /// - `registration_id` - The id of the registration
     /// - `disqualified` - `true` is the participant is disqualified, `false`
     ///   otherwise.
     /// - `time_millis` - The result time of the participant in milliseconds.
+    ///
+    /// # Returns:
+    /// The new records set with the result.
     #[instrument(skip(self))]
     pub async fn add_result_for_registration(
         &self,
         registration_id: Uuid,
         disqualified: bool,
         time_millis: u32,
-    ) -> Result<(), AddRegistrationResultError> {
+    ) -> Result<Vec<model::Record>, AddRegistrationResultError> {
         tracing::debug!("Ensuring the registration actually exists");
-        self.registration_repo
+        let registration = self
+            .registration_repo
             .registration_by_id(registration_id)
             .await
             .context("Failed to search registration by id in repository")?
             .ok_or(AddRegistrationResultError::RegistrationDoesNotExist)?;
 
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            tracing::debug!("Results of a finalized event can't be changed");
+            return Err(AddRegistrationResultError::EventFinalized);
+        }
+
+        if self.finalists_promoted(registration.competition_id).await? {
+            tracing::debug!("Results of prelims can't be changed once the finals are set");
+            return Err(AddRegistrationResultError::FinalistsAlreadyPromoted);
+        }
+
         tracing::debug!("Ensuring no result already exists for registration");
         if self
             .registration_repo
@@ -94,8 +317,104 @@ impl RegistrationService {
         self.registration_repo
             .create_registration_result(registration_id, time_millis as _, disqualified)
             .await
-            .context("Failed to create result for registration in repository")
-            .map_err(AddRegistrationResultError::from)
+            .context("Failed to create result for registration in repository")?;
+
+        let competition = self
+            .competition_repo
+            .competition_by_id(registration.competition_id)
+            .await
+            .context("Failed to fetch competition for registration from repository")?
+            .context(
+                "Repository has reference to competition but competition could not be found",
+            )?;
+        if competition.round == db::competitions::CompetitionRound::Prelims {
+            self.promote_finalists(competition.id).await?;
+        }
+
+        // The time of a relay is swum by the team, not by the registered
+        // swimmer alone
+        if disqualified || competition.relay {
+            return Ok(Vec::new());
+        }
+
+        let participant = self
+            .participant_repo
+            .participant_by_id(registration.participant_id)
+            .await
+            .context("Failed to fetch participant for registration from repository")?
+            .context(
+                "Repository has reference to participant but participant could not be found",
+            )?;
+
+        tracing::debug!("Recording the result as best time of the participant");
+        self.best_time_repo
+            .record_best_time(&db::best_times::BestTime {
+                id: Uuid::new_v4(),
+                first_name: participant.first_name.clone(),
+                last_name: participant.last_name.clone(),
+                birthday: participant.birthday,
+                stroke: competition.stroke,
+                distance: competition.distance,
+                course: event.course,
+                time_millis: time_millis.min(i32::MAX as u32) as _,
+                swum_on: Utc::now().date_naive(),
+                meet: event.name.clone(),
+                registration_id: Some(registration_id),
+            })
+            .await
+            .context("Failed to record best time in repository")?;
+
+        tracing::debug!("Checking the result for new records");
+        let new_records = super::record::check_records(
+            self.record_repo.as_ref(),
+            &event,
+            &participant,
+            &competition,
+            registration_id,
+            time_millis,
+        )
+        .await?;
+
+        Ok(new_records)
+    }
+
+    /// Promote the finalists of the prelims once every swimmer has a result.
+    async fn promote_finalists(&self, competition_id: Uuid) -> Result<()> {
+        match self
+            .finals_service()
+            .promote_finalists(competition_id)
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Promoted the finalists with the last result of the prelims");
+                Ok(())
+            }
+            Err(PromoteFinalistsError::RepositoryError(e)) => Err(e),
+            Err(reason) => {
+                tracing::debug!(%reason, "Finalists are not promoted yet");
+                Ok(())
+            }
+        }
+    }
+
+    /// The finalists of the prelims have been promoted, so the results of the
+    /// prelims have decided the finals and can't be changed anymore.
+    async fn finalists_promoted(&self, competition_id: Uuid) -> Result<bool> {
+        let finals = self
+            .finals_repo
+            .finals_of_competition(competition_id)
+            .await
+            .context("Failed to fetch finals from repository")?;
+        if finals.is_none() {
+            return Ok(false);
+        }
+
+        let entries = self
+            .finals_repo
+            .finals_entries(competition_id)
+            .await
+            .context("Failed to fetch finals entries from repository")?;
+        Ok(!entries.is_empty())
     }
 
     /// Remove a result for a registration.
@@ -108,18 +427,35 @@ impl RegistrationService {
         registration_id: Uuid,
     ) -> Result<(), RemoveRegistrationResultError> {
         tracing::debug!("Ensuring the registration actually exists");
-        self.registration_repo
+        let registration = self
+            .registration_repo
             .registration_by_id(registration_id)
             .await
             .context("Failed to search registration by id in repository")?
             .ok_or(RemoveRegistrationResultError::RegistrationDoesNotExist)?;
 
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            tracing::debug!("Results of a finalized event can't be changed");
+            return Err(RemoveRegistrationResultError::EventFinalized);
+        }
+
+        if self.finalists_promoted(registration.competition_id).await? {
+            tracing::debug!("Results of prelims can't be changed once the finals are set");
+            return Err(RemoveRegistrationResultError::FinalistsAlreadyPromoted);
+        }
+
         tracing::debug!("Trying to delete the registration result in the repository");
         self.registration_repo
             .delete_result_for_registration(registration_id)
             .await
             .context("Failed to delete registration result in repository")?
-            .ok_or(RemoveRegistrationResultError::RegistrationHasNoResult)
+            .ok_or(RemoveRegistrationResultError::RegistrationHasNoResult)?;
+
+        tracing::debug!("Rejecting the pending records of the result");
+        super::record::reject_records_of_result(self.record_repo.as_ref(), registration_id).await?;
+
+        Ok(())
     }
 
     /// Get the details of a registration.
@@ -177,3 +513,356 @@ impl RegistrationService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{Gender, Stroke};
+    use crate::infra::card_code::CardCode;
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    async fn registration(fixture: &Fixture) -> Uuid {
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 40_000)
+            .await;
+        fixture.register(participant_id, competition_id).await
+    }
+
+    /// Code of the card as printed for the event of the fixture.
+    async fn card(fixture: &Fixture, registration_id: Uuid) -> (CardCode, Vec<u8>) {
+        let event = crate::services::current_event(fixture.repos.event.as_ref())
+            .await
+            .unwrap();
+        let code = CardCode {
+            event_id: event.id,
+            registration_id,
+            short_code: "1234".to_string(),
+        };
+        (code, event.card_secret)
+    }
+
+    #[tokio::test]
+    async fn signed_cards_of_the_event_are_accepted() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let (code, secret) = card(&fixture, registration_id).await;
+
+        let scanned = fixture
+            .registration_service()
+            .scan_card(&code.encode(&secret))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            scanned,
+            ScannedCard {
+                registration_id,
+                legacy: false
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn legacy_cards_are_accepted() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+
+        let scanned = fixture
+            .registration_service()
+            .scan_card(&registration_id.to_string())
+            .await
+            .unwrap();
+
+        assert!(scanned.legacy);
+        assert_eq!(scanned.registration_id, registration_id);
+    }
+
+    #[tokio::test]
+    async fn cards_of_other_events_are_rejected() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let (code, secret) = card(&Fixture::new(), registration_id).await;
+
+        let result = fixture
+            .registration_service()
+            .scan_card(&code.encode(&secret))
+            .await;
+
+        assert!(
+            matches!(result, Err(ScanCardError::CardOfOtherEvent)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn registrations_are_found_by_card_number() {
+        let fixture = Fixture::new();
+        let first = registration(&fixture).await;
+        let participant_id = fixture
+            .participant(
+                fixture.group("Sharks").await,
+                Gender::Female,
+                "Other swimmer",
+            )
+            .await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Breast, 50, 45_000)
+            .await;
+        let second = fixture.register(participant_id, competition_id).await;
+        let registration_service = fixture.registration_service();
+
+        let found = registration_service
+            .find_registration(RegistrationLookup::CardNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(found.id, second);
+        let found = registration_service
+            .find_registration(RegistrationLookup::CardNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(found.id, first);
+
+        let result = registration_service
+            .find_registration(RegistrationLookup::CardNumber(3))
+            .await;
+        assert!(
+            matches!(result, Err(FindRegistrationError::RegistrationDoesNotExist)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn registrations_are_found_by_short_code_and_competition() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let details = fixture
+            .registration_service()
+            .registration_details(registration_id)
+            .await
+            .unwrap();
+        let registration_service = fixture.registration_service();
+
+        let found = registration_service
+            .find_registration(RegistrationLookup::ShortCode {
+                short_code: details.participant.short_code.clone(),
+                competition_id: details.competition.id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(found, details);
+
+        let result = registration_service
+            .find_registration(RegistrationLookup::ShortCode {
+                short_code: details.participant.short_code.clone(),
+                competition_id: Uuid::new_v4(),
+            })
+            .await;
+        assert!(
+            matches!(result, Err(FindRegistrationError::RegistrationDoesNotExist)),
+            "{result:?}"
+        );
+        let result = registration_service
+            .find_registration(RegistrationLookup::ShortCode {
+                short_code: "abc".to_string(),
+                competition_id: details.competition.id,
+            })
+            .await;
+        assert!(
+            matches!(result, Err(FindRegistrationError::ParticipantDoesNotExist)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn forged_cards_are_rejected() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let (code, _) = card(&fixture, registration_id).await;
+
+        let result = fixture
+            .registration_service()
+            .scan_card(&code.encode(b"guessed secret"))
+            .await;
+
+        assert!(
+            matches!(result, Err(ScanCardError::InvalidSignature)),
+            "{result:?}"
+        );
+        assert!(matches!(
+            fixture.registration_service().scan_card("no card").await,
+            Err(ScanCardError::InvalidCode)
+        ));
+    }
+
+    #[tokio::test]
+    async fn results_of_finalized_events_are_read_only() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        fixture.result(registration_id, 41_000, false).await;
+        let event_service = fixture.event_service();
+        event_service
+            .change_phase(model::EventPhase::Finalized)
+            .await
+            .unwrap();
+        let registration_service = fixture.registration_service();
+
+        let result = registration_service
+            .remove_registration_result(registration_id)
+            .await;
+        assert!(
+            matches!(result, Err(RemoveRegistrationResultError::EventFinalized)),
+            "{result:?}"
+        );
+
+        event_service
+            .reopen_event(model::EventPhase::MeetInProgress, "Wrong time")
+            .await
+            .unwrap();
+        registration_service
+            .remove_registration_result(registration_id)
+            .await
+            .unwrap();
+        registration_service
+            .add_result_for_registration(registration_id, false, 40_500)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn results_are_recorded_as_best_times() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let registration = fixture
+            .repos
+            .registrations
+            .registration_by_id(registration_id)
+            .await
+            .unwrap()
+            .unwrap();
+        fixture
+            .event_service()
+            .set_event_info(model::EventInfo {
+                name: "Autumn Cup".to_string(),
+                organization: "SC Example".to_string(),
+            })
+            .await
+            .unwrap();
+        let registration_service = fixture.registration_service();
+        let best_times = || async {
+            fixture
+                .best_time_service()
+                .participant_best_times(registration.participant_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|b| (b.time, b.meet))
+                .collect::<Vec<_>>()
+        };
+
+        registration_service
+            .add_result_for_registration(registration_id, false, 42_000)
+            .await
+            .unwrap();
+        assert_eq!(best_times().await, vec![(42_000, "Autumn Cup".to_string())]);
+
+        // A corrected result replaces the time, also with a slower one
+        for (disqualified, time_millis, expected) in [
+            (false, 43_000, vec![(43_000, "Autumn Cup".to_string())]),
+            (true, 39_000, vec![]),
+            (false, 41_000, vec![(41_000, "Autumn Cup".to_string())]),
+        ] {
+            registration_service
+                .remove_registration_result(registration_id)
+                .await
+                .unwrap();
+            assert_eq!(best_times().await, vec![]);
+            registration_service
+                .add_result_for_registration(registration_id, disqualified, time_millis)
+                .await
+                .unwrap();
+            assert_eq!(best_times().await, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn relay_results_are_no_best_times_or_records() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Swimmer")
+            .await;
+        let relay = fixture
+            .relay(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let registration_id = fixture.register(participant_id, relay).await;
+
+        let new_records = fixture
+            .registration_service()
+            .add_result_for_registration(registration_id, false, 29_000)
+            .await
+            .unwrap();
+
+        assert!(new_records.is_empty());
+        assert!(fixture
+            .best_time_service()
+            .participant_best_times(participant_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn results_of_this_event_are_no_previous_best() {
+        let fixture = Fixture::new();
+        let registration_id = registration(&fixture).await;
+        let competition_id = fixture
+            .repos
+            .registrations
+            .registration_by_id(registration_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .competition_id;
+        fixture
+            .registration_service()
+            .add_result_for_registration(registration_id, false, 41_000)
+            .await
+            .unwrap();
+        let previous_best = || async {
+            fixture
+                .score_service()
+                .competition_scoreboard(competition_id)
+                .await
+                .unwrap()
+                .scores[0]
+                .previous_best
+        };
+        assert_eq!(previous_best().await, None);
+
+        // Imported times count however late they have been swum
+        fixture
+            .repos
+            .best_times
+            .insert_best_times(&[db::best_times::BestTime {
+                id: Uuid::new_v4(),
+                first_name: "Kim".to_string(),
+                last_name: "Swimmer".to_string(),
+                birthday: chrono::NaiveDate::from_ymd_opt(2014, 6, 1).unwrap(),
+                stroke: Stroke::Back,
+                distance: 50,
+                course: db::Course::Short,
+                time_millis: 42_000,
+                swum_on: Utc::now().date_naive(),
+                meet: "Club Championships".to_string(),
+                registration_id: None,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(previous_best().await, Some(42_000));
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use clubswimcomp_types::model;
 use qrcode::{render::svg, QrCode};
@@ -5,12 +7,16 @@ use thiserror::Error;
 use tracing::instrument;
 use uuid::Uuid;
 
+use crate::infra::{
+    card_code::CardCode, registration_card::CardLayout, typst_compiler::TypstOutput,
+};
 use crate::{db, infra};
 
 pub struct RegistrationCardService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
     typst_compiler: infra::typst_compiler::TypstCompiler,
 }
 
@@ -19,6 +25,9 @@ pub enum EventRegistrationCardsError {
     #[error("Failed to generate the start card PDF")]
     PdfGenerationFailed(anyhow::Error),
 
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -31,6 +40,9 @@ pub enum ParticipantRegistrationCardsError {
     #[error("Failed to generate the start card PDF")]
     PdfGenerationFailed(anyhow::Error),
 
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -43,43 +55,64 @@ pub enum RegistrationCardsError {
     #[error("Failed to generate the start card PDF")]
     PdfGenerationFailed(anyhow::Error),
 
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum CardCalibrationError {
+    #[error("Failed to generate the calibration grid PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+}
+
 impl RegistrationCardService {
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
         typst_compiler: infra::typst_compiler::TypstCompiler,
     ) -> Self {
         Self {
             participant_repo,
             registration_repo,
             competition_repo,
+            event_repo,
             typst_compiler,
         }
     }
 
     #[instrument(skip(self))]
-    async fn generate_pdf_for_cards(
+    async fn generate_cards(
         &self,
         cards: Vec<infra::registration_card::RegistrationCard>,
+        layout: CardLayout,
+        calibration: bool,
+        output: TypstOutput,
     ) -> anyhow::Result<Vec<u8>> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
         infra::registration_card::RegistrationCards {
-            event_name: "TEST EVENT NAME".to_string(),
-            organization: "TEST ORGANIZATION".to_string(),
+            event_name: event.name,
+            organization: event.organization,
+            layout,
+            calibration,
             cards,
         }
-        .generate_pdf(&self.typst_compiler)
+        .generate(&self.typst_compiler, output)
         .await
-        .context("Failed to generate PDF for registration cards")
+        .context("Failed to generate registration cards")
     }
 
     #[instrument(skip(self))]
     async fn load_for_registration(
         &self,
+        event: &db::event::Event,
         registration_id: Uuid,
     ) -> anyhow::Result<Option<infra::registration_card::RegistrationCard>> {
         tracing::debug!("Fetching registration from repository");
@@ -114,8 +147,7 @@ impl RegistrationCardService {
                 "Competition is referenced in registration but could not be found in repository",
             )?;
 
-        let qr_code = QrCode::new(registration.id.to_string().as_bytes()).unwrap();
-        let qr_code = qr_code.render::<svg::Color>().build();
+        let qr_code = qr_code(event, registration.id, &participant.short_code);
 
         Ok(Some(infra::registration_card::RegistrationCard {
             first_name: participant.first_name.clone(),
@@ -124,6 +156,7 @@ impl RegistrationCardService {
             stroke: competition.stroke.into(),
             gender: competition.gender.into(),
             participant_number: participant.short_code.clone(),
+            card_number: registration.card_number as _,
             qr_code,
         }))
     }
@@ -131,6 +164,7 @@ impl RegistrationCardService {
     #[instrument(skip(self))]
     async fn load_for_participant(
         &self,
+        event: &db::event::Event,
         participant: model::Participant,
     ) -> anyhow::Result<Vec<infra::registration_card::RegistrationCard>> {
         tracing::debug!("Loading all registrations for participant");
@@ -155,8 +189,7 @@ impl RegistrationCardService {
                     .map(model::Competition::from)
                     .context("Competition is referenced in registration but could not be found in repository")?;
 
-            let qr_code = QrCode::new(db_registration.id.to_string().as_bytes()).unwrap();
-            let qr_code = qr_code.render::<svg::Color>().build();
+            let qr_code = qr_code(event, db_registration.id, &participant.short_code);
 
             let registeration_card = infra::registration_card::RegistrationCard {
                 first_name: participant.first_name.clone(),
@@ -165,6 +198,7 @@ impl RegistrationCardService {
                 stroke: competition.stroke.into(),
                 gender: competition.gender.into(),
                 participant_number: participant.short_code.clone(),
+                card_number: db_registration.card_number as _,
                 qr_code,
             };
 
@@ -175,7 +209,11 @@ impl RegistrationCardService {
     }
 
     #[instrument(skip(self))]
-    pub async fn all_registration_cards(&self) -> Result<Vec<u8>, EventRegistrationCardsError> {
+    pub async fn all_registration_cards(
+        &self,
+        layout: CardLayout,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, EventRegistrationCardsError> {
         tracing::debug!("Fetching participants from repository");
         let mut participants = self
             .participant_repo
@@ -188,11 +226,12 @@ impl RegistrationCardService {
 
         participants.sort_by_cached_key(|p| format!("{}, {}", p.last_name, p.first_name));
 
+        let event = super::current_event(self.event_repo.as_ref()).await?;
         let mut registration_cards = Vec::new();
         for participant in participants {
             tracing::debug!(participant_id = ?participant.id, "Loading participants card information");
             let mut participant_cards = self
-                .load_for_participant(participant)
+                .load_for_participant(&event, participant)
                 .await
                 .context("Failed to load participant registration card information")?;
 
@@ -200,16 +239,24 @@ impl RegistrationCardService {
         }
 
         tracing::debug!("Generating registration cards PDF");
-        self.generate_pdf_for_cards(registration_cards)
+        self.generate_cards(registration_cards, layout, false, output)
             .await
             .context("Failed to generate registration cards for participant")
-            .map_err(EventRegistrationCardsError::PdfGenerationFailed)
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    EventRegistrationCardsError::PageDoesNotExist,
+                    EventRegistrationCardsError::PdfGenerationFailed,
+                )
+            })
     }
 
     #[instrument(skip(self))]
     pub async fn participants_registration_cards(
         &self,
         participant_id: Uuid,
+        layout: CardLayout,
+        output: TypstOutput,
     ) -> Result<Vec<u8>, ParticipantRegistrationCardsError> {
         tracing::debug!("Fetching participant from repository");
         let participant = self
@@ -221,34 +268,168 @@ impl RegistrationCardService {
             .ok_or(ParticipantRegistrationCardsError::ParticipantDoesNotExist)?;
 
         tracing::debug!("Loading participants registration card information");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
         let participant_cards = self
-            .load_for_participant(participant)
+            .load_for_participant(&event, participant)
             .await
             .context("Failed to load participant registration card information")?;
 
         tracing::debug!("Generating registration cards PDF");
-        self.generate_pdf_for_cards(participant_cards)
+        self.generate_cards(participant_cards, layout, false, output)
             .await
             .context("Failed to generate registration cards for participant")
-            .map_err(ParticipantRegistrationCardsError::PdfGenerationFailed)
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    ParticipantRegistrationCardsError::PageDoesNotExist,
+                    ParticipantRegistrationCardsError::PdfGenerationFailed,
+                )
+            })
     }
 
     #[instrument(skip(self))]
     pub async fn registration_card(
         &self,
         registration_id: Uuid,
+        layout: CardLayout,
+        output: TypstOutput,
     ) -> Result<Vec<u8>, RegistrationCardsError> {
         tracing::debug!("Fetching registration card content from repository");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
         let card = self
-            .load_for_registration(registration_id)
+            .load_for_registration(&event, registration_id)
             .await
             .context("Failed to fetch registration card content from repository")?
             .ok_or(RegistrationCardsError::RegistrationDoesNotExist)?;
 
         tracing::debug!("Generating registration card PDF");
-        self.generate_pdf_for_cards(vec![card])
+        self.generate_cards(vec![card], layout, false, output)
             .await
             .context("Failed to generate registration card for registration")
-            .map_err(RegistrationCardsError::PdfGenerationFailed)
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    RegistrationCardsError::PageDoesNotExist,
+                    RegistrationCardsError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    /// Test grid with the outlines of the cards, printed before the cards to
+    /// align the paper or labels in the printer.
+    #[instrument(skip(self))]
+    pub async fn calibration_sheet(
+        &self,
+        layout: CardLayout,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, CardCalibrationError> {
+        tracing::debug!("Generating calibration grid PDF");
+        self.generate_cards(Vec::new(), layout, true, output)
+            .await
+            .context("Failed to generate calibration grid")
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    CardCalibrationError::PageDoesNotExist,
+                    CardCalibrationError::PdfGenerationFailed,
+                )
+            })
+    }
+}
+
+/// QR code of a registration card as SVG, signed with the secret of the event.
+fn qr_code(event: &db::event::Event, registration_id: Uuid, short_code: &str) -> String {
+    let content = CardCode {
+        event_id: event.id,
+        registration_id,
+        short_code: short_code.to_string(),
+    }
+    .encode(&event.card_secret);
+
+    QrCode::new(content.as_bytes())
+        .expect("Card codes fit into a QR code")
+        .render::<svg::Color>()
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db;
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    const LABEL: CardLayout = CardLayout::Label {
+        width_mm: 62,
+        height_mm: 29,
+    };
+
+    /// Participant registered for nine competitions.
+    async fn participant_with_nine_registrations(fixture: &Fixture) -> Uuid {
+        let group_id = fixture.group("Delphine").await;
+        let participant_id = fixture
+            .participant(group_id, db::Gender::Female, "Schwimmer")
+            .await;
+        for distance in (1..=9).map(|i| i * 25) {
+            let competition_id = fixture
+                .competition(db::Gender::Female, db::Stroke::Freestyle, distance, 60_000)
+                .await;
+            fixture.register(participant_id, competition_id).await;
+        }
+        participant_id
+    }
+
+    #[tokio::test]
+    async fn layouts_place_cards_on_their_pages() {
+        let fixture = Fixture::new();
+        let participant_id = participant_with_nine_registrations(&fixture).await;
+        let service = fixture.registration_card_service();
+
+        for (layout, pages) in [(CardLayout::A6, 9), (CardLayout::A4Grid, 2), (LABEL, 9)] {
+            let last_page = service
+                .participants_registration_cards(
+                    participant_id,
+                    layout,
+                    TypstOutput::Svg { page: pages },
+                )
+                .await;
+            assert!(last_page.is_ok(), "{layout:?}: {last_page:?}");
+
+            let result = service
+                .participants_registration_cards(
+                    participant_id,
+                    layout,
+                    TypstOutput::Svg { page: pages + 1 },
+                )
+                .await;
+            assert!(
+                matches!(
+                    result,
+                    Err(ParticipantRegistrationCardsError::PageDoesNotExist(_))
+                ),
+                "{layout:?} has more than {pages} pages"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn calibration_sheets_have_a_single_page() {
+        let service = Fixture::new().registration_card_service();
+
+        for layout in [CardLayout::A6, CardLayout::A4Grid, LABEL] {
+            let pdf = service
+                .calibration_sheet(layout, TypstOutput::Pdf)
+                .await
+                .unwrap();
+            assert!(pdf.starts_with(b"%PDF"));
+
+            let result = service
+                .calibration_sheet(layout, TypstOutput::Png { page: 2 })
+                .await;
+            assert!(
+                matches!(result, Err(CardCalibrationError::PageDoesNotExist(2))),
+                "{layout:?}: {result:?}"
+            );
+        }
     }
 }
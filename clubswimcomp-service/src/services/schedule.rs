@@ -0,0 +1,593 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDateTime};
+use clubswimcomp_types::{api, model};
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::infra::typst_compiler::TypstOutput;
+use crate::{db, infra};
+
+use super::{seed_heats, ServiceRepositoryError};
+
+pub struct ScheduleService {
+    session_repo: Arc<dyn db::sessions::Repository>,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
+    lanes: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum SaveSessionError {
+    #[error("The session does not exist")]
+    SessionDoesNotExist,
+
+    #[error("The name of the session must not be empty")]
+    InvalidName,
+
+    #[error("The warm-up or turnaround time is too long")]
+    InvalidDuration,
+
+    #[error("The competition {0} does not exist")]
+    CompetitionDoesNotExist(Uuid),
+
+    #[error("The competition {0} is already swum in a session")]
+    CompetitionAlreadyScheduled(Uuid),
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SessionDetailsError {
+    #[error("The session does not exist")]
+    SessionDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveSessionError {
+    #[error("The session does not exist")]
+    SessionDoesNotExist,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum TimetableError {
+    #[error("Failed to generate the timetable PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+/// A lane of a heat as far as it matters for the duration of the heat.
+struct LaneEstimate {
+    name: String,
+    entry_time_millis: Option<u32>,
+    result_time_millis: Option<u32>,
+}
+
+impl ScheduleService {
+    pub fn new(
+        session_repo: Arc<dyn db::sessions::Repository>,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
+        lanes: u32,
+    ) -> Self {
+        Self {
+            session_repo,
+            participant_repo,
+            registration_repo,
+            competition_repo,
+            event_repo,
+            typst_compiler,
+            lanes,
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list_sessions(&self) -> Result<Vec<model::Session>, ServiceRepositoryError> {
+        Ok(self
+            .session_repo
+            .all_sessions()
+            .await
+            .context("Failed to fetch sessions from repository")?
+            .into_iter()
+            .map(model::Session::from)
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn session(&self, session_id: Uuid) -> Result<model::Session, SessionDetailsError> {
+        self.session_repo
+            .session_by_id(session_id)
+            .await
+            .context("Failed to fetch session from repository")?
+            .map(model::Session::from)
+            .ok_or(SessionDetailsError::SessionDoesNotExist)
+    }
+
+    /// Check the settings of a session before it is stored.
+    ///
+    /// Every competition may only be swum once, in one session.
+    #[instrument(skip(self))]
+    async fn validate_session(
+        &self,
+        session: &db::sessions::Session,
+    ) -> Result<(), SaveSessionError> {
+        if session.name.trim().is_empty() {
+            return Err(SaveSessionError::InvalidName);
+        }
+
+        let competition_ids = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?
+            .into_iter()
+            .map(|c| c.id)
+            .collect::<HashSet<_>>();
+        let mut scheduled = self
+            .session_repo
+            .all_sessions()
+            .await
+            .context("Failed to fetch sessions from repository")?
+            .into_iter()
+            .filter(|s| s.id != session.id)
+            .flat_map(|s| s.competition_ids)
+            .collect::<HashSet<_>>();
+
+        for competition_id in session.competition_ids.iter() {
+            if !competition_ids.contains(competition_id) {
+                return Err(SaveSessionError::CompetitionDoesNotExist(*competition_id));
+            }
+            if !scheduled.insert(*competition_id) {
+                return Err(SaveSessionError::CompetitionAlreadyScheduled(
+                    *competition_id,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn add_session(&self, body: api::SessionBody) -> Result<Uuid, SaveSessionError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SaveSessionError::EventFinalized);
+        }
+
+        let session = db_session(Uuid::new_v4(), body)?;
+        self.validate_session(&session).await?;
+
+        tracing::debug!("Creating session in repository");
+        self.session_repo
+            .create_session(&session)
+            .await
+            .context("Failed to create session in repository")?;
+
+        Ok(session.id)
+    }
+
+    /// Replace the settings and the order of competitions of a session.
+    #[instrument(skip(self))]
+    pub async fn update_session(
+        &self,
+        session_id: Uuid,
+        body: api::SessionBody,
+    ) -> Result<(), SaveSessionError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SaveSessionError::EventFinalized);
+        }
+
+        let session = db_session(session_id, body)?;
+        self.validate_session(&session).await?;
+
+        tracing::debug!("Updating session in repository");
+        self.session_repo
+            .update_session(&session)
+            .await
+            .context("Failed to update session in repository")?
+            .ok_or(SaveSessionError::SessionDoesNotExist)
+    }
+
+    /// Remove a session, its competitions are no longer scheduled.
+    #[instrument(skip(self))]
+    pub async fn remove_session(&self, session_id: Uuid) -> Result<(), RemoveSessionError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(RemoveSessionError::EventFinalized);
+        }
+
+        self.session_repo
+            .delete_session(session_id)
+            .await
+            .context("Failed to delete session from repository")?
+            .ok_or(RemoveSessionError::SessionDoesNotExist)
+    }
+
+    /// Seed the registrations of a competition into heats like the start
+    /// list, to estimate how long each heat takes.
+    #[instrument(skip(self, participants))]
+    async fn competition_heats(
+        &self,
+        competition: &db::competitions::Competition,
+        participants: &HashMap<Uuid, db::participants::Participant>,
+    ) -> anyhow::Result<Vec<Vec<LaneEstimate>>> {
+        let registrations = self
+            .registration_repo
+            .registrations_for_competition(competition.id)
+            .await
+            .context("Failed to fetch registrations for competition from repository")?;
+
+        let mut entries = Vec::with_capacity(registrations.len());
+        for registration in registrations.into_iter() {
+            let participant = participants
+                .get(&registration.participant_id)
+                .context("Participant is referenced in registration but could not be found")?;
+            let result = self
+                .registration_repo
+                .result_for_registration(registration.id)
+                .await
+                .context("Failed to fetch result of registration from repository")?;
+
+            entries.push(LaneEstimate {
+                name: format!("{}, {}", participant.last_name, participant.first_name),
+                entry_time_millis: registration.entry_time_millis.map(|t| t as u32),
+                result_time_millis: result.map(|r| r.time_millis as u32),
+            });
+        }
+        // Same order as the start list, so the heats match the printed ones
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(seed_heats(entries, |e| e.entry_time_millis, self.lanes)
+            .into_iter()
+            .map(|heat| heat.into_iter().map(|(_, lane)| lane).collect())
+            .collect())
+    }
+
+    /// Estimate when the heats of all sessions start.
+    ///
+    /// Heats follow each other with the turnaround time of their session.
+    /// Swum heats take as long as their slowest result, so the estimates
+    /// follow the progress of the event.
+    #[instrument(skip(self))]
+    pub async fn schedule(&self) -> Result<model::Schedule, ServiceRepositoryError> {
+        tracing::debug!("Fetching sessions from repository");
+        let sessions = self
+            .session_repo
+            .all_sessions()
+            .await
+            .context("Failed to fetch sessions from repository")?;
+        let competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect::<HashMap<_, _>>();
+        let participants = self
+            .participant_repo
+            .list_participants()
+            .await
+            .context("Failed to fetch participants from repository")?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect::<HashMap<_, _>>();
+
+        let mut schedule = model::Schedule {
+            sessions: Vec::with_capacity(sessions.len()),
+        };
+        for session in sessions.into_iter() {
+            let turnaround = Duration::seconds(session.turnaround_seconds.into());
+            let mut next_start = session.starts_at;
+            let mut ends_at = session.starts_at;
+
+            let mut competition_schedules = Vec::with_capacity(session.competition_ids.len());
+            for competition_id in session.competition_ids.iter() {
+                let competition = competitions
+                    .get(competition_id)
+                    .context("Competition is referenced in session but could not be found")?;
+                let heats = self
+                    .competition_heats(competition, &participants)
+                    .await
+                    .context("Failed to estimate heats of competition")?;
+
+                let starts_at = next_start;
+                let mut heat_schedules = Vec::with_capacity(heats.len());
+                for (index, lanes) in heats.into_iter().enumerate() {
+                    let duration_millis = heat_duration(&lanes, competition.target_time as u32);
+                    heat_schedules.push(model::HeatSchedule {
+                        number: index as u32 + 1,
+                        starts_at: next_start,
+                        duration_millis,
+                        finished: lanes.iter().all(|l| l.result_time_millis.is_some()),
+                    });
+                    ends_at = next_start + Duration::milliseconds(duration_millis.into());
+                    next_start = ends_at + turnaround;
+                }
+
+                competition_schedules.push(model::CompetitionSchedule {
+                    competition: competition.clone().into(),
+                    starts_at,
+                    heats: heat_schedules,
+                });
+            }
+
+            schedule.sessions.push(model::SessionSchedule {
+                warm_up_at: session.starts_at - Duration::minutes(session.warm_up_minutes.into()),
+                ends_at,
+                competitions: competition_schedules,
+                session: session.into(),
+            });
+        }
+
+        Ok(schedule)
+    }
+
+    /// Generate the printable timetable of all sessions.
+    #[instrument(skip(self))]
+    pub async fn timetable(&self, output: TypstOutput) -> Result<Vec<u8>, TimetableError> {
+        let schedule = self.schedule().await.map_err(|e| match e {
+            ServiceRepositoryError::RepositoryError(e) => TimetableError::RepositoryError(e),
+        })?;
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+
+        let timetable = infra::timetable::Timetable {
+            event_name: event.name,
+            organization: event.organization,
+            sessions: schedule
+                .sessions
+                .into_iter()
+                .map(|s| infra::timetable::Session {
+                    name: s.session.name,
+                    date: s.session.starts_at.format("%d.%m.%Y").to_string(),
+                    warm_up_at: clock_time(s.warm_up_at),
+                    starts_at: clock_time(s.session.starts_at),
+                    ends_at: clock_time(s.ends_at),
+                    competitions: s
+                        .competitions
+                        .into_iter()
+                        .map(|c| infra::timetable::Competition {
+                            starts_at: clock_time(c.starts_at),
+                            distance: c.competition.distance,
+                            stroke: c.competition.stroke.into(),
+                            gender: c.competition.gender.into(),
+                            heats: c.heats.len() as u32,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        tracing::debug!("Generating timetable PDF");
+        timetable
+            .generate(&self.typst_compiler, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    TimetableError::PageDoesNotExist,
+                    TimetableError::PdfGenerationFailed,
+                )
+            })
+    }
+}
+
+/// The session as it is stored, durations beyond the database range are
+/// rejected.
+fn db_session(id: Uuid, body: api::SessionBody) -> Result<db::sessions::Session, SaveSessionError> {
+    Ok(db::sessions::Session {
+        id,
+        name: body.name.trim().to_string(),
+        starts_at: body.starts_at,
+        warm_up_minutes: i32::try_from(body.warm_up_minutes)
+            .map_err(|_| SaveSessionError::InvalidDuration)?,
+        turnaround_seconds: i32::try_from(body.turnaround_seconds)
+            .map_err(|_| SaveSessionError::InvalidDuration)?,
+        competition_ids: body.competition_ids,
+    })
+}
+
+/// A heat lasts until its slowest swimmer touches, lanes without a result
+/// or entry time are estimated with the target time of the competition.
+fn heat_duration(lanes: &[LaneEstimate], target_time: u32) -> u32 {
+    lanes
+        .iter()
+        .map(|l| {
+            l.result_time_millis
+                .or(l.entry_time_millis)
+                .unwrap_or(target_time)
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+fn clock_time(time: NaiveDateTime) -> String {
+    time.format("%H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 18)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn session_body(name: &str, competition_ids: Vec<Uuid>) -> api::SessionBody {
+        api::SessionBody {
+            name: name.to_string(),
+            starts_at: at(9, 0, 0),
+            warm_up_minutes: 30,
+            turnaround_seconds: 60,
+            competition_ids,
+        }
+    }
+
+    /// Fixture with two entries in the last and one without an entry time in
+    /// the first heat of a pool with two lanes, an empty competition and one
+    /// with a single participant.
+    async fn event() -> (Fixture, Vec<Uuid>, Uuid) {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let seeded = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 40_000)
+            .await;
+        let empty = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 50, 40_000)
+            .await;
+        let single = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 30_000)
+            .await;
+
+        for (name, entry_time) in [("Fast", Some(35_000)), ("Slow", Some(50_000))] {
+            let participant_id = fixture.participant(group_id, Gender::Female, name).await;
+            fixture
+                .repos
+                .registrations
+                .create_registration(participant_id, seeded, entry_time, false)
+                .await
+                .unwrap();
+        }
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Unseeded")
+            .await;
+        let registration_id = fixture.register(participant_id, seeded).await;
+        fixture.register(participant_id, single).await;
+
+        (fixture, vec![seeded, empty, single], registration_id)
+    }
+
+    fn heat_starts(schedule: &model::Schedule) -> Vec<Vec<NaiveDateTime>> {
+        schedule.sessions[0]
+            .competitions
+            .iter()
+            .map(|c| c.heats.iter().map(|h| h.starts_at).collect())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn heats_are_estimated_from_entry_and_target_times() {
+        let (fixture, competition_ids, _) = event().await;
+        let service = fixture.schedule_service(2);
+        service
+            .add_session(session_body("Morning", competition_ids))
+            .await
+            .unwrap();
+
+        let schedule = service.schedule().await.unwrap();
+
+        let session = &schedule.sessions[0];
+        assert_eq!(session.warm_up_at, at(8, 30, 0));
+        assert_eq!(
+            heat_starts(&schedule),
+            vec![vec![at(9, 0, 0), at(9, 1, 40)], vec![], vec![at(9, 3, 30)]]
+        );
+        // Without an entry time the heat takes the target time
+        assert_eq!(session.competitions[0].heats[0].duration_millis, 40_000);
+        assert_eq!(session.competitions[0].heats[1].duration_millis, 50_000);
+        assert_eq!(session.competitions[1].starts_at, at(9, 3, 30));
+        assert_eq!(session.ends_at, at(9, 4, 0));
+        assert!(session
+            .competitions
+            .iter()
+            .flat_map(|c| c.heats.iter())
+            .all(|h| !h.finished));
+    }
+
+    #[tokio::test]
+    async fn finished_heats_take_their_result_times() {
+        let (fixture, competition_ids, registration_id) = event().await;
+        let service = fixture.schedule_service(2);
+        service
+            .add_session(session_body("Morning", competition_ids))
+            .await
+            .unwrap();
+        fixture.result(registration_id, 45_000, false).await;
+
+        let schedule = service.schedule().await.unwrap();
+
+        let heats = &schedule.sessions[0].competitions[0].heats;
+        assert!(heats[0].finished);
+        assert_eq!(heats[0].duration_millis, 45_000);
+        assert_eq!(heats[1].starts_at, at(9, 1, 45));
+        assert_eq!(schedule.sessions[0].ends_at, at(9, 4, 5));
+    }
+
+    #[tokio::test]
+    async fn competitions_are_only_swum_in_one_session() {
+        let (fixture, competition_ids, _) = event().await;
+        let service = fixture.schedule_service(2);
+        let session_id = service
+            .add_session(session_body("Morning", vec![competition_ids[0]]))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service
+                .add_session(session_body("Afternoon", vec![competition_ids[0]]))
+                .await,
+            Err(SaveSessionError::CompetitionAlreadyScheduled(id)) if id == competition_ids[0]
+        ));
+        assert!(matches!(
+            service
+                .update_session(
+                    session_id,
+                    session_body("Morning", vec![competition_ids[1], competition_ids[1]])
+                )
+                .await,
+            Err(SaveSessionError::CompetitionAlreadyScheduled(_))
+        ));
+        assert!(matches!(
+            service.add_session(session_body(" ", Vec::new())).await,
+            Err(SaveSessionError::InvalidName)
+        ));
+
+        service
+            .update_session(
+                session_id,
+                session_body("Morning", vec![competition_ids[1], competition_ids[0]]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            service.session(session_id).await.unwrap().competition_ids,
+            vec![competition_ids[1], competition_ids[0]]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::{Context, Result};
 use chrono::Datelike;
@@ -7,16 +7,42 @@ use thiserror::Error;
 use tracing::instrument;
 use uuid::Uuid;
 
+use crate::infra::typst_compiler::TypstOutput;
 use crate::{db, infra, services::ParticipantService};
 
-use super::{CompetitionService, ServiceRepositoryError};
+use super::{ranking, CompetitionService, ServiceRepositoryError};
 
 pub struct ScoreService {
-    participant_repo: db::participants::Repository,
-    registration_repo: db::registrations::Repository,
-    competition_repo: db::competitions::Repository,
-    group_repo: db::groups::Repository,
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
     typst_compiler: infra::typst_compiler::TypstCompiler,
+    certificate_signatures: Vec<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum SetRankingRulesError {
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetGroupScoringError {
+    #[error("At least one result must be counted")]
+    InvalidResultCount,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
 }
 
 #[derive(Debug, Error)]
@@ -51,9 +77,12 @@ pub enum ParticipantCertificateError {
     #[error("The participant does not exist")]
     ParticipantDoesNotExist,
 
-    #[error("The participant does not exist")]
+    #[error("Failed to generate the certificate PDF")]
     PdfGenerationFailed(anyhow::Error),
 
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
     #[error("The repository ran into an error: {0:#?}")]
     RepositoryError(#[from] anyhow::Error),
 }
@@ -67,6 +96,45 @@ pub enum GroupScoreboardError {
     RepositoryError(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum EventCertificatesError {
+    #[error("Failed to generate the certificates PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum GroupCertificatesError {
+    #[error("The group does not exist")]
+    GroupDoesNotExist,
+
+    #[error("Failed to generate the certificates PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ResultsProtocolError {
+    #[error("Failed to generate the results protocol PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
 impl From<super::competition::CompetitionDetailsError> for CompetitionScoreboardError {
     fn from(err: super::competition::CompetitionDetailsError) -> Self {
         use super::competition::CompetitionDetailsError::*;
@@ -98,11 +166,15 @@ impl From<ParticipantScoreboardError> for ParticipantCertificateError {
 }
 
 impl ScoreService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        participant_repo: db::participants::Repository,
-        registration_repo: db::registrations::Repository,
-        competition_repo: db::competitions::Repository,
-        group_repo: db::groups::Repository,
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
         typst_compiler: infra::typst_compiler::TypstCompiler,
     ) -> Self {
         Self {
@@ -110,15 +182,46 @@ impl ScoreService {
             registration_repo,
             competition_repo,
             group_repo,
+            event_repo,
+            best_time_repo,
+            payment_repo,
             typst_compiler,
+            certificate_signatures: Vec::new(),
         }
     }
 
+    /// Print signature lines with the given labels below the results of the
+    /// certificates, e.g. for the referee.
+    pub fn with_certificate_signatures(mut self, certificate_signatures: Vec<String>) -> Self {
+        self.certificate_signatures = certificate_signatures;
+        self
+    }
+
+    #[instrument(skip(self, certificates))]
+    async fn generate_certificates(
+        &self,
+        certificates: Vec<infra::certificate::Certificate>,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        infra::certificate::Certificates {
+            event_name: event.name,
+            organization: event.organization,
+            signatures: self.certificate_signatures.clone(),
+            certificates,
+        }
+        .generate(&self.typst_compiler, output)
+        .await
+        .context("Failed to generate certificates")
+    }
+
     fn competition_service(&self) -> CompetitionService {
         CompetitionService::new(
             self.participant_repo.clone(),
             self.registration_repo.clone(),
             self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
         )
     }
 
@@ -128,9 +231,86 @@ impl ScoreService {
             self.registration_repo.clone(),
             self.competition_repo.clone(),
             self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
         )
     }
 
+    /// How the results are scored in the group rankings of the event.
+    #[instrument(skip(self))]
+    pub async fn group_scoring(&self) -> Result<model::GroupScoring, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::GroupScoring::from(&event))
+    }
+
+    /// Change how the results are scored in the group rankings, applies to
+    /// all scoreboards and certificates generated afterwards.
+    #[instrument(skip(self))]
+    pub async fn set_group_scoring(
+        &self,
+        scoring: model::GroupScoring,
+    ) -> Result<(), SetGroupScoringError> {
+        if let model::GroupScoringRule::BestResults { count } = scoring.rule {
+            if count == 0 || count > i32::MAX as u32 {
+                return Err(SetGroupScoringError::InvalidResultCount);
+            }
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetGroupScoringError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the group scoring with the event");
+        let (group_scoring, scoring_result_count) =
+            crate::conversions::group_scoring_rule(scoring.rule);
+        self.event_repo
+            .update_group_scoring(
+                group_scoring,
+                scoring_result_count,
+                scoring.min_starts.min(i32::MAX as u32) as _,
+            )
+            .await
+            .context("Failed to update group scoring in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
+    /// How tied participants are ranked in the competition and group rankings.
+    #[instrument(skip(self))]
+    pub async fn ranking_rules(&self) -> Result<model::RankingRules, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::RankingRules::from(&event))
+    }
+
+    /// Change how tied participants are ranked, applies to all scoreboards and
+    /// documents generated afterwards.
+    #[instrument(skip(self))]
+    pub async fn set_ranking_rules(
+        &self,
+        rules: model::RankingRules,
+    ) -> Result<(), SetRankingRulesError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetRankingRulesError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the ranking rules with the event");
+        self.event_repo
+            .update_ranking_rules(
+                rules.shared_ranks.into(),
+                rules.time_precision.into(),
+                rules.group_tie_break.into(),
+            )
+            .await
+            .context("Failed to update ranking rules in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
     /// Scorboard for a competition.
     ///
     /// Generates the scoreboard for the given competition. The scoreboard
@@ -148,8 +328,7 @@ impl ScoreService {
         let competition_details = self
             .competition_service()
             .competition_details(competition_id)
-            .await
-            .unwrap();
+            .await?;
 
         tracing::debug!("Partitioning registrations into ones with and without result");
         let registrations = competition_details.registrations;
@@ -163,27 +342,45 @@ impl ScoreService {
                 result.disqualified
             });
 
+        tracing::debug!("Loading the ranking rules of the event");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let rules = model::RankingRules::from(&event);
+
         tracing::debug!("Ranking the qualified results");
+        let time_keys = qualified
+            .iter()
+            .map(|r| {
+                ranking::time_key(r.result.as_ref().unwrap().time_millis, rules.time_precision)
+            })
+            .collect::<Vec<_>>();
+        let placings = ranking::rank(&time_keys, rules.shared_ranks);
+
         let mut scores = Vec::with_capacity(qualified.len());
-        for registration in qualified.clone().into_iter() {
-            let own_time_millis = registration.result.as_ref().unwrap().time_millis;
-            let faster_registrations = qualified
-                .iter()
-                .filter(|r| r.result.as_ref().unwrap().time_millis < own_time_millis)
-                .count();
+        for (registration, placing) in qualified.into_iter().zip(placings) {
+            tracing::debug!(participant_id = ?registration.participant.id, "Looking up the previous best time of participant");
+            let previous_best = self
+                .previous_best(
+                    &registration.participant,
+                    &competition_details.competition,
+                    &event,
+                )
+                .await?;
 
-            // If there is nobody faster than you (faster_registration == 0), then
-            // you are the first in the ranking.
-            let rank = faster_registrations as u32 + 1;
             let result = registration.result.unwrap();
-            let competition_score = model::CompetitionScore {
+            // Compared like the ranking, a time equal to the best is no new best
+            let personal_best = previous_best.is_some_and(|best| {
+                ranking::time_key(result.time_millis, rules.time_precision)
+                    < ranking::time_key(best, rules.time_precision)
+            });
+            scores.push(model::CompetitionScore {
                 participant: registration.participant,
-                rank,
+                rank: placing.rank,
+                tied: placing.tied,
                 time: result.time_millis,
                 fina_points: result.fina_points,
-            };
-
-            scores.push(competition_score);
+                personal_best,
+                previous_best,
+            });
         }
 
         Ok(model::CompetitionScoreboard {
@@ -194,6 +391,40 @@ impl ScoreService {
         })
     }
 
+    /// Best time the participant swam before this event, in the stroke and
+    /// distance of the competition and the course of the event.
+    ///
+    /// The results of this event are recorded as best times as well, they
+    /// are told apart by the result they have been recorded from.
+    async fn previous_best(
+        &self,
+        participant: &model::Participant,
+        competition: &model::Competition,
+        event: &db::event::Event,
+    ) -> Result<Option<u32>> {
+        let best_times = self
+            .best_time_repo
+            .best_times_of_swimmer(
+                &participant.first_name,
+                &participant.last_name,
+                participant.birthday,
+            )
+            .await
+            .context("Failed to fetch best times of participant from repository")?;
+
+        let stroke = db::Stroke::from(competition.stroke);
+        Ok(best_times
+            .into_iter()
+            .filter(|b| {
+                b.stroke == stroke
+                    && b.distance as u32 == competition.distance
+                    && b.course == event.course
+                    && b.registration_id.is_none()
+            })
+            .map(|b| b.time_millis as u32)
+            .min())
+    }
+
     #[instrument(skip(self))]
     pub async fn group_scoreboard(
         &self,
@@ -226,16 +457,34 @@ impl ScoreService {
             participant_details.push(pd);
         }
 
-        // Precompute the FINA points for all participants
-        let participant_points = participant_details
+        tracing::debug!("Loading the group scoring and ranking rules of the event");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let scoring = model::GroupScoring::from(&event);
+        let rules = model::RankingRules::from(&event);
+
+        // Participants with too few starts are listed without a rank
+        let (ranked, unranked): (Vec<_>, Vec<_>) = participant_details
+            .into_iter()
+            .partition(|pd| pd.starts() >= scoring.min_starts);
+        let group_keys = ranked
             .iter()
-            .map(|pd| pd.fina_points())
+            .map(|pd| {
+                ranking::group_key(
+                    pd.group_points(&scoring),
+                    pd.result_points(),
+                    rules.group_tie_break,
+                )
+            })
             .collect::<Vec<_>>();
+        let placings = ranking::rank(&group_keys, rules.shared_ranks);
 
-        // Calculate the rank for each of the participants
-        let mut scores = Vec::with_capacity(participant_details.len());
+        let mut scores = Vec::with_capacity(ranked.len() + unranked.len());
         let mut missing_results = Vec::new();
-        for pd in participant_details.clone().into_iter() {
+        let participants = ranked
+            .into_iter()
+            .zip(placings.into_iter().map(Some))
+            .chain(unranked.into_iter().map(|pd| (pd, None)));
+        for (pd, placing) in participants {
             // Search for all missing results and add them to the list of missing
             // results.
             if pd.results_missing() {
@@ -252,20 +501,11 @@ impl ScoreService {
                 missing_results.extend(missing);
             }
 
-            // Calulation of the rank within the group
-            let own_fina_points = pd.fina_points();
-            let participants_with_higher = participant_points
-                .iter()
-                .filter(|points| **points > own_fina_points)
-                .count();
-
-            // 0 with higher points means 1st rank
-            let rank = participants_with_higher as u32 + 1;
-
             scores.push(model::GroupScore {
+                fina_points: pd.group_points(&scoring),
                 participant: pd.participant,
-                fina_points: own_fina_points,
-                rank,
+                rank: placing.map(|p| p.rank),
+                tied: placing.is_some_and(|p| p.tied),
             });
         }
 
@@ -317,6 +557,8 @@ impl ScoreService {
                 time: participants_score.time,
                 fina_points: participants_score.fina_points,
                 rank: participants_score.rank,
+                personal_best: participants_score.personal_best,
+                previous_best: participants_score.previous_best,
             });
         }
 
@@ -344,6 +586,7 @@ impl ScoreService {
     pub async fn participant_certificate(
         &self,
         participant_id: Uuid,
+        output: TypstOutput,
     ) -> Result<Vec<u8>, ParticipantCertificateError> {
         let participant_scoreboard = self.participant_scoreboard(participant_id).await?;
 
@@ -355,6 +598,7 @@ impl ScoreService {
                 stroke: cs.competition.stroke.into(),
                 millis: cs.time,
                 rank: cs.rank,
+                personal_best: cs.personal_best,
             })
             .collect();
 
@@ -362,45 +606,1011 @@ impl ScoreService {
             first_name: participant_scoreboard.participant.first_name,
             last_name: participant_scoreboard.participant.last_name,
             birthyear: participant_scoreboard.participant.birthday.year() as _,
+            group: participant_scoreboard.group_score.group.name,
             group_points: participant_scoreboard.group_score.fina_points,
             group_rank: participant_scoreboard.group_score.rank,
             results,
+            results_missing: !participant_scoreboard.missing_results.is_empty(),
         };
 
-        let pdf = infra::certificate::Certificates(vec![certificate])
-            .generate_pdf(&self.typst_compiler)
+        self.generate_certificates(vec![certificate], output)
             .await
-            .map_err(ParticipantCertificateError::PdfGenerationFailed)?;
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    ParticipantCertificateError::PageDoesNotExist,
+                    ParticipantCertificateError::PdfGenerationFailed,
+                )
+            })
+    }
 
-        Ok(pdf)
+    /// Certificates of all participants of the event.
+    ///
+    /// # Parameters:
+    /// - `skip_missing_results` - Leave out participants that do not have
+    ///   all results yet, otherwise their certificates are flagged
+    /// - `output` - The format to generate the certificates in
+    #[instrument(skip(self))]
+    pub async fn event_certificates(
+        &self,
+        skip_missing_results: bool,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, EventCertificatesError> {
+        tracing::debug!("Fetching groups from repository");
+        let groups = self
+            .group_repo
+            .all_groups()
+            .await
+            .context("Failed to fetch groups from repository")?
+            .into_iter()
+            .map(model::Group::from)
+            .collect();
+
+        let certificates = self
+            .load_certificates(groups, skip_missing_results)
+            .await
+            .context("Failed to load certificates of the event")?;
+
+        tracing::debug!("Generating certificates PDF");
+        self.generate_certificates(certificates, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    EventCertificatesError::PageDoesNotExist,
+                    EventCertificatesError::PdfGenerationFailed,
+                )
+            })
     }
 
+    /// Certificates of all participants of a group.
+    ///
+    /// # Parameters:
+    /// - `group_id` - The id of the group
+    /// - `skip_missing_results` - Leave out participants that do not have
+    ///   all results yet, otherwise their certificates are flagged
+    /// - `output` - The format to generate the certificates in
     #[instrument(skip(self))]
-    pub async fn participants_fina_points(
+    pub async fn group_certificates(
         &self,
-    ) -> Result<HashMap<Uuid, (bool, u32)>, ServiceRepositoryError> {
-        tracing::debug!("Fetching all participants from repository");
-        let participants = self
-            .participant_repo
-            .list_participants()
+        group_id: Uuid,
+        skip_missing_results: bool,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, GroupCertificatesError> {
+        tracing::debug!("Fetching group from repository");
+        let group = self
+            .group_repo
+            .group_by_id(group_id)
+            .await
+            .context("Failed to fetch group from repository")?
+            .map(model::Group::from)
+            .ok_or(GroupCertificatesError::GroupDoesNotExist)?;
+
+        let certificates = self
+            .load_certificates(vec![group], skip_missing_results)
+            .await
+            .context("Failed to load certificates of the group")?;
+
+        tracing::debug!("Generating certificates PDF");
+        self.generate_certificates(certificates, output)
             .await
-            .context("Failed to fetch all participants from repository")?
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    GroupCertificatesError::PageDoesNotExist,
+                    GroupCertificatesError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    /// Load the certificates of all participants in the given groups, sorted
+    /// by group and name.
+    ///
+    /// Every scoreboard is only computed once, instead of once per participant
+    /// like for a single certificate.
+    #[instrument(skip(self))]
+    async fn load_certificates(
+        &self,
+        mut groups: Vec<model::Group>,
+        skip_missing_results: bool,
+    ) -> Result<Vec<infra::certificate::Certificate>> {
+        tracing::debug!("Fetching competitions from repository");
+        let competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?;
+
+        let mut participant_results = HashMap::<Uuid, Vec<_>>::new();
+        for competition in competitions {
+            tracing::debug!(competition_id = ?competition.id, "Loading scoreboard of competition");
+            let scoreboard = self
+                .competition_scoreboard(competition.id)
+                .await
+                .context("Failed to load scoreboard of competition even though it exists")?;
+
+            for score in scoreboard.scores {
+                participant_results
+                    .entry(score.participant.id)
+                    .or_default()
+                    .push(infra::certificate::CompetitionResult {
+                        distance: scoreboard.competition.distance,
+                        stroke: scoreboard.competition.stroke.into(),
+                        millis: score.time,
+                        rank: score.rank,
+                        personal_best: score.personal_best,
+                    });
+            }
+        }
+
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut certificates = Vec::new();
+        for group in groups {
+            tracing::debug!(group_id = ?group.id, "Loading scoreboard of group");
+            let mut scoreboard = self
+                .group_scoreboard(group.id)
+                .await
+                .context("Failed to load scoreboard of group even though it exists")?;
+            scoreboard.scores.sort_by_cached_key(|s| {
+                format!("{}, {}", s.participant.last_name, s.participant.first_name)
+            });
+
+            for score in scoreboard.scores {
+                let results_missing = scoreboard
+                    .missing_results
+                    .iter()
+                    .any(|r| r.participant.id == score.participant.id);
+                if results_missing && skip_missing_results {
+                    tracing::debug!(participant_id = ?score.participant.id, "Skipping participant with missing results");
+                    continue;
+                }
+
+                certificates.push(infra::certificate::Certificate {
+                    birthyear: score.participant.birthday.year() as _,
+                    results: participant_results
+                        .remove(&score.participant.id)
+                        .unwrap_or_default(),
+                    first_name: score.participant.first_name,
+                    last_name: score.participant.last_name,
+                    group: group.name.clone(),
+                    group_points: score.fina_points,
+                    group_rank: score.rank,
+                    results_missing,
+                });
+            }
+        }
+
+        Ok(certificates)
+    }
+
+    /// The official results protocol of the whole event.
+    ///
+    /// Lists the results of all competitions, including disqualifications,
+    /// missing results and the rankings within each birth year, followed by
+    /// the standings of all groups.
+    #[instrument(skip(self))]
+    pub async fn results_protocol(
+        &self,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, ResultsProtocolError> {
+        let protocol = self.load_results_protocol().await?;
+
+        tracing::debug!("Generating results protocol PDF");
+        protocol
+            .generate(&self.typst_compiler, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    ResultsProtocolError::PageDoesNotExist,
+                    ResultsProtocolError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn load_results_protocol(&self) -> Result<infra::results_protocol::ResultsProtocol> {
+        tracing::debug!("Loading the ranking rules of the event");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let rules = model::RankingRules::from(&event);
+
+        tracing::debug!("Fetching groups from repository");
+        let groups = self
+            .group_repo
+            .all_groups()
+            .await
+            .context("Failed to fetch groups from repository")?
             .into_iter()
-            .map(|p| (p.id, model::Participant::from(p)))
+            .map(model::Group::from)
+            .collect::<Vec<_>>();
+        let group_names = groups
+            .iter()
+            .map(|g| (g.id, g.name.clone()))
             .collect::<HashMap<_, _>>();
+        let swimmer = |participant: &model::Participant| infra::results_protocol::Swimmer {
+            first_name: participant.first_name.clone(),
+            last_name: participant.last_name.clone(),
+            birthyear: participant.birthday.year() as _,
+            group: group_names
+                .get(&participant.group_id)
+                .cloned()
+                .unwrap_or_default(),
+        };
 
-        let mut particpant_scores = HashMap::with_capacity(participants.len());
-        for participant in participants.values() {
-            tracing::debug!(participant_id = ?participant.id, "Fetching details of participant");
-            let pd = self
-                .participant_service()
-                .participant_details(participant.id)
+        tracing::debug!("Fetching competitions from repository");
+        let mut competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?
+            .into_iter()
+            .map(model::Competition::from)
+            .collect::<Vec<_>>();
+        competitions.sort_by_key(|c| (c.stroke, c.distance, c.gender == model::Gender::Male));
+
+        let mut competition_results = Vec::with_capacity(competitions.len());
+        for competition in competitions {
+            tracing::debug!(competition_id = ?competition.id, "Loading scoreboard of competition");
+            let mut scoreboard = self
+                .competition_scoreboard(competition.id)
+                .await
+                .context("Failed to load scoreboard of competition even though it exists")?;
+            scoreboard.scores.sort_by_cached_key(|s| {
+                (
+                    s.rank,
+                    format!("{}, {}", s.participant.last_name, s.participant.first_name),
+                )
+            });
+
+            let results = scoreboard
+                .scores
+                .iter()
+                .map(|s| infra::results_protocol::RankedResult {
+                    rank: s.rank,
+                    swimmer: swimmer(&s.participant),
+                    millis: s.time,
+                    fina_points: s.fina_points,
+                })
+                .collect::<Vec<_>>();
+
+            // Within a birth year, the swimmers are ranked with the same rules
+            // as in the overall ranking.
+            let mut birthyears = results
+                .iter()
+                .map(|r| r.swimmer.birthyear)
+                .collect::<Vec<_>>();
+            birthyears.sort();
+            birthyears.dedup();
+            let age_classes = birthyears
+                .into_iter()
+                .map(|birthyear| {
+                    let class_results = results
+                        .iter()
+                        .filter(|r| r.swimmer.birthyear == birthyear)
+                        .collect::<Vec<_>>();
+                    let time_keys = class_results
+                        .iter()
+                        .map(|r| ranking::time_key(r.millis, rules.time_precision))
+                        .collect::<Vec<_>>();
+                    let results = class_results
+                        .iter()
+                        .zip(ranking::rank(&time_keys, rules.shared_ranks))
+                        .map(|(r, placing)| infra::results_protocol::RankedResult {
+                            rank: placing.rank,
+                            ..(*r).clone()
+                        })
+                        .collect();
+                    infra::results_protocol::AgeClass { birthyear, results }
+                })
+                .collect();
+
+            competition_results.push(infra::results_protocol::CompetitionResults {
+                distance: competition.distance,
+                stroke: competition.stroke.into(),
+                gender: competition.gender.into(),
+                results,
+                age_classes,
+                disqualifications: scoreboard
+                    .disqualifications
+                    .iter()
+                    .map(|r| swimmer(&r.participant))
+                    .collect(),
+                did_not_start: scoreboard
+                    .missing_results
+                    .iter()
+                    .map(|r| swimmer(&r.participant))
+                    .collect(),
+            });
+        }
+
+        let mut group_standings = Vec::with_capacity(groups.len());
+        for group in groups.iter() {
+            tracing::debug!(group_id = ?group.id, "Loading scoreboard of group");
+            let mut scoreboard = self
+                .group_scoreboard(group.id)
                 .await
-                .context("Could not fetch participant details even though participant exists")?;
+                .context("Failed to load scoreboard of group even though it exists")?;
+            scoreboard.scores.sort_by_cached_key(|s| {
+                (
+                    s.rank.is_none(),
+                    s.rank,
+                    format!("{}, {}", s.participant.last_name, s.participant.first_name),
+                )
+            });
+
+            group_standings.push(infra::results_protocol::GroupStandings {
+                name: group.name.clone(),
+                scores: scoreboard
+                    .scores
+                    .into_iter()
+                    .map(|s| infra::results_protocol::GroupStanding {
+                        rank: s.rank,
+                        first_name: s.participant.first_name,
+                        last_name: s.participant.last_name,
+                        birthyear: s.participant.birthday.year() as _,
+                        fina_points: s.fina_points,
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(infra::results_protocol::ResultsProtocol {
+            event_name: event.name,
+            organization: event.organization,
+            competitions: competition_results,
+            groups: group_standings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Set up a competition with one participant per given result.
+    ///
+    /// Results are `(last_name, time_millis, disqualified)`, `None` marks a
+    /// registration without a result.
+    async fn competition_with_results(
+        fixture: &Fixture,
+        results: &[(&str, Option<(i32, bool)>)],
+    ) -> Uuid {
+        let group_id = fixture.group("Dolphins").await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        for (last_name, result) in results {
+            let participant_id = fixture
+                .participant(group_id, Gender::Female, last_name)
+                .await;
+            let registration_id = fixture.register(participant_id, competition_id).await;
+            if let Some((time_millis, disqualified)) = result {
+                fixture
+                    .result(registration_id, *time_millis, *disqualified)
+                    .await;
+            }
+        }
+        competition_id
+    }
+
+    fn ranks(scoreboard: &model::CompetitionScoreboard) -> Vec<(String, u32)> {
+        let mut ranks = scoreboard
+            .scores
+            .iter()
+            .map(|s| (s.participant.last_name.clone(), s.rank))
+            .collect::<Vec<_>>();
+        ranks.sort_by_key(|(name, rank)| (*rank, name.clone()));
+        ranks
+    }
+
+    #[tokio::test]
+    async fn competition_scoreboard_ranks_by_time() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("Slow", Some((32_000, false))),
+                ("Fast", Some((30_000, false))),
+                ("Medium", Some((31_000, false))),
+            ],
+        )
+        .await;
+
+        let scoreboard = fixture
+            .score_service()
+            .competition_scoreboard(competition_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ranks(&scoreboard),
+            vec![
+                ("Fast".to_string(), 1),
+                ("Medium".to_string(), 2),
+                ("Slow".to_string(), 3)
+            ]
+        );
+        let fastest = scoreboard.scores.iter().find(|s| s.rank == 1).unwrap();
+        assert_eq!(fastest.time, 30_000);
+        assert_eq!(fastest.fina_points, 1000);
+    }
+
+    #[tokio::test]
+    async fn competition_scoreboard_ties_share_rank() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("A", Some((30_000, false))),
+                ("B", Some((30_000, false))),
+                ("C", Some((31_000, false))),
+            ],
+        )
+        .await;
+
+        let scoreboard = fixture
+            .score_service()
+            .competition_scoreboard(competition_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ranks(&scoreboard),
+            vec![
+                ("A".to_string(), 1),
+                ("B".to_string(), 1),
+                ("C".to_string(), 3)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn competition_ties_follow_the_ranking_rules() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("A", Some((30_004, false))),
+                ("B", Some((30_009, false))),
+                ("C", Some((31_000, false))),
+            ],
+        )
+        .await;
+        let score_service = fixture.score_service();
+
+        let cases = [
+            (
+                model::SharedRanks::Skip,
+                model::TimePrecision::Hundredths,
+                [(1, true), (1, true), (3, false)],
+            ),
+            (
+                model::SharedRanks::Dense,
+                model::TimePrecision::Hundredths,
+                [(1, true), (1, true), (2, false)],
+            ),
+            (
+                model::SharedRanks::Dense,
+                model::TimePrecision::Thousandths,
+                [(1, false), (2, false), (3, false)],
+            ),
+        ];
+        for (shared_ranks, time_precision, expected) in cases {
+            score_service
+                .set_ranking_rules(model::RankingRules {
+                    shared_ranks,
+                    time_precision,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+
+            let mut scores = score_service
+                .competition_scoreboard(competition_id)
+                .await
+                .unwrap()
+                .scores;
+            scores.sort_by_key(|s| s.time);
+            let placings = scores.iter().map(|s| (s.rank, s.tied)).collect::<Vec<_>>();
+            assert_eq!(placings, expected, "{shared_ranks:?} {time_precision:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn competition_scoreboard_separates_disqualified_and_missing() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("Ranked", Some((35_000, false))),
+                ("Disqualified", Some((30_000, true))),
+                ("Missing", None),
+            ],
+        )
+        .await;
+
+        let scoreboard = fixture
+            .score_service()
+            .competition_scoreboard(competition_id)
+            .await
+            .unwrap();
+
+        assert_eq!(ranks(&scoreboard), vec![("Ranked".to_string(), 1)]);
+        assert_eq!(scoreboard.disqualifications.len(), 1);
+        assert_eq!(
+            scoreboard.disqualifications[0].participant.last_name,
+            "Disqualified"
+        );
+        assert_eq!(scoreboard.missing_results.len(), 1);
+        assert_eq!(
+            scoreboard.missing_results[0].participant.last_name,
+            "Missing"
+        );
+    }
+
+    #[tokio::test]
+    async fn competition_scoreboard_of_unknown_competition() {
+        let fixture = Fixture::new();
+        let result = fixture
+            .score_service()
+            .competition_scoreboard(Uuid::new_v4())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CompetitionScoreboardError::CompetitionDoesNotExist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn group_scoreboard_ranks_by_fina_points_and_ties_share_rank() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let free = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Male, Stroke::Back, 50, 30_000)
+            .await;
+
+        // (last name, freestyle result, backstroke result)
+        let swimmers = [
+            ("A", (30_000, false), (60_000, false)), // 1000 + 125
+            ("B", (60_000, false), (30_000, false)), // 125 + 1000
+            ("C", (30_000, false), (30_000, true)),  // 1000 + disqualified
+        ];
+        for (last_name, free_result, back_result) in swimmers {
+            let participant_id = fixture.participant(group_id, Gender::Male, last_name).await;
+            let registration_id = fixture.register(participant_id, free).await;
+            fixture
+                .result(registration_id, free_result.0, free_result.1)
+                .await;
+            let registration_id = fixture.register(participant_id, back).await;
+            fixture
+                .result(registration_id, back_result.0, back_result.1)
+                .await;
+        }
+
+        let scoreboard = fixture
+            .score_service()
+            .group_scoreboard(group_id)
+            .await
+            .unwrap();
+
+        let mut scores = scoreboard
+            .scores
+            .iter()
+            .map(|s| (s.participant.last_name.as_str(), s.fina_points, s.rank))
+            .collect::<Vec<_>>();
+        scores.sort();
+        assert_eq!(
+            scores,
+            vec![
+                ("A", 1125, Some(1)),
+                ("B", 1125, Some(1)),
+                ("C", 1000, Some(3))
+            ]
+        );
+        assert!(scoreboard.missing_results.is_empty());
+    }
+
+    /// Group with a specialist who swims one race and an all-rounder who
+    /// swims one race of each stroke.
+    ///
+    /// All competitions have a target time of 30 s, so 30 s scores 1000
+    /// points and 60 s scores 125 points.
+    async fn specialist_and_all_rounder(fixture: &Fixture) -> Uuid {
+        let group_id = fixture.group("Dolphins").await;
+        let specialist = fixture
+            .participant(group_id, Gender::Male, "Specialist")
+            .await;
+        let all_rounder = fixture
+            .participant(group_id, Gender::Male, "AllRounder")
+            .await;
+
+        let free_50 = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let registration_id = fixture.register(specialist, free_50).await;
+        fixture.result(registration_id, 30_000, false).await;
 
-            particpant_scores.insert(participant.id, (pd.results_missing(), pd.fina_points()));
+        let free_25 = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 25, 30_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Male, Stroke::Back, 50, 30_000)
+            .await;
+        let breast = fixture
+            .competition(Gender::Male, Stroke::Breast, 50, 30_000)
+            .await;
+        let results = [
+            (free_50, 60_000, false),
+            (free_25, 60_000, false),
+            (back, 60_000, false),
+            (breast, 30_000, true),
+        ];
+        for (competition_id, time_millis, disqualified) in results {
+            let registration_id = fixture.register(all_rounder, competition_id).await;
+            fixture
+                .result(registration_id, time_millis, disqualified)
+                .await;
         }
 
-        Ok(particpant_scores)
+        group_id
+    }
+
+    async fn group_scores(
+        fixture: &Fixture,
+        group_id: Uuid,
+        rule: model::GroupScoringRule,
+        min_starts: u32,
+    ) -> Vec<(String, u32, Option<u32>)> {
+        let score_service = fixture.score_service();
+        score_service
+            .set_group_scoring(model::GroupScoring { rule, min_starts })
+            .await
+            .unwrap();
+
+        let mut scores = score_service
+            .group_scoreboard(group_id)
+            .await
+            .unwrap()
+            .scores
+            .into_iter()
+            .map(|s| (s.participant.last_name, s.fina_points, s.rank))
+            .collect::<Vec<_>>();
+        scores.sort();
+        scores
+    }
+
+    #[tokio::test]
+    async fn group_scoring_rules_weigh_the_results() {
+        let fixture = Fixture::new();
+        let group_id = specialist_and_all_rounder(&fixture).await;
+
+        // The all-rounder has 3 x 125 points and a disqualification
+        let cases = [
+            (model::GroupScoringRule::Sum, 375),
+            (model::GroupScoringRule::BestResults { count: 2 }, 250),
+            (model::GroupScoringRule::OnePerStroke, 250),
+            (model::GroupScoringRule::Average, 94),
+        ];
+        for (rule, all_rounder_points) in cases {
+            assert_eq!(
+                group_scores(&fixture, group_id, rule, 0).await,
+                vec![
+                    ("AllRounder".to_string(), all_rounder_points, Some(2)),
+                    ("Specialist".to_string(), 1000, Some(1)),
+                ],
+                "{rule:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn participants_with_too_few_starts_are_not_ranked() {
+        let fixture = Fixture::new();
+        let group_id = specialist_and_all_rounder(&fixture).await;
+
+        let scores = group_scores(&fixture, group_id, model::GroupScoringRule::Sum, 2).await;
+
+        assert_eq!(
+            scores,
+            vec![
+                ("AllRounder".to_string(), 375, Some(1)),
+                ("Specialist".to_string(), 1000, None),
+            ]
+        );
+        let details = fixture
+            .group_service()
+            .group_details(group_id)
+            .await
+            .unwrap();
+        let mut ranks = details
+            .scores
+            .into_iter()
+            .map(|s| (s.participant.last_name, s.rank))
+            .collect::<Vec<_>>();
+        ranks.sort();
+        assert_eq!(
+            ranks,
+            vec![
+                ("AllRounder".to_string(), Some(1)),
+                ("Specialist".to_string(), None)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn better_single_result_breaks_group_ties() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let free = fixture
+            .competition(Gender::Male, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Male, Stroke::Back, 50, 30_000)
+            .await;
+
+        // Both count 1000 points with the best result, but only one has
+        // points in the second result.
+        let swimmers = [
+            ("Second", (60_000, false)), // 125 points
+            ("Disqualified", (30_000, true)),
+        ];
+        for (last_name, back_result) in swimmers {
+            let participant_id = fixture.participant(group_id, Gender::Male, last_name).await;
+            let registration_id = fixture.register(participant_id, free).await;
+            fixture.result(registration_id, 30_000, false).await;
+            let registration_id = fixture.register(participant_id, back).await;
+            fixture
+                .result(registration_id, back_result.0, back_result.1)
+                .await;
+        }
+        let score_service = fixture.score_service();
+        score_service
+            .set_group_scoring(model::GroupScoring {
+                rule: model::GroupScoringRule::BestResults { count: 1 },
+                min_starts: 0,
+            })
+            .await
+            .unwrap();
+
+        let cases = [
+            (
+                model::GroupTieBreak::None,
+                [(Some(1), true), (Some(1), true)],
+            ),
+            (
+                model::GroupTieBreak::BestSingleResult,
+                [(Some(2), false), (Some(1), false)],
+            ),
+        ];
+        for (group_tie_break, expected) in cases {
+            score_service
+                .set_ranking_rules(model::RankingRules {
+                    group_tie_break,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+
+            let mut scores = score_service
+                .group_scoreboard(group_id)
+                .await
+                .unwrap()
+                .scores;
+            scores.sort_by(|a, b| a.participant.last_name.cmp(&b.participant.last_name));
+            let placings = scores.iter().map(|s| (s.rank, s.tied)).collect::<Vec<_>>();
+            assert_eq!(placings, expected, "{group_tie_break:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn best_results_must_count_a_result() {
+        let result = Fixture::new()
+            .score_service()
+            .set_group_scoring(model::GroupScoring {
+                rule: model::GroupScoringRule::BestResults { count: 0 },
+                min_starts: 0,
+            })
+            .await;
+
+        assert!(
+            matches!(result, Err(SetGroupScoringError::InvalidResultCount)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn group_scoreboard_lists_missing_results() {
+        let fixture = Fixture::new();
+        let group_id = fixture.group("Dolphins").await;
+        let competition_id = fixture
+            .competition(Gender::Female, Stroke::Breast, 25, 20_000)
+            .await;
+        let participant_id = fixture
+            .participant(group_id, Gender::Female, "Pending")
+            .await;
+        let registration_id = fixture.register(participant_id, competition_id).await;
+
+        let scoreboard = fixture
+            .score_service()
+            .group_scoreboard(group_id)
+            .await
+            .unwrap();
+
+        assert_eq!(scoreboard.scores.len(), 1);
+        assert_eq!(scoreboard.scores[0].fina_points, 0);
+        assert_eq!(scoreboard.missing_results.len(), 1);
+        assert_eq!(scoreboard.missing_results[0].id, registration_id);
+    }
+
+    #[tokio::test]
+    async fn participant_scoreboard_contains_competition_and_group_ranks() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("Winner", Some((29_000, false))),
+                ("Second", Some((31_000, false))),
+            ],
+        )
+        .await;
+        let second = fixture
+            .repos
+            .participants
+            .list_participants()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|p| p.last_name == "Second")
+            .unwrap();
+
+        let scoreboard = fixture
+            .score_service()
+            .participant_scoreboard(second.id)
+            .await
+            .unwrap();
+
+        assert_eq!(scoreboard.competition_scores.len(), 1);
+        assert_eq!(
+            scoreboard.competition_scores[0].competition.id,
+            competition_id
+        );
+        assert_eq!(scoreboard.competition_scores[0].rank, 2);
+        assert_eq!(scoreboard.group_score.rank, Some(2));
+        assert!(scoreboard.disqualifications.is_empty());
+        assert!(scoreboard.missing_results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn results_protocol_ranks_within_birth_years() {
+        let fixture = Fixture::new();
+        let competition_id = competition_with_results(
+            &fixture,
+            &[
+                ("Fast", Some((30_000, false))),
+                ("Slow", Some((32_000, false))),
+                ("Disqualified", Some((29_000, true))),
+                ("Missing", None),
+            ],
+        )
+        .await;
+        let group_id = fixture.repos.groups.all_groups().await.unwrap()[0].id;
+        let younger = fixture
+            .repos
+            .participants
+            .create_participant(
+                "Kim",
+                "Younger",
+                Gender::Female,
+                chrono::NaiveDate::from_ymd_opt(2016, 6, 1).unwrap(),
+                group_id,
+            )
+            .await
+            .unwrap();
+        let registration_id = fixture.register(younger, competition_id).await;
+        fixture.result(registration_id, 31_000, false).await;
+        fixture
+            .event_service()
+            .set_event_info(model::EventInfo {
+                name: "Autumn Cup".to_string(),
+                organization: "SC Example".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let protocol = fixture
+            .score_service()
+            .load_results_protocol()
+            .await
+            .unwrap();
+
+        assert_eq!(protocol.event_name, "Autumn Cup");
+        assert_eq!(protocol.organization, "SC Example");
+        let competition = &protocol.competitions[0];
+        let ranks = |results: &[infra::results_protocol::RankedResult]| {
+            results
+                .iter()
+                .map(|r| (r.swimmer.last_name.clone(), r.rank))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ranks(&competition.results),
+            vec![
+                ("Fast".to_string(), 1),
+                ("Younger".to_string(), 2),
+                ("Slow".to_string(), 3)
+            ]
+        );
+        assert_eq!(competition.age_classes.len(), 2);
+        assert_eq!(competition.age_classes[0].birthyear, 2014);
+        assert_eq!(
+            ranks(&competition.age_classes[0].results),
+            vec![("Fast".to_string(), 1), ("Slow".to_string(), 2)]
+        );
+        assert_eq!(
+            ranks(&competition.age_classes[1].results),
+            vec![("Younger".to_string(), 1)]
+        );
+        assert_eq!(competition.disqualifications[0].last_name, "Disqualified");
+        assert_eq!(competition.did_not_start[0].last_name, "Missing");
+        assert_eq!(protocol.groups[0].scores.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn certificates_are_sorted_and_flag_missing_results() {
+        let fixture = Fixture::new();
+        competition_with_results(
+            &fixture,
+            &[
+                ("Slow", Some((32_000, false))),
+                ("Fast", Some((30_000, false))),
+                ("Missing", None),
+            ],
+        )
+        .await;
+        let groups = fixture
+            .repos
+            .groups
+            .all_groups()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(model::Group::from)
+            .collect::<Vec<_>>();
+
+        let certificates = fixture
+            .score_service()
+            .load_certificates(groups.clone(), false)
+            .await
+            .unwrap();
+
+        let names = certificates
+            .iter()
+            .map(|c| (c.last_name.as_str(), c.results_missing))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![("Fast", false), ("Missing", true), ("Slow", false)]
+        );
+        assert_eq!(certificates[0].results.len(), 1);
+        assert_eq!(certificates[0].results[0].rank, 1);
+        assert_eq!(certificates[0].group_rank, Some(1));
+
+        let certificates = fixture
+            .score_service()
+            .load_certificates(groups, true)
+            .await
+            .unwrap();
+
+        assert_eq!(certificates.len(), 2);
+        assert!(certificates.iter().all(|c| !c.results_missing));
     }
 }
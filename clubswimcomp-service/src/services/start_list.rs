@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::Datelike;
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::infra::typst_compiler::TypstOutput;
+use crate::{db, infra};
+
+pub struct StartListService {
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
+    lanes: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum CompetitionStartListError {
+    #[error("The competition does not exist")]
+    CompetitionDoesNotExist,
+
+    #[error("Failed to generate the start list PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum EventStartListError {
+    #[error("Failed to generate the start list PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl StartListService {
+    pub fn new(
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
+        lanes: u32,
+    ) -> Self {
+        Self {
+            participant_repo,
+            registration_repo,
+            competition_repo,
+            group_repo,
+            event_repo,
+            typst_compiler,
+            lanes,
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn generate_start_lists(
+        &self,
+        start_lists: Vec<infra::start_list::StartList>,
+        output: TypstOutput,
+    ) -> anyhow::Result<Vec<u8>> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        infra::start_list::StartLists {
+            event_name: event.name,
+            organization: event.organization,
+            start_lists,
+        }
+        .generate(&self.typst_compiler, output)
+        .await
+        .context("Failed to generate start lists")
+    }
+
+    /// Load the registrations of a competition and seed them into heats.
+    #[instrument(skip(self, participants, groups))]
+    async fn load_for_competition(
+        &self,
+        competition: model::Competition,
+        participants: &HashMap<Uuid, db::participants::Participant>,
+        groups: &HashMap<Uuid, String>,
+    ) -> anyhow::Result<infra::start_list::StartList> {
+        tracing::debug!("Loading registrations for competition");
+        let registrations = self
+            .registration_repo
+            .registrations_for_competition(competition.id)
+            .await
+            .context("Failed to fetch registrations for competition from repository")?;
+
+        let mut entries = Vec::with_capacity(registrations.len());
+        for registration in registrations.into_iter() {
+            let participant = participants
+                .get(&registration.participant_id)
+                .context("Participant is referenced in registration but could not be found")?;
+            let group = groups
+                .get(&participant.group_id)
+                .context("Group is referenced in participant but could not be found")?;
+
+            entries.push(infra::start_list::Lane {
+                lane: 0,
+                first_name: participant.first_name.clone(),
+                last_name: participant.last_name.clone(),
+                birthyear: participant.birthday.year() as u32,
+                group: group.clone(),
+                entry_time_millis: registration.entry_time_millis.map(|t| t as u32),
+            });
+        }
+        // Keeps the seeding of participants without or with equal entry times stable
+        entries.sort_by_cached_key(|e| format!("{}, {}", e.last_name, e.first_name));
+
+        let heats = seed_heats(entries, |e| e.entry_time_millis, self.lanes)
+            .into_iter()
+            .enumerate()
+            .map(|(index, lanes)| infra::start_list::Heat {
+                number: index as u32 + 1,
+                lanes: lanes
+                    .into_iter()
+                    .map(|(lane, entry)| infra::start_list::Lane { lane, ..entry })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(infra::start_list::StartList {
+            distance: competition.distance,
+            stroke: competition.stroke.into(),
+            gender: competition.gender.into(),
+            heats,
+        })
+    }
+
+    /// Load all participants and group names, to look them up while seeding.
+    #[instrument(skip(self))]
+    async fn load_participants_and_groups(
+        &self,
+    ) -> anyhow::Result<(
+        HashMap<Uuid, db::participants::Participant>,
+        HashMap<Uuid, String>,
+    )> {
+        tracing::debug!("Fetching participants from repository");
+        let participants = self
+            .participant_repo
+            .list_participants()
+            .await
+            .context("Failed to fetch participants from repository")?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect();
+
+        tracing::debug!("Fetching groups from repository");
+        let groups = self
+            .group_repo
+            .all_groups()
+            .await
+            .context("Failed to fetch groups from repository")?
+            .into_iter()
+            .map(|g| (g.id, g.name))
+            .collect();
+
+        Ok((participants, groups))
+    }
+
+    /// Generate the start list of a single competition.
+    #[instrument(skip(self))]
+    pub async fn competition_start_list(
+        &self,
+        competition_id: Uuid,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, CompetitionStartListError> {
+        tracing::debug!("Fetching competition from repository");
+        let competition = self
+            .competition_repo
+            .competition_by_id(competition_id)
+            .await
+            .context("Failed to fetch competition from repository")?
+            .map(model::Competition::from)
+            .ok_or(CompetitionStartListError::CompetitionDoesNotExist)?;
+
+        let (participants, groups) = self.load_participants_and_groups().await?;
+        let start_list = self
+            .load_for_competition(competition, &participants, &groups)
+            .await
+            .context("Failed to load start list of competition")?;
+
+        tracing::debug!("Generating start list PDF");
+        self.generate_start_lists(vec![start_list], output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    CompetitionStartListError::PageDoesNotExist,
+                    CompetitionStartListError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    /// Generate the start lists of all competitions of the event.
+    #[instrument(skip(self))]
+    pub async fn event_start_list(
+        &self,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, EventStartListError> {
+        tracing::debug!("Fetching competitions from repository");
+        let mut competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?
+            .into_iter()
+            .map(model::Competition::from)
+            .collect::<Vec<_>>();
+
+        competitions.sort_by_key(|c| (c.stroke, c.distance, c.gender == model::Gender::Male));
+
+        let (participants, groups) = self.load_participants_and_groups().await?;
+        let mut start_lists = Vec::with_capacity(competitions.len());
+        for competition in competitions.into_iter() {
+            tracing::debug!(competition_id = ?competition.id, "Loading start list of competition");
+            let start_list = self
+                .load_for_competition(competition, &participants, &groups)
+                .await
+                .context("Failed to load start list of competition")?;
+            start_lists.push(start_list);
+        }
+
+        tracing::debug!("Generating start list PDF");
+        self.generate_start_lists(start_lists, output)
+            .await
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    EventStartListError::PageDoesNotExist,
+                    EventStartListError::PdfGenerationFailed,
+                )
+            })
+    }
+}
+
+/// The order in which the lanes of a pool with the given number of lanes are
+/// assigned, the fastest swimmer gets the first lane.
+///
+/// Lanes are assigned from the center of the pool to the outside, e.g. for
+/// 8 lanes the order is `4, 5, 3, 6, 2, 7, 1, 8`.
+fn lane_order(lanes: u32) -> Vec<u32> {
+    let center = lanes.div_ceil(2);
+    (0..lanes)
+        .map(|i| match i % 2 {
+            0 => center - i / 2,
+            _ => center + i.div_ceil(2),
+        })
+        .collect()
+}
+
+/// Seed the entries of a competition into heats.
+///
+/// Entries are ordered by their entry time, entries without an entry time
+/// are treated as the slowest. The fastest entries swim in the last heat, so
+/// only the first heat may not be filled completely. Within a heat the lanes
+/// are assigned by [`lane_order`].
+///
+/// Returns the heats in the order they are swum, each with the lane number
+/// of its entries.
+pub fn seed_heats<T>(
+    mut entries: Vec<T>,
+    entry_time: impl Fn(&T) -> Option<u32>,
+    lanes: u32,
+) -> Vec<Vec<(u32, T)>> {
+    if entries.is_empty() || lanes == 0 {
+        return Vec::new();
+    }
+
+    // The sort is stable, the given order breaks ties
+    entries.sort_by_key(|e| match entry_time(e) {
+        Some(time) => (false, time),
+        None => (true, 0),
+    });
+
+    let lane_order = lane_order(lanes);
+    let heat_count = entries.len().div_ceil(lanes as usize);
+    let mut heats = (0..heat_count).map(|_| Vec::new()).collect::<Vec<_>>();
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let heat = heat_count - 1 - index / lanes as usize;
+        let lane = lane_order[index % lanes as usize];
+        heats[heat].push((lane, entry));
+    }
+
+    heats
+        .iter_mut()
+        .for_each(|heat| heat.sort_by_key(|(lane, _)| *lane));
+    heats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes_are_assigned_from_the_center() {
+        assert_eq!(lane_order(8), vec![4, 5, 3, 6, 2, 7, 1, 8]);
+        assert_eq!(lane_order(6), vec![3, 4, 2, 5, 1, 6]);
+        assert_eq!(lane_order(5), vec![3, 4, 2, 5, 1]);
+        assert_eq!(lane_order(1), vec![1]);
+    }
+
+    #[test]
+    fn fastest_entries_swim_in_the_last_heat() {
+        let entries = vec![Some(30), Some(10), None, Some(20), Some(40)];
+
+        let heats = seed_heats(entries, |e| *e, 3);
+
+        assert_eq!(
+            heats,
+            vec![
+                vec![(2, Some(40)), (3, None)],
+                vec![(1, Some(30)), (2, Some(10)), (3, Some(20))],
+            ]
+        );
+    }
+
+    #[test]
+    fn no_entries_have_no_heats() {
+        let heats = seed_heats(Vec::<Option<u32>>::new(), |e| *e, 6);
+
+        assert!(heats.is_empty());
+    }
+}
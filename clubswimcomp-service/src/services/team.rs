@@ -0,0 +1,607 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::{db, infra, services::score::ScoreService};
+
+use super::{ranking, ServiceRepositoryError};
+
+/// Standings of the teams, the groups of the participants, across all
+/// competitions.
+pub struct TeamService {
+    participant_repo: Arc<dyn db::participants::Repository>,
+    registration_repo: Arc<dyn db::registrations::Repository>,
+    competition_repo: Arc<dyn db::competitions::Repository>,
+    group_repo: Arc<dyn db::groups::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    best_time_repo: Arc<dyn db::best_times::Repository>,
+    payment_repo: Arc<dyn db::payments::Repository>,
+    typst_compiler: infra::typst_compiler::TypstCompiler,
+}
+
+#[derive(Debug, Error)]
+pub enum SetTeamScoringError {
+    #[error("At least one place must score points")]
+    NoPlacingPoints,
+
+    #[error("A place must not score more points than the places before")]
+    PlacingPointsIncrease,
+
+    #[error("At least one swimmer of a team must score")]
+    InvalidMaxScorers,
+
+    #[error("Relays must score at least the placing points")]
+    InvalidRelayWeight,
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+impl TeamService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        participant_repo: Arc<dyn db::participants::Repository>,
+        registration_repo: Arc<dyn db::registrations::Repository>,
+        competition_repo: Arc<dyn db::competitions::Repository>,
+        group_repo: Arc<dyn db::groups::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        best_time_repo: Arc<dyn db::best_times::Repository>,
+        payment_repo: Arc<dyn db::payments::Repository>,
+        typst_compiler: infra::typst_compiler::TypstCompiler,
+    ) -> Self {
+        Self {
+            participant_repo,
+            registration_repo,
+            competition_repo,
+            group_repo,
+            event_repo,
+            best_time_repo,
+            payment_repo,
+            typst_compiler,
+        }
+    }
+
+    fn score_service(&self) -> ScoreService {
+        ScoreService::new(
+            self.participant_repo.clone(),
+            self.registration_repo.clone(),
+            self.competition_repo.clone(),
+            self.group_repo.clone(),
+            self.event_repo.clone(),
+            self.best_time_repo.clone(),
+            self.payment_repo.clone(),
+            self.typst_compiler.clone(),
+        )
+    }
+
+    /// How the places in the competitions score for the teams.
+    #[instrument(skip(self))]
+    pub async fn team_scoring(&self) -> Result<model::TeamScoring, ServiceRepositoryError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        Ok(model::TeamScoring::from(&event))
+    }
+
+    /// Change how the places score for the teams, applies to the standings
+    /// loaded afterwards.
+    #[instrument(skip(self))]
+    pub async fn set_team_scoring(
+        &self,
+        scoring: model::TeamScoring,
+    ) -> Result<(), SetTeamScoringError> {
+        if scoring.placing_points.iter().all(|points| *points == 0) {
+            return Err(SetTeamScoringError::NoPlacingPoints);
+        }
+        if scoring.placing_points.windows(2).any(|w| w[0] < w[1]) {
+            return Err(SetTeamScoringError::PlacingPointsIncrease);
+        }
+        if let Some(max_scorers) = scoring.max_scorers {
+            if max_scorers == 0 || max_scorers > i32::MAX as u32 {
+                return Err(SetTeamScoringError::InvalidMaxScorers);
+            }
+        }
+        if scoring.relay_weight == 0 || scoring.relay_weight > i32::MAX as u32 {
+            return Err(SetTeamScoringError::InvalidRelayWeight);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(SetTeamScoringError::EventFinalized);
+        }
+
+        tracing::debug!("Storing the team scoring with the event");
+        self.event_repo
+            .update_team_scoring(
+                &crate::conversions::team_placing_points(&scoring.placing_points),
+                scoring.max_scorers.map(|m| m as _),
+                scoring.relay_weight as _,
+            )
+            .await
+            .context("Failed to update team scoring in repository")?
+            .context("Event is missing in repository even though it has been created")?;
+
+        Ok(())
+    }
+
+    /// Standings of all teams, best first.
+    ///
+    /// In each competition the places score the placing points for the team
+    /// of the swimmer. Swimmers beyond the maximum number of scorers of their
+    /// team do not score and do not take a place from the others. Tied
+    /// swimmers each score the points of their shared place. Competitions
+    /// with finals are placed by the finals once they have been swum. The
+    /// places in relays score the placing points times the relay weight.
+    #[instrument(skip(self))]
+    pub async fn team_standings(&self) -> Result<Vec<model::TeamStanding>, ServiceRepositoryError> {
+        tracing::debug!("Loading the team scoring and ranking rules of the event");
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        let scoring = model::TeamScoring::from(&event);
+        let rules = model::RankingRules::from(&event);
+
+        tracing::debug!("Fetching groups from repository");
+        let groups = self
+            .group_repo
+            .all_groups()
+            .await
+            .context("Failed to fetch groups from repository")?
+            .into_iter()
+            .map(model::Group::from)
+            .collect::<Vec<_>>();
+
+        tracing::debug!("Fetching competitions from repository");
+        let competitions = self
+            .competition_repo
+            .all_competitions()
+            .await
+            .context("Failed to fetch competitions from repository")?;
+
+        let mut team_scores = HashMap::<_, Vec<_>>::new();
+        for competition in competitions
+            .iter()
+            .filter(|c| c.round.is_individual_start())
+        {
+            tracing::debug!(competition_id = ?competition.id, "Loading scoreboard of competition");
+            let (competition, mut scores) = self.placed_scores(competition, &competitions).await?;
+            scores.sort_by_cached_key(|(place, s)| {
+                (
+                    *place,
+                    format!("{}, {}", s.participant.last_name, s.participant.first_name),
+                )
+            });
+
+            let mut scorers_per_team = HashMap::<_, u32>::new();
+            let scorers = scores
+                .into_iter()
+                .filter(|(_, s)| {
+                    let scorers = scorers_per_team.entry(s.participant.group_id).or_default();
+                    *scorers += 1;
+                    scoring.max_scorers.is_none_or(|max| *scorers <= max)
+                })
+                .collect::<Vec<_>>();
+
+            let weight = if competition.relay {
+                scoring.relay_weight
+            } else {
+                1
+            };
+            let places = scorers.iter().map(|(place, _)| *place).collect::<Vec<_>>();
+            let placings = ranking::rank(&places, rules.shared_ranks);
+            for ((_, score), placing) in scorers.into_iter().zip(placings) {
+                let points = scoring
+                    .placing_points
+                    .get(placing.rank as usize - 1)
+                    .map(|points| points.saturating_mul(weight))
+                    .unwrap_or_default();
+                if points == 0 {
+                    continue;
+                }
+
+                team_scores
+                    .entry(score.participant.group_id)
+                    .or_default()
+                    .push(model::TeamScore {
+                        participant: score.participant,
+                        competition: competition.clone(),
+                        rank: placing.rank,
+                        points,
+                    });
+            }
+        }
+
+        tracing::debug!("Ranking the teams by their points");
+        let points = groups
+            .iter()
+            .map(|g| {
+                team_scores
+                    .get(&g.id)
+                    .map(|scores| scores.iter().map(|s| s.points).sum())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<u32>>();
+        let keys = points
+            .iter()
+            .map(|points| std::cmp::Reverse(*points))
+            .collect::<Vec<_>>();
+        let placings = ranking::rank(&keys, rules.shared_ranks);
+
+        let mut standings = groups
+            .into_iter()
+            .zip(points)
+            .zip(placings)
+            .map(|((group, points), placing)| model::TeamStanding {
+                scores: team_scores.remove(&group.id).unwrap_or_default(),
+                group,
+                points,
+                rank: placing.rank,
+                tied: placing.tied,
+            })
+            .collect::<Vec<_>>();
+        standings.sort_by(|a, b| (a.rank, &a.group.name).cmp(&(b.rank, &b.group.name)));
+
+        Ok(standings)
+    }
+
+    /// The competition with its ranked scores and the place they finished in.
+    ///
+    /// Once the finals have been swum, the swimmers with a result in the
+    /// finals are placed by the finals, the other swimmers of the prelims
+    /// follow them by their rank in the prelims.
+    async fn placed_scores(
+        &self,
+        competition: &db::competitions::Competition,
+        competitions: &[db::competitions::Competition],
+    ) -> anyhow::Result<(
+        model::Competition,
+        Vec<((u8, u32), model::CompetitionScore)>,
+    )> {
+        let scoreboard = self
+            .score_service()
+            .competition_scoreboard(competition.id)
+            .await
+            .context("Failed to load scoreboard of competition even though it exists")?;
+
+        let finals = competitions.iter().find(|c| {
+            c.round == db::competitions::CompetitionRound::Finals
+                && (c.gender, c.stroke, c.distance, c.relay)
+                    == (
+                        competition.gender,
+                        competition.stroke,
+                        competition.distance,
+                        competition.relay,
+                    )
+        });
+        let Some(finals) = finals else {
+            let scores = scoreboard
+                .scores
+                .into_iter()
+                .map(|s| ((0, s.rank), s))
+                .collect();
+            return Ok((scoreboard.competition, scores));
+        };
+
+        tracing::debug!(competition_id = ?finals.id, "Loading scoreboard of the finals");
+        let finals_scoreboard = self
+            .score_service()
+            .competition_scoreboard(finals.id)
+            .await
+            .context("Failed to load scoreboard of finals even though they exist")?;
+        let swum_finals = finals_scoreboard
+            .scores
+            .iter()
+            .map(|s| s.participant.id)
+            .chain(
+                finals_scoreboard
+                    .disqualifications
+                    .iter()
+                    .map(|r| r.participant.id),
+            )
+            .collect::<HashSet<_>>();
+
+        let scores = finals_scoreboard
+            .scores
+            .into_iter()
+            .map(|s| ((0, s.rank), s))
+            .chain(
+                scoreboard
+                    .scores
+                    .into_iter()
+                    .filter(|s| !swum_finals.contains(&s.participant.id))
+                    .map(|s| ((1, s.rank), s)),
+            )
+            .collect();
+        Ok((scoreboard.competition, scores))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clubswimcomp_types::api;
+    use uuid::Uuid;
+
+    use crate::db::{Gender, Stroke};
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    /// Two teams with three swimmers in freestyle and two in backstroke.
+    async fn dolphins_and_sharks(fixture: &Fixture) {
+        let dolphins = fixture.group("Dolphins").await;
+        let sharks = fixture.group("Sharks").await;
+        let free = fixture
+            .competition(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let back = fixture
+            .competition(Gender::Female, Stroke::Back, 50, 30_000)
+            .await;
+
+        // (group, last name, freestyle time, backstroke time)
+        let swimmers: [(Uuid, &str, i32, Option<i32>); 3] = [
+            (dolphins, "First", 30_000, Some(31_000)),
+            (dolphins, "Second", 31_000, None),
+            (sharks, "Third", 32_000, Some(30_000)),
+        ];
+        for (group_id, last_name, free_time, back_time) in swimmers {
+            let participant_id = fixture
+                .participant(group_id, Gender::Female, last_name)
+                .await;
+            let registration_id = fixture.register(participant_id, free).await;
+            fixture.result(registration_id, free_time, false).await;
+            if let Some(back_time) = back_time {
+                let registration_id = fixture.register(participant_id, back).await;
+                fixture.result(registration_id, back_time, false).await;
+            }
+        }
+    }
+
+    async fn standings(fixture: &Fixture) -> Vec<(String, u32, u32, bool)> {
+        fixture
+            .team_service()
+            .team_standings()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.group.name, s.points, s.rank, s.tied))
+            .collect()
+    }
+
+    async fn group_points(fixture: &Fixture) -> Vec<(String, u32)> {
+        let score_service = fixture.score_service();
+        let mut points = Vec::new();
+        for group in fixture.repos.groups.all_groups().await.unwrap() {
+            let scoreboard = score_service.group_scoreboard(group.id).await.unwrap();
+            points.extend(
+                scoreboard
+                    .scores
+                    .into_iter()
+                    .map(|s| (s.participant.last_name, s.fina_points)),
+            );
+        }
+        points.sort();
+        points
+    }
+
+    #[tokio::test]
+    async fn places_score_points_for_the_teams() {
+        let fixture = Fixture::new();
+        dolphins_and_sharks(&fixture).await;
+
+        // Freestyle: 9 + 7 for the dolphins, 6 for the sharks
+        // Backstroke: 9 for the sharks, 7 for the dolphins
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 23, 1, false),
+                ("Sharks".to_string(), 15, 2, false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn swimmers_beyond_the_max_scorers_make_room() {
+        let fixture = Fixture::new();
+        dolphins_and_sharks(&fixture).await;
+        fixture
+            .team_service()
+            .set_team_scoring(model::TeamScoring {
+                placing_points: vec![3, 2, 1],
+                max_scorers: Some(1),
+                relay_weight: 2,
+            })
+            .await
+            .unwrap();
+
+        // The second dolphin does not score, so the shark is second in freestyle
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 5, 1, true),
+                ("Sharks".to_string(), 5, 1, true),
+            ]
+        );
+        let standings = fixture.team_service().team_standings().await.unwrap();
+        let dolphin_scorers = standings[0]
+            .scores
+            .iter()
+            .map(|s| s.participant.last_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(dolphin_scorers.len(), 2);
+        assert!(dolphin_scorers.iter().all(|name| *name == "First"));
+    }
+
+    #[tokio::test]
+    async fn relays_score_the_weighted_placing_points() {
+        let fixture = Fixture::new();
+        dolphins_and_sharks(&fixture).await;
+        let relay = fixture
+            .relay(Gender::Female, Stroke::Freestyle, 50, 30_000)
+            .await;
+        let participants = fixture
+            .repos
+            .participants
+            .list_participants()
+            .await
+            .unwrap();
+        for participant in participants {
+            let time = match participant.last_name.as_str() {
+                "First" => 29_000,
+                "Third" => 28_000,
+                _ => continue,
+            };
+            let registration_id = fixture.register(participant.id, relay).await;
+            fixture.result(registration_id, time, false).await;
+        }
+
+        // Relay: 2 * 9 for the sharks, 2 * 7 for the dolphins
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 37, 1, false),
+                ("Sharks".to_string(), 33, 2, false),
+            ]
+        );
+
+        fixture
+            .team_service()
+            .set_team_scoring(model::TeamScoring {
+                relay_weight: 3,
+                ..model::TeamScoring::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 44, 1, false),
+                ("Sharks".to_string(), 42, 2, false),
+            ]
+        );
+        let standings = fixture.team_service().team_standings().await.unwrap();
+        let relay_points = standings[1]
+            .scores
+            .iter()
+            .filter(|s| s.competition.relay)
+            .map(|s| s.points)
+            .collect::<Vec<_>>();
+        assert_eq!(relay_points, vec![27]);
+    }
+
+    #[tokio::test]
+    async fn finals_decide_the_placings() {
+        let fixture = Fixture::new();
+        dolphins_and_sharks(&fixture).await;
+        let free = fixture
+            .repos
+            .competitions
+            .all_competitions()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|c| c.stroke == Stroke::Freestyle)
+            .unwrap()
+            .id;
+        let finals_service = fixture.finals_service();
+        finals_service
+            .set_finals(
+                free,
+                api::FinalsRequest {
+                    finalists: 3,
+                    alternates: 0,
+                },
+            )
+            .await
+            .unwrap();
+        finals_service.promote_finalists(free).await.unwrap();
+
+        // Until the finals are swum the prelims place the swimmers
+        let points_before = group_points(&fixture).await;
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 23, 1, false),
+                ("Sharks".to_string(), 15, 2, false),
+            ]
+        );
+
+        // The shark wins the finals ahead of the two dolphins
+        let finals = finals_service.finals(free).await.unwrap();
+        let registrations = fixture
+            .repos
+            .registrations
+            .registrations_for_competition(finals.finals_competition_id)
+            .await
+            .unwrap();
+        for registration in registrations {
+            let time = match registration.entry_time_millis {
+                Some(30_000) => 30_500,
+                Some(31_000) => 31_000,
+                _ => 29_000,
+            };
+            fixture.result(registration.id, time, false).await;
+        }
+
+        // Freestyle: 9 for the sharks, 7 + 6 for the dolphins
+        assert_eq!(
+            standings(&fixture).await,
+            vec![
+                ("Dolphins".to_string(), 20, 1, false),
+                ("Sharks".to_string(), 18, 2, false),
+            ]
+        );
+        let points_after = group_points(&fixture).await;
+        assert!(points_after[0].1 < points_before[0].1, "{points_after:?}");
+        assert_eq!(points_after[1], points_before[1]);
+        assert!(points_after[2].1 > points_before[2].1, "{points_after:?}");
+    }
+
+    #[tokio::test]
+    async fn invalid_team_scorings_are_rejected() {
+        let team_service = Fixture::new().team_service();
+        let scoring = |placing_points: Vec<u32>, max_scorers| model::TeamScoring {
+            placing_points,
+            max_scorers,
+            relay_weight: 2,
+        };
+
+        let result = team_service.set_team_scoring(scoring(vec![], None)).await;
+        assert!(
+            matches!(result, Err(SetTeamScoringError::NoPlacingPoints)),
+            "{result:?}"
+        );
+        let result = team_service
+            .set_team_scoring(scoring(vec![1, 2], None))
+            .await;
+        assert!(
+            matches!(result, Err(SetTeamScoringError::PlacingPointsIncrease)),
+            "{result:?}"
+        );
+        let result = team_service
+            .set_team_scoring(scoring(vec![2, 1], Some(0)))
+            .await;
+        assert!(
+            matches!(result, Err(SetTeamScoringError::InvalidMaxScorers)),
+            "{result:?}"
+        );
+        let result = team_service
+            .set_team_scoring(model::TeamScoring {
+                relay_weight: 0,
+                ..scoring(vec![2, 1], None)
+            })
+            .await;
+        assert!(
+            matches!(result, Err(SetTeamScoringError::InvalidRelayWeight)),
+            "{result:?}"
+        );
+        assert_eq!(
+            team_service.team_scoring().await.unwrap(),
+            model::TeamScoring::default()
+        );
+    }
+}
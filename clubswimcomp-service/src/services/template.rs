@@ -0,0 +1,393 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context;
+use clubswimcomp_types::model;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::db;
+use crate::infra::{
+    templates,
+    typst_compiler::{TypstCompiler, TypstOutput, BUNDLED_ASSETS},
+};
+
+/// Extensions of the assets that can be uploaded.
+const ASSET_EXTENSIONS: [&str; 5] = ["typst", "svg", "png", "jpg", "jpeg"];
+
+pub struct TemplateService {
+    asset_repo: Arc<dyn db::assets::Repository>,
+    event_repo: Arc<dyn db::event::Repository>,
+    typst_compiler: TypstCompiler,
+}
+
+#[derive(Debug, Error)]
+pub enum AssetError {
+    #[error("The asset does not exist")]
+    AssetDoesNotExist,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UploadAssetError {
+    #[error("The asset name must only contain letters, digits, '-' and '_' and end with one of: {}", ASSET_EXTENSIONS.join(", "))]
+    InvalidAssetName,
+
+    #[error("The documents can not be generated with the asset: {0}")]
+    InvalidTemplate(String),
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DeleteAssetError {
+    #[error("The asset does not exist")]
+    AssetDoesNotExist,
+
+    #[error("The documents can not be generated without the asset: {0}")]
+    InvalidTemplate(String),
+
+    #[error("The event is finalized")]
+    EventFinalized,
+
+    #[error("The repository ran into an error: {0:#?}")]
+    RepositoryError(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum TemplatePreviewError {
+    #[error("Failed to generate the preview PDF")]
+    PdfGenerationFailed(anyhow::Error),
+
+    #[error("The document has no page {0}")]
+    PageDoesNotExist(usize),
+}
+
+impl TemplateService {
+    pub fn new(
+        asset_repo: Arc<dyn db::assets::Repository>,
+        event_repo: Arc<dyn db::event::Repository>,
+        typst_compiler: TypstCompiler,
+    ) -> Self {
+        Self {
+            asset_repo,
+            event_repo,
+            typst_compiler,
+        }
+    }
+
+    /// List the bundled and the uploaded assets.
+    #[instrument(skip(self))]
+    pub async fn list_assets(&self) -> Result<Vec<model::Asset>, AssetError> {
+        tracing::debug!("Loading uploaded assets");
+        let uploaded = self.asset_repo.all_assets().await?;
+
+        let mut assets = BUNDLED_ASSETS
+            .iter()
+            .map(|(name, _)| model::Asset {
+                name: name.to_string(),
+                customized: false,
+                bundled: true,
+                updated_at: None,
+            })
+            .collect::<Vec<_>>();
+
+        for asset in uploaded {
+            match assets.iter_mut().find(|a| a.name == asset.name) {
+                Some(bundled) => {
+                    bundled.customized = true;
+                    bundled.updated_at = Some(asset.updated_at);
+                }
+                None => assets.push(model::Asset {
+                    name: asset.name,
+                    customized: true,
+                    bundled: false,
+                    updated_at: Some(asset.updated_at),
+                }),
+            }
+        }
+
+        assets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(assets)
+    }
+
+    /// Content of an asset as it is used to generate the documents.
+    #[instrument(skip(self))]
+    pub async fn asset(&self, name: &str) -> Result<Vec<u8>, AssetError> {
+        self.typst_compiler
+            .asset(name)
+            .ok_or(AssetError::AssetDoesNotExist)
+    }
+
+    /// Upload an asset, replacing the bundled or uploaded asset with the same name.
+    ///
+    /// All documents are generated with sample data first, the asset is
+    /// rejected if any of them fails.
+    #[instrument(skip(self, content))]
+    pub async fn upload_asset(&self, name: &str, content: Vec<u8>) -> Result<(), UploadAssetError> {
+        if !is_valid_asset_name(name) {
+            return Err(UploadAssetError::InvalidAssetName);
+        }
+
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(UploadAssetError::EventFinalized);
+        }
+
+        tracing::debug!("Validating templates with the uploaded asset");
+        let mut assets = self.custom_assets().await?;
+        assets.insert(name.to_string(), content.clone());
+        self.validate_templates(assets)
+            .await
+            .map_err(UploadAssetError::InvalidTemplate)?;
+
+        tracing::debug!("Storing asset");
+        self.asset_repo.upsert_asset(name, &content).await?;
+        self.reload_assets().await?;
+
+        Ok(())
+    }
+
+    /// Delete an uploaded asset, the bundled asset with the same name is used again.
+    #[instrument(skip(self))]
+    pub async fn delete_asset(&self, name: &str) -> Result<(), DeleteAssetError> {
+        let event = super::current_event(self.event_repo.as_ref()).await?;
+        if event.phase == db::event::EventPhase::Finalized {
+            return Err(DeleteAssetError::EventFinalized);
+        }
+
+        let mut assets = self.custom_assets().await?;
+        if assets.remove(name).is_none() {
+            return Err(DeleteAssetError::AssetDoesNotExist);
+        }
+
+        tracing::debug!("Validating templates without the asset");
+        self.validate_templates(assets)
+            .await
+            .map_err(DeleteAssetError::InvalidTemplate)?;
+
+        tracing::debug!("Deleting asset");
+        self.asset_repo
+            .delete_asset(name)
+            .await?
+            .ok_or(DeleteAssetError::AssetDoesNotExist)?;
+        self.reload_assets().await?;
+
+        Ok(())
+    }
+
+    /// Generate a document with sample data.
+    #[instrument(skip(self))]
+    pub async fn preview(
+        &self,
+        template: model::DocumentTemplate,
+        output: TypstOutput,
+    ) -> Result<Vec<u8>, TemplatePreviewError> {
+        let data =
+            templates::sample_data(template).map_err(TemplatePreviewError::PdfGenerationFailed)?;
+
+        self.typst_compiler
+            .compile(templates::template_file(template), output, data)
+            .await
+            .context("Failed to compile template preview")
+            .map_err(|e| {
+                super::document_error(
+                    e,
+                    TemplatePreviewError::PageDoesNotExist,
+                    TemplatePreviewError::PdfGenerationFailed,
+                )
+            })
+    }
+
+    /// Load the uploaded assets into the typst compiler, needed whenever they
+    /// have been changed in the repository.
+    #[instrument(skip(self))]
+    pub async fn reload_assets(&self) -> anyhow::Result<()> {
+        let assets = self.custom_assets().await?;
+        tracing::debug!(count = assets.len(), "Activating uploaded assets");
+        self.typst_compiler.set_custom_assets(assets);
+
+        Ok(())
+    }
+
+    async fn custom_assets(&self) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+        let assets = self
+            .asset_repo
+            .all_assets()
+            .await
+            .context("Failed to load assets from repository")?;
+
+        Ok(assets
+            .into_iter()
+            .map(|asset| (asset.name, asset.content))
+            .collect())
+    }
+
+    /// Compile all documents with the given assets, returns the errors of the
+    /// first document that failed.
+    async fn validate_templates(&self, assets: HashMap<String, Vec<u8>>) -> Result<(), String> {
+        for template in templates::DOCUMENT_TEMPLATES {
+            let data = templates::sample_data(template).map_err(|e| format!("{e:#}"))?;
+            self.typst_compiler
+                .compile_with_assets(
+                    templates::template_file(template),
+                    TypstOutput::Pdf,
+                    data,
+                    assets.clone(),
+                )
+                .await
+                .map_err(|e| format!("{}: {e:#}", templates::template_file(template)))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_valid_asset_name(name: &str) -> bool {
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+
+    !stem.is_empty()
+        && stem
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && ASSET_EXTENSIONS.contains(&extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::fixtures::Fixture;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn bundled_assets_are_listed() {
+        let assets = Fixture::new()
+            .template_service()
+            .list_assets()
+            .await
+            .unwrap();
+
+        let labels = assets.iter().find(|a| a.name == "labels.typst").unwrap();
+        assert!(labels.bundled);
+        assert!(!labels.customized);
+    }
+
+    #[tokio::test]
+    async fn uploaded_assets_override_bundled_ones() {
+        let fixture = Fixture::new();
+        let template_service = fixture.template_service();
+        let labels = template_service.asset("labels.typst").await.unwrap();
+        let translated = String::from_utf8(labels)
+            .unwrap()
+            .replace("\"Startliste\"", "\"Start list\"");
+
+        template_service
+            .upload_asset("labels.typst", translated.clone().into_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            template_service.asset("labels.typst").await.unwrap(),
+            translated.into_bytes()
+        );
+        let assets = template_service.list_assets().await.unwrap();
+        let labels = assets.iter().find(|a| a.name == "labels.typst").unwrap();
+        assert!(labels.bundled && labels.customized);
+    }
+
+    #[tokio::test]
+    async fn broken_templates_are_rejected() {
+        let fixture = Fixture::new();
+        let template_service = fixture.template_service();
+
+        let result = template_service
+            .upload_asset("start_list.typst", b"#let x = (".to_vec())
+            .await;
+
+        let Err(UploadAssetError::InvalidTemplate(message)) = result else {
+            panic!("Expected the template to be rejected, got {result:?}");
+        };
+        assert!(message.contains("start_list.typst:1"), "{message}");
+        assert!(fixture.repos.assets.all_assets().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn assets_must_have_valid_names() {
+        let template_service = Fixture::new().template_service();
+
+        for name in ["../labels.typst", "labels", "labels.exe", ".typst"] {
+            let result = template_service.upload_asset(name, Vec::new()).await;
+            assert!(
+                matches!(result, Err(UploadAssetError::InvalidAssetName)),
+                "{name} was accepted"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn deleting_an_asset_restores_the_bundled_one() {
+        let fixture = Fixture::new();
+        let template_service = fixture.template_service();
+        let bundled = template_service.asset("logo.svg").await.unwrap();
+        template_service
+            .upload_asset(
+                "logo.svg",
+                b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec(),
+            )
+            .await
+            .unwrap();
+
+        template_service.delete_asset("logo.svg").await.unwrap();
+
+        assert_eq!(template_service.asset("logo.svg").await.unwrap(), bundled);
+        assert!(matches!(
+            template_service.delete_asset("logo.svg").await,
+            Err(DeleteAssetError::AssetDoesNotExist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn previews_render_single_pages() {
+        let template_service = Fixture::new().template_service();
+
+        let png = template_service
+            .preview(
+                model::DocumentTemplate::Certificate,
+                TypstOutput::Png { page: 1 },
+            )
+            .await
+            .unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let result = template_service
+            .preview(
+                model::DocumentTemplate::Certificate,
+                TypstOutput::Svg { page: 2 },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(TemplatePreviewError::PageDoesNotExist(2))),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn all_templates_can_be_previewed() {
+        let template_service = Fixture::new().template_service();
+
+        for template in templates::DOCUMENT_TEMPLATES {
+            let pdf = template_service
+                .preview(template, TypstOutput::Pdf)
+                .await
+                .unwrap();
+            assert!(pdf.starts_with(b"%PDF"));
+        }
+    }
+}
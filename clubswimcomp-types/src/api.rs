@@ -1,10 +1,11 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::model;
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddParticipantBody {
     pub first_name: String,
     pub last_name: String,
@@ -14,55 +15,451 @@ pub struct AddParticipantBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddParticipantResponse {
     pub participant_id: Uuid,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
 pub struct RemoveParticipantParameters {
     pub force_delete: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterForCompetitionBody {
     pub competition_id: Uuid,
+    /// Time the participant expects to swim, used to seed the heats.
+    #[serde(default)]
+    pub entry_time_millis: Option<u32>,
+    /// Accept the entry after the registration has closed.
+    #[serde(default)]
+    pub late_entry: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterForCompetitionResponse {
     pub registration_id: Uuid,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct UnregisterFromCompetitionParams {
+    /// Withdraw the entry after the registration has closed.
+    pub late_entry: Option<bool>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddCompetitionRequest {
     pub gender: model::Gender,
     pub stroke: model::Stroke,
     pub distance: u32,
     pub target_time: u32,
+    /// Swum by teams, see [`model::Competition::relay`].
+    #[serde(default)]
+    pub relay: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddCompetitionResponse {
     pub competition_id: Uuid,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
 pub struct DeleteCompetitionParams {
     pub force_delete: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct CertificatesParams {
+    /// Leave out participants with missing results instead of flagging them.
+    pub skip_missing_results: Option<bool>,
+}
+
+/// File format of a generated document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    #[default]
+    Pdf,
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct DocumentParams {
+    /// Format of the document, PNG and SVG only contain a single page.
+    pub format: Option<DocumentFormat>,
+    /// Page to render as PNG or SVG, starting at 1.
+    pub page: Option<u32>,
+}
+
+/// How the registration cards are arranged on the paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CardLayout {
+    /// One card per A6 page.
+    #[default]
+    A6,
+    /// Eight cards per A4 page, to be cut apart.
+    A4Grid,
+    /// One card per label of a label printer.
+    Label,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct CardLayoutParams {
+    /// Layout of the cards, A6 by default.
+    pub layout: Option<CardLayout>,
+    /// Width of the labels in millimeters, only used by the label layout.
+    pub label_width_mm: Option<u32>,
+    /// Height of the labels in millimeters, only used by the label layout.
+    pub label_height_mm: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EnterResultBody {
     pub disqualified: bool,
     pub time_millis: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EnterResultResponse {
+    /// Records broken with the result, they wait for the approval.
+    pub new_records: Vec<model::Record>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScanCardRequest {
+    /// Content of the QR code on the registration card.
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScanCardResponse {
+    pub registration_id: Uuid,
+    /// The card only contains the registration id and could not be verified.
+    pub legacy: bool,
+}
+
+/// Either the card number or the short code and the competition of the
+/// registration to look up.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct RegistrationLookupParams {
+    /// Number printed on the registration card.
+    pub card_number: Option<u32>,
+    /// Short code of the participant.
+    pub short_code: Option<String>,
+    /// Competition of the registration, required with the short code.
+    pub competition_id: Option<Uuid>,
+}
+
+/// File format of imported best times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum BestTimesFormat {
+    /// A header and one time per line, see the import of the best times.
+    #[default]
+    Csv,
+    /// Results of a meet in the uncompressed Lenex XML format.
+    Lenex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ImportBestTimesParams {
+    /// Format of the file, CSV by default.
+    pub format: Option<BestTimesFormat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportBestTimesResponse {
+    pub imported: u64,
+    /// Times that have already been imported before.
+    pub duplicates: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct RecordsParams {
+    /// Only list club or meet records.
+    pub kind: Option<model::RecordKind>,
+    /// Only list records with the status, all records by default.
+    pub status: Option<model::RecordStatus>,
+}
+
+/// A record from the paper list, it replaces the current record.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddRecordRequest {
+    pub kind: model::RecordKind,
+    pub gender: model::Gender,
+    pub stroke: model::Stroke,
+    pub distance: u32,
+    pub course: model::Course,
+    pub age_class: u32,
+    pub time_millis: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub swum_on: NaiveDate,
+    /// Name of the meet the record has been swum at.
+    pub meet: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddRecordResponse {
+    pub record_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddGroupRequest {
     pub name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AddGroupResponse {
     pub group_id: Uuid,
 }
+
+/// The settings of a session, the competitions are swum in the given order.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionBody {
+    pub name: String,
+    pub starts_at: NaiveDateTime,
+    pub warm_up_minutes: u32,
+    pub turnaround_seconds: u32,
+    pub competition_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddSessionResponse {
+    pub session_id: Uuid,
+}
+
+/// How many swimmers advance from the prelims into the finals.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalsRequest {
+    pub finalists: u32,
+    #[serde(default)]
+    pub alternates: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalsResponse {
+    pub finals_competition_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddSwimOffResponse {
+    pub competition_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScratchFinalistRequest {
+    pub participant_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScratchFinalistResponse {
+    /// The alternate that moved up into the finals, if there is one left.
+    pub promoted_participant_id: Option<Uuid>,
+}
+
+/// A received payment, either from a participant or for a whole group.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddPaymentRequest {
+    pub participant_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    /// Amount in cents.
+    pub amount: u32,
+    pub received_on: NaiveDate,
+    #[serde(default)]
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddPaymentResponse {
+    pub payment_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangePhaseRequest {
+    pub phase: model::EventPhase,
+}
+
+/// Re-open a finalized event, the reason is kept in the phase history.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReopenEventRequest {
+    /// The phase the event continues in.
+    pub phase: model::EventPhase,
+    pub reason: String,
+}
+
+/// Error document returned by the API for every failed request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    /// Stable, machine readable code of the error.
+    pub code: ErrorCode,
+    /// Human readable description of the error.
+    pub message: String,
+    /// Validation errors of individual fields of the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+    /// Id of the request in the service logs.
+    pub correlation_id: Option<Uuid>,
+}
+
+impl std::fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ErrorResponse {}
+
+/// Validation error of a single field of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Machine readable codes of the errors the API returns.
+///
+/// The codes are serialized in `SCREAMING_SNAKE_CASE` and will not change,
+/// clients can rely on them to react to specific errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ParticipantDoesNotExist,
+    CompetitionDoesNotExist,
+    RegistrationDoesNotExist,
+    GroupDoesNotExist,
+    ParticipantHasRegistrations,
+    CompetitionHasRegistrations,
+    AlreadyRegistered,
+    NotEligible,
+    InvalidDistance,
+    SameCompetitionExists,
+    ResultAlreadyExists,
+    RegistrationHasNoResult,
+    UnsupportedBackupVersion,
+    /// Backups can only be restored into an empty database.
+    DatabaseNotEmpty,
+    InvalidBackup,
+    AssetDoesNotExist,
+    /// Asset names may only contain letters, digits, `-` and `_` and must have
+    /// a supported file extension.
+    InvalidAssetName,
+    /// The uploaded asset breaks at least one document template.
+    InvalidTemplate,
+    /// The requested page of a document preview does not exist.
+    PageDoesNotExist,
+    /// The scanned code is not from a registration card.
+    InvalidCardCode,
+    /// The scanned registration card belongs to another event.
+    CardOfOtherEvent,
+    /// The signature of the scanned registration card is invalid, the card
+    /// has been forged or altered.
+    InvalidCardSignature,
+    /// The imported best times could not be read, see the message for the
+    /// line or element.
+    InvalidBestTimes,
+    RecordDoesNotExist,
+    /// Only pending records can be approved or rejected.
+    RecordNotPending,
+    /// The time does not beat the current record.
+    NotFasterThanRecord,
+    /// The entry deadline has passed, the change needs a late entry.
+    RegistrationClosed,
+    /// The event is finalized and has to be re-opened for changes.
+    EventFinalized,
+    /// Only finalized events can be re-opened.
+    EventNotFinalized,
+    SessionDoesNotExist,
+    /// A competition can only be swum in one session.
+    CompetitionAlreadyScheduled,
+    CompetitionHasNoFinals,
+    /// Only competitions swum in prelims can have finals.
+    NotPrelims,
+    /// Not every swimmer of the prelims has a result yet.
+    PrelimsNotFinished,
+    FinalistsAlreadyPromoted,
+    FinalistsNotPromoted,
+    /// Swimmers are tied for the last place in the finals.
+    SwimOffRequired,
+    /// The swim-off of the tie at the cutoff has no results yet.
+    SwimOffNotFinished,
+    NoTieAtCutoff,
+    NotAFinalist,
+    PaymentDoesNotExist,
+    /// Payments are kept for the accounts, their payer can't be deleted.
+    ParticipantHasPayments,
+    /// The request could not be parsed, see the details for the fields.
+    InvalidRequest,
+    RouteNotFound,
+    PdfGenerationFailed,
+    InternalError,
+    /// Code sent by a newer version of the service.
+    #[serde(other)]
+    Unknown,
+}
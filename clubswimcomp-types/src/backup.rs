@@ -0,0 +1,260 @@
+//! Versioned format of event backups.
+//!
+//! A backup contains the complete dataset of an event with the original ids,
+//! so that registration cards that have already been printed stay valid
+//! after a restore.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model;
+
+/// The version of the backup format written by this version.
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Backup {
+    /// Version of the backup format.
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub groups: Vec<GroupRecord>,
+    pub participants: Vec<ParticipantRecord>,
+    pub competitions: Vec<CompetitionRecord>,
+    pub registrations: Vec<RegistrationRecord>,
+    pub results: Vec<ResultRecord>,
+    #[serde(default)]
+    pub assets: Vec<AssetRecord>,
+    #[serde(default)]
+    pub best_times: Vec<BestTimeRecord>,
+    #[serde(default)]
+    pub records: Vec<RecordRecord>,
+    #[serde(default)]
+    pub eligibilities: Vec<EligibilityRecord>,
+    #[serde(default)]
+    pub phase_changes: Vec<PhaseChangeRecord>,
+    #[serde(default)]
+    pub sessions: Vec<SessionRecord>,
+    #[serde(default)]
+    pub finals: Vec<FinalsRecord>,
+    #[serde(default)]
+    pub payments: Vec<PaymentRecord>,
+    /// Missing in backups of events without registration cards.
+    #[serde(default)]
+    pub event: Option<EventRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GroupRecord {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParticipantRecord {
+    pub id: Uuid,
+    pub short_id: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub gender: model::Gender,
+    pub birthday: NaiveDate,
+    pub group_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CompetitionRecord {
+    pub id: Uuid,
+    pub gender: model::Gender,
+    pub stroke: model::Stroke,
+    pub distance: u32,
+    pub target_time: u32,
+    /// Missing in older backups, which have no relays.
+    #[serde(default)]
+    pub relay: bool,
+    #[serde(default)]
+    pub round: model::CompetitionRound,
+}
+
+/// The eligibility rules of a competition.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EligibilityRecord {
+    pub competition_id: Uuid,
+    #[serde(flatten)]
+    pub eligibility: model::Eligibility,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RegistrationRecord {
+    pub id: Uuid,
+    pub participant_id: Uuid,
+    pub competition_id: Uuid,
+    #[serde(default)]
+    pub entry_time_millis: Option<u32>,
+    /// Missing in backups of events without card numbers, new numbers are
+    /// assigned when such a backup is restored.
+    #[serde(default)]
+    pub card_number: Option<u32>,
+    /// Missing in older backups, whose registrations are charged without the
+    /// late entry surcharge.
+    #[serde(default)]
+    pub late_entry: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResultRecord {
+    pub registration_id: Uuid,
+    pub disqualified: bool,
+    pub time_millis: u32,
+}
+
+/// An uploaded template or image of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AssetRecord {
+    pub name: String,
+    /// Base64 encoded content of the asset.
+    pub content: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A time a swimmer swam at an earlier event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BestTimeRecord {
+    pub id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub stroke: model::Stroke,
+    pub distance: u32,
+    pub course: model::Course,
+    pub time_millis: u32,
+    pub swum_on: NaiveDate,
+    pub meet: String,
+    /// The result the time has been recorded from, missing for imported
+    /// times and in older backups.
+    #[serde(default)]
+    pub registration_id: Option<Uuid>,
+}
+
+/// A club or meet record, including the superseded and rejected ones.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecordRecord {
+    pub id: Uuid,
+    pub kind: model::RecordKind,
+    pub gender: model::Gender,
+    pub stroke: model::Stroke,
+    pub distance: u32,
+    pub course: model::Course,
+    pub age_class: u32,
+    pub time_millis: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub swum_on: NaiveDate,
+    pub meet: Option<String>,
+    pub registration_id: Option<Uuid>,
+    pub status: model::RecordStatus,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub superseded_by: Option<Uuid>,
+}
+
+/// A change of the phase of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PhaseChangeRecord {
+    pub id: Uuid,
+    pub phase: model::EventPhase,
+    pub changed_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+/// A session with its competitions in the order they are swum.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub starts_at: NaiveDateTime,
+    pub warm_up_minutes: u32,
+    pub turnaround_seconds: u32,
+    pub competition_ids: Vec<Uuid>,
+}
+
+/// The finals of a competition with the swimmers promoted from the prelims.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalsRecord {
+    pub competition_id: Uuid,
+    pub finals_competition_id: Uuid,
+    pub finalists: u32,
+    pub alternates: u32,
+    pub swim_off_ids: Vec<Uuid>,
+    pub entries: Vec<FinalsEntryRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalsEntryRecord {
+    pub participant_id: Uuid,
+    pub seed: u32,
+    pub time_millis: u32,
+    pub status: model::FinalsEntryStatus,
+}
+
+/// Identity of the event, needed to verify the registration cards.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EventRecord {
+    pub id: Uuid,
+    /// Base64 encoded key of the QR code signatures.
+    pub card_secret: String,
+    pub created_at: DateTime<Utc>,
+    /// Missing in backups of events that scored all results.
+    #[serde(default)]
+    pub group_scoring: model::GroupScoring,
+    /// Missing in older backups, which are ranked with the default rules.
+    #[serde(default)]
+    pub ranking_rules: model::RankingRules,
+    /// Missing in older backups, which score the teams with the default
+    /// points.
+    #[serde(default)]
+    pub team_scoring: model::TeamScoring,
+    /// Missing in older backups of events without limits.
+    #[serde(default)]
+    pub registration_limits: model::RegistrationLimits,
+    /// Missing in older backups of events in a short course pool.
+    #[serde(default)]
+    pub course: model::Course,
+    /// Missing in older backups of events that are open for registrations.
+    #[serde(default)]
+    pub phase: model::EventPhase,
+    /// Missing in older backups of events without fees.
+    #[serde(default)]
+    pub entry_fees: model::EntryFees,
+    /// Missing in older backups of events without a name.
+    #[serde(default)]
+    pub info: model::EventInfo,
+}
+
+/// A payment received from a participant or for a group.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PaymentRecord {
+    pub id: Uuid,
+    pub participant_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    /// Amount in cents.
+    pub amount: u32,
+    pub received_on: NaiveDate,
+    pub reference: String,
+}
@@ -1,2 +1,3 @@
 pub mod api;
+pub mod backup;
 pub mod model;
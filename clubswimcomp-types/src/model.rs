@@ -1,14 +1,16 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum Gender {
     Female,
     Male,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum Stroke {
     Butterfly,
     Back,
@@ -17,6 +19,7 @@ pub enum Stroke {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Participant {
     pub id: Uuid,
     pub short_code: String,
@@ -29,6 +32,7 @@ pub struct Participant {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ParticipantDetails {
     #[serde(flatten)]
     pub participant: Participant,
@@ -37,19 +41,95 @@ pub struct ParticipantDetails {
 }
 
 impl ParticipantDetails {
-    /// The total number of FINA points, the participant has achieved.
+    /// The FINA points of the participant in the group ranking.
     ///
     /// Registrations, that do not have a result yet, are ignored. Results that
-    /// are classified as disqualified are counted as 0 FINA points.
-    pub fn fina_points(&self) -> u32 {
+    /// are classified as disqualified are counted as 0 FINA points. Each race
+    /// scores once, see [`ParticipantDetails::scoring_results`].
+    pub fn group_points(&self, scoring: &GroupScoring) -> u32 {
+        let mut results = self
+            .scoring_results()
+            .map(|(competition, result)| {
+                (
+                    competition.stroke,
+                    !result.disqualified as u32 * result.fina_points,
+                )
+            })
+            .collect::<Vec<_>>();
+        results.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+        match scoring.rule {
+            GroupScoringRule::Sum => results.iter().map(|(_, points)| points).sum(),
+            GroupScoringRule::BestResults { count } => results
+                .iter()
+                .take(count as usize)
+                .map(|(_, points)| points)
+                .sum(),
+            GroupScoringRule::OnePerStroke => {
+                // Sorted by points, so the first result of a stroke is the best
+                let mut strokes = Vec::new();
+                results
+                    .iter()
+                    .filter(|(stroke, _)| match strokes.contains(stroke) {
+                        true => false,
+                        false => {
+                            strokes.push(*stroke);
+                            true
+                        }
+                    })
+                    .map(|(_, points)| points)
+                    .sum()
+            }
+            GroupScoringRule::Average => match results.len() as u32 {
+                0 => 0,
+                count => {
+                    let total = results.iter().map(|(_, points)| points).sum::<u32>();
+                    (total + count / 2) / count
+                }
+            },
+        }
+    }
+
+    /// The points of each individual start of the participant, best first.
+    ///
+    /// Disqualified results are counted as 0 FINA points.
+    pub fn result_points(&self) -> Vec<u32> {
+        let mut points = self
+            .scoring_results()
+            .map(|(_, result)| !result.disqualified as u32 * result.fina_points)
+            .collect::<Vec<_>>();
+        points.sort_by_key(|points| std::cmp::Reverse(*points));
+        points
+    }
+
+    /// The results the participant scores with, one for each race.
+    ///
+    /// A finalist scores with the result of the finals instead of the one of
+    /// the prelims, swim-offs only decide who advances into the finals.
+    pub fn scoring_results(&self) -> impl Iterator<Item = (&Competition, &RegistrationResult)> {
+        self.registrations.iter().filter_map(|r| {
+            let result = r.result.as_ref()?;
+            let scores = match r.competition.round {
+                CompetitionRound::TimedFinal | CompetitionRound::Finals => true,
+                CompetitionRound::Prelims => !self.registrations.iter().any(|f| {
+                    f.competition.round == CompetitionRound::Finals
+                        && f.competition.is_same_race(&r.competition)
+                        && f.result.is_some()
+                }),
+                CompetitionRound::SwimOff => false,
+            };
+            scores.then_some((&r.competition, result))
+        })
+    }
+
+    /// The number of individual starts the participant has swum,
+    /// disqualifications included, see
+    /// [`CompetitionRound::is_individual_start`].
+    pub fn starts(&self) -> u32 {
         self.registrations
             .iter()
-            .filter(|r| r.result.is_some())
-            .map(|r| {
-                let result = r.result.as_ref().unwrap();
-                !result.disqualified as u32 * result.fina_points
-            })
-            .sum()
+            .filter(|r| r.competition.round.is_individual_start() && r.result.is_some())
+            .count() as u32
     }
 
     /// Checks of any registration does not have a result yet.
@@ -59,15 +139,98 @@ impl ParticipantDetails {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Competition {
     pub id: Uuid,
     pub gender: Gender,
     pub distance: u32,
     pub stroke: Stroke,
     pub target_time: u32,
+    /// Swum by teams, each team is registered with one of its swimmers and
+    /// scores for the group of that swimmer.
+    #[serde(default)]
+    pub relay: bool,
+    #[serde(default)]
+    pub round: CompetitionRound,
+}
+
+impl Competition {
+    /// Both competitions are rounds of the same race, e.g. the prelims and
+    /// their finals.
+    pub fn is_same_race(&self, other: &Competition) -> bool {
+        (self.gender, self.stroke, self.distance, self.relay)
+            == (other.gender, other.stroke, other.distance, other.relay)
+    }
+}
+
+/// The round a competition is swum in, finals and swim-offs are competitions
+/// of the same kind as their prelims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CompetitionRound {
+    /// The only round, the results are final.
+    #[default]
+    TimedFinal,
+    /// The fastest swimmers advance into the finals.
+    Prelims,
+    Finals,
+    /// Decides a tie for the last place in the finals.
+    SwimOff,
+}
+
+impl CompetitionRound {
+    /// Whether a registration for the round is a start of its own.
+    ///
+    /// Finals and swim-offs repeat the race of the prelims, so they do not
+    /// count as further starts. The placings of a finalist come from the
+    /// finals though, see [`ParticipantDetails::scoring_results`].
+    pub fn is_individual_start(self) -> bool {
+        matches!(self, Self::TimedFinal | Self::Prelims)
+    }
+}
+
+/// The finals of a competition swum in prelims.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Finals {
+    /// The prelims the finalists advance from.
+    pub competition_id: Uuid,
+    pub finals_competition_id: Uuid,
+    pub finalists: u32,
+    /// Swimmers that move up into the finals when finalists scratch.
+    pub alternates: u32,
+    /// Empty until the finalists have been promoted.
+    pub entries: Vec<FinalsEntry>,
+    /// Swim-offs of ties at the cutoff, in the order they have been swum.
+    pub swim_off_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalsEntry {
+    pub participant: Participant,
+    /// Place after the prelims and swim-offs, alternates follow the finalists.
+    pub seed: u32,
+    /// Time of the prelims, used to seed the finals.
+    pub time_millis: u32,
+    pub status: FinalsEntryStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FinalsEntryStatus {
+    /// Registered for the finals.
+    Finalist,
+    /// Waits to move up when a finalist scratches.
+    Alternate,
+    /// Withdrew from the finals.
+    Scratched,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CompetitionDetails {
     #[serde(flatten)]
     pub competition: Competition,
@@ -75,7 +238,120 @@ pub struct CompetitionDetails {
     pub registrations: Vec<CompetitionRegistration>,
 }
 
+/// Who may register for a competition besides the gender of the
+/// competition. Rules that are missing or empty don't restrict the
+/// registrations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Eligibility {
+    pub min_age: Option<u32>,
+    pub max_age: Option<u32>,
+    pub min_birth_year: Option<i32>,
+    pub max_birth_year: Option<i32>,
+    /// Only participants of these groups may register.
+    #[serde(default)]
+    pub group_ids: Vec<Uuid>,
+    /// Participants need a best time in the course of the event that is at
+    /// least this fast.
+    pub qualifying_time: Option<u32>,
+}
+
+/// Name and organizer of the event, printed in the header of the documents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EventInfo {
+    pub name: String,
+    /// The club organizing the event.
+    pub organization: String,
+}
+
+/// Limits of the registrations of a participant over all competitions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RegistrationLimits {
+    /// Individual starts per participant, unlimited if missing.
+    pub max_individual_starts: Option<u32>,
+}
+
+/// Fees of the entries, all amounts are in cents of the currency.
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EntryFees {
+    /// Paid once by every participant with at least one start.
+    pub participant_fee: u32,
+    /// Paid for every start, finals and swim-offs are free.
+    pub start_fee: u32,
+    /// Added to the start fee of every late entry.
+    pub late_entry_surcharge: u32,
+    /// Three letter code of the currency, e.g. `EUR`.
+    pub currency: String,
+}
+
+impl Default for EntryFees {
+    fn default() -> Self {
+        Self {
+            participant_fee: 0,
+            start_fee: 0,
+            late_entry_surcharge: 0,
+            currency: "EUR".to_string(),
+        }
+    }
+}
+
+/// Money received for the entry fees, either from a participant or for a
+/// whole group.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Payment {
+    pub id: Uuid,
+    pub participant_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    /// Amount in cents.
+    pub amount: u32,
+    pub received_on: NaiveDate,
+    /// Reference of the transfer or receipt.
+    pub reference: String,
+}
+
+/// What every participant and group owes and has paid, amounts are in cents.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Balances {
+    pub currency: String,
+    pub participants: Vec<ParticipantBalance>,
+    pub groups: Vec<GroupBalance>,
+    pub owed: u64,
+    pub paid: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParticipantBalance {
+    pub participant: Participant,
+    /// Starts that are charged, finals and swim-offs are free.
+    pub starts: u32,
+    pub late_entries: u32,
+    pub owed: u64,
+    /// Payments of the participant, without the payments of the group.
+    pub paid: u64,
+    /// Negative if the participant paid too much.
+    pub outstanding: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GroupBalance {
+    pub group: Group,
+    /// Owed by all participants of the group.
+    pub owed: u64,
+    /// Payments for the group and of its participants.
+    pub paid: u64,
+    /// Negative if the group paid too much.
+    pub outstanding: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CompetitionRegistration {
     pub id: Uuid,
     pub participant: Participant,
@@ -83,6 +359,7 @@ pub struct CompetitionRegistration {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ParticipantRegistration {
     pub id: Uuid,
     pub competition: Competition,
@@ -90,6 +367,7 @@ pub struct ParticipantRegistration {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegistrationDetails {
     pub id: Uuid,
     pub participant: Participant,
@@ -98,6 +376,7 @@ pub struct RegistrationDetails {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegistrationResult {
     pub disqualified: bool,
     pub time_millis: u32,
@@ -105,12 +384,14 @@ pub struct RegistrationResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Group {
     pub id: Uuid,
     pub name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GroupDetails {
     #[serde(flatten)]
     pub group: Group,
@@ -118,15 +399,24 @@ pub struct GroupDetails {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CompetitionScore {
     #[serde(flatten)]
     pub participant: Participant,
     pub time: u32,
     pub fina_points: u32,
     pub rank: u32,
+    /// Shares the rank with other participants.
+    pub tied: bool,
+    /// Faster than the best time the participant swam before.
+    pub personal_best: bool,
+    /// Best time of the participant before this event, in the course of the
+    /// event.
+    pub previous_best: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CompetitionScoreboard {
     pub competition: Competition,
     pub scores: Vec<CompetitionScore>,
@@ -135,22 +425,29 @@ pub struct CompetitionScoreboard {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ParticipantGroupScore {
     pub group: Group,
     pub fina_points: u32,
-    pub rank: u32,
+    /// Missing if the participant has fewer starts than required.
+    pub rank: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ParticipantCompetitionScore {
     #[serde(flatten)]
     pub competition: Competition,
     pub time: u32,
     pub fina_points: u32,
     pub rank: u32,
+    /// Faster than the best time the participant swam before.
+    pub personal_best: bool,
+    pub previous_best: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ParticipantScoreboard {
     #[serde(flatten)]
     pub participant: Participant,
@@ -161,15 +458,345 @@ pub struct ParticipantScoreboard {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GroupScore {
     pub participant: Participant,
     pub fina_points: u32,
+    /// Missing if the participant has fewer starts than required.
+    pub rank: Option<u32>,
+    /// Shares the rank with other participants.
+    pub tied: bool,
+}
+
+/// How the results of the participants are scored in the group rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GroupScoring {
+    pub rule: GroupScoringRule,
+    /// Participants with fewer starts are not ranked.
+    pub min_starts: u32,
+}
+
+/// Which results add up to the points of a participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GroupScoringRule {
+    /// All results.
+    #[default]
+    Sum,
+    /// Only the results with the most points.
+    BestResults { count: u32 },
+    /// The best result of each stroke, rewarding swimmers of all strokes.
+    OnePerStroke,
+    /// The average of all results, independent of the number of starts.
+    Average,
+}
+
+/// How tied participants are ranked in the competition and group rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RankingRules {
+    pub shared_ranks: SharedRanks,
+    pub time_precision: TimePrecision,
+    pub group_tie_break: GroupTieBreak,
+}
+
+/// Which rank follows participants sharing a rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SharedRanks {
+    /// The ranks of the tied participants are skipped, e.g. 1, 1, 3.
+    #[default]
+    Skip,
+    /// The next rank follows directly, e.g. 1, 1, 2.
+    Dense,
+}
+
+/// Precision of the times compared in the competition rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TimePrecision {
+    /// Times with the same hundredths are tied, like the official results.
+    #[default]
+    Hundredths,
+    /// Times are compared to the millisecond.
+    Thousandths,
+}
+
+/// How participants with the same points are ordered in the group rankings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GroupTieBreak {
+    /// Participants with the same points share the rank.
+    #[default]
+    None,
+    /// The better single result wins, then the second best and so on.
+    BestSingleResult,
+}
+
+/// How the places in the competitions score for the teams, the groups of
+/// the participants.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamScoring {
+    /// Points for the first, second and following places of a competition.
+    pub placing_points: Vec<u32>,
+    /// Only the best swimmers of a team score in a competition, all if
+    /// missing.
+    pub max_scorers: Option<u32>,
+    /// The places in relays score this many times the placing points.
+    #[serde(default = "TeamScoring::default_relay_weight")]
+    pub relay_weight: u32,
+}
+
+impl TeamScoring {
+    fn default_relay_weight() -> u32 {
+        2
+    }
+}
+
+impl Default for TeamScoring {
+    fn default() -> Self {
+        Self {
+            placing_points: vec![9, 7, 6, 5, 4, 3, 2, 1],
+            max_scorers: None,
+            relay_weight: Self::default_relay_weight(),
+        }
+    }
+}
+
+/// A place in a competition that scored for a team.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamScore {
+    pub participant: Participant,
+    pub competition: Competition,
+    /// Place among the scoring swimmers of the competition.
     pub rank: u32,
+    /// The placing points, weighted for relays.
+    pub points: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamStanding {
+    pub group: Group,
+    pub points: u32,
+    pub rank: u32,
+    /// Shares the rank with other teams.
+    pub tied: bool,
+    pub scores: Vec<TeamScore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GroupScoreboard {
     pub group: Group,
     pub scores: Vec<GroupScore>,
     pub missing_results: Vec<RegistrationDetails>,
 }
+
+/// Length of the pool, times are only compared within the same course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Course {
+    /// 25 meter pool.
+    #[default]
+    Short,
+    /// 50 meter pool.
+    Long,
+}
+
+/// Where the event is in its course, the phase decides which changes are
+/// allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EventPhase {
+    /// Participants can be registered for the competitions.
+    #[default]
+    RegistrationOpen,
+    /// The entry deadline has passed, only late entries are accepted.
+    RegistrationClosed,
+    /// The competitions are swum, only late entries are accepted.
+    MeetInProgress,
+    /// The results are final and the event can't be changed.
+    Finalized,
+}
+
+/// A change of the phase of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PhaseChange {
+    pub phase: EventPhase,
+    pub changed_at: DateTime<Utc>,
+    /// Why a finalized event has been re-opened.
+    pub reason: Option<String>,
+}
+
+/// The current phase of the event with all changes, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EventPhaseDetails {
+    pub phase: EventPhase,
+    pub changes: Vec<PhaseChange>,
+}
+
+/// A part of the event with its own warm-up, the competitions are swum in
+/// the given order.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Session {
+    pub id: Uuid,
+    pub name: String,
+    /// Local time the first heat starts.
+    pub starts_at: NaiveDateTime,
+    /// The warm-up ends when the first heat starts.
+    pub warm_up_minutes: u32,
+    /// Time between the end of a heat and the start of the next one.
+    pub turnaround_seconds: u32,
+    pub competition_ids: Vec<Uuid>,
+}
+
+/// The estimated start times of all sessions of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Schedule {
+    pub sessions: Vec<SessionSchedule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionSchedule {
+    pub session: Session,
+    pub warm_up_at: NaiveDateTime,
+    /// Estimated end of the last heat of the session.
+    pub ends_at: NaiveDateTime,
+    pub competitions: Vec<CompetitionSchedule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CompetitionSchedule {
+    pub competition: Competition,
+    pub starts_at: NaiveDateTime,
+    pub heats: Vec<HeatSchedule>,
+}
+
+/// A heat with its estimated start time.
+///
+/// The heat takes as long as its slowest lane, lanes are estimated with the
+/// result time once swum, else with the entry time or the target time of the
+/// competition.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HeatSchedule {
+    pub number: u32,
+    pub starts_at: NaiveDateTime,
+    pub duration_millis: u32,
+    /// All lanes of the heat have a result.
+    pub finished: bool,
+}
+
+/// A time a participant swam at an earlier event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BestTime {
+    pub stroke: Stroke,
+    pub distance: u32,
+    pub course: Course,
+    pub time: u32,
+    pub swum_on: NaiveDate,
+    /// Name of the event the time was swum at.
+    pub meet: String,
+}
+
+/// Whether a record is kept for the club or for this meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    /// Fastest time of a club swimmer at any meet.
+    Club,
+    /// Fastest time swum at this meet over the years.
+    Meet,
+}
+
+/// Where a record is in the approval workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RecordStatus {
+    /// A new record from a result that waits for the approval.
+    Pending,
+    /// The current record.
+    Approved,
+    Rejected,
+    /// A former record that has been beaten.
+    Superseded,
+}
+
+/// A record time, the records are kept per kind, gender, stroke, distance,
+/// course and age class.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Record {
+    pub id: Uuid,
+    pub kind: RecordKind,
+    pub gender: Gender,
+    pub stroke: Stroke,
+    pub distance: u32,
+    pub course: Course,
+    /// Age the swimmer turns in the year of the swim.
+    pub age_class: u32,
+    pub time: u32,
+    pub first_name: String,
+    pub last_name: String,
+    pub birthday: NaiveDate,
+    pub swum_on: NaiveDate,
+    /// Name of an earlier meet, `None` for records swum at this event.
+    pub meet: Option<String>,
+    /// The result the record has been detected in.
+    pub registration_id: Option<Uuid>,
+    pub status: RecordStatus,
+    pub created_at: DateTime<Utc>,
+    /// When the record has been approved, rejected or superseded.
+    pub decided_at: Option<DateTime<Utc>>,
+    /// The record that replaced this one.
+    pub superseded_by: Option<Uuid>,
+}
+
+/// The documents that are generated from typst templates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentTemplate {
+    Certificate,
+    RegistrationCard,
+    /// Registration cards, eight on an A4 page.
+    RegistrationCardGrid,
+    /// Registration cards printed on labels.
+    RegistrationCardLabel,
+    ResultsProtocol,
+    StartList,
+    Timetable,
+    Invoice,
+    BalanceReport,
+}
+
+/// A template, image or other file used to generate the documents.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Asset {
+    pub name: String,
+    /// The asset has been uploaded for the event and overrides the bundled one.
+    pub customized: bool,
+    /// The asset is bundled with the service and can be reset to it.
+    pub bundled: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
@@ -7,7 +7,7 @@ pub async fn list_competitions() -> Result<Vec<model::Competition>> {
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
@@ -18,12 +18,14 @@ pub async fn add_competition(
     gender: model::Gender,
     stroke: model::Stroke,
     target_time: u32,
+    relay: bool,
 ) -> Result<Uuid> {
     let request = api::AddCompetitionRequest {
         distance,
         gender,
         stroke,
         target_time,
+        relay,
     };
 
     let response = Request::post(&format!("{BASE_URL}/competitions"))
@@ -34,7 +36,7 @@ pub async fn add_competition(
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     let response: api::AddCompetitionResponse = response.json().await.unwrap();
@@ -49,7 +51,7 @@ pub async fn competition_details(competition_id: Uuid) -> Result<model::Competit
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
@@ -63,7 +65,7 @@ pub async fn delete_competition(competition_id: Uuid, force_delete: bool) -> Res
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(())
@@ -78,8 +80,139 @@ pub async fn competition_scoreboard(competition_id: Uuid) -> Result<model::Compe
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
 }
+
+pub async fn competition_eligibility(competition_id: Uuid) -> Result<model::Eligibility> {
+    let response = Request::get(&format!(
+        "{BASE_URL}/competitions/{competition_id}/eligibility"
+    ))
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_competition_eligibility(
+    competition_id: Uuid,
+    eligibility: model::Eligibility,
+) -> Result<()> {
+    let response = Request::put(&format!(
+        "{BASE_URL}/competitions/{competition_id}/eligibility"
+    ))
+    .json(&eligibility)
+    .unwrap()
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn competition_finals(competition_id: Uuid) -> Result<model::Finals> {
+    let response = Request::get(&format!(
+        "{BASE_URL}/competitions/{competition_id}/finals"
+    ))
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_competition_finals(
+    competition_id: Uuid,
+    finalists: u32,
+    alternates: u32,
+) -> Result<Uuid> {
+    let request = api::FinalsRequest {
+        finalists,
+        alternates,
+    };
+
+    let response = Request::put(&format!(
+        "{BASE_URL}/competitions/{competition_id}/finals"
+    ))
+    .json(&request)
+    .unwrap()
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::FinalsResponse = response.json().await.unwrap();
+
+    Ok(response.finals_competition_id)
+}
+
+pub async fn promote_finalists(competition_id: Uuid) -> Result<()> {
+    let response = Request::post(&format!(
+        "{BASE_URL}/competitions/{competition_id}/finals/promote"
+    ))
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn add_swim_off(competition_id: Uuid) -> Result<Uuid> {
+    let response = Request::post(&format!(
+        "{BASE_URL}/competitions/{competition_id}/finals/swim-off"
+    ))
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::AddSwimOffResponse = response.json().await.unwrap();
+
+    Ok(response.competition_id)
+}
+
+pub async fn scratch_finalist(competition_id: Uuid, participant_id: Uuid) -> Result<Option<Uuid>> {
+    let request = api::ScratchFinalistRequest { participant_id };
+
+    let response = Request::post(&format!(
+        "{BASE_URL}/competitions/{competition_id}/finals/scratch"
+    ))
+    .json(&request)
+    .unwrap()
+    .send()
+    .await
+    .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::ScratchFinalistResponse = response.json().await.unwrap();
+
+    Ok(response.promoted_participant_id)
+}
@@ -0,0 +1,255 @@
+use super::*;
+
+pub async fn group_scoring() -> Result<model::GroupScoring> {
+    let response = Request::get(&format!("{BASE_URL}/event/group-scoring"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_group_scoring(scoring: model::GroupScoring) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/group-scoring"))
+        .json(&scoring)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn ranking_rules() -> Result<model::RankingRules> {
+    let response = Request::get(&format!("{BASE_URL}/event/ranking-rules"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_ranking_rules(rules: model::RankingRules) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/ranking-rules"))
+        .json(&rules)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn team_scoring() -> Result<model::TeamScoring> {
+    let response = Request::get(&format!("{BASE_URL}/event/team-scoring"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_team_scoring(scoring: model::TeamScoring) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/team-scoring"))
+        .json(&scoring)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn course() -> Result<model::Course> {
+    let response = Request::get(&format!("{BASE_URL}/event/course"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_course(course: model::Course) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/course"))
+        .json(&course)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn event_info() -> Result<model::EventInfo> {
+    let response = Request::get(&format!("{BASE_URL}/event/info"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_event_info(info: model::EventInfo) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/info"))
+        .json(&info)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn registration_limits() -> Result<model::RegistrationLimits> {
+    let response = Request::get(&format!("{BASE_URL}/event/registration-limits"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_registration_limits(limits: model::RegistrationLimits) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/registration-limits"))
+        .json(&limits)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn event_phase() -> Result<model::EventPhaseDetails> {
+    let response = Request::get(&format!("{BASE_URL}/event/phase"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_event_phase(phase: model::EventPhase) -> Result<()> {
+    let request_body = api::ChangePhaseRequest { phase };
+    let response = Request::put(&format!("{BASE_URL}/event/phase"))
+        .json(&request_body)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn reopen_event(phase: model::EventPhase, reason: String) -> Result<()> {
+    let request_body = api::ReopenEventRequest { phase, reason };
+    let response = Request::post(&format!("{BASE_URL}/event/reopen"))
+        .json(&request_body)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn schedule() -> Result<model::Schedule> {
+    let response = Request::get(&format!("{BASE_URL}/event/schedule"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn entry_fees() -> Result<model::EntryFees> {
+    let response = Request::get(&format!("{BASE_URL}/event/entry-fees"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn set_entry_fees(fees: model::EntryFees) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/event/entry-fees"))
+        .json(&fees)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ pub async fn list_groups() -> Result<Vec<model::Group>> {
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
@@ -23,7 +23,7 @@ pub async fn add_group(name: String) -> Result<Uuid> {
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     let response: api::AddGroupResponse = response.json().await.unwrap();
@@ -37,7 +37,20 @@ pub async fn group_details(group_id: Uuid) -> Result<model::GroupDetails> {
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn team_standings() -> Result<Vec<model::TeamStanding>> {
+    let response = Request::get(&format!("{BASE_URL}/groups/team-standings"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
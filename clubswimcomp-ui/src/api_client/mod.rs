@@ -1,19 +1,49 @@
+//! Client of the service API.
+//!
+//! The requests and responses are the types of `clubswimcomp-types`, the
+//! same types the service generates its `openapi.json` from. The routes are
+//! written out by hand and are not generated from the document.
+
 #![allow(dead_code)]
 
 use chrono::NaiveDate;
 use clubswimcomp_types::{api, model};
-use gloo_net::http::Request;
+use gloo_net::http::{Request, Response};
 use uuid::Uuid;
 
 mod competitions;
+mod event;
 mod groups;
 mod participants;
+mod payments;
+mod records;
 mod registrations;
+mod sessions;
 
 pub use competitions::*;
+pub use event::*;
 pub use groups::*;
 pub use participants::*;
+pub use payments::*;
+pub use records::*;
 pub use registrations::*;
+pub use sessions::*;
 
 const BASE_URL: &str = "http://localhost:3000";
-type Result<T> = core::result::Result<T, String>;
+type Result<T> = core::result::Result<T, api::ErrorResponse>;
+
+/// Read the error document from a failed response.
+///
+/// Responses without one, e.g. from a proxy in between, get the `Unknown` code.
+async fn error_response(response: Response) -> api::ErrorResponse {
+    let status = response.status();
+    match response.json().await {
+        Ok(error) => error,
+        Err(_) => api::ErrorResponse {
+            code: api::ErrorCode::Unknown,
+            message: format!("Request failed with status {status}"),
+            details: Vec::new(),
+            correlation_id: None,
+        },
+    }
+}
@@ -7,7 +7,7 @@ pub async fn list_participants() -> Result<Vec<model::Participant>> {
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
@@ -20,7 +20,7 @@ pub async fn participant_details(participant_id: Uuid) -> Result<model::Particip
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
@@ -49,7 +49,7 @@ pub async fn add_participant(
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     let response: api::AddParticipantResponse = response.json().await.unwrap();
@@ -65,7 +65,7 @@ pub async fn remove_participant(participant_id: Uuid, force_delete: bool) -> Res
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(())
@@ -82,14 +82,22 @@ pub async fn available_competitions_for_registration(
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
 }
 
-pub async fn register_for_competition(participant_id: Uuid, competition_id: Uuid) -> Result<Uuid> {
-    let request_body = api::RegisterForCompetitionBody { competition_id };
+pub async fn register_for_competition(
+    participant_id: Uuid,
+    competition_id: Uuid,
+    late_entry: bool,
+) -> Result<Uuid> {
+    let request_body = api::RegisterForCompetitionBody {
+        competition_id,
+        entry_time_millis: None,
+        late_entry,
+    };
     let response = Request::post(&format!(
         "{BASE_URL}/participants/{participant_id}/registrations"
     ))
@@ -100,7 +108,7 @@ pub async fn register_for_competition(participant_id: Uuid, competition_id: Uuid
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     let response: api::RegisterForCompetitionResponse = response.json().await.unwrap();
@@ -111,17 +119,54 @@ pub async fn register_for_competition(participant_id: Uuid, competition_id: Uuid
 pub async fn unregister_from_competition(
     participant_id: Uuid,
     registration_id: Uuid,
+    late_entry: bool,
 ) -> Result<()> {
     let response = Request::delete(&format!(
-        "{BASE_URL}/participants/{participant_id}/registrations/{registration_id}"
+        "{BASE_URL}/participants/{participant_id}/registrations/{registration_id}?late_entry={late_entry}"
     ))
     .send()
     .await
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(())
 }
+
+pub async fn participant_best_times(participant_id: Uuid) -> Result<Vec<model::BestTime>> {
+    let response = Request::get(&format!("{BASE_URL}/participants/{participant_id}/best-times"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn import_best_times(
+    format: api::BestTimesFormat,
+    content: String,
+) -> Result<api::ImportBestTimesResponse> {
+    let format = match format {
+        api::BestTimesFormat::Csv => "csv",
+        api::BestTimesFormat::Lenex => "lenex",
+    };
+    let response = Request::post(&format!("{BASE_URL}/participants/best-times"))
+        .query([("format", format)])
+        .body(content)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+pub async fn payments() -> Result<Vec<model::Payment>> {
+    let response = Request::get(&format!("{BASE_URL}/payments"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn add_payment(payment: api::AddPaymentRequest) -> Result<Uuid> {
+    let response = Request::post(&format!("{BASE_URL}/payments"))
+        .json(&payment)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::AddPaymentResponse = response.json().await.unwrap();
+    Ok(response.payment_id)
+}
+
+pub async fn remove_payment(payment_id: Uuid) -> Result<()> {
+    let response = Request::delete(&format!("{BASE_URL}/payments/{payment_id}"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn balances() -> Result<model::Balances> {
+    let response = Request::get(&format!("{BASE_URL}/payments/balances"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
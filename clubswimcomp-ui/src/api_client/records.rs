@@ -0,0 +1,56 @@
+use super::*;
+
+pub async fn records() -> Result<Vec<model::Record>> {
+    let response = Request::get(&format!("{BASE_URL}/records"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn add_record(record: api::AddRecordRequest) -> Result<Uuid> {
+    let response = Request::post(&format!("{BASE_URL}/records"))
+        .json(&record)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::AddRecordResponse = response.json().await.unwrap();
+    Ok(response.record_id)
+}
+
+pub async fn approve_record(record_id: Uuid) -> Result<()> {
+    let response = Request::post(&format!("{BASE_URL}/records/{record_id}/approve"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn reject_record(record_id: Uuid) -> Result<()> {
+    let response = Request::post(&format!("{BASE_URL}/records/{record_id}/reject"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
@@ -9,7 +9,7 @@ pub async fn remove_registration_result(registration_id: Uuid) -> Result<()> {
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(())
@@ -22,13 +22,18 @@ pub async fn registration_details(registration_id: Uuid) -> Result<model::Regist
         .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
     Ok(response.json().await.unwrap())
 }
 
-pub async fn add_result(registration_id: Uuid, disqualified: bool, time_millis: u32) -> Result<()> {
+/// Enter a result, returns the records broken with it.
+pub async fn add_result(
+    registration_id: Uuid,
+    disqualified: bool,
+    time_millis: u32,
+) -> Result<Vec<model::Record>> {
     let body = api::EnterResultBody {
         disqualified,
         time_millis,
@@ -43,8 +48,52 @@ pub async fn add_result(registration_id: Uuid, disqualified: bool, time_millis:
     .unwrap();
 
     if !response.ok() {
-        return Err(response.text().await.unwrap());
+        return Err(error_response(response).await);
     }
 
-    Ok(())
+    let response: api::EnterResultResponse = response.json().await.unwrap();
+    Ok(response.new_records)
+}
+
+pub async fn scan_card(code: String) -> Result<api::ScanCardResponse> {
+    let body = api::ScanCardRequest { code };
+    let response = Request::post(&format!("{BASE_URL}/registrations/scan"))
+        .json(&body)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn lookup_registration(
+    params: api::RegistrationLookupParams,
+) -> Result<model::RegistrationDetails> {
+    let mut query = Vec::new();
+    if let Some(card_number) = params.card_number {
+        query.push(("card_number", card_number.to_string()));
+    }
+    if let Some(short_code) = params.short_code {
+        query.push(("short_code", short_code));
+    }
+    if let Some(competition_id) = params.competition_id {
+        query.push(("competition_id", competition_id.to_string()));
+    }
+
+    let response = Request::get(&format!("{BASE_URL}/registrations/lookup"))
+        .query(query)
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
 }
@@ -0,0 +1,58 @@
+use super::*;
+
+pub async fn sessions() -> Result<Vec<model::Session>> {
+    let response = Request::get(&format!("{BASE_URL}/sessions"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(response.json().await.unwrap())
+}
+
+pub async fn add_session(session: api::SessionBody) -> Result<Uuid> {
+    let response = Request::post(&format!("{BASE_URL}/sessions"))
+        .json(&session)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    let response: api::AddSessionResponse = response.json().await.unwrap();
+    Ok(response.session_id)
+}
+
+pub async fn update_session(session_id: Uuid, session: api::SessionBody) -> Result<()> {
+    let response = Request::put(&format!("{BASE_URL}/sessions/{session_id}"))
+        .json(&session)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
+
+pub async fn remove_session(session_id: Uuid) -> Result<()> {
+    let response = Request::delete(&format!("{BASE_URL}/sessions/{session_id}"))
+        .send()
+        .await
+        .unwrap();
+
+    if !response.ok() {
+        return Err(error_response(response).await);
+    }
+
+    Ok(())
+}
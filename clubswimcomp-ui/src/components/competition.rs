@@ -72,6 +72,148 @@ pub fn CompetitionRegistrationsTable(
     }
 }
 
+/// Choose who may register for a competition besides its gender.
+#[component]
+pub fn EligibilityForm(#[prop(into)] competition_id: MaybeSignal<Uuid>) -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (min_age, set_min_age) = create_signal(String::new());
+    let (max_age, set_max_age) = create_signal(String::new());
+    let (min_birth_year, set_min_birth_year) = create_signal(String::new());
+    let (max_birth_year, set_max_birth_year) = create_signal(String::new());
+    let (qualifying_time, set_qualifying_time) = create_signal(String::new());
+    let (group_ids, set_group_ids) = create_signal(Vec::<Uuid>::new());
+
+    let groups = create_local_resource(
+        || (),
+        |_| async move { api_client::list_groups().await.unwrap_or_default() },
+    );
+    let current_eligibility = create_local_resource(competition_id, |competition_id| async move {
+        api_client::competition_eligibility(competition_id).await
+    });
+    create_effect(move |_| {
+        if let Some(Ok(eligibility)) = current_eligibility.get() {
+            let text = |value: Option<String>| value.unwrap_or_default();
+            set_min_age(text(eligibility.min_age.map(|a| a.to_string())));
+            set_max_age(text(eligibility.max_age.map(|a| a.to_string())));
+            set_min_birth_year(text(eligibility.min_birth_year.map(|y| y.to_string())));
+            set_max_birth_year(text(eligibility.max_birth_year.map(|y| y.to_string())));
+            set_qualifying_time(text(eligibility.qualifying_time.map(|millis| {
+                format!(
+                    "{:02}{:02}{:02}",
+                    millis / (60 * 1000),
+                    (millis / 1000) % 60,
+                    (millis / 10) % 100
+                )
+            })));
+            set_group_ids(eligibility.group_ids);
+        }
+    });
+
+    let save_action = create_action(move |eligibility: &model::Eligibility| {
+        let eligibility = eligibility.clone();
+        let competition_id = competition_id.get_untracked();
+        async move { api_client::set_competition_eligibility(competition_id, eligibility).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        let qualifying_time = match qualifying_time().trim() {
+            "" => None,
+            time => {
+                let parts = (time.len() == 6).then(|| {
+                    (
+                        time[0..=1].parse::<u32>(),
+                        time[2..=3].parse::<u32>(),
+                        time[4..=5].parse::<u32>(),
+                    )
+                });
+                let Some((Ok(min), Ok(sec), Ok(hundredths))) = parts else {
+                    set_error_message(Some(
+                        "The qualifying time must be entered as MMSSHH".to_string(),
+                    ));
+                    return;
+                };
+                Some((min * 60 * 1000) + (sec * 1000) + (hundredths * 10))
+            }
+        };
+        save_action.dispatch(model::Eligibility {
+            min_age: min_age().trim().parse().ok(),
+            max_age: max_age().trim().parse().ok(),
+            min_birth_year: min_birth_year().trim().parse().ok(),
+            max_birth_year: max_birth_year().trim().parse().ok(),
+            group_ids: group_ids(),
+            qualifying_time,
+        });
+    };
+
+    let toggle_group = move |group_id: Uuid| {
+        set_group_ids.update(|ids| match ids.iter().position(|id| *id == group_id) {
+            Some(position) => {
+                ids.remove(position);
+            }
+            None => ids.push(group_id),
+        })
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Minimum Age">
+                <input class="input input-bordered" type="number" min=0 placeholder="Any" prop:value=min_age on:input=move |ev| set_min_age(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Maximum Age">
+                <input class="input input-bordered" type="number" min=0 placeholder="Any" prop:value=max_age on:input=move |ev| set_max_age(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Born in or after">
+                <input class="input input-bordered" type="number" placeholder="Any" prop:value=min_birth_year on:input=move |ev| set_min_birth_year(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Born in or before">
+                <input class="input input-bordered" type="number" placeholder="Any" prop:value=max_birth_year on:input=move |ev| set_max_birth_year(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Qualifying Time (MMSSHH)">
+                <input class="input input-bordered" type="text" maxlength=6 placeholder="None" prop:value=qualifying_time on:input=move |ev| set_qualifying_time(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Groups (all if none are selected)">
+                <Transition>
+                    <For each=move || groups().unwrap_or_default() key=|g| g.id let:group>
+                        <label class="label cursor-pointer justify-start gap-4">
+                            <input
+                                type="checkbox"
+                                class="checkbox"
+                                prop:checked=move || group_ids().contains(&group.id)
+                                on:change=move |_| toggle_group(group.id)
+                            />
+                            <span class="label-text">{group.name}</span>
+                        </label>
+                    </For>
+                </Transition>
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The following registrations are checked against the new rules.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Eligibility" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
 #[component]
 pub fn AddCompetitionForm(
     on_competition_added: Callback<Uuid>,
@@ -83,6 +225,7 @@ pub fn AddCompetitionForm(
     let (stroke, set_stroke) = create_signal(model::Stroke::Butterfly);
     let (distance, set_distance) = create_signal(25);
     let (target_time, set_target_time) = create_signal(None);
+    let (relay, set_relay) = create_signal(false);
 
     #[derive(Clone)]
     struct AddCompetitionAction {
@@ -90,6 +233,7 @@ pub fn AddCompetitionForm(
         stroke: model::Stroke,
         gender: model::Gender,
         target_time: u32,
+        relay: bool,
     }
     let add_competition_action = create_action(|input: &AddCompetitionAction| {
         let input = input.clone();
@@ -99,6 +243,7 @@ pub fn AddCompetitionForm(
                 input.gender,
                 input.stroke,
                 input.target_time as _,
+                input.relay,
             )
             .await
         }
@@ -106,7 +251,7 @@ pub fn AddCompetitionForm(
 
     let on_competition_added_handler = move || match add_competition_action.value().get() {
         Some(Ok(competition_id)) => on_competition_added(competition_id),
-        Some(Err(e)) => set_error_message(Some(e)),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
         None => (),
     };
 
@@ -124,6 +269,7 @@ pub fn AddCompetitionForm(
             stroke: stroke(),
             gender: gender(),
             target_time: target_time,
+            relay: relay(),
         };
         add_competition_action.dispatch(input);
     };
@@ -151,6 +297,11 @@ pub fn AddCompetitionForm(
             <FormItem label="Target Time">
                 <InputTime set_time=set_target_time />
             </FormItem>
+
+            <label class="label cursor-pointer justify-start gap-2 max-w-2xl">
+                <input type="checkbox" class="checkbox checkbox-sm" prop:checked=relay on:change=move |ev| set_relay(event_target_checked(&ev)) />
+                <span class="label-text">Relay, each team is registered with one of its swimmers</span>
+            </label>
             {
                 move|| error_message().map(|e| view!{<p class="text text-error">{e}</p>})
             }
@@ -191,3 +342,200 @@ pub fn AddCompetitionDialog(
         </dialog>
     }
 }
+
+/// Prelims and finals of a competition with the promoted swimmers.
+#[component]
+pub fn FinalsSection(#[prop(into)] competition_id: MaybeSignal<Uuid>) -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (finalists, set_finalists) = create_signal(String::from("8"));
+    let (alternates, set_alternates) = create_signal(String::from("2"));
+
+    let finals = create_local_resource(competition_id, |competition_id| async move {
+        api_client::competition_finals(competition_id).await.ok()
+    });
+    create_effect(move |_| {
+        if let Some(Some(finals)) = finals.get() {
+            set_finalists(finals.finalists.to_string());
+            set_alternates(finals.alternates.to_string());
+        }
+    });
+
+    let save_action = create_action(move |(finalists, alternates): &(u32, u32)| {
+        let (finalists, alternates) = (*finalists, *alternates);
+        let competition_id = competition_id.get_untracked();
+        async move {
+            api_client::set_competition_finals(competition_id, finalists, alternates)
+                .await
+                .map(|_| ())
+        }
+    });
+    let promote_action = create_action(move |_: &()| {
+        let competition_id = competition_id.get_untracked();
+        async move { api_client::promote_finalists(competition_id).await }
+    });
+    let swim_off_action = create_action(move |_: &()| {
+        let competition_id = competition_id.get_untracked();
+        async move {
+            api_client::add_swim_off(competition_id)
+                .await
+                .map(|_| ())
+        }
+    });
+    let scratch_action = create_action(move |participant_id: &Uuid| {
+        let participant_id = *participant_id;
+        let competition_id = competition_id.get_untracked();
+        async move {
+            api_client::scratch_finalist(competition_id, participant_id)
+                .await
+                .map(|_| ())
+        }
+    });
+
+    let on_done_handler = move |result: Option<Result<(), api::ErrorResponse>>| match result {
+        Some(Ok(())) => finals.refetch(),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+
+        let (Ok(finalists), Ok(alternates)) = (
+            finalists().trim().parse::<u32>(),
+            alternates().trim().parse::<u32>(),
+        ) else {
+            set_error_message(Some(
+                "The number of finalists and alternates must be entered as numbers".to_string(),
+            ));
+            return;
+        };
+        save_action.dispatch((finalists, alternates));
+    };
+
+    let entry_rows = move || {
+        finals()
+            .flatten()
+            .map(|f| f.entries)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| {
+                let participant_id = e.participant.id;
+                let is_finalist = e.status == model::FinalsEntryStatus::Finalist;
+
+                view! {
+                    <tr>
+                        <td>{e.seed}</td>
+                        <td>{e.participant.short_code}</td>
+                        <td>{e.participant.last_name}</td>
+                        <td>{e.participant.first_name}</td>
+                        <td><values::Time millis=e.time_millis /></td>
+                        <td>
+                            {
+                                match e.status {
+                                    model::FinalsEntryStatus::Finalist => "Finalist",
+                                    model::FinalsEntryStatus::Alternate => "Alternate",
+                                    model::FinalsEntryStatus::Scratched => "Scratched",
+                                }
+                            }
+                        </td>
+                        <td class="w-0">
+                            <Show when=move || is_finalist>
+                                <button
+                                    class="btn btn-xs btn-error"
+                                    on:click=move |_| {
+                                        set_error_message(None);
+                                        scratch_action.dispatch(participant_id)
+                                    }
+                                    disabled=scratch_action.pending()
+                                >
+                                    Scratch
+                                </button>
+                            </Show>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let finals_link = move || {
+        finals()
+            .flatten()
+            .map(|f| format!("/competitions/{}", f.finals_competition_id))
+    };
+
+    view! {
+        {move || on_done_handler(save_action.value().get())}
+        {move || on_done_handler(promote_action.value().get())}
+        {move || on_done_handler(swim_off_action.value().get())}
+        {move || on_done_handler(scratch_action.value().get())}
+
+        <form on:submit=on_submit>
+            <FormItem label="Finalists">
+                <input class="input input-bordered" type="number" min=1 prop:value=finalists on:input=move |ev| set_finalists(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Alternates">
+                <input class="input input-bordered" type="number" min=0 prop:value=alternates on:input=move |ev| set_alternates(event_target_value(&ev)) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Finals" disabled=save_action.pending() />
+            </div>
+        </form>
+
+        <Transition>
+            {
+                move || finals_link().map(|link| view! {
+                    <ActionRow>
+                        <A class="btn btn-sm rounded-full mr-4" href=link>
+                            <phosphor_leptos::MagnifyingGlass />
+                            Finals Competition
+                        </A>
+                        <button
+                            class="btn btn-sm btn-primary rounded-full mr-4"
+                            on:click=move |_| {
+                                set_error_message(None);
+                                promote_action.dispatch(())
+                            }
+                            disabled=promote_action.pending()
+                        >
+                            Promote Finalists
+                        </button>
+                        <button
+                            class="btn btn-sm rounded-full mr-4"
+                            on:click=move |_| {
+                                set_error_message(None);
+                                swim_off_action.dispatch(())
+                            }
+                            disabled=swim_off_action.pending()
+                        >
+                            Add Swim-off
+                        </button>
+                    </ActionRow>
+                })
+            }
+            <div class="overflow-x-auto">
+                <table class="table table-xs">
+                    <thead>
+                        <tr>
+                            <th>Seed</th>
+                            <th>Code</th>
+                            <th>Last Name</th>
+                            <th>First Name</th>
+                            <th>Prelims Time</th>
+                            <th>Status</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || entry_rows()}
+                    </tbody>
+                </table>
+            </div>
+        </Transition>
+    }
+}
@@ -48,6 +48,12 @@ pub fn CompetitionInfo(
                 <Item key="Target Time">
                     <values::Time millis=competition.target_time />
                 </Item>
+                <Item key="Relay">
+                    {if competition.relay { "Yes" } else { "No" }}
+                </Item>
+                <Item key="Round">
+                    <values::CompetitionRound round=competition.round />
+                </Item>
             </Table>
         }
     }
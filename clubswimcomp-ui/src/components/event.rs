@@ -0,0 +1,844 @@
+use leptos::*;
+
+use crate::components::*;
+
+/// Choose how the results are scored in the group rankings of the event.
+#[component]
+pub fn GroupScoringForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (rule, set_rule) = create_signal("sum".to_string());
+    let (result_count, set_result_count) = create_signal(3u32);
+    let (min_starts, set_min_starts) = create_signal(0u32);
+
+    let current_scoring = create_local_resource(
+        || (),
+        |_| async move { api_client::group_scoring().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(scoring)) = current_scoring.get() {
+            let rule = match scoring.rule {
+                model::GroupScoringRule::Sum => "sum",
+                model::GroupScoringRule::BestResults { count } => {
+                    set_result_count(count);
+                    "best_results"
+                }
+                model::GroupScoringRule::OnePerStroke => "one_per_stroke",
+                model::GroupScoringRule::Average => "average",
+            };
+            set_rule(rule.to_string());
+            set_min_starts(scoring.min_starts);
+        }
+    });
+
+    let save_action = create_action(|scoring: &model::GroupScoring| {
+        let scoring = *scoring;
+        async move { api_client::set_group_scoring(scoring).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        let rule = match rule().as_str() {
+            "best_results" => model::GroupScoringRule::BestResults {
+                count: result_count(),
+            },
+            "one_per_stroke" => model::GroupScoringRule::OnePerStroke,
+            "average" => model::GroupScoringRule::Average,
+            _ => model::GroupScoringRule::Sum,
+        };
+        save_action.dispatch(model::GroupScoring {
+            rule,
+            min_starts: min_starts(),
+        });
+    };
+
+    let number_changed = |set: WriteSignal<u32>| {
+        move |ev| set(event_target_value(&ev).parse().unwrap_or_default())
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Points of a Participant">
+                <select class="input input-bordered" prop:value=rule on:change=move |ev| set_rule(event_target_value(&ev))>
+                    <option value="sum">Sum of all results</option>
+                    <option value="best_results">Sum of the best results</option>
+                    <option value="one_per_stroke">Best result of each stroke</option>
+                    <option value="average">Average of all results</option>
+                </select>
+            </FormItem>
+            <Show when=move || rule() == "best_results">
+                <FormItem label="Counted Results">
+                    <input class="input input-bordered" type="number" min=1 prop:value=result_count on:input=number_changed(set_result_count) />
+                </FormItem>
+            </Show>
+            <FormItem label="Minimum Starts to be Ranked">
+                <input class="input input-bordered" type="number" min=0 prop:value=min_starts on:input=number_changed(set_min_starts) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The group rankings use the new scoring.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Scoring" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Choose how tied participants are ranked in the competition and group
+/// rankings.
+#[component]
+pub fn RankingRulesForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (rules, set_rules) = create_signal(model::RankingRules::default());
+
+    let current_rules = create_local_resource(
+        || (),
+        |_| async move { api_client::ranking_rules().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(current_rules)) = current_rules.get() {
+            set_rules(current_rules);
+        }
+    });
+
+    let save_action = create_action(|rules: &model::RankingRules| {
+        let rules = *rules;
+        async move { api_client::set_ranking_rules(rules).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+        save_action.dispatch(rules());
+    };
+
+    let shared_ranks = move || match rules().shared_ranks {
+        model::SharedRanks::Skip => "skip",
+        model::SharedRanks::Dense => "dense",
+    };
+    let on_shared_ranks_change = move |ev| {
+        let shared_ranks = match event_target_value(&ev).as_str() {
+            "dense" => model::SharedRanks::Dense,
+            _ => model::SharedRanks::Skip,
+        };
+        set_rules.update(|rules| rules.shared_ranks = shared_ranks);
+    };
+
+    let time_precision = move || match rules().time_precision {
+        model::TimePrecision::Hundredths => "hundredths",
+        model::TimePrecision::Thousandths => "thousandths",
+    };
+    let on_time_precision_change = move |ev| {
+        let time_precision = match event_target_value(&ev).as_str() {
+            "thousandths" => model::TimePrecision::Thousandths,
+            _ => model::TimePrecision::Hundredths,
+        };
+        set_rules.update(|rules| rules.time_precision = time_precision);
+    };
+
+    let group_tie_break = move || match rules().group_tie_break {
+        model::GroupTieBreak::None => "none",
+        model::GroupTieBreak::BestSingleResult => "best_single_result",
+    };
+    let on_group_tie_break_change = move |ev| {
+        let group_tie_break = match event_target_value(&ev).as_str() {
+            "best_single_result" => model::GroupTieBreak::BestSingleResult,
+            _ => model::GroupTieBreak::None,
+        };
+        set_rules.update(|rules| rules.group_tie_break = group_tie_break);
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Ranks after a Tie">
+                <select class="input input-bordered" prop:value=shared_ranks on:change=on_shared_ranks_change>
+                    <option value="skip">Skipped (1, 1, 3)</option>
+                    <option value="dense">Continued (1, 1, 2)</option>
+                </select>
+            </FormItem>
+            <FormItem label="Compared Times">
+                <select class="input input-bordered" prop:value=time_precision on:change=on_time_precision_change>
+                    <option value="hundredths">Hundredths of a second</option>
+                    <option value="thousandths">Thousandths of a second</option>
+                </select>
+            </FormItem>
+            <FormItem label="Same Points in a Group">
+                <select class="input input-bordered" prop:value=group_tie_break on:change=on_group_tie_break_change>
+                    <option value="none">Share the rank</option>
+                    <option value="best_single_result">Better single result wins</option>
+                </select>
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The rankings use the new rules.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Rules" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Choose how the places in the competitions score for the teams.
+#[component]
+pub fn TeamScoringForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (placing_points, set_placing_points) = create_signal(String::new());
+    let (max_scorers, set_max_scorers) = create_signal(String::new());
+    let (relay_weight, set_relay_weight) = create_signal(String::new());
+
+    let current_scoring = create_local_resource(
+        || (),
+        |_| async move { api_client::team_scoring().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(scoring)) = current_scoring.get() {
+            let points = scoring
+                .placing_points
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>();
+            set_placing_points(points.join(", "));
+            set_max_scorers(scoring.max_scorers.map(|m| m.to_string()).unwrap_or_default());
+            set_relay_weight(scoring.relay_weight.to_string());
+        }
+    });
+
+    let save_action = create_action(|scoring: &model::TeamScoring| {
+        let scoring = scoring.clone();
+        async move { api_client::set_team_scoring(scoring).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        let placing_points = placing_points()
+            .split(',')
+            .map(|points| points.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>();
+        let Ok(placing_points) = placing_points else {
+            set_error_message(Some("The placing points must be numbers separated by commas".to_string()));
+            return;
+        };
+        let max_scorers = match max_scorers().trim() {
+            "" => None,
+            max_scorers => max_scorers.parse().ok(),
+        };
+        let Ok(relay_weight) = relay_weight().trim().parse() else {
+            set_error_message(Some("The relay weight must be a number".to_string()));
+            return;
+        };
+        save_action.dispatch(model::TeamScoring {
+            placing_points,
+            max_scorers,
+            relay_weight,
+        });
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Points for the Places">
+                <input class="input input-bordered" type="text" placeholder="9, 7, 6, 5, 4, 3, 2, 1" prop:value=placing_points on:input=move |ev| set_placing_points(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Scoring Swimmers per Team and Competition">
+                <input class="input input-bordered" type="number" min=1 placeholder="All" prop:value=max_scorers on:input=move |ev| set_max_scorers(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Relays Score the Points Times">
+                <input class="input input-bordered" type="number" min=1 placeholder="2" prop:value=relay_weight on:input=move |ev| set_relay_weight(event_target_value(&ev)) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The team standings use the new scoring.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Scoring" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Set the name and organizer printed in the header of the documents.
+#[component]
+pub fn EventInfoForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (name, set_name) = create_signal(String::new());
+    let (organization, set_organization) = create_signal(String::new());
+
+    let current_info =
+        create_local_resource(|| (), |_| async move { api_client::event_info().await });
+    create_effect(move |_| {
+        if let Some(Ok(info)) = current_info.get() {
+            set_name(info.name);
+            set_organization(info.organization);
+        }
+    });
+
+    let save_action = create_action(|info: &model::EventInfo| {
+        let info = info.clone();
+        async move { api_client::set_event_info(info).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        save_action.dispatch(model::EventInfo {
+            name: name(),
+            organization: organization(),
+        });
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Name">
+                <input class="input input-bordered" type="text" required prop:value=name on:input=move |ev| set_name(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Organization">
+                <input class="input input-bordered" type="text" prop:value=organization on:input=move |ev| set_organization(event_target_value(&ev)) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The documents are printed with the new name.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Event" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Limit how many competitions each participant may register for.
+#[component]
+pub fn RegistrationLimitsForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (max_starts, set_max_starts) = create_signal(String::new());
+
+    let current_limits = create_local_resource(
+        || (),
+        |_| async move { api_client::registration_limits().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(limits)) = current_limits.get() {
+            set_max_starts(
+                limits
+                    .max_individual_starts
+                    .map(|m| m.to_string())
+                    .unwrap_or_default(),
+            );
+        }
+    });
+
+    let save_action = create_action(|limits: &model::RegistrationLimits| {
+        let limits = limits.clone();
+        async move { api_client::set_registration_limits(limits).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        let max_individual_starts = match max_starts().trim() {
+            "" => None,
+            max_starts => max_starts.parse().ok(),
+        };
+        save_action.dispatch(model::RegistrationLimits {
+            max_individual_starts,
+        });
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Individual Starts per Participant">
+                <input class="input input-bordered" type="number" min=1 placeholder="Unlimited" prop:value=max_starts on:input=move |ev| set_max_starts(event_target_value(&ev)) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The following registrations are checked against the new limits.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Limits" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Parse an amount like `3.50` or `3,50` into cents.
+pub fn parse_cents(value: &str) -> Option<u32> {
+    let value = value.trim().replace(',', ".");
+    let (units, fraction) = value.split_once('.').unwrap_or((&value, ""));
+    if fraction.len() > 2 {
+        return None;
+    }
+    let units: u32 = units.parse().ok()?;
+    let fraction: u32 = format!("{fraction:0<2}").parse().ok()?;
+    units.checked_mul(100)?.checked_add(fraction)
+}
+
+fn format_cents(cents: u32) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Set the fees of the entries, amounts are entered in the currency.
+#[component]
+pub fn EntryFeesForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (participant_fee, set_participant_fee) = create_signal(String::new());
+    let (start_fee, set_start_fee) = create_signal(String::new());
+    let (late_entry_surcharge, set_late_entry_surcharge) = create_signal(String::new());
+    let (currency, set_currency) = create_signal(String::new());
+
+    let current_fees = create_local_resource(
+        || (),
+        |_| async move { api_client::entry_fees().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(fees)) = current_fees.get() {
+            set_participant_fee(format_cents(fees.participant_fee));
+            set_start_fee(format_cents(fees.start_fee));
+            set_late_entry_surcharge(format_cents(fees.late_entry_surcharge));
+            set_currency(fees.currency);
+        }
+    });
+
+    let save_action = create_action(|fees: &model::EntryFees| {
+        let fees = fees.clone();
+        async move { api_client::set_entry_fees(fees).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+
+        let (Some(participant_fee), Some(start_fee), Some(late_entry_surcharge)) = (
+            parse_cents(&participant_fee()),
+            parse_cents(&start_fee()),
+            parse_cents(&late_entry_surcharge()),
+        ) else {
+            set_error_message(Some("Enter the fees as amounts like 3.50.".to_string()));
+            return;
+        };
+        save_action.dispatch(model::EntryFees {
+            participant_fee,
+            start_fee,
+            late_entry_surcharge,
+            currency: currency(),
+        });
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Participant Fee">
+                <input class="input input-bordered" type="text" inputmode="decimal" prop:value=participant_fee on:input=move |ev| set_participant_fee(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Fee per Start">
+                <input class="input input-bordered" type="text" inputmode="decimal" prop:value=start_fee on:input=move |ev| set_start_fee(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Late Entry Surcharge">
+                <input class="input input-bordered" type="text" inputmode="decimal" prop:value=late_entry_surcharge on:input=move |ev| set_late_entry_surcharge(event_target_value(&ev)) />
+            </FormItem>
+            <FormItem label="Currency">
+                <input class="input input-bordered" type="text" maxlength=3 placeholder="EUR" prop:value=currency on:input=move |ev| set_currency(event_target_value(&ev)) />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The amounts owed are calculated with the new fees.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Fees" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+fn phase_label(phase: model::EventPhase) -> &'static str {
+    match phase {
+        model::EventPhase::RegistrationOpen => "Registration open",
+        model::EventPhase::RegistrationClosed => "Registration closed",
+        model::EventPhase::MeetInProgress => "Meet in progress",
+        model::EventPhase::Finalized => "Finalized",
+    }
+}
+
+fn phase_value(phase: model::EventPhase) -> &'static str {
+    match phase {
+        model::EventPhase::RegistrationOpen => "registration_open",
+        model::EventPhase::RegistrationClosed => "registration_closed",
+        model::EventPhase::MeetInProgress => "meet_in_progress",
+        model::EventPhase::Finalized => "finalized",
+    }
+}
+
+fn parse_phase(value: &str) -> model::EventPhase {
+    match value {
+        "registration_closed" => model::EventPhase::RegistrationClosed,
+        "meet_in_progress" => model::EventPhase::MeetInProgress,
+        "finalized" => model::EventPhase::Finalized,
+        _ => model::EventPhase::RegistrationOpen,
+    }
+}
+
+/// Move the event through its phases, a finalized event is read-only until
+/// it is re-opened with a reason.
+#[component]
+pub fn EventPhaseForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+
+    let (phase, set_phase) = create_signal(model::EventPhase::default());
+    let (reason, set_reason) = create_signal(String::new());
+
+    let details = create_local_resource(
+        || (),
+        |_| async move { api_client::event_phase().await },
+    );
+    let finalized = move || {
+        details
+            .get()
+            .and_then(|d| d.ok())
+            .is_some_and(|d| d.phase == model::EventPhase::Finalized)
+    };
+    create_effect(move |_| {
+        if let Some(Ok(details)) = details.get() {
+            let phase = match details.phase {
+                // A re-opened meet usually continues where it stopped
+                model::EventPhase::Finalized => model::EventPhase::MeetInProgress,
+                phase => phase,
+            };
+            set_phase(phase);
+        }
+    });
+
+    let save_action = create_action(|(phase, reason): &(model::EventPhase, Option<String>)| {
+        let (phase, reason) = (*phase, reason.clone());
+        async move {
+            match reason {
+                Some(reason) => api_client::reopen_event(phase, reason).await,
+                None => api_client::set_event_phase(phase).await,
+            }
+        }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => {
+            set_reason(String::new());
+            details.refetch();
+        }
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        let reason = finalized().then(reason);
+        save_action.dispatch((phase(), reason));
+    };
+
+    let phase_options = move || {
+        let phases = [
+            model::EventPhase::RegistrationOpen,
+            model::EventPhase::RegistrationClosed,
+            model::EventPhase::MeetInProgress,
+            model::EventPhase::Finalized,
+        ];
+        phases
+            .into_iter()
+            .filter(|p| !finalized() || *p != model::EventPhase::Finalized)
+            .map(|p| view! { <option value=phase_value(p)>{phase_label(p)}</option> })
+            .collect_view()
+    };
+
+    let changes = move || {
+        details
+            .get()
+            .and_then(|d| d.ok())
+            .map(|d| d.changes)
+            .unwrap_or_default()
+            .into_iter()
+            .rev()
+            .map(|c| {
+                view! {
+                    <tr>
+                        <td>{c.changed_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                        <td>{phase_label(c.phase)}</td>
+                        <td>{c.reason.unwrap_or_default()}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <p class="text mb-2">
+                "Current phase: "
+                <span class="font-bold">
+                    {move || details.get().and_then(|d| d.ok()).map(|d| phase_label(d.phase))}
+                </span>
+            </p>
+            <FormItem label="Phase">
+                <select class="input input-bordered" prop:value=move || phase_value(phase()) on:change=move |ev| set_phase(parse_phase(&event_target_value(&ev)))>
+                    {phase_options}
+                </select>
+            </FormItem>
+            <Show when=finalized>
+                <FormItem label="Reason for Re-opening">
+                    <input class="input input-bordered" type="text" placeholder="Wrong time in heat 3" prop:value=reason on:input=move |ev| set_reason(event_target_value(&ev)) />
+                </FormItem>
+            </Show>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value=move || if finalized() { "Re-open Event" } else { "Change Phase" } disabled=save_action.pending() />
+            </div>
+        </form>
+
+        <div class="overflow-x-auto mt-4">
+            <table class="table table-xs">
+                <thead>
+                    <tr>
+                        <th>Changed At</th>
+                        <th>Phase</th>
+                        <th>Reason</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {changes}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Choose the pool of the event, the personal bests are compared with the
+/// earlier times in the same course.
+#[component]
+pub fn CourseForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+    let (saved, set_saved) = create_signal(false);
+
+    let (course, set_course) = create_signal(model::Course::default());
+
+    let current_course = create_local_resource(
+        || (),
+        |_| async move { api_client::course().await },
+    );
+    create_effect(move |_| {
+        if let Some(Ok(current_course)) = current_course.get() {
+            set_course(current_course);
+        }
+    });
+
+    let save_action = create_action(|course: &model::Course| {
+        let course = *course;
+        async move { api_client::set_course(course).await }
+    });
+
+    let on_saved_handler = move || match save_action.value().get() {
+        Some(Ok(())) => set_saved(true),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+        set_saved(false);
+        save_action.dispatch(course());
+    };
+
+    let course_value = move || match course() {
+        model::Course::Short => "short",
+        model::Course::Long => "long",
+    };
+    let on_course_change = move |ev| {
+        let course = match event_target_value(&ev).as_str() {
+            "long" => model::Course::Long,
+            _ => model::Course::Short,
+        };
+        set_course(course);
+    };
+
+    view! {
+        {on_saved_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Pool">
+                <select class="input input-bordered" prop:value=course_value on:change=on_course_change>
+                    <option value="short">Short course (25 m)</option>
+                    <option value="long">Long course (50 m)</option>
+                </select>
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            <Show when=saved>
+                <p class="text text-success">The personal bests are compared in the new course.</p>
+            </Show>
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Save Course" disabled=save_action.pending() />
+            </div>
+        </form>
+    }
+}
+
+/// Import the times the swimmers swam at earlier events from a CSV or Lenex
+/// file.
+#[component]
+pub fn BestTimesImportForm() -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+
+    let (format, set_format) = create_signal("csv".to_string());
+    let (content, set_content) = create_signal(String::new());
+
+    let import_action = create_action(|(format, content): &(api::BestTimesFormat, String)| {
+        let (format, content) = (*format, content.clone());
+        async move { api_client::import_best_times(format, content).await }
+    });
+
+    let imported_message = move || match import_action.value().get() {
+        Some(Ok(response)) => Some(format!(
+            "Imported {} times, {} have already been imported before.",
+            response.imported, response.duplicates
+        )),
+        _ => None,
+    };
+    let on_imported_handler = move || {
+        if let Some(Err(e)) = import_action.value().get() {
+            set_error_message(Some(describe_error(&e)));
+        }
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+
+        let format = match format().as_str() {
+            "lenex" => api::BestTimesFormat::Lenex,
+            _ => api::BestTimesFormat::Csv,
+        };
+        import_action.dispatch((format, content()));
+    };
+
+    view! {
+        {on_imported_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Format">
+                <select class="input input-bordered" prop:value=format on:change=move |ev| set_format(event_target_value(&ev))>
+                    <option value="csv">CSV</option>
+                    <option value="lenex">Lenex (.lef or .lxf)</option>
+                </select>
+            </FormItem>
+            <FormItem label="Times">
+                <textarea
+                    class="textarea textarea-bordered font-mono h-40"
+                    placeholder="first_name,last_name,birthday,stroke,distance,course,time,date,meet"
+                    prop:value=content
+                    on:input=move |ev| set_content(event_target_value(&ev))
+                />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+            {
+                move || imported_message().map(|m| view!{<p class="text text-success">{m}</p>})
+            }
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Import Times" disabled=import_action.pending() />
+            </div>
+        </form>
+    }
+}
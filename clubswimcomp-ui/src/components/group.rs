@@ -19,7 +19,7 @@ pub fn AddGroupForm(on_group_added: Callback<Uuid>, on_cancel: Callback<()>) ->
 
     let on_group_added_handler = move || match add_group_action.value().get() {
         Some(Ok(group_id)) => on_group_added(group_id),
-        Some(Err(e)) => set_error_message(Some(e)),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
         None => (),
     };
 
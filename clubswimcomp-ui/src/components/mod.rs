@@ -1,27 +1,90 @@
-use clubswimcomp_types::model;
+use clubswimcomp_types::{api, model};
 use leptos::*;
 use leptos_router::*;
 
 mod competition;
+mod event;
 pub mod data;
 mod group;
 mod page;
 mod participant;
+mod payment;
+mod print;
+mod record;
 mod registrations;
 mod scanner;
+mod schedule;
 pub mod tables;
 pub mod values;
 
 pub use competition::*;
+pub use event::*;
 pub use group::*;
 pub use page::*;
 pub use participant::*;
+pub use payment::*;
+pub use print::*;
+pub use record::*;
 pub use registrations::*;
 pub use scanner::*;
+pub use schedule::*;
 use uuid::Uuid;
 
 use crate::api_client;
 
+/// Message to show the user for an error returned by the API.
+pub fn describe_error(err: &api::ErrorResponse) -> String {
+    use api::ErrorCode::*;
+
+    let message = match err.code {
+        ParticipantHasRegistrations => {
+            "The participant is still registered for competitions. Remove the registrations first."
+        }
+        CompetitionHasRegistrations => {
+            "There are still registrations for the competition. Remove them first."
+        }
+        AlreadyRegistered => "The participant is already registered for this competition.",
+        ResultAlreadyExists => "There is already a result for this registration.",
+        InvalidCardCode => "The scanned code is not from a registration card.",
+        CardOfOtherEvent => "The registration card belongs to another event.",
+        InvalidCardSignature => {
+            "The registration card has been altered or forged. Enter the result manually."
+        }
+        RecordNotPending => "The record has already been approved or rejected.",
+        NotFasterThanRecord => "The time does not beat the current record.",
+        RegistrationClosed => {
+            "The registration is closed. Mark the change as late entry to accept it anyway."
+        }
+        EventFinalized => "The event is finalized. Re-open it on the event page to make changes.",
+        EventNotFinalized => "Only finalized events can be re-opened.",
+        CompetitionAlreadyScheduled => {
+            "A competition is already swum in another session. Remove it there first."
+        }
+        NotPrelims => "Only competitions swum as timed final or prelims can have finals.",
+        PrelimsNotFinished => "Not every swimmer of the prelims has a result yet.",
+        FinalistsAlreadyPromoted => "The finalists have already been promoted.",
+        FinalistsNotPromoted => "Promote the finalists first.",
+        SwimOffRequired => {
+            "Swimmers are tied for the last place in the finals. Add a swim-off to decide the tie."
+        }
+        SwimOffNotFinished => "Enter the results of the swim-off first.",
+        NoTieAtCutoff => "No swimmers are tied for the last place in the finals.",
+        NotAFinalist => "The participant is not a finalist.",
+        PaymentDoesNotExist => "The payment has already been removed.",
+        ParticipantHasPayments => {
+            "Payments of the participant are recorded. Remove the payments first."
+        }
+        InternalError | PdfGenerationFailed | Unknown => {
+            return match err.correlation_id {
+                Some(correlation_id) => format!("{} (Reference: {correlation_id})", err.message),
+                None => err.message.clone(),
+            };
+        }
+        _ => return err.message.clone(),
+    };
+    message.to_string()
+}
+
 #[component]
 pub fn FormItem(#[prop(into)] label: String, children: Children) -> impl IntoView {
     view! {
@@ -159,6 +222,24 @@ pub fn InputDistance(#[prop(into)] set_distance: WriteSignal<u32>) -> impl IntoV
     }
 }
 
+/// Layout of the registration cards, set as the query of the card links.
+#[component]
+pub fn InputCardLayout(#[prop(into)] set_layout: WriteSignal<String>) -> impl IntoView {
+    let input_changed = move |ev| {
+        set_layout(event_target_value(&ev));
+    };
+
+    view! {
+        <select class="input input-bordered input-sm mr-4" on:change=input_changed>
+            <option value="layout=a6">A6 Cards</option>
+            <option value="layout=a4_grid">8 Cards per A4 Page</option>
+            <option value="layout=label&label_width_mm=62&label_height_mm=29">Labels 62 x 29 mm</option>
+            <option value="layout=label&label_width_mm=89&label_height_mm=36">Labels 89 x 36 mm</option>
+            <option value="layout=label&label_width_mm=100&label_height_mm=50">Labels 100 x 50 mm</option>
+        </select>
+    }
+}
+
 #[component]
 pub fn InputDisqualified(#[prop(into)] set_disqualified: WriteSignal<bool>) -> impl IntoView {
     let input_changed = move |ev| {
@@ -220,6 +301,37 @@ pub fn InputDate(#[prop(into)] set_date: WriteSignal<Option<chrono::NaiveDate>>)
     }
 }
 
+#[component]
+pub fn InputCompetition(
+    #[prop(into)] set_competition_id: WriteSignal<Option<Uuid>>,
+) -> impl IntoView {
+    let input_changed = move |ev| {
+        let value = event_target_value(&ev);
+        let competition_id = Uuid::parse_str(&value).ok();
+        set_competition_id(competition_id);
+    };
+
+    let available_competitions = create_resource(
+        || (),
+        |_| async move { api_client::list_competitions().await.unwrap() },
+    );
+
+    view! {
+        <select class="input input-bordered" on:input=input_changed>
+            <option selected></option>
+            <Transition>
+                <For each=move || available_competitions().unwrap_or_default() key=|c| c.id let:competition>
+                    <option value={competition.id.to_string()}>
+                        <values::Distance distance=competition.distance />" "
+                        <values::Stroke stroke=competition.stroke />" "
+                        <values::Gender gender=competition.gender />
+                    </option>
+                </For>
+            </Transition>
+        </select>
+    }
+}
+
 #[component]
 pub fn InputGroup(#[prop(into)] set_group_id: WriteSignal<Option<Uuid>>) -> impl IntoView {
     let input_changed = move |ev| {
@@ -7,27 +7,35 @@ use crate::components::*;
 pub fn ParticipantRegistrationsTable(
     #[prop(into)] registrations: MaybeSignal<Vec<model::ParticipantRegistration>>,
     #[prop(into)] participant_id: MaybeSignal<Uuid>,
+    /// Withdraw registrations after the registration has closed.
+    #[prop(into, optional)] late_entry: MaybeSignal<bool>,
     #[prop(into, optional)] on_unregister: Option<Callback<()>>,
     #[prop(into, optional)] on_result_removed: Option<Callback<()>>,
     #[prop(into, optional)] on_result_added: Option<Callback<()>>,
 ) -> impl IntoView {
     let (error_msg, set_error_msg) = create_signal(None);
 
-    let unregister_action = create_action(|input: &(Uuid, Uuid)| {
-        let participant_id = input.0;
-        let registration_id = input.1;
+    let unregister_action = create_action(|input: &(Uuid, Uuid, bool)| {
+        let (participant_id, registration_id, late_entry) = *input;
         async move {
-            api_client::unregister_from_competition(participant_id, registration_id)
+            api_client::unregister_from_competition(participant_id, registration_id, late_entry)
                 .await
-                .unwrap();
         }
     });
 
     let on_unregistered_effect = create_memo(move |_| {
-        if !unregister_action.pending().get() && unregister_action.value().get().is_some() {
-            if let Some(on_unregister) = on_unregister {
-                on_unregister(());
+        if unregister_action.pending().get() {
+            return;
+        }
+
+        match unregister_action.value().get() {
+            Some(Ok(())) => {
+                if let Some(on_unregister) = on_unregister {
+                    on_unregister(());
+                }
             }
+            Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
+            None => (),
         }
     });
 
@@ -48,12 +56,19 @@ pub fn ParticipantRegistrationsTable(
                     on_result_removed(());
                 }
             }
-            Some(Err(err)) => set_error_msg(Some(err)),
+            Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
             None => return, // Action is still running
         };
     });
 
     let result_dialog_id = create_rw_signal(None);
+    let (new_records, set_new_records) = create_signal(Vec::new());
+    let on_added_callback = Callback::new(move |records| {
+        set_new_records(records);
+        if let Some(on_result_added) = on_result_added {
+            on_result_added(());
+        }
+    });
 
     let rows = move || {
         registrations()
@@ -93,7 +108,7 @@ pub fn ParticipantRegistrationsTable(
 
                         <cells::Button
                             action_type=ActionType::Error
-                            on:click=move |_| unregister_action.dispatch((participant_id(), r.id))
+                            on:click=move |_| unregister_action.dispatch((participant_id(), r.id, late_entry()))
                         >
                             "Unregister"
                         </cells::Button>
@@ -106,9 +121,10 @@ pub fn ParticipantRegistrationsTable(
     view! {
         {on_unregistered_effect}
         {on_remove_result_effect}
-        <AddResultDialog registration_id=result_dialog_id on_result_added=on_result_added />
+        <AddResultDialog registration_id=result_dialog_id on_result_added=on_added_callback />
 
         { move || error_msg().map(|err| view! { <p class="text-error">{err}</p>}) }
+        <NewRecordsAlert new_records />
         <div class="overflow-x-auto">
             <table class="table table-xs">
                 <thead>
@@ -131,6 +147,8 @@ pub fn ParticipantRegistrationsTable(
 #[component]
 pub fn AvailableCompetitionsTable(
     #[prop(into)] participant_id: Uuid,
+    /// Accept registrations after the registration has closed.
+    #[prop(into, optional)] late_entry: MaybeSignal<bool>,
     #[prop(default = None, into)] on_registered: Option<Callback<Uuid>>,
 ) -> impl IntoView {
     let (error_msg, set_error_msg) = create_signal(None);
@@ -151,11 +169,17 @@ pub fn AvailableCompetitionsTable(
     struct RegisterAction {
         participant_id: Uuid,
         competition_id: Uuid,
+        late_entry: bool,
     }
     let register_action = create_action(|input: &RegisterAction| {
         let input = input.clone();
         async move {
-            api_client::register_for_competition(input.participant_id, input.competition_id).await
+            api_client::register_for_competition(
+                input.participant_id,
+                input.competition_id,
+                input.late_entry,
+            )
+            .await
         }
     });
 
@@ -167,7 +191,13 @@ pub fn AvailableCompetitionsTable(
                     on_registered(registration_id);
                 }
             }
-            Some(Err(e)) => set_error_msg(Some(e)),
+            Some(Err(e)) => {
+                if e.code == api::ErrorCode::AlreadyRegistered {
+                    // The list is outdated, the competition is no longer available
+                    available_competitions.refetch();
+                }
+                set_error_msg(Some(describe_error(&e)))
+            }
             None => return,
         };
 
@@ -178,6 +208,7 @@ pub fn AvailableCompetitionsTable(
         let input = RegisterAction {
             participant_id,
             competition_id,
+            late_entry: late_entry(),
         };
         register_action.dispatch(input);
     });
@@ -265,7 +296,7 @@ pub fn AddParticipantForm(
 
     let on_participant_added_handler = move || match add_participant_action.value().get() {
         Some(Ok(participant_id)) => on_participant_added(participant_id),
-        Some(Err(e)) => set_error_message(Some(e)),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
         None => (),
     };
 
@@ -0,0 +1,239 @@
+use leptos::*;
+
+use crate::components::*;
+
+/// Who a payment has been received from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Payer {
+    Participant(Uuid),
+    Group(Uuid),
+}
+
+fn parse_payer(value: &str) -> Option<Payer> {
+    let (kind, id) = value.split_once(':')?;
+    let id = Uuid::parse_str(id).ok()?;
+    match kind {
+        "participant" => Some(Payer::Participant(id)),
+        "group" => Some(Payer::Group(id)),
+        _ => None,
+    }
+}
+
+/// Record a payment of a participant or for a whole group.
+#[component]
+pub fn AddPaymentForm(#[prop(into)] on_payment_added: Callback<Uuid>) -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+
+    let (payer, set_payer) = create_signal(None);
+    let (amount, set_amount) = create_signal(String::new());
+    let (received_on, set_received_on) = create_signal(None);
+    let (reference, set_reference) = create_signal(String::new());
+
+    let groups = create_local_resource(
+        || (),
+        |_| async move { api_client::list_groups().await.unwrap() },
+    );
+    let participants = create_local_resource(
+        || (),
+        |_| async move { api_client::list_participants().await.unwrap() },
+    );
+
+    let add_payment_action = create_action(|payment: &api::AddPaymentRequest| {
+        let payment = payment.clone();
+        async move { api_client::add_payment(payment).await }
+    });
+
+    let on_payment_added_handler = move || match add_payment_action.value().get() {
+        Some(Ok(payment_id)) => {
+            set_amount(String::new());
+            set_reference(String::new());
+            on_payment_added(payment_id)
+        }
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+
+        let Some(payer) = payer() else {
+            set_error_message(Some("Choose who paid.".to_string()));
+            return;
+        };
+        let Some(amount) = parse_cents(&amount()) else {
+            set_error_message(Some("Enter the amount like 12.50.".to_string()));
+            return;
+        };
+        let Some(received_on) = received_on() else {
+            set_error_message(Some("Enter the day the payment has been received.".to_string()));
+            return;
+        };
+
+        let (participant_id, group_id) = match payer {
+            Payer::Participant(id) => (Some(id), None),
+            Payer::Group(id) => (None, Some(id)),
+        };
+        add_payment_action.dispatch(api::AddPaymentRequest {
+            participant_id,
+            group_id,
+            amount,
+            received_on,
+            reference: reference(),
+        });
+    };
+
+    view! {
+        {on_payment_added_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Paid by">
+                <select class="input input-bordered" on:input=move |ev| set_payer(parse_payer(&event_target_value(&ev)))>
+                    <option selected></option>
+                    <Transition>
+                        <optgroup label="Groups">
+                            <For each=move || groups().unwrap_or_default() key=|g| g.id let:group>
+                                <option value={format!("group:{}", group.id)}>{group.name}</option>
+                            </For>
+                        </optgroup>
+                        <optgroup label="Participants">
+                            <For each=move || participants().unwrap_or_default() key=|p| p.id let:participant>
+                                <option value={format!("participant:{}", participant.id)}>
+                                    {format!("{}, {}", participant.last_name, participant.first_name)}
+                                </option>
+                            </For>
+                        </optgroup>
+                    </Transition>
+                </select>
+            </FormItem>
+
+            <FormItem label="Amount">
+                <input class="input input-bordered" type="text" inputmode="decimal" placeholder="12.50" prop:value=amount on:input=move |ev| set_amount(event_target_value(&ev)) />
+            </FormItem>
+
+            <FormItem label="Received on">
+                <InputDate set_date=set_received_on />
+            </FormItem>
+
+            <FormItem label="Reference">
+                <input class="input input-bordered" type="text" prop:value=reference on:input=move |ev| set_reference(event_target_value(&ev)) />
+            </FormItem>
+
+            <div class="mt-6">
+                <ActionButton action_type=ActionType::Primary>
+                    Add Payment
+                </ActionButton>
+            </div>
+            {move || error_message().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+        </form>
+    }
+}
+
+/// What every group owes and has paid, with the invoice of each group.
+#[component]
+pub fn GroupBalancesTable(balances: model::Balances) -> impl IntoView {
+    let currency = balances.currency.clone();
+
+    view! {
+        <table class="table table-sm">
+            <thead>
+                <tr>
+                    <th>Group</th>
+                    <th>Owed</th>
+                    <th>Paid</th>
+                    <th>Outstanding</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {
+                    balances.groups.into_iter().map(|g| {
+                        let invoice_url = format!("http://localhost:3000/groups/{}/invoice", g.group.id);
+                        view! {
+                            <tr class:text-error=g.outstanding > 0>
+                                <td>{g.group.name}</td>
+                                <td><values::Amount cents=g.owed as i64 currency=currency.clone() /></td>
+                                <td><values::Amount cents=g.paid as i64 currency=currency.clone() /></td>
+                                <td><values::Amount cents=g.outstanding currency=currency.clone() /></td>
+                                <td><PrintButton url=invoice_url label="Invoice" /></td>
+                            </tr>
+                        }
+                    }).collect_view()
+                }
+            </tbody>
+        </table>
+    }
+}
+
+/// What every participant with a charged start owes and has paid.
+#[component]
+pub fn ParticipantBalancesTable(balances: model::Balances) -> impl IntoView {
+    let currency = balances.currency.clone();
+
+    view! {
+        <table class="table table-sm">
+            <thead>
+                <tr>
+                    <th>Name</th>
+                    <th>Starts</th>
+                    <th>Late Entries</th>
+                    <th>Owed</th>
+                    <th>Paid</th>
+                </tr>
+            </thead>
+            <tbody>
+                {
+                    balances.participants.into_iter().filter(|p| p.starts > 0 || p.paid > 0).map(|p| view! {
+                        <tr>
+                            <td>{format!("{}, {}", p.participant.last_name, p.participant.first_name)}</td>
+                            <td>{p.starts}</td>
+                            <td>{p.late_entries}</td>
+                            <td><values::Amount cents=p.owed as i64 currency=currency.clone() /></td>
+                            <td><values::Amount cents=p.paid as i64 currency=currency.clone() /></td>
+                        </tr>
+                    }).collect_view()
+                }
+            </tbody>
+        </table>
+    }
+}
+
+/// All payments in the order they have been received.
+#[component]
+pub fn PaymentsTable(
+    payments: Vec<model::Payment>,
+    #[prop(into)] currency: String,
+    #[prop(into)] on_remove: Callback<Uuid>,
+) -> impl IntoView {
+    view! {
+        <table class="table table-sm">
+            <thead>
+                <tr>
+                    <th>Received on</th>
+                    <th>Reference</th>
+                    <th>Amount</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {
+                    payments.into_iter().map(|payment| {
+                        let payment_id = payment.id;
+                        view! {
+                            <tr>
+                                <td><values::Date date=payment.received_on /></td>
+                                <td>{payment.reference}</td>
+                                <td><values::Amount cents=payment.amount as i64 currency=currency.clone() /></td>
+                                <td>
+                                    <button class="btn btn-xs btn-ghost" on:click=move |_| on_remove(payment_id)>
+                                        <phosphor_leptos::Trash />
+                                    </button>
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view()
+                }
+            </tbody>
+        </table>
+    }
+}
@@ -0,0 +1,86 @@
+use leptos::*;
+
+/// Append query parameters to a document link.
+fn with_query(url: &str, query: &str) -> String {
+    match url.contains('?') {
+        true => format!("{url}&{query}"),
+        false => format!("{url}?{query}"),
+    }
+}
+
+/// Button to print a document, shows the pages of the document as thumbnails
+/// before the PDF is opened.
+#[component]
+pub fn PrintButton(
+    #[prop(into)] url: MaybeSignal<String>,
+    #[prop(into)] label: String,
+) -> impl IntoView {
+    let show = create_rw_signal(false);
+    let page = create_rw_signal(1u32);
+    let page_missing = create_rw_signal(false);
+
+    let document_url = url.clone();
+    let preview_url =
+        Signal::derive(move || with_query(&document_url(), &format!("format=png&page={}", page())));
+    let show_page = move |number: u32| {
+        page.set(number);
+        page_missing.set(false);
+    };
+
+    let title = label.clone();
+    view! {
+        <button
+            class="btn btn-sm btn-primary rounded-full mr-4"
+            on:click=move |_| {
+                show_page(1);
+                show.set(true);
+            }
+        >
+            <phosphor_leptos::Printer />
+            {label}
+        </button>
+        <dialog class="modal bg-black bg-opacity-30" autofocus open=show>
+            <div class="modal-box max-w-3xl">
+                <h3 class="text-xl text-black mb-4">{title}</h3>
+                <Show
+                    when=move || show() && !page_missing()
+                    fallback=move || view! {
+                        <p class="text-sm text-gray-800">There are no more pages in this document.</p>
+                    }
+                >
+                    <img
+                        class="w-full border shadow"
+                        src=preview_url
+                        on:error=move |_| page_missing.set(true)
+                    />
+                </Show>
+                <div class="flex items-center justify-center mt-4">
+                    <button
+                        class="btn btn-sm btn-ghost"
+                        disabled=move || page() <= 1
+                        on:click=move |_| show_page(page() - 1)
+                    >
+                        <phosphor_leptos::CaretLeft />
+                    </button>
+                    <span class="text-sm mx-4">Page {page}</span>
+                    <button
+                        class="btn btn-sm btn-ghost"
+                        disabled=page_missing
+                        on:click=move |_| show_page(page() + 1)
+                    >
+                        <phosphor_leptos::CaretRight />
+                    </button>
+                </div>
+                <div class="modal-action">
+                    <button class="btn btn-neutral" on:click=move |_| show.set(false)>
+                        Cancel
+                    </button>
+                    <a class="btn btn-primary" target="_blank" href=url on:click=move |_| show.set(false)>
+                        <phosphor_leptos::Printer />
+                        Print
+                    </a>
+                </div>
+            </div>
+        </dialog>
+    }
+}
@@ -0,0 +1,180 @@
+use leptos::*;
+
+use crate::components::*;
+
+/// Announce the records broken with a result that has just been entered.
+#[component]
+pub fn NewRecordsAlert(#[prop(into)] new_records: Signal<Vec<model::Record>>) -> impl IntoView {
+    view! {
+        <Show when=move || !new_records().is_empty()>
+            <div role="alert" class="alert alert-success my-4">
+                <phosphor_leptos::Trophy />
+                <div>
+                    <h3 class="font-bold">New Record!</h3>
+                    {
+                        move || new_records()
+                            .into_iter()
+                            .map(|r| view! {
+                                <p>
+                                    {
+                                        match r.kind {
+                                            model::RecordKind::Club => "Club record",
+                                            model::RecordKind::Meet => "Meet record",
+                                        }
+                                    }
+                                    {format!(" of age class {}: {} {}, ", r.age_class, r.first_name, r.last_name)}
+                                    <values::Distance distance=r.distance />" "
+                                    <values::Stroke stroke=r.stroke />" in "
+                                    <values::Time millis=r.time />
+                                </p>
+                            })
+                            .collect_view()
+                    }
+                    <A class="link" href="/records">The records wait for the approval.</A>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+/// Enter a record of the paper list, it replaces the current record.
+#[component]
+pub fn AddRecordForm(#[prop(into)] on_record_added: Callback<Uuid>) -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+
+    let (kind, set_kind) = create_signal(model::RecordKind::Club);
+    let (gender, set_gender) = create_signal(model::Gender::Female);
+    let (stroke, set_stroke) = create_signal(model::Stroke::Butterfly);
+    let (distance, set_distance) = create_signal(25);
+    let (course, set_course) = create_signal(model::Course::default());
+    let (age_class, set_age_class) = create_signal(None::<u32>);
+    let (time, set_time) = create_signal(None);
+    let (first_name, set_first_name) = create_signal(String::new());
+    let (last_name, set_last_name) = create_signal(String::new());
+    let (birthday, set_birthday) = create_signal(None);
+    let (swum_on, set_swum_on) = create_signal(None);
+    let (meet, set_meet) = create_signal(String::new());
+
+    let add_record_action = create_action(|record: &api::AddRecordRequest| {
+        let record = record.clone();
+        async move { api_client::add_record(record).await }
+    });
+
+    let on_record_added_handler = move || match add_record_action.value().get() {
+        Some(Ok(record_id)) => on_record_added(record_id),
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+
+        let (Some(age_class), Some(time_millis), Some(birthday), Some(swum_on)) =
+            (age_class(), time(), birthday(), swum_on())
+        else {
+            set_error_message(Some(
+                "Enter the age class, time, birthday and date of the record.".to_string(),
+            ));
+            return;
+        };
+
+        add_record_action.dispatch(api::AddRecordRequest {
+            kind: kind(),
+            gender: gender(),
+            stroke: stroke(),
+            distance: distance(),
+            course: course(),
+            age_class,
+            time_millis,
+            first_name: first_name(),
+            last_name: last_name(),
+            birthday,
+            swum_on,
+            meet: Some(meet()).filter(|m| !m.trim().is_empty()),
+        });
+    };
+
+    let on_kind_change = move |ev| {
+        let kind = match event_target_value(&ev).as_str() {
+            "meet" => model::RecordKind::Meet,
+            _ => model::RecordKind::Club,
+        };
+        set_kind(kind);
+    };
+    let on_course_change = move |ev| {
+        let course = match event_target_value(&ev).as_str() {
+            "long" => model::Course::Long,
+            _ => model::Course::Short,
+        };
+        set_course(course);
+    };
+    let on_age_class_change = move |ev| set_age_class(event_target_value(&ev).parse().ok());
+
+    view! {
+        {on_record_added_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Kind">
+                <select class="input input-bordered" on:change=on_kind_change>
+                    <option value="club">Club record</option>
+                    <option value="meet">Meet record</option>
+                </select>
+            </FormItem>
+
+            <FormItem label="Age Class">
+                <input class="input input-bordered" type="number" min=0 on:input=on_age_class_change />
+            </FormItem>
+
+            <FormItem label="Gender">
+                <InputGender set_gender />
+            </FormItem>
+
+            <FormItem label="Distance">
+                <InputDistance set_distance />
+            </FormItem>
+
+            <FormItem label="Stroke">
+                <InputStroke set_stroke />
+            </FormItem>
+
+            <FormItem label="Pool">
+                <select class="input input-bordered" on:change=on_course_change>
+                    <option value="short">Short course (25 m)</option>
+                    <option value="long">Long course (50 m)</option>
+                </select>
+            </FormItem>
+
+            <FormItem label="Time">
+                <InputTime set_time />
+            </FormItem>
+
+            <FormItem label="Last Name">
+                <InputName set_name=set_last_name />
+            </FormItem>
+
+            <FormItem label="First Name">
+                <InputName set_name=set_first_name />
+            </FormItem>
+
+            <FormItem label="Birthday">
+                <InputDate set_date=set_birthday />
+            </FormItem>
+
+            <FormItem label="Date">
+                <InputDate set_date=set_swum_on />
+            </FormItem>
+
+            <FormItem label="Meet">
+                <InputName set_name=set_meet />
+            </FormItem>
+            {
+                move || error_message().map(|e| view!{<p class="text text-error">{e}</p>})
+            }
+
+            <div class="form-control w-full max-w-2xl mt-4">
+                <input class="btn btn-primary" type="submit" value="Add Record" disabled=add_record_action.pending() />
+            </div>
+        </form>
+    }
+}
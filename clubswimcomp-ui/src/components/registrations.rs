@@ -6,7 +6,7 @@ use crate::components::*;
 #[component]
 pub fn AddResultForm(
     registration_id: Uuid,
-    #[prop(optional, into)] on_added: Option<Callback<()>>,
+    #[prop(optional, into)] on_added: Option<Callback<Vec<model::Record>>>,
     #[prop(optional, into)] on_cancel: Option<Callback<()>>,
 ) -> impl IntoView {
     // Form/Action error dispaly
@@ -35,13 +35,13 @@ pub fn AddResultForm(
     let save_to_server_done = move || {
         let response = save_to_server.value().get();
         match response {
-            Some(Ok(())) => {
+            Some(Ok(new_records)) => {
                 if let Some(on_added) = on_added {
-                    on_added(());
+                    on_added(new_records);
                 }
                 save_to_server.value().set(None);
             }
-            Some(Err(e)) => set_error(Some(e)),
+            Some(Err(e)) => set_error(Some(describe_error(&e))),
             None => (),
         };
     };
@@ -113,15 +113,113 @@ pub fn AddResultForm(
     }
 }
 
+/// Find a registration by typing the number of its card, or the short code of
+/// the participant and the competition when the card is lost.
+#[component]
+pub fn RegistrationLookupForm(#[prop(into)] on_found: Callback<Uuid>) -> impl IntoView {
+    let (error, set_error) = create_signal(None);
+
+    let (card_number, set_card_number) = create_signal(String::new());
+    let (short_code, set_short_code) = create_signal(String::new());
+    let (competition_id, set_competition_id) = create_signal(None);
+
+    let lookup_action = create_action(|params: &api::RegistrationLookupParams| {
+        let params = params.clone();
+        async move { api_client::lookup_registration(params).await }
+    });
+
+    let lookup_done = move || match lookup_action.value().get() {
+        Some(Ok(registration)) => {
+            set_error(None);
+            on_found(registration.id);
+            lookup_action.value().set(None);
+        }
+        Some(Err(e)) => set_error(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_card_number_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let Ok(card_number) = card_number().trim().parse::<u32>() else {
+            set_error(Some("The card number must be a number.".to_string()));
+            return;
+        };
+        set_card_number(String::new());
+        lookup_action.dispatch(api::RegistrationLookupParams {
+            card_number: Some(card_number),
+            ..Default::default()
+        });
+    };
+
+    let on_short_code_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let Some(competition_id) = competition_id() else {
+            set_error(Some("Select the competition of the registration.".to_string()));
+            return;
+        };
+        lookup_action.dispatch(api::RegistrationLookupParams {
+            short_code: Some(short_code().trim().to_string()),
+            competition_id: Some(competition_id),
+            ..Default::default()
+        });
+    };
+
+    view! {
+        {lookup_done}
+
+        <Show when=move || error().is_some()>
+            <p class="font-bold text-error">
+                {error()}
+            </p>
+        </Show>
+
+        <div class="flex flex-row gap-8">
+            {/* Card number, the fastest way without a scanner */}
+            <form on:submit=on_card_number_submit>
+                <FormItem label="Card Number">
+                    <input
+                        class="input input-bordered"
+                        type="text"
+                        inputmode="numeric"
+                        autofocus
+                        prop:value=card_number
+                        on:input=move |ev| set_card_number(event_target_value(&ev))
+                    />
+                </FormItem>
+                <input class="btn btn-primary btn-sm mt-2" type="submit" value="Find" disabled=lookup_action.pending() />
+            </form>
+
+            {/* Short code and competition, for lost or unreadable cards */}
+            <form on:submit=on_short_code_submit>
+                <FormItem label="Short Code">
+                    <input
+                        class="input input-bordered"
+                        type="text"
+                        inputmode="numeric"
+                        maxlength=4
+                        on:input=move |ev| set_short_code(event_target_value(&ev))
+                    />
+                </FormItem>
+                <FormItem label="Competition">
+                    <InputCompetition set_competition_id />
+                </FormItem>
+                <input class="btn btn-primary btn-sm mt-2" type="submit" value="Find" disabled=lookup_action.pending() />
+            </form>
+        </div>
+    }
+}
+
 #[component]
 pub fn AddResultDialog(
     #[prop(into)] registration_id: RwSignal<Option<Uuid>>,
-    #[prop(default = None, into)] on_result_added: Option<Callback<()>>,
+    #[prop(default = None, into)] on_result_added: Option<Callback<Vec<model::Record>>>,
 ) -> impl IntoView {
-    let on_added_callback = Callback::new(move |()| {
+    let on_added_callback = Callback::new(move |new_records| {
         registration_id.set(None);
         if let Some(on_result_added) = on_result_added {
-            on_result_added(());
+            on_result_added(new_records);
         }
     });
 
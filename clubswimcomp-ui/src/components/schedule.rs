@@ -0,0 +1,221 @@
+use leptos::*;
+
+use crate::components::*;
+
+fn clock_time(time: chrono::NaiveDateTime) -> String {
+    time.format("%H:%M").to_string()
+}
+
+/// Add a session with the competitions in the order they are swum.
+#[component]
+pub fn AddSessionForm(#[prop(into)] on_session_added: Callback<Uuid>) -> impl IntoView {
+    let (error_message, set_error_message) = create_signal(None);
+
+    let (name, set_name) = create_signal(String::new());
+    let (starts_at, set_starts_at) = create_signal(None);
+    let (warm_up_minutes, set_warm_up_minutes) = create_signal(30u32);
+    let (turnaround_seconds, set_turnaround_seconds) = create_signal(60u32);
+    let (competition_id, set_competition_id) = create_signal(None);
+    let (competition_ids, set_competition_ids) = create_signal(Vec::<Uuid>::new());
+
+    let competitions = create_local_resource(
+        || (),
+        |_| async move { api_client::list_competitions().await.unwrap() },
+    );
+    let competition_label = move |competition_id: Uuid| {
+        competitions()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|c| c.id == competition_id)
+            .map(|c| {
+                view! {
+                    <values::Distance distance=c.distance />" "
+                    <values::Stroke stroke=c.stroke />" "
+                    <values::Gender gender=c.gender />
+                }
+            })
+    };
+
+    let add_session_action = create_action(|session: &api::SessionBody| {
+        let session = session.clone();
+        async move { api_client::add_session(session).await }
+    });
+
+    let on_session_added_handler = move || match add_session_action.value().get() {
+        Some(Ok(session_id)) => {
+            set_competition_ids(Vec::new());
+            on_session_added(session_id)
+        }
+        Some(Err(e)) => set_error_message(Some(describe_error(&e))),
+        None => (),
+    };
+
+    let on_add_competition = move |_| {
+        if let Some(competition_id) = competition_id() {
+            set_competition_ids.update(|ids| {
+                if !ids.contains(&competition_id) {
+                    ids.push(competition_id);
+                }
+            });
+        }
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error_message(None);
+
+        let Some(starts_at) = starts_at() else {
+            set_error_message(Some("Enter the start of the session.".to_string()));
+            return;
+        };
+
+        add_session_action.dispatch(api::SessionBody {
+            name: name(),
+            starts_at,
+            warm_up_minutes: warm_up_minutes(),
+            turnaround_seconds: turnaround_seconds(),
+            competition_ids: competition_ids(),
+        });
+    };
+
+    let on_starts_at_change = move |ev| {
+        let value = event_target_value(&ev);
+        set_starts_at(chrono::NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M").ok());
+    };
+
+    view! {
+        {on_session_added_handler}
+
+        <form on:submit=on_submit>
+            <FormItem label="Name">
+                <InputName set_name />
+            </FormItem>
+
+            <FormItem label="Start of the first heat">
+                <input class="input input-bordered" type="datetime-local" on:change=on_starts_at_change required />
+            </FormItem>
+
+            <FormItem label="Warm-up (minutes)">
+                <input
+                    class="input input-bordered"
+                    type="number"
+                    min=0
+                    prop:value=move || warm_up_minutes().to_string()
+                    on:input=move |ev| set_warm_up_minutes(event_target_value(&ev).parse().unwrap_or_default())
+                />
+            </FormItem>
+
+            <FormItem label="Time between heats (seconds)">
+                <input
+                    class="input input-bordered"
+                    type="number"
+                    min=0
+                    prop:value=move || turnaround_seconds().to_string()
+                    on:input=move |ev| set_turnaround_seconds(event_target_value(&ev).parse().unwrap_or_default())
+                />
+            </FormItem>
+
+            <FormItem label="Competitions">
+                <div class="flex">
+                    <InputCompetition set_competition_id />
+                    <button type="button" class="btn btn-sm rounded-full ml-4 self-center" on:click=on_add_competition>
+                        Add
+                    </button>
+                </div>
+            </FormItem>
+            <ol class="list-decimal list-inside my-4">
+                <For each=competition_ids key=|id| *id let:competition_id>
+                    <li>
+                        {move || competition_label(competition_id)}
+                        <button
+                            type="button"
+                            class="btn btn-xs btn-ghost ml-2"
+                            on:click=move |_| set_competition_ids.update(|ids| ids.retain(|id| *id != competition_id))
+                        >
+                            <phosphor_leptos::X />
+                        </button>
+                    </li>
+                </For>
+            </ol>
+
+            <div class="mt-6">
+                <ActionButton action_type=ActionType::Primary>
+                    Add Session
+                </ActionButton>
+            </div>
+            {move || error_message().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+        </form>
+    }
+}
+
+/// The estimated start of every heat of a session.
+#[component]
+pub fn SessionSchedule(
+    session: model::SessionSchedule,
+    #[prop(into)] on_remove: Callback<Uuid>,
+) -> impl IntoView {
+    let session_id = session.session.id;
+
+    view! {
+        <div class="mb-8">
+            <h3 class="text-lg font-bold">
+                {session.session.name.clone()}
+                <button class="btn btn-xs btn-ghost ml-2" on:click=move |_| on_remove(session_id)>
+                    <phosphor_leptos::Trash />
+                </button>
+            </h3>
+            <p class="mb-2">
+                {format!(
+                    "{}: warm-up {}, start {}, estimated end {}",
+                    session.session.starts_at.format("%Y-%m-%d"),
+                    clock_time(session.warm_up_at),
+                    clock_time(session.session.starts_at),
+                    clock_time(session.ends_at),
+                )}
+            </p>
+            <table class="table table-sm">
+                <thead>
+                    <tr>
+                        <th>Start</th>
+                        <th>Competition</th>
+                        <th>Heat</th>
+                        <th>Duration</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        session.competitions.into_iter().flat_map(|c| {
+                            let competition = c.competition;
+                            let no_heats = c.heats.is_empty().then(|| view! {
+                                <tr>
+                                    <td>{clock_time(c.starts_at)}</td>
+                                    <td>
+                                        <values::Distance distance=competition.distance />" "
+                                        <values::Stroke stroke=competition.stroke />" "
+                                        <values::Gender gender=competition.gender />
+                                    </td>
+                                    <td colspan=3>No entries</td>
+                                </tr>
+                            });
+                            let heats = c.heats.into_iter().map(move |h| view! {
+                                <tr class:opacity-50=h.finished>
+                                    <td>{clock_time(h.starts_at)}</td>
+                                    <td>
+                                        <values::Distance distance=competition.distance />" "
+                                        <values::Stroke stroke=competition.stroke />" "
+                                        <values::Gender gender=competition.gender />
+                                    </td>
+                                    <td>{h.number}</td>
+                                    <td><values::Time millis=h.duration_millis /></td>
+                                    <td>{h.finished.then_some("Finished")}</td>
+                                </tr>
+                            });
+                            no_heats.into_iter().chain(heats).collect::<Vec<_>>()
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -114,7 +114,11 @@ pub fn GroupScore(#[prop(into)] group_score: MaybeSignal<model::GroupScore>) ->
         let group_score = group_score();
         view! {
             <cells::FinaPoints fina_points=group_score.fina_points/>
-            <cells::Name name=group_score.rank.to_string() />
+            <cells::Name name=match group_score.rank {
+                Some(rank) if group_score.tied => format!("{rank} (tied)"),
+                Some(rank) => rank.to_string(),
+                None => "-".to_string(),
+            } />
         }
     }
 }
@@ -1,6 +1,7 @@
 use clubswimcomp_types::model;
 use leptos::*;
 use leptos_router::*;
+use uuid::Uuid;
 
 use crate::components::values;
 
@@ -219,6 +220,201 @@ pub fn GroupScoreRow(group_score: model::GroupScore) -> impl IntoView {
     }
 }
 
+#[component]
+pub fn TeamStandings(
+    #[prop(into)] team_standings: MaybeSignal<Vec<model::TeamStanding>>,
+) -> impl IntoView {
+    view! {
+        <Table>
+            <thead>
+                <tr>
+                    <th></th>
+                    <th>Rank</th>
+                    <columns::GroupHeadings />
+                    <th>Points</th>
+                    <th>Scoring Swims</th>
+                </tr>
+            </thead>
+            <tbody>
+                <For each=team_standings key=|s| s.group.id let:team_standing>
+                    <TeamStandingRow team_standing />
+                </For>
+            </tbody>
+        </Table>
+    }
+}
+
+#[component]
+pub fn TeamStandingRow(team_standing: model::TeamStanding) -> impl IntoView {
+    let group_link = format!("/groups/{}", team_standing.group.id);
+    let rank = match team_standing.tied {
+        true => format!("{} (tied)", team_standing.rank),
+        false => team_standing.rank.to_string(),
+    };
+    view! {
+        <tr>
+            <cells::Link href=group_link>
+                <phosphor_leptos::UsersThree />
+            </cells::Link>
+            <cells::Name name=rank />
+            <columns::Group group=team_standing.group />
+            <cells::Name name=team_standing.points.to_string() />
+            <cells::Name name=team_standing.scores.len().to_string() />
+        </tr>
+    }
+}
+
+#[component]
+pub fn TeamScores(#[prop(into)] team_scores: MaybeSignal<Vec<model::TeamScore>>) -> impl IntoView {
+    view! {
+        <Table>
+            <thead>
+                <tr>
+                    <columns::ParticipantHeadings />
+                    <columns::CompetitionHeadings />
+                    <th>Place</th>
+                    <th>Points</th>
+                </tr>
+            </thead>
+            <tbody>
+                <For each=team_scores key=|s| (s.participant.id, s.competition.id) let:team_score>
+                    <tr>
+                        <columns::Participant participant=team_score.participant />
+                        <columns::Competition competition=team_score.competition />
+                        <cells::Name name=team_score.rank.to_string() />
+                        <cells::Name name=team_score.points.to_string() />
+                    </tr>
+                </For>
+            </tbody>
+        </Table>
+    }
+}
+
+/// The times a participant swam at earlier events and this one.
+#[component]
+pub fn BestTimes(#[prop(into)] best_times: MaybeSignal<Vec<model::BestTime>>) -> impl IntoView {
+    view! {
+        <Table>
+            <thead>
+                <tr>
+                    <th>Date</th>
+                    <th>Meet</th>
+                    <th>Distance</th>
+                    <th>Stroke</th>
+                    <th>Course</th>
+                    <th>Time</th>
+                </tr>
+            </thead>
+            <tbody>
+                <For each=best_times key=|b| (b.swum_on, b.meet.clone(), b.stroke, b.distance, b.course) let:best_time>
+                    <tr>
+                        <td><values::Date date=best_time.swum_on /></td>
+                        <cells::Name name=best_time.meet />
+                        <td><values::Distance distance=best_time.distance /></td>
+                        <td><values::Stroke stroke=best_time.stroke /></td>
+                        <td>
+                            {
+                                match best_time.course {
+                                    model::Course::Short => "Short",
+                                    model::Course::Long => "Long",
+                                }
+                            }
+                        </td>
+                        <td><values::Time millis=best_time.time /></td>
+                    </tr>
+                </For>
+            </tbody>
+        </Table>
+    }
+}
+
+/// Club and meet records, the rows of pending records can be approved or
+/// rejected.
+#[component]
+pub fn Records(
+    #[prop(into)] records: MaybeSignal<Vec<model::Record>>,
+    #[prop(optional, into)] on_approve: Option<Callback<Uuid>>,
+    #[prop(optional, into)] on_reject: Option<Callback<Uuid>>,
+) -> impl IntoView {
+    view! {
+        <Table>
+            <thead>
+                <tr>
+                    <th>Kind</th>
+                    <th>Age Class</th>
+                    <th>Gender</th>
+                    <th>Distance</th>
+                    <th>Stroke</th>
+                    <th>Course</th>
+                    <th>Time</th>
+                    <th>Swimmer</th>
+                    <th>Date</th>
+                    <th>Meet</th>
+                    <th>Status</th>
+                    <Show when=move || on_approve.is_some() || on_reject.is_some()>
+                        <th></th>
+                        <th></th>
+                    </Show>
+                </tr>
+            </thead>
+            <tbody>
+                <For each=records key=|r| (r.id, r.status) let:record>
+                    <tr>
+                        <td>
+                            {
+                                match record.kind {
+                                    model::RecordKind::Club => "Club",
+                                    model::RecordKind::Meet => "Meet",
+                                }
+                            }
+                        </td>
+                        <cells::Name name=record.age_class.to_string() />
+                        <td><values::Gender gender=record.gender short=true /></td>
+                        <td><values::Distance distance=record.distance /></td>
+                        <td><values::Stroke stroke=record.stroke /></td>
+                        <td>
+                            {
+                                match record.course {
+                                    model::Course::Short => "Short",
+                                    model::Course::Long => "Long",
+                                }
+                            }
+                        </td>
+                        <td><values::Time millis=record.time /></td>
+                        <cells::Name name=format!("{} {}", record.first_name, record.last_name) />
+                        <td><values::Date date=record.swum_on /></td>
+                        <cells::Name name=record.meet.clone().unwrap_or_else(|| "This event".to_string()) />
+                        <td>
+                            {
+                                match record.status {
+                                    model::RecordStatus::Pending => "Pending",
+                                    model::RecordStatus::Approved => "Approved",
+                                    model::RecordStatus::Rejected => "Rejected",
+                                    model::RecordStatus::Superseded => "Superseded",
+                                }
+                            }
+                        </td>
+                        {
+                            on_approve.map(|on_approve| view! {
+                                <cells::Button action_type=ActionType::Primary on:click=move |_| on_approve(record.id)>
+                                    "Approve"
+                                </cells::Button>
+                            })
+                        }
+                        {
+                            on_reject.map(|on_reject| view! {
+                                <cells::Button action_type=ActionType::Error on:click=move |_| on_reject(record.id)>
+                                    "Reject"
+                                </cells::Button>
+                            })
+                        }
+                    </tr>
+                </For>
+            </tbody>
+        </Table>
+    }
+}
+
 #[component]
 pub fn CompetitionScores(scores: Vec<model::CompetitionScore>) -> impl IntoView {
     let (mut top_scores, mut scores): (Vec<_>, Vec<_>) =
@@ -271,10 +467,17 @@ pub fn CompetitionScoresRow(
             <td>{competition_score.participant.first_name}</td>
             <td>{competition_score.participant.age}</td>
             <td><values::FinaPoints fina_points=competition_score.fina_points /></td>
-            <td><values::Time millis=competition_score.time /></td>
+            <td>
+                <values::Time millis=competition_score.time />
+                <Show when=move || competition_score.personal_best>
+                    <span class="badge badge-success badge-sm ml-2">PB</span>
+                </Show>
+            </td>
             <td>
                 {
-                    if show_rank {
+                    if show_rank && competition_score.tied {
+                        format!("{} (tied)", competition_score.rank)
+                    } else if show_rank {
                         competition_score.rank.to_string()
                     } else {
                         "".to_string()
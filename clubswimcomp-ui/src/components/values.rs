@@ -29,6 +29,18 @@ pub fn Stroke(#[prop(into)] stroke: MaybeSignal<model::Stroke>) -> impl IntoView
     }
 }
 
+#[component]
+pub fn CompetitionRound(
+    #[prop(into)] round: MaybeSignal<model::CompetitionRound>,
+) -> impl IntoView {
+    move || match round() {
+        model::CompetitionRound::TimedFinal => "Timed Final",
+        model::CompetitionRound::Prelims => "Prelims",
+        model::CompetitionRound::Finals => "Finals",
+        model::CompetitionRound::SwimOff => "Swim-off",
+    }
+}
+
 #[component]
 pub fn Date(#[prop(into)] date: MaybeSignal<chrono::NaiveDate>) -> impl IntoView {
     move || date().format("%Y-%m-%d").to_string()
@@ -43,6 +55,20 @@ pub fn Time(#[prop(into)] millis: MaybeSignal<u32>) -> impl IntoView {
     move || format!("{:02}:{:02},{:02}", minutes(), seconds(), hundreths())
 }
 
+/// Amount of cents in the currency, negative if too much has been paid.
+#[component]
+pub fn Amount(
+    #[prop(into)] cents: MaybeSignal<i64>,
+    #[prop(into)] currency: MaybeSignal<String>,
+) -> impl IntoView {
+    move || {
+        let cents = cents();
+        let sign = if cents < 0 { "-" } else { "" };
+        let cents = cents.unsigned_abs();
+        format!("{sign}{}.{:02} {}", cents / 100, cents % 100, currency())
+    }
+}
+
 #[component]
 pub fn Disqualified(#[prop(into)] disqualified: MaybeSignal<bool>) -> impl IntoView {
     move || match disqualified() {
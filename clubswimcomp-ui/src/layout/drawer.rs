@@ -14,6 +14,12 @@ pub fn drawer() -> impl IntoView {
                 <li>
                     <A active_class="active" href="/event">Event</A>
                 </li>
+                <li>
+                    <A active_class="active" href="/schedule">Schedule</A>
+                </li>
+                <li>
+                    <A active_class="active" href="/payments">Payments</A>
+                </li>
                 <li>
                     <A active_class="active" href="/competitions">Competitions</A>
                 </li>
@@ -26,6 +32,9 @@ pub fn drawer() -> impl IntoView {
                 <li>
                     <A active_class="active" href="/groups">Groups</A>
                 </li>
+                <li>
+                    <A active_class="active" href="/records">Records</A>
+                </li>
             </ul>
         </div>
     }
@@ -37,7 +37,14 @@ fn ClubSwimCompUi() -> impl IntoView {
 
                     <Route path="/registrations/ingest" view=pages::ResultIngest/>
 
+                    <Route path="/records" view=pages::Records/>
+
+                    <Route path="/schedule" view=pages::Schedule/>
+
+                    <Route path="/payments" view=pages::Payments/>
+
                     <Route path="/groups" view=pages::GroupOverview/>
+                    <Route path="/groups/team-standings" view=pages::TeamStandings/>
                     <Route path="/groups/:group_id" view=pages::GroupDetails/>
                 </Routes>
             </AppShell>
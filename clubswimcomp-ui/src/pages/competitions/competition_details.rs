@@ -1,3 +1,4 @@
+use clubswimcomp_types::api;
 use leptos::*;
 use leptos_router::*;
 use uuid::Uuid;
@@ -28,7 +29,13 @@ pub fn CompetitionDetails() -> impl IntoView {
     let (error_msg, set_error_msg) = create_signal(None);
     let redirect_after_delete = move || match delete_competition_action.value().get() {
         Some(Ok(_)) => navigate("/competitions", Default::default()),
-        Some(Err(e)) => set_error_msg(Some(e)),
+        Some(Err(e)) => {
+            if e.code == api::ErrorCode::CompetitionHasRegistrations {
+                // Registrations were added since the details have been loaded
+                competition_details.refetch();
+            }
+            set_error_msg(Some(describe_error(&e)))
+        }
         None => (),
     };
     let can_be_deleted = move || {
@@ -50,6 +57,10 @@ pub fn CompetitionDetails() -> impl IntoView {
                     <phosphor_leptos::Table />
                     Scoreboard
                 </A>
+                <PrintButton
+                    url=format!("http://localhost:3000/competitions/{}/start-list", competition_id())
+                    label="Print Start List"
+                />
                 <button
                     class="btn btn-sm btn-error rounded-full mr-4"
                     on:click=move |_| delete_competition_action.dispatch(competition_id())
@@ -77,6 +88,16 @@ pub fn CompetitionDetails() -> impl IntoView {
                     )
                 }
             </Transition>
+            <SectionTitle
+                title="Eligibility".to_string()
+                subtitle="Who may register for the competition besides the participants of its gender.".to_string().into()
+            />
+            <EligibilityForm competition_id=Signal::derive(competition_id) />
+            <SectionTitle
+                title="Finals".to_string()
+                subtitle="Swim the competition as prelims and promote the fastest swimmers into its finals.".to_string().into()
+            />
+            <FinalsSection competition_id=Signal::derive(competition_id) />
         </PageLayout>
     }
 }
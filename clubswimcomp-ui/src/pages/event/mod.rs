@@ -5,29 +5,52 @@ use crate::components::*;
 
 #[component]
 pub fn Event() -> impl IntoView {
-    let start_card_link = format!(
-        "http://localhost:3000/event/cards",
-    );
+    let (card_layout, set_card_layout) = create_signal("layout=a6".to_string());
+    let cards_link = Signal::derive(move || {
+        format!("http://localhost:3000/event/cards?{}", card_layout())
+    });
+    let calibration_link = Signal::derive(move || {
+        format!("http://localhost:3000/event/cards/calibration?{}", card_layout())
+    });
     view! {
         <PageLayout>
             <PageTitle
                 title="Event".to_string()
                 subtitle="Do actions that are relevant to the whole event.".to_string().into()
             />
+            <div class="mb-4">
+                <InputCardLayout set_layout=set_card_layout />
+                <PrintButton url=cards_link label="Print Registration Cards" />
+                <PrintButton url=calibration_link label="Print Calibration Grid" />
+            </div>
             <div class="mb-8">
-
-                <A href="http://localhost:3000/event/cards" class="btn btn-sm btn-primary rounded-full mr-4">
-                    <phosphor_leptos::Printer />
-                    Print Registration Cards
-                </A>
-                <A href="/competitions/add" class="btn btn-sm btn-primary rounded-full mr-4">
-                    <phosphor_leptos::Printer />
-                    Print Certificates
+                <PrintButton url="http://localhost:3000/event/start-list".to_string() label="Print Start Lists" />
+                <PrintButton url="http://localhost:3000/event/results.pdf".to_string() label="Print Results Protocol" />
+                <PrintButton url="http://localhost:3000/event/certificates".to_string() label="Print Certificates" />
+                <A href="http://localhost:3000/event/backup" class="btn btn-sm btn-primary rounded-full mr-4">
+                    <phosphor_leptos::DownloadSimple />
+                    Download Backup
                 </A>
             </div>
 
-            <div>
-            </div>
+            <SectionTitle title="Event" subtitle="The name and organizer printed in the header of the start lists, protocols, certificates and invoices.".to_string().into() />
+            <EventInfoForm />
+            <SectionTitle title="Phase" subtitle="Registrations are only accepted while open, a finalized event is read-only until it is re-opened.".to_string().into() />
+            <EventPhaseForm />
+            <SectionTitle title="Group Scoring" subtitle="How the results of the participants add up in the group rankings and on the certificates.".to_string().into() />
+            <GroupScoringForm />
+            <SectionTitle title="Ranking Rules" subtitle="How tied participants are ranked in the competitions and groups.".to_string().into() />
+            <RankingRulesForm />
+            <SectionTitle title="Team Scoring" subtitle="How the places in the competitions score for the team standings of the groups.".to_string().into() />
+            <TeamScoringForm />
+            <SectionTitle title="Registration Limits" subtitle="How many competitions each participant may register for.".to_string().into() />
+            <RegistrationLimitsForm />
+            <SectionTitle title="Entry Fees" subtitle="What every participant pays, finals and swim-offs are free. Late entries are charged with the surcharge.".to_string().into() />
+            <EntryFeesForm />
+            <SectionTitle title="Course" subtitle="The pool of the event, the personal bests are compared with the earlier times in this course.".to_string().into() />
+            <CourseForm />
+            <SectionTitle title="Best Times" subtitle="Import the times the swimmers swam at earlier events from a CSV or Lenex file to flag their personal bests.".to_string().into() />
+            <BestTimesImportForm />
         </PageLayout>
     }
 }
@@ -30,6 +30,10 @@ pub fn GroupDetails() -> impl IntoView {
                 subtitle="The details of a group including the results of the participants.".to_string().into()
             />
             <ActionRow>
+                <PrintButton
+                    url=Signal::derive(move || format!("http://localhost:3000/groups/{}/certificates", group_id()))
+                    label="Print Certificates"
+                />
                 <button class="btn btn-sm btn-error rounded-full">
                     <phosphor_leptos::Trash />
                     Delete Group
@@ -28,6 +28,10 @@ pub fn GroupOverview() -> impl IntoView {
                     <phosphor_leptos::Plus />
                     Add Group
                 </button>
+                <A class="btn btn-sm btn-primary rounded-full ml-4" href="/groups/team-standings">
+                    <phosphor_leptos::Trophy />
+                    Team Standings
+                </A>
             </ActionRow>
 
             <SectionTitle title="Groups" subtitle="List of all groups.".to_string().into() />
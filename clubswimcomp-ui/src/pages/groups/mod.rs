@@ -1,5 +1,7 @@
 mod group_details;
 mod group_overview;
+mod team_standings;
 
 pub use group_details::*;
 pub use group_overview::*;
+pub use team_standings::*;
@@ -0,0 +1,49 @@
+use leptos::*;
+
+use crate::{api_client, components::*};
+
+#[component]
+pub fn TeamStandings() -> impl IntoView {
+    let team_standings = create_local_resource(
+        || (),
+        move |_| async { api_client::team_standings().await.unwrap() },
+    );
+
+    view! {
+        <PageLayout>
+            <PageTitle
+                title="Team Standings"
+                subtitle="The groups as teams, scored by the places of their swimmers in the competitions.".to_string().into()
+            />
+
+            <SectionTitle title="Standings" />
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                {
+                    move || team_standings.get().map(|s|
+                        view! {
+                            <tables::TeamStandings team_standings=s />
+                        }
+                    )
+                }
+            </Transition>
+
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                {
+                    move || team_standings.get().map(|standings|
+                        standings
+                            .into_iter()
+                            .filter(|s| !s.scores.is_empty())
+                            .map(|s| view! {
+                                <SectionTitle
+                                    title=format!("{} ({} Points)", s.group.name, s.points)
+                                    subtitle="The swims that scored for the team.".to_string().into()
+                                />
+                                <tables::TeamScores team_scores=s.scores />
+                            })
+                            .collect_view()
+                    )
+                }
+            </Transition>
+        </PageLayout>
+    }
+}
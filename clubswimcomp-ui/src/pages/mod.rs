@@ -2,10 +2,16 @@ mod competitions;
 mod event;
 mod groups;
 mod participants;
+mod payments;
+mod records;
 mod registrations;
+mod schedule;
 
 pub use competitions::*;
 pub use event::*;
 pub use groups::*;
 pub use participants::*;
+pub use payments::*;
+pub use records::*;
 pub use registrations::*;
+pub use schedule::*;
@@ -1,3 +1,4 @@
+use clubswimcomp_types::api;
 use leptos::*;
 use leptos_router::*;
 use uuid::Uuid;
@@ -28,6 +29,10 @@ pub fn ParticipantDetails() -> impl IntoView {
             .unwrap()
     });
 
+    let best_times = create_local_resource(participant_id, move |p| async move {
+        api_client::participant_best_times(p).await.unwrap()
+    });
+
     let refetch_data = move |_| {
         participant_details.refetch();
         available_competitions.refetch();
@@ -35,17 +40,20 @@ pub fn ParticipantDetails() -> impl IntoView {
 
     let delete_participant_action = create_action(|participant_id: &Uuid| {
         let participant_id = *participant_id;
-        async move {
-            api_client::remove_participant(participant_id, false)
-                .await
-                .unwrap();
-        }
+        async move { api_client::remove_participant(participant_id, false).await }
     });
 
-    let redirect_on_deletion = move || {
-        if delete_participant_action.value().get().is_some() {
-            navigate("/participants", Default::default());
+    let (error_msg, set_error_msg) = create_signal(None);
+    let redirect_on_deletion = move || match delete_participant_action.value().get() {
+        Some(Ok(())) => navigate("/participants", Default::default()),
+        Some(Err(e)) => {
+            if e.code == api::ErrorCode::ParticipantHasRegistrations {
+                // Registrations were added since the details have been loaded
+                participant_details.refetch();
+            }
+            set_error_msg(Some(describe_error(&e)))
         }
+        None => (),
     };
 
     let can_be_deleted = move || {
@@ -54,6 +62,8 @@ pub fn ParticipantDetails() -> impl IntoView {
             .is_some_and(|pd| pd.registrations.is_empty())
     };
 
+    let (late_entry, set_late_entry) = create_signal(false);
+
     let on_registered = Callback::new(move |_| {
         participant_details.refetch();
         available_competitions.refetch();
@@ -66,6 +76,7 @@ pub fn ParticipantDetails() -> impl IntoView {
                 title="Participant Details"
                 subtitle="Details about a specific participant all of hist registrations.".to_string().into()
             />
+            { move || error_msg().map(|e| view! {<p class="text-error">{e}</p>}) }
             <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
                 {
                     // FIXME: Participant Start Card Link
@@ -74,14 +85,8 @@ pub fn ParticipantDetails() -> impl IntoView {
                         let certificate_link = format!("http://localhost:3000/participants/{}/certificate", pd.participant.id);
                         view! {
                             <ActionRow>
-                                <A target="about:blank" href=start_card_link class="btn btn-sm btn-primary rounded-full mr-4">
-                                    <phosphor_leptos::Printer />
-                                    Print Registration Cards
-                                </A>
-                                <A target="about:blank" href=certificate_link class="btn btn-sm btn-primary rounded-full mr-4">
-                                    <phosphor_leptos::Printer />
-                                    Print Certificate
-                                </A>
+                                <PrintButton url=start_card_link label="Print Registration Cards" />
+                                <PrintButton url=certificate_link label="Print Certificate" />
                                 <button
                                     class="btn btn-sm btn-error rounded-full"
                                     on:click=move |_| delete_participant_action.dispatch(participant_id())
@@ -94,6 +99,11 @@ pub fn ParticipantDetails() -> impl IntoView {
 
                             <data::ParticipantInfo participant=pd.participant.clone() />
 
+                            <label class="label cursor-pointer justify-start gap-2 max-w-2xl">
+                                <input type="checkbox" class="checkbox checkbox-sm" prop:checked=late_entry on:change=move |ev| set_late_entry(event_target_checked(&ev)) />
+                                <span class="label-text">Late entry, change the registrations after the registration has closed</span>
+                            </label>
+
                             <SectionTitle
                                 title="Registrations".to_string()
                                 subtitle="This is an overview over all of the registrations that exist for this participant.".to_string().into()
@@ -101,6 +111,7 @@ pub fn ParticipantDetails() -> impl IntoView {
                             <ParticipantRegistrationsTable
                                 participant_id=pd.participant.id
                                 registrations=pd.registrations
+                                late_entry
                                 on_unregister=refetch_data
                                 on_result_removed=refetch_data
                                 on_result_added=refetch_data
@@ -112,8 +123,15 @@ pub fn ParticipantDetails() -> impl IntoView {
                             />
                             <AvailableCompetitionsTable
                                 participant_id=participant_id()
+                                late_entry
                                 on_registered
                             />
+
+                            <SectionTitle
+                                title="Best Times".to_string()
+                                subtitle="The times the participant swam at earlier events and this one.".to_string().into()
+                            />
+                            <tables::BestTimes best_times=Signal::derive(move || best_times.get().unwrap_or_default()) />
                         }
                     })
                 }
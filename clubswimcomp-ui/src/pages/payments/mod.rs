@@ -0,0 +1,76 @@
+use leptos::*;
+use uuid::Uuid;
+
+use crate::{api_client, components::*};
+
+#[component]
+pub fn Payments() -> impl IntoView {
+    let (error_msg, set_error_msg) = create_signal(None);
+
+    let balances = create_local_resource(
+        || (),
+        move |_| async { api_client::balances().await.unwrap() },
+    );
+    let payments = create_local_resource(
+        || (),
+        move |_| async { api_client::payments().await.unwrap() },
+    );
+    let refetch = move || {
+        balances.refetch();
+        payments.refetch();
+    };
+
+    let remove_action = create_action(|payment_id: &Uuid| {
+        let payment_id = *payment_id;
+        async move { api_client::remove_payment(payment_id).await }
+    });
+    let on_remove = Callback::new(move |payment_id: Uuid| {
+        set_error_msg(None);
+        remove_action.dispatch(payment_id);
+    });
+    create_effect(move |_| match remove_action.value().get() {
+        Some(Ok(())) => refetch(),
+        Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
+        None => (),
+    });
+
+    view! {
+        <PageLayout>
+            <PageTitle
+                title="Payments"
+                subtitle="Entry fees owed by the groups and the payments received. Set the fees on the event page.".to_string().into()
+            />
+            <div class="mb-8">
+                <PrintButton url="http://localhost:3000/payments/invoices".to_string() label="Print Invoices" />
+                <PrintButton url="http://localhost:3000/payments/outstanding".to_string() label="Print Outstanding Balances" />
+            </div>
+            {move || error_msg().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                {
+                    move || balances.get().map(|balances| view! {
+                        <SectionTitle title="Groups" />
+                        <p class="mb-2">
+                            "Owed: " <values::Amount cents=balances.owed as i64 currency=balances.currency.clone() />
+                            ", paid: " <values::Amount cents=balances.paid as i64 currency=balances.currency.clone() />
+                        </p>
+                        <GroupBalancesTable balances=balances.clone() />
+
+                        <SectionTitle title="Participants" subtitle="Payments of the group are not split onto its participants.".to_string().into() />
+                        <ParticipantBalancesTable balances=balances.clone() />
+
+                        <SectionTitle title="Received Payments" />
+                        {
+                            move || payments.get().map(|payments| view! {
+                                <PaymentsTable payments currency=balances.currency.clone() on_remove />
+                            })
+                        }
+                    })
+                }
+            </Transition>
+
+            <SectionTitle title="Add Payment" subtitle="Record a payment from the bank statement or a cash receipt.".to_string().into() />
+            <AddPaymentForm on_payment_added=move |_| refetch() />
+        </PageLayout>
+    }
+}
@@ -0,0 +1,88 @@
+use clubswimcomp_types::model;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::{api_client, components::*};
+
+#[component]
+pub fn Records() -> impl IntoView {
+    let (error_msg, set_error_msg) = create_signal(None);
+
+    let records = create_local_resource(
+        || (),
+        move |_| async { api_client::records().await.unwrap() },
+    );
+    let with_status = move |statuses: &'static [model::RecordStatus]| {
+        move || {
+            records
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|r| statuses.contains(&r.status))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let approve_action = create_action(|record_id: &Uuid| {
+        let record_id = *record_id;
+        async move { api_client::approve_record(record_id).await }
+    });
+    let reject_action = create_action(|record_id: &Uuid| {
+        let record_id = *record_id;
+        async move { api_client::reject_record(record_id).await }
+    });
+    let on_approve = Callback::new(move |record_id: Uuid| {
+        set_error_msg(None);
+        approve_action.dispatch(record_id);
+    });
+    let on_reject = Callback::new(move |record_id: Uuid| {
+        set_error_msg(None);
+        reject_action.dispatch(record_id);
+    });
+    create_effect(move |_| {
+        for result in [approve_action.value().get(), reject_action.value().get()] {
+            match result {
+                Some(Ok(())) => records.refetch(),
+                Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
+                None => (),
+            }
+        }
+    });
+
+    view! {
+        <PageLayout>
+            <PageTitle
+                title="Records"
+                subtitle="Club and meet records per age class, new records from results wait for an approval.".to_string().into()
+            />
+            {move || error_msg().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+
+            <SectionTitle title="Pending Records" subtitle="Records broken at this event.".to_string().into() />
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                <tables::Records
+                    records=Signal::derive(with_status(&[model::RecordStatus::Pending]))
+                    on_approve
+                    on_reject
+                />
+            </Transition>
+
+            <SectionTitle title="Current Records" />
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                <tables::Records records=Signal::derive(with_status(&[model::RecordStatus::Approved])) />
+            </Transition>
+
+            <SectionTitle title="History" subtitle="Former records and rejected ones.".to_string().into() />
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                <tables::Records
+                    records=Signal::derive(with_status(&[
+                        model::RecordStatus::Superseded,
+                        model::RecordStatus::Rejected,
+                    ]))
+                />
+            </Transition>
+
+            <SectionTitle title="Add Record" subtitle="Enter a record from the paper list, it replaces the current record.".to_string().into() />
+            <AddRecordForm on_record_added=move |_| records.refetch() />
+        </PageLayout>
+    }
+}
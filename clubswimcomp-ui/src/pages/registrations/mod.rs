@@ -9,10 +9,37 @@ use crate::{api_client, components::*};
 pub fn ResultIngest() -> impl IntoView {
     let (error_msg, set_error_msg) = create_signal(None);
     let (registration_id, set_registration_id) = create_signal(None);
+    let (legacy_card, set_legacy_card) = create_signal(false);
+    let (new_records, set_new_records) = create_signal(Vec::new());
+
+    let scan_action = create_action(|code: &String| {
+        let code = code.clone();
+        async move { api_client::scan_card(code).await }
+    });
 
     let on_scanned = Callback::new(move |value: String| {
-        let registration_id = Uuid::parse_str(&value).ok();
-        set_registration_id(registration_id);
+        set_error_msg(None);
+        set_new_records(Vec::new());
+        scan_action.dispatch(value);
+    });
+
+    let on_found = Callback::new(move |registration_id: Uuid| {
+        set_error_msg(None);
+        set_new_records(Vec::new());
+        set_legacy_card(false);
+        set_registration_id(Some(registration_id));
+    });
+
+    create_effect(move |_| match scan_action.value().get() {
+        Some(Ok(scanned)) => {
+            set_legacy_card(scanned.legacy);
+            set_registration_id(Some(scanned.registration_id));
+        }
+        Some(Err(err)) => {
+            set_registration_id(None);
+            set_error_msg(Some(describe_error(&err)));
+        }
+        None => {}
     });
 
     let load_registration_details = create_resource(registration_id, |registration_id| {
@@ -41,19 +68,26 @@ pub fn ResultIngest() -> impl IntoView {
             Some(Ok(_)) => {
                 load_registration_details.refetch();
             }
-            Some(Err(err)) => set_error_msg(Some(err)),
+            Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
             None => return, // Action is still running
         };
     });
 
     view! {
         <PageLayout>
-            <PageTitle title="Scan Registration Cards" subtitle="Add a result to a registration by scanning the QR code on the registration card.".to_string().into() />
+            <PageTitle title="Scan Registration Cards" subtitle="Add a result to a registration by scanning the QR code on the registration card or typing its number.".to_string().into() />
             <ActionRow>
                 <Scanner value_scanned=on_scanned />
             </ActionRow>
+            <RegistrationLookupForm on_found=on_found />
 
             {move || error_msg().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+            <NewRecordsAlert new_records />
+            {move || (legacy_card() && registration_id().is_some()).then(|| view! {
+                <p class="text-warning font-bold">
+                    "This card has been printed without a signature, make sure it belongs to this event."
+                </p>
+            })}
 
             <Transition>
                 {
@@ -87,7 +121,10 @@ pub fn ResultIngest() -> impl IntoView {
                                 view! {
                                     <AddResultForm
                                         registration_id=rd.id
-                                        on_added=move |_| load_registration_details.refetch()
+                                        on_added=move |new_records| {
+                                            set_new_records(new_records);
+                                            load_registration_details.refetch();
+                                        }
                                         on_cancel=move |_| set_registration_id(None)
                                     />
                                 }
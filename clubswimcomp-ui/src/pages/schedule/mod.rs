@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use leptos::*;
+use uuid::Uuid;
+
+use crate::{api_client, components::*};
+
+/// How often the estimated start times are reloaded while heats finish.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[component]
+pub fn Schedule() -> impl IntoView {
+    let (error_msg, set_error_msg) = create_signal(None);
+
+    let schedule = create_local_resource(
+        || (),
+        move |_| async { api_client::schedule().await.unwrap() },
+    );
+    if let Ok(handle) = set_interval_with_handle(move || schedule.refetch(), REFRESH_INTERVAL) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let remove_action = create_action(|session_id: &Uuid| {
+        let session_id = *session_id;
+        async move { api_client::remove_session(session_id).await }
+    });
+    let on_remove = Callback::new(move |session_id: Uuid| {
+        set_error_msg(None);
+        remove_action.dispatch(session_id);
+    });
+    create_effect(move |_| match remove_action.value().get() {
+        Some(Ok(())) => schedule.refetch(),
+        Some(Err(err)) => set_error_msg(Some(describe_error(&err))),
+        None => (),
+    });
+
+    view! {
+        <PageLayout>
+            <PageTitle
+                title="Schedule"
+                subtitle="Sessions with the estimated start of every heat, updated as the heats finish.".to_string().into()
+            />
+            <div class="mb-8">
+                <PrintButton url="http://localhost:3000/event/timetable".to_string() label="Print Timetable" />
+            </div>
+            {move || error_msg().map(|e| view! {<p class="text-error font-bold">{e}</p>})}
+
+            <SectionTitle title="Sessions" />
+            <Transition fallback=|| view!{<span class="loading loading-spinner loading-lg"></span>}>
+                {
+                    move || schedule.get().map(|schedule| schedule.sessions
+                        .into_iter()
+                        .map(|session| view! { <SessionSchedule session on_remove /> })
+                        .collect_view())
+                }
+            </Transition>
+
+            <SectionTitle title="Add Session" subtitle="Heats follow each other with the time between heats, they take as long as the slowest entry time.".to_string().into() />
+            <AddSessionForm on_session_added=move |_| schedule.refetch() />
+        </PageLayout>
+    }
+}